@@ -29,11 +29,15 @@ pub(crate) const ROUTE_PEERS: &str = "/api/v1/peers";
 pub(crate) const ROUTE_REMOVE_PEER: &str = "/api/v1/peers/:peerId";
 pub(crate) const ROUTE_SUBMIT_MESSAGE: &str = "/api/v1/messages";
 pub(crate) const ROUTE_SUBMIT_MESSAGE_RAW: &str = "/api/v1/messages";
+pub(crate) const ROUTE_SUBMIT_TRUSTED_MESSAGE: &str = "/api/v1/messages/trusted";
 pub(crate) const ROUTE_TIPS: &str = "/api/v1/tips";
 pub(crate) const ROUTE_RECEIPTS: &str = "/api/v1/receipts";
 pub(crate) const ROUTE_RECEIPTS_AT: &str = "/api/v1/receipts/:milestoneIndex";
+pub(crate) const ROUTE_DECISIONS: &str = "/api/plugins/debug/decisions";
+pub(crate) const ROUTE_REQUESTED_MESSAGES: &str = "/api/plugins/debug/requests";
 pub(crate) const ROUTE_TREASURY: &str = "/api/v1/treasury";
 pub(crate) const ROUTE_TRANSACTION_INCLUDED_MESSAGE: &str = "/api/v1/transactions/:transactionId/included-message";
+pub(crate) const ROUTE_UPDATE_NETWORK_ID: &str = "/api/v1/network-id";
 pub(crate) const ROUTE_WHITE_FLAG: &str = "/api/plugins/debug/whiteflag";
 
 /// the routes that are available for public use
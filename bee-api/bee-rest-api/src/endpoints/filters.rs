@@ -6,7 +6,8 @@ use crate::endpoints::{config::RestApiConfig, storage::StorageBackend, Bech32Hrp
 use bee_gossip::NetworkCommandSender;
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, AcceptedNetworkIds, DecisionLog, MessageRequesterWorker, MessageSubmitterWorkerEvent,
+    PeerManager, RequestedMessages,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -64,6 +65,12 @@ pub(crate) fn with_peer_manager(
     warp::any().map(move || peer_manager.clone())
 }
 
+pub(crate) fn with_accepted_network_ids(
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
+) -> impl Filter<Extract = (ResourceHandle<AcceptedNetworkIds>,), Error = Infallible> + Clone {
+    warp::any().map(move || accepted_network_ids.clone())
+}
+
 pub(crate) fn with_network_command_sender(
     command_sender: ResourceHandle<NetworkCommandSender>,
 ) -> impl Filter<Extract = (ResourceHandle<NetworkCommandSender>,), Error = Infallible> + Clone {
@@ -94,6 +101,12 @@ pub(crate) fn with_requested_messages(
     warp::any().map(move || requested_messages.clone())
 }
 
+pub(crate) fn with_decision_log(
+    decision_log: ResourceHandle<DecisionLog>,
+) -> impl Filter<Extract = (ResourceHandle<DecisionLog>,), Error = Infallible> + Clone {
+    warp::any().map(move || decision_log.clone())
+}
+
 pub(crate) fn with_consensus_worker(
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
 ) -> impl Filter<Extract = (mpsc::UnboundedSender<ConsensusWorkerCommand>,), Error = Infallible> + Clone {
@@ -19,8 +19,8 @@ use crate::types::body::{DefaultErrorResponse, ErrorBody};
 use bee_gossip::NetworkCommandSender;
 use bee_ledger::workers::consensus::ConsensusWorker;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorker, PeerManager, PeerManagerResWorker,
-    RequestedMessages,
+    config::ProtocolConfig, AcceptedNetworkIds, DecisionLog, MessageRequesterWorker, MessageSubmitterWorker,
+    PeerManager, PeerManagerResWorker, RequestedMessages,
 };
 use bee_runtime::{
     node::{Node, NodeBuilder},
@@ -82,7 +82,9 @@ where
         let message_submitter = node.worker::<MessageSubmitterWorker>().unwrap().tx.clone();
         let message_requester = node.worker::<MessageRequesterWorker>().unwrap().clone();
         let requested_messages = node.resource::<RequestedMessages>();
+        let decision_log = node.resource::<DecisionLog>();
         let peer_manager = node.resource::<PeerManager>();
+        let accepted_network_ids = node.resource::<AcceptedNetworkIds>();
         let network_controller = node.resource::<NetworkCommandSender>();
         let node_info = node.info();
         let bus = node.bus();
@@ -97,6 +99,7 @@ where
                 storage,
                 message_submitter,
                 network_id,
+                accepted_network_ids,
                 bech32_hrp,
                 rest_api_config.clone(),
                 protocol_config,
@@ -106,6 +109,7 @@ where
                 bus,
                 message_requester,
                 requested_messages,
+                decision_log,
                 consensus_worker,
             )
             .recover(handle_rejection);
@@ -9,7 +9,8 @@ use crate::endpoints::{config::RestApiConfig, storage::StorageBackend, Bech32Hrp
 use bee_gossip::NetworkCommandSender;
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, AcceptedNetworkIds, DecisionLog, MessageRequesterWorker, MessageSubmitterWorkerEvent,
+    PeerManager, RequestedMessages,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -31,6 +32,7 @@ pub(crate) fn filter<B: StorageBackend>(
     storage: ResourceHandle<B>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
     network_id: NetworkId,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
     bech32_hrp: Bech32Hrp,
     rest_api_config: RestApiConfig,
     protocol_config: ProtocolConfig,
@@ -40,6 +42,7 @@ pub(crate) fn filter<B: StorageBackend>(
     bus: ResourceHandle<Bus<'static>>,
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
+    decision_log: ResourceHandle<DecisionLog>,
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     v1::filter(
@@ -49,6 +52,7 @@ pub(crate) fn filter<B: StorageBackend>(
         storage.clone(),
         message_submitter,
         network_id,
+        accepted_network_ids,
         bech32_hrp,
         rest_api_config.clone(),
         protocol_config,
@@ -65,6 +69,7 @@ pub(crate) fn filter<B: StorageBackend>(
         bus,
         message_requester,
         requested_messages,
+        decision_log,
         rest_api_config,
     ))
 }
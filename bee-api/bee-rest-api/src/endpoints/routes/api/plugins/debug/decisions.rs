@@ -0,0 +1,57 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    endpoints::{
+        config::ROUTE_DECISIONS, filters::with_decision_log, permission::has_permission, rejection::CustomRejection,
+    },
+    types::{body::SuccessBody, dtos::DecisionRecordDto, responses::DecisionsResponse},
+};
+
+use bee_protocol::workers::DecisionLog;
+use bee_runtime::resource::ResourceHandle;
+
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use std::net::IpAddr;
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path().and(warp::path("decisions")).and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    decision_log: ResourceHandle<DecisionLog>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_DECISIONS, public_routes, allowed_ips))
+        .and(with_decision_log(decision_log))
+        .and_then(|decision_log| async move { decisions(decision_log) })
+        .boxed()
+}
+
+pub(crate) fn decisions(decision_log: ResourceHandle<DecisionLog>) -> Result<impl Reply, Rejection> {
+    if !decision_log.is_enabled() {
+        return Err(reject::custom(CustomRejection::ServiceUnavailable(
+            "the decision log is disabled".to_string(),
+        )));
+    }
+
+    let decisions = decision_log
+        .records()
+        .into_iter()
+        .map(|record| DecisionRecordDto {
+            message_id: record.message_id.map(|message_id| message_id.to_string()),
+            peer_id: record.source.map(|peer_id| peer_id.to_string()),
+            timestamp: record.timestamp,
+            decision: record.decision.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(warp::reply::json(&SuccessBody::new(DecisionsResponse {
+        count: decisions.len(),
+        decisions,
+    })))
+}
@@ -1,11 +1,13 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod decisions;
+mod requests;
 mod white_flag;
 
 use crate::endpoints::{config::RestApiConfig, storage::StorageBackend};
 
-use bee_protocol::workers::{MessageRequesterWorker, RequestedMessages};
+use bee_protocol::workers::{DecisionLog, MessageRequesterWorker, RequestedMessages};
 use bee_runtime::{event::Bus, resource::ResourceHandle};
 use bee_tangle::Tangle;
 
@@ -26,16 +28,23 @@ pub(crate) fn filter<B: StorageBackend>(
     bus: ResourceHandle<Bus<'static>>,
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
+    decision_log: ResourceHandle<DecisionLog>,
     rest_api_config: RestApiConfig,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    white_flag::filter(
-        public_routes,
-        allowed_ips,
-        storage,
-        tangle,
-        bus,
-        message_requester,
-        requested_messages,
-        rest_api_config,
-    )
+    requests::filter(public_routes.clone(), allowed_ips.clone(), requested_messages.clone())
+        .or(decisions::filter(
+            public_routes.clone(),
+            allowed_ips.clone(),
+            decision_log,
+        ))
+        .or(white_flag::filter(
+            public_routes,
+            allowed_ips,
+            storage,
+            tangle,
+            bus,
+            message_requester,
+            requested_messages,
+            rest_api_config,
+        ))
 }
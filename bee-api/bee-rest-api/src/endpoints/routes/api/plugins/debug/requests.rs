@@ -0,0 +1,64 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    endpoints::{
+        config::ROUTE_REQUESTED_MESSAGES, filters::with_requested_messages, permission::has_permission,
+        routes::api::v1::messages_find::parse_query_param,
+    },
+    types::{body::SuccessBody, dtos::RequestedMessageDto, responses::RequestedMessagesResponse},
+};
+
+use bee_protocol::workers::RequestedMessages;
+use bee_runtime::resource::ResourceHandle;
+
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use std::{collections::HashMap, net::IpAddr};
+
+const MAX_RESULTS: usize = 1000;
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path().and(warp::path("requests")).and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    requested_messages: ResourceHandle<RequestedMessages>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_REQUESTED_MESSAGES, public_routes, allowed_ips))
+        .and(warp::query().and_then(|query: HashMap<String, String>| async move {
+            let offset = parse_query_param(&query, "offset", 0)?;
+            let limit = parse_query_param(&query, "limit", MAX_RESULTS)?.min(MAX_RESULTS);
+
+            Ok::<_, Rejection>((offset, limit))
+        }))
+        .untuple_one()
+        .and(with_requested_messages(requested_messages))
+        .and_then(|offset, limit, requested_messages| async move { requests(offset, limit, requested_messages) })
+        .boxed()
+}
+
+pub(crate) fn requests(
+    offset: usize,
+    limit: usize,
+    requested_messages: ResourceHandle<RequestedMessages>,
+) -> Result<impl Reply, Rejection> {
+    let (count, requests) = requested_messages.requests(offset, limit);
+
+    Ok(warp::reply::json(&SuccessBody::new(RequestedMessagesResponse {
+        max_results: limit,
+        count,
+        requests: requests
+            .into_iter()
+            .map(|(message_id, index, age)| RequestedMessageDto {
+                message_id: message_id.to_string(),
+                milestone_index: *index,
+                age: age.as_secs(),
+            })
+            .collect(),
+    })))
+}
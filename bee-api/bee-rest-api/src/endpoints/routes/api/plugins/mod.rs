@@ -5,7 +5,7 @@ mod debug;
 
 use crate::endpoints::{config::RestApiConfig, storage::StorageBackend};
 
-use bee_protocol::workers::{MessageRequesterWorker, RequestedMessages};
+use bee_protocol::workers::{DecisionLog, MessageRequesterWorker, RequestedMessages};
 use bee_runtime::{event::Bus, resource::ResourceHandle};
 use bee_tangle::Tangle;
 
@@ -26,6 +26,7 @@ pub(crate) fn filter<B: StorageBackend>(
     bus: ResourceHandle<Bus<'static>>,
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
+    decision_log: ResourceHandle<DecisionLog>,
     rest_api_config: RestApiConfig,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     debug::filter(
@@ -36,6 +37,7 @@ pub(crate) fn filter<B: StorageBackend>(
         bus,
         message_requester,
         requested_messages,
+        decision_log,
         rest_api_config,
     )
 }
@@ -15,10 +15,16 @@ use bee_message::{
 };
 use bee_runtime::resource::ResourceHandle;
 use bee_storage::access::Fetch;
+use bee_tangle::{indexed_message::IndexedMessage, metadata::MessageMetadata};
 
 use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
 
-use std::{collections::HashMap, net::IpAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+};
+
+const MAX_RESULTS: usize = 1000;
 
 fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
     super::path().and(warp::path("messages")).and(warp::path::end())
@@ -33,43 +39,176 @@ pub(crate) fn filter<B: StorageBackend>(
         .and(warp::get())
         .and(has_permission(ROUTE_MESSAGES_FIND, public_routes, allowed_ips))
         .and(warp::query().and_then(|query: HashMap<String, String>| async move {
-            match query.get("index") {
-                Some(i) => Ok(i.to_string()),
-                None => Err(reject::custom(CustomRejection::BadRequest(
-                    "invalid query parameter".to_string(),
-                ))),
-            }
+            let index = match query.get("index") {
+                Some(i) => i.to_string(),
+                None => {
+                    return Err(reject::custom(CustomRejection::BadRequest(
+                        "invalid query parameter".to_string(),
+                    )));
+                }
+            };
+            let offset = parse_query_param(&query, "offset", 0)?;
+            let limit = parse_query_param(&query, "limit", MAX_RESULTS)?.min(MAX_RESULTS);
+
+            Ok((index, offset, limit))
         }))
+        .untuple_one()
         .and(with_storage(storage))
-        .and_then(|index, storage| async move { messages_find(index, storage) })
+        .and_then(|index, offset, limit, storage| async move { messages_find(index, offset, limit, storage) })
         .boxed()
 }
 
+pub(crate) fn parse_query_param(
+    query: &HashMap<String, String>,
+    name: &str,
+    default: usize,
+) -> Result<usize, Rejection> {
+    match query.get(name) {
+        Some(value) => value.parse().map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(format!(
+                "invalid query parameter: {}",
+                name
+            )))
+        }),
+        None => Ok(default),
+    }
+}
+
+/// Fetches the message ids stored under an index, in the order their messages arrived, skipping the first `offset`
+/// ones and returning at most `limit` ones. Alongside the page, the total number of message ids stored under the index
+/// is returned.
+pub(crate) fn messages_by_index<B>(
+    storage: &B,
+    index: &PaddedIndex,
+    offset: usize,
+    limit: usize,
+) -> Result<(usize, Vec<MessageId>), B::Error>
+where
+    B: Fetch<PaddedIndex, Vec<IndexedMessage>> + Fetch<PaddedIndex, Vec<MessageId>> + Fetch<MessageId, MessageMetadata>,
+{
+    let mut fetched = Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, index)?.unwrap_or_default();
+    let indexed = fetched.iter().map(|m| *m.message_id()).collect::<HashSet<_>>();
+
+    // Messages indexed before the arrival ordered index existed are only found in the legacy index. They are ordered by
+    // the arrival timestamp of their metadata, or first if it is gone.
+    for message_id in Fetch::<PaddedIndex, Vec<MessageId>>::fetch(storage, index)?.unwrap_or_default() {
+        if !indexed.contains(&message_id) {
+            let arrival_timestamp = Fetch::<MessageId, MessageMetadata>::fetch(storage, &message_id)?
+                .map_or(0, |metadata| metadata.arrival_timestamp());
+
+            fetched.push(IndexedMessage::new(arrival_timestamp, message_id));
+        }
+    }
+
+    fetched.sort_unstable();
+
+    let count = fetched.len();
+
+    Ok((
+        count,
+        fetched
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(IndexedMessage::message_id)
+            .copied()
+            .collect(),
+    ))
+}
+
 pub(crate) fn messages_find<B: StorageBackend>(
     index: String,
+    offset: usize,
+    limit: usize,
     storage: ResourceHandle<B>,
 ) -> Result<impl Reply, Rejection> {
     let index_bytes = hex::decode(index.clone())
         .map_err(|_| reject::custom(CustomRejection::BadRequest("Invalid index".to_owned())))?;
     let hashed_index = IndexationPayload::new(&index_bytes, &[]).unwrap().padded_index();
 
-    let mut fetched = match Fetch::<PaddedIndex, Vec<MessageId>>::fetch(&*storage, &hashed_index).map_err(|_| {
+    let (count, fetched) = messages_by_index(&*storage, &hashed_index, offset, limit).map_err(|_| {
         reject::custom(CustomRejection::ServiceUnavailable(
             "can not fetch from storage".to_string(),
         ))
-    })? {
-        Some(ids) => ids,
-        None => vec![],
-    };
-
-    let count = fetched.len();
-    let max_results = 1000;
-    fetched.truncate(max_results);
+    })?;
 
     Ok(warp::reply::json(&SuccessBody::new(MessagesFindResponse {
         index,
-        max_results,
+        max_results: MAX_RESULTS,
         count,
         message_ids: fetched.iter().map(|id| id.to_string()).collect(),
     })))
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_storage::{access::Insert, backend::StorageBackend as _};
+    use bee_storage_memory::storage::Storage;
+
+    #[test]
+    fn messages_by_index_pages() {
+        let storage = Storage::start(()).unwrap();
+        let index = IndexationPayload::new(b"bee", &[]).unwrap().padded_index();
+        let other_index = IndexationPayload::new(b"wasp", &[]).unwrap().padded_index();
+
+        // Arrived out of the lexicographic order of their ids.
+        let message_ids = [3u8, 0, 4, 1, 2].map(|i| MessageId::new([i; 32]));
+        for (arrival_timestamp, message_id) in (1000..).zip(message_ids) {
+            let indexed_message = IndexedMessage::new(arrival_timestamp, message_id);
+            Insert::<(PaddedIndex, MessageId), ()>::insert(&storage, &(index, message_id), &()).unwrap();
+            Insert::<(PaddedIndex, IndexedMessage), ()>::insert(&storage, &(index, indexed_message), &()).unwrap();
+        }
+        let indexed_message = IndexedMessage::new(1000, MessageId::new([9; 32]));
+        Insert::<(PaddedIndex, IndexedMessage), ()>::insert(&storage, &(other_index, indexed_message), &()).unwrap();
+
+        let mut paged = Vec::new();
+        for offset in (0..6).step_by(2) {
+            let (count, page) = messages_by_index(&storage, &index, offset, 2).unwrap();
+            assert_eq!(count, 5);
+            assert!(page.len() <= 2);
+            paged.extend(page);
+        }
+        assert_eq!(paged, message_ids);
+
+        let (count, page) = messages_by_index(&storage, &index, 5, 2).unwrap();
+        assert_eq!(count, 5);
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn messages_only_in_the_legacy_index_are_found() {
+        let storage = Storage::start(()).unwrap();
+        let index = IndexationPayload::new(b"bee", &[]).unwrap().padded_index();
+
+        // Indexed before the arrival ordered index existed, one of them without metadata anymore.
+        let legacy_with_metadata = MessageId::new([1; 32]);
+        let legacy_without_metadata = MessageId::new([2; 32]);
+        let metadata = MessageMetadata::new(Default::default(), None, 2000, 0, 0, None, None, Default::default());
+        Insert::<MessageId, MessageMetadata>::insert(&storage, &legacy_with_metadata, &metadata).unwrap();
+        for message_id in [legacy_with_metadata, legacy_without_metadata] {
+            Insert::<(PaddedIndex, MessageId), ()>::insert(&storage, &(index, message_id), &()).unwrap();
+        }
+
+        let indexed = [(1000, MessageId::new([3; 32])), (3000, MessageId::new([0; 32]))];
+        for (arrival_timestamp, message_id) in indexed {
+            let indexed_message = IndexedMessage::new(arrival_timestamp, message_id);
+            Insert::<(PaddedIndex, MessageId), ()>::insert(&storage, &(index, message_id), &()).unwrap();
+            Insert::<(PaddedIndex, IndexedMessage), ()>::insert(&storage, &(index, indexed_message), &()).unwrap();
+        }
+
+        let (count, page) = messages_by_index(&storage, &index, 0, 10).unwrap();
+        assert_eq!(count, 4);
+        assert_eq!(
+            page,
+            [
+                legacy_without_metadata,
+                indexed[0].1,
+                legacy_with_metadata,
+                indexed[1].1
+            ]
+        );
+    }
+}
@@ -21,15 +21,17 @@ pub mod receipts;
 pub mod receipts_at;
 pub mod remove_peer;
 pub mod submit_message;
+pub mod submit_trusted_message;
 pub mod tips;
 pub mod transaction_included_message;
 pub mod treasury;
+pub mod update_network_id;
 
 use crate::endpoints::{config::RestApiConfig, storage::StorageBackend, Bech32Hrp, NetworkId};
 
 use bee_gossip::NetworkCommandSender;
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
-use bee_protocol::workers::{config::ProtocolConfig, MessageSubmitterWorkerEvent, PeerManager};
+use bee_protocol::workers::{config::ProtocolConfig, AcceptedNetworkIds, MessageSubmitterWorkerEvent, PeerManager};
 use bee_runtime::{node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
 
@@ -50,6 +52,7 @@ pub(crate) fn filter<B: StorageBackend>(
     storage: ResourceHandle<B>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
     network_id: NetworkId,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
     bech32_hrp: Bech32Hrp,
     rest_api_config: RestApiConfig,
     protocol_config: ProtocolConfig,
@@ -161,11 +164,17 @@ pub(crate) fn filter<B: StorageBackend>(
         public_routes.clone(),
         allowed_ips.clone(),
         tangle.clone(),
-        message_submitter,
-        network_id,
+        message_submitter.clone(),
+        accepted_network_ids.clone(),
         rest_api_config,
         protocol_config,
     ))
+    .or(submit_trusted_message::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+        message_submitter,
+    ))
     .or(tips::filter(public_routes.clone(), allowed_ips.clone(), tangle.clone()))
     .or(treasury::filter(
         public_routes.clone(),
@@ -173,9 +182,14 @@ pub(crate) fn filter<B: StorageBackend>(
         storage.clone(),
     ))
     .or(transaction_included_message::filter(
-        public_routes,
-        allowed_ips,
+        public_routes.clone(),
+        allowed_ips.clone(),
         storage,
         tangle,
     ))
+    .or(update_network_id::filter(
+        public_routes,
+        allowed_ips,
+        accepted_network_ids,
+    ))
 }
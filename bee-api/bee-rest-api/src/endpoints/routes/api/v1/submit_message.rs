@@ -4,11 +4,12 @@
 use crate::{
     endpoints::{
         config::{RestApiConfig, ROUTE_SUBMIT_MESSAGE, ROUTE_SUBMIT_MESSAGE_RAW},
-        filters::{with_message_submitter, with_network_id, with_protocol_config, with_rest_api_config, with_tangle},
+        filters::{
+            with_accepted_network_ids, with_message_submitter, with_protocol_config, with_rest_api_config, with_tangle,
+        },
         permission::has_permission,
         rejection::CustomRejection,
         storage::StorageBackend,
-        NetworkId,
     },
     types::{body::SuccessBody, dtos::PayloadDto, responses::SubmitMessageResponse},
 };
@@ -16,7 +17,7 @@ use crate::{
 use bee_common::packable::Packable;
 use bee_message::{parents::Parents, payload::Payload, Message, MessageBuilder, MessageId};
 use bee_pow::providers::{miner::MinerBuilder, NonceProviderBuilder};
-use bee_protocol::workers::{config::ProtocolConfig, MessageSubmitterError, MessageSubmitterWorkerEvent};
+use bee_protocol::workers::{config::ProtocolConfig, AcceptedNetworkIds, MessageSubmitterWorkerEvent, SubmitOutcome};
 use bee_runtime::resource::ResourceHandle;
 use bee_tangle::Tangle;
 
@@ -37,7 +38,7 @@ pub(crate) fn filter<B: StorageBackend>(
     allowed_ips: Box<[IpAddr]>,
     tangle: ResourceHandle<Tangle<B>>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
-    network_id: NetworkId,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
     rest_api_config: RestApiConfig,
     protocol_config: ProtocolConfig,
 ) -> BoxedFilter<(impl Reply,)> {
@@ -53,7 +54,7 @@ pub(crate) fn filter<B: StorageBackend>(
                 .and(warp::body::json())
                 .and(with_tangle(tangle.clone()))
                 .and(with_message_submitter(message_submitter.clone()))
-                .and(with_network_id(network_id))
+                .and(with_accepted_network_ids(accepted_network_ids))
                 .and(with_rest_api_config(rest_api_config))
                 .and(with_protocol_config(protocol_config))
                 .and_then(submit_message))
@@ -71,7 +72,7 @@ pub(crate) async fn submit_message<B: StorageBackend>(
     value: JsonValue,
     tangle: ResourceHandle<Tangle<B>>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
-    network_id: NetworkId,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
     rest_api_config: RestApiConfig,
     protocol_config: ProtocolConfig,
 ) -> Result<impl Reply, Rejection> {
@@ -84,7 +85,7 @@ pub(crate) async fn submit_message<B: StorageBackend>(
     // processing
 
     let network_id = if network_id_v.is_null() {
-        network_id.1
+        accepted_network_ids.current()
     } else {
         network_id_v
             .as_str()
@@ -161,7 +162,7 @@ pub(crate) async fn submit_message<B: StorageBackend>(
     };
 
     let message = build_message(network_id, parents, payload, nonce, rest_api_config, protocol_config).await?;
-    let message_id = forward_to_message_submitter(message, tangle, message_submitter).await?;
+    let message_id = forward_to_message_submitter(message, tangle, message_submitter, false).await?;
 
     Ok(warp::reply::with_status(
         warp::reply::json(&SuccessBody::new(SubmitMessageResponse {
@@ -221,6 +222,16 @@ pub(crate) async fn submit_message_raw<B: StorageBackend>(
     buf: warp::hyper::body::Bytes,
     tangle: ResourceHandle<Tangle<B>>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+) -> Result<impl Reply, Rejection> {
+    submit_message_bytes(buf, tangle, message_submitter, false).await
+}
+
+// Submits the bytes of a message, skipping the check of its PoW if it is `trusted`.
+pub(crate) async fn submit_message_bytes<B: StorageBackend>(
+    buf: warp::hyper::body::Bytes,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    trusted: bool,
 ) -> Result<impl Reply, Rejection> {
     let message = Message::unpack(&mut &(*buf)).map_err(|e| {
         reject::custom(CustomRejection::BadRequest(format!(
@@ -228,7 +239,7 @@ pub(crate) async fn submit_message_raw<B: StorageBackend>(
             e
         )))
     })?;
-    let message_id = forward_to_message_submitter(message, tangle, message_submitter).await?;
+    let message_id = forward_to_message_submitter(message, tangle, message_submitter, trusted).await?;
     Ok(warp::reply::with_status(
         warp::reply::json(&SuccessBody::new(SubmitMessageResponse {
             message_id: message_id.to_string(),
@@ -241,6 +252,7 @@ pub(crate) async fn forward_to_message_submitter<B: StorageBackend>(
     message: Message,
     tangle: ResourceHandle<Tangle<B>>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    trusted: bool,
 ) -> Result<MessageId, Rejection> {
     let (message_id, message_bytes) = message.id();
 
@@ -248,12 +260,13 @@ pub(crate) async fn forward_to_message_submitter<B: StorageBackend>(
         return Ok(message_id);
     }
 
-    let (notifier, waiter) = oneshot::channel::<Result<MessageId, MessageSubmitterError>>();
+    let (notifier, waiter) = oneshot::channel::<SubmitOutcome>();
 
     message_submitter
         .send(MessageSubmitterWorkerEvent {
             message: message_bytes,
             notifier,
+            trusted,
         })
         .map_err(|e| {
             error!("can not submit message: {}", e);
@@ -262,12 +275,16 @@ pub(crate) async fn forward_to_message_submitter<B: StorageBackend>(
             ))
         })?;
 
-    match waiter.await.map_err(|e| {
-        error!("can not submit message: {}", e);
-        reject::custom(CustomRejection::ServiceUnavailable(
-            "can not submit message".to_string(),
-        ))
-    })? {
+    match waiter
+        .await
+        .map_err(|e| {
+            error!("can not submit message: {}", e);
+            reject::custom(CustomRejection::ServiceUnavailable(
+                "can not submit message".to_string(),
+            ))
+        })?
+        .into_result(message_id)
+    {
         Ok(message_id) => Ok(message_id),
         Err(e) => Err(reject::custom(CustomRejection::BadRequest(format!(
             "can not submit message: message is invalid: {}",
@@ -0,0 +1,47 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::endpoints::{
+    config::ROUTE_SUBMIT_TRUSTED_MESSAGE,
+    filters::{with_message_submitter, with_tangle},
+    permission::has_permission,
+    routes::api::v1::submit_message::submit_message_bytes,
+    storage::StorageBackend,
+};
+
+use bee_protocol::workers::MessageSubmitterWorkerEvent;
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+
+use tokio::sync::mpsc;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use std::net::IpAddr;
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("messages"))
+        .and(warp::path("trusted"))
+        .and(warp::path::end())
+}
+
+// Submits the bytes of a message without checking its PoW. Such messages are not broadcast, and the route is not
+// public by default.
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(warp::header::exact("content-type", "application/octet-stream"))
+        .and(has_permission(ROUTE_SUBMIT_TRUSTED_MESSAGE, public_routes, allowed_ips))
+        .and(warp::body::bytes())
+        .and(with_tangle(tangle))
+        .and(with_message_submitter(message_submitter))
+        .and_then(|buf, tangle, message_submitter| async move {
+            submit_message_bytes(buf, tangle, message_submitter, true).await
+        })
+        .boxed()
+}
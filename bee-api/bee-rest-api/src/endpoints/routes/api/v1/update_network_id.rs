@@ -0,0 +1,80 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    endpoints::{
+        config::ROUTE_UPDATE_NETWORK_ID, filters::with_accepted_network_ids, permission::has_permission,
+        rejection::CustomRejection,
+    },
+    types::{body::SuccessBody, responses::UpdateNetworkIdResponse},
+};
+
+use bee_protocol::workers::AcceptedNetworkIds;
+use bee_runtime::resource::ResourceHandle;
+
+use serde_json::Value as JsonValue;
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use std::{net::IpAddr, time::Duration};
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path().and(warp::path("network-id")).and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::put())
+        .and(has_permission(ROUTE_UPDATE_NETWORK_ID, public_routes, allowed_ips))
+        .and(warp::body::json())
+        .and(with_accepted_network_ids(accepted_network_ids))
+        .and_then(|value, accepted_network_ids| async move { update_network_id(value, accepted_network_ids) })
+        .boxed()
+}
+
+pub(crate) fn update_network_id(
+    value: JsonValue,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
+) -> Result<impl Reply, Rejection> {
+    let network_id_v = &value["networkId"];
+    let transition_window_v = &value["transitionWindow"];
+
+    let network_id = network_id_v
+        .as_str()
+        .ok_or_else(|| {
+            reject::custom(CustomRejection::BadRequest(
+                "invalid network id: expected an u64-string".to_string(),
+            ))
+        })?
+        .parse::<u64>()
+        .map_err(|_| {
+            reject::custom(CustomRejection::BadRequest(
+                "invalid network id: expected an u64-string".to_string(),
+            ))
+        })?;
+
+    let transition_window = if transition_window_v.is_null() {
+        0
+    } else {
+        transition_window_v.as_u64().ok_or_else(|| {
+            reject::custom(CustomRejection::BadRequest(
+                "invalid transition window: expected a number of seconds".to_string(),
+            ))
+        })?
+    };
+
+    let previous_network_id = accepted_network_ids.current();
+    if !accepted_network_ids.update(network_id, Duration::from_secs(transition_window)) {
+        return Err(reject::custom(CustomRejection::BadRequest(
+            "invalid transition window: too large".to_string(),
+        )));
+    }
+
+    Ok(warp::reply::json(&SuccessBody::new(UpdateNetworkIdResponse {
+        network_id: network_id.to_string(),
+        previous_network_id: previous_network_id.to_string(),
+    })))
+}
@@ -9,7 +9,8 @@ use crate::endpoints::{config::RestApiConfig, storage::StorageBackend, Bech32Hrp
 use bee_gossip::NetworkCommandSender;
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, AcceptedNetworkIds, DecisionLog, MessageRequesterWorker, MessageSubmitterWorkerEvent,
+    PeerManager, RequestedMessages,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -27,6 +28,7 @@ pub(crate) fn filter_all<B: StorageBackend>(
     storage: ResourceHandle<B>,
     message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
     network_id: NetworkId,
+    accepted_network_ids: ResourceHandle<AcceptedNetworkIds>,
     bech32_hrp: Bech32Hrp,
     rest_api_config: RestApiConfig,
     protocol_config: ProtocolConfig,
@@ -36,6 +38,7 @@ pub(crate) fn filter_all<B: StorageBackend>(
     bus: ResourceHandle<Bus<'static>>,
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
+    decision_log: ResourceHandle<DecisionLog>,
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     api::filter(
@@ -45,6 +48,7 @@ pub(crate) fn filter_all<B: StorageBackend>(
         storage,
         message_submitter,
         network_id,
+        accepted_network_ids,
         bech32_hrp,
         rest_api_config,
         protocol_config,
@@ -54,6 +58,7 @@ pub(crate) fn filter_all<B: StorageBackend>(
         bus,
         message_requester,
         requested_messages,
+        decision_log,
         consensus_worker,
     )
     .or(health::filter(public_routes, allowed_ips, tangle, peer_manager))
@@ -9,10 +9,12 @@ use bee_storage::{
     access::{AsIterator, Fetch},
     backend,
 };
+use bee_tangle::indexed_message::IndexedMessage;
 
 pub trait StorageBackend:
     backend::StorageBackend
     + Fetch<PaddedIndex, Vec<MessageId>>
+    + Fetch<PaddedIndex, Vec<IndexedMessage>>
     + Fetch<Ed25519Address, Vec<OutputId>>
     + Fetch<MilestoneIndex, OutputDiff>
     + Fetch<MilestoneIndex, Vec<Receipt>>
@@ -26,6 +28,7 @@ pub trait StorageBackend:
 impl<T> StorageBackend for T where
     T: backend::StorageBackend
         + Fetch<PaddedIndex, Vec<MessageId>>
+        + Fetch<PaddedIndex, Vec<IndexedMessage>>
         + Fetch<Ed25519Address, Vec<OutputId>>
         + Fetch<MilestoneIndex, OutputDiff>
         + Fetch<MilestoneIndex, Vec<Receipt>>
@@ -861,6 +861,7 @@ impl From<&Peer> for PeerDto {
                     sent_heartbeats: peer.metrics().heartbeats_sent(),
                     dropped_packets: 0,
                 },
+                reputation: peer.reputation(),
             }),
         }
     }
@@ -871,6 +872,7 @@ impl From<&Peer> for PeerDto {
 pub struct GossipDto {
     pub heartbeat: HeartbeatDto,
     pub metrics: MetricsDto,
+    pub reputation: i64,
 }
 
 /// Describes the relation with the peer.
@@ -926,6 +928,29 @@ pub struct MetricsDto {
     pub dropped_packets: u64,
 }
 
+/// Describes an outstanding message request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestedMessageDto {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+    /// Number of seconds since the message was first requested.
+    pub age: u64,
+}
+
+/// Describes a decision of the processor on a message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionRecordDto {
+    #[serde(rename = "messageId", skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(rename = "peerId", skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    /// Unix timestamp, in seconds, of the decision.
+    pub timestamp: u64,
+    pub decision: String,
+}
+
 /// Describes a receipt.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ReceiptDto {
@@ -3,7 +3,9 @@
 
 use crate::types::{
     body::BodyInner,
-    dtos::{LedgerInclusionStateDto, MessageDto, OutputDto, PeerDto, ReceiptDto},
+    dtos::{
+        DecisionRecordDto, LedgerInclusionStateDto, MessageDto, OutputDto, PeerDto, ReceiptDto, RequestedMessageDto,
+    },
 };
 
 use serde::{Deserialize, Serialize};
@@ -248,6 +250,40 @@ pub struct PeerResponse(pub PeerDto);
 
 impl BodyInner for PeerResponse {}
 
+/// Response of PUT /api/v1/network-id.
+/// Returns the new and the previous network id of the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpdateNetworkIdResponse {
+    #[serde(rename = "networkId")]
+    pub network_id: String,
+    #[serde(rename = "previousNetworkId")]
+    pub previous_network_id: String,
+}
+
+impl BodyInner for UpdateNetworkIdResponse {}
+
+/// Response of GET /api/plugins/debug/requests.
+/// Returns the outstanding message requests, from the oldest to the most recent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestedMessagesResponse {
+    #[serde(rename = "maxResults")]
+    pub max_results: usize,
+    pub count: usize,
+    pub requests: Vec<RequestedMessageDto>,
+}
+
+impl BodyInner for RequestedMessagesResponse {}
+
+/// Response of GET /api/plugins/debug/decisions.
+/// Returns the decisions of the processor on the latest messages, from the oldest to the most recent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionsResponse {
+    pub count: usize,
+    pub decisions: Vec<DecisionRecordDto>,
+}
+
+impl BodyInner for DecisionsResponse {}
+
 /// Response of GET /api/plugins/debug/whiteflag.
 /// Returns the computed merkle tree hash for the given white flag traversal.
 #[derive(Clone, Debug, Serialize, Deserialize)]
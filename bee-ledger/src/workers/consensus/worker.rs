@@ -194,6 +194,7 @@ where
             .await;
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
+            milestone_index: milestone.essence().index(),
         });
     }
 
@@ -206,6 +207,7 @@ where
             .await;
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
+            milestone_index: milestone.essence().index(),
         });
     }
 
@@ -218,6 +220,7 @@ where
             .await;
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
+            milestone_index: milestone.essence().index(),
         });
     }
 
@@ -40,6 +40,8 @@ pub struct MilestoneConfirmed {
 pub struct MessageReferenced {
     /// The message identifier of the message.
     pub message_id: MessageId,
+    /// The index of the milestone referencing the message.
+    pub milestone_index: MilestoneIndex,
 }
 
 /// An event that indicates that an output was consumed.
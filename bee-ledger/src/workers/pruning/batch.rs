@@ -25,7 +25,8 @@ use bee_message::{
 };
 use bee_storage::access::{Batch, Fetch};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage, Tangle,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage, Tangle,
 };
 
 use hashbrown::{HashMap, HashSet};
@@ -371,6 +372,8 @@ fn prune_message_and_metadata<S: StorageBackend>(
     Batch::<MessageId, Message>::batch_delete(storage, batch, message_id).map_err(|e| Error::Storage(Box::new(e)))?;
     Batch::<MessageId, MessageMetadata>::batch_delete(storage, batch, message_id)
         .map_err(|e| Error::Storage(Box::new(e)))?;
+    Batch::<MessageId, RawMessage>::batch_delete(storage, batch, message_id)
+        .map_err(|e| Error::Storage(Box::new(e)))?;
 
     Ok(())
 }
@@ -393,6 +396,27 @@ fn prune_indexation_data<S: StorageBackend>(
     Batch::<(PaddedIndex, MessageId), ()>::batch_delete(storage, batch, index_message_id)
         .map_err(|e| Error::Storage(Box::new(e)))?;
 
+    let (padded_index, message_id) = index_message_id;
+
+    // The indexed message is keyed by the arrival timestamp held in the metadata. Without the metadata, it is looked up
+    // among the indexed messages, where being among the oldest of its index, a pruned message comes first.
+    let indexed_messages = match Fetch::<MessageId, MessageMetadata>::fetch(storage, message_id)
+        .map_err(|e| Error::Storage(Box::new(e)))?
+    {
+        Some(metadata) => vec![IndexedMessage::new(metadata.arrival_timestamp(), *message_id)],
+        None => Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, padded_index)
+            .map_err(|e| Error::Storage(Box::new(e)))?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|indexed_message| indexed_message.message_id() == message_id)
+            .collect(),
+    };
+
+    for indexed_message in indexed_messages {
+        Batch::<(PaddedIndex, IndexedMessage), ()>::batch_delete(storage, batch, &(*padded_index, indexed_message))
+            .map_err(|e| Error::Storage(Box::new(e)))?;
+    }
+
     Ok(())
 }
 
@@ -23,7 +23,8 @@ use bee_storage::{
     backend,
 };
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 use std::collections::HashMap;
@@ -44,9 +45,11 @@ pub trait StorageBackend:
     + Batch<SolidEntryPoint, MilestoneIndex>
     + Batch<(MilestoneIndex, UnreferencedMessage), ()>
     + Batch<(PaddedIndex, MessageId), ()>
+    + Batch<(PaddedIndex, IndexedMessage), ()>
     + Batch<(MessageId, MessageId), ()>
     + Batch<MessageId, Message>
     + Batch<MessageId, MessageMetadata>
+    + Batch<MessageId, RawMessage>
     + Batch<MilestoneIndex, Milestone>
     + Exist<Unspent, ()>
     + Fetch<(), SnapshotInfo>
@@ -59,6 +62,7 @@ pub trait StorageBackend:
     + Fetch<MilestoneIndex, Vec<Receipt>>
     + Fetch<MilestoneIndex, Vec<UnreferencedMessage>>
     + Fetch<MilestoneIndex, OutputDiff>
+    + Fetch<PaddedIndex, Vec<IndexedMessage>>
     + Insert<(), SnapshotInfo>
     + Insert<(), LedgerIndex>
     + Insert<(bool, TreasuryOutput), ()>
@@ -85,9 +89,11 @@ impl<T> StorageBackend for T where
         + Batch<SolidEntryPoint, MilestoneIndex>
         + Batch<(MilestoneIndex, UnreferencedMessage), ()>
         + Batch<(PaddedIndex, MessageId), ()>
+        + Batch<(PaddedIndex, IndexedMessage), ()>
         + Batch<(MessageId, MessageId), ()>
         + Batch<MessageId, Message>
         + Batch<MessageId, MessageMetadata>
+        + Batch<MessageId, RawMessage>
         + Batch<MilestoneIndex, Milestone>
         + Exist<Unspent, ()>
         + Fetch<(), SnapshotInfo>
@@ -100,6 +106,7 @@ impl<T> StorageBackend for T where
         + Fetch<MilestoneIndex, Vec<Receipt>>
         + Fetch<MilestoneIndex, Vec<UnreferencedMessage>>
         + Fetch<MilestoneIndex, OutputDiff>
+        + Fetch<PaddedIndex, Vec<IndexedMessage>>
         + Insert<(), SnapshotInfo>
         + Insert<(), LedgerIndex>
         + Insert<(bool, TreasuryOutput), ()>
@@ -15,6 +15,7 @@
 //!          "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
 //!     ],
 //!     "entryNodesPreferIPv6": true,
+//!     "entryNodeWeights": ["iotaPHdAn7eueBnXtikZMwhfPXaeGJGXDt4RBuLuGgb:3"],
 //! }
 //! ```
 //!
@@ -30,14 +31,18 @@
 //!     "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
 //! ]
 //! entry_nodes_prefer_ipv6 = true
+//! entry_node_weights = ["iotaPHdAn7eueBnXtikZMwhfPXaeGJGXDt4RBuLuGgb:3"]
 //! ```
 
-use crate::multiaddr::AutopeeringMultiaddr;
+use crate::{
+    multiaddr::{self, AutopeeringMultiaddr},
+    peer::PeerId,
+};
 
 use serde::{Deserialize, Serialize};
 
 use std::{
-    fmt::Debug,
+    fmt::{self, Debug},
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
 };
@@ -49,7 +54,169 @@ const AUTOPEERING_BIND_PORT_DEFAULT: u16 = 14626;
 const ENTRYNODES_PREFER_IPV6_DEFAULT: bool = false;
 const RUN_AS_ENTRYNODE_DEFAULT: bool = false;
 const DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT: bool = false;
+const SALT_LIFETIME_SECS_DEFAULT: u64 = 2 * 60 * 60;
 const PEER_STORAGE_PATH_DEFAULT: &str = "./storage/mainnet/peers";
+const VERIFICATION_RETRIES_DEFAULT: usize = 2;
+const VERIFICATION_RETRY_TIMEOUT_MILLIS_DEFAULT: u64 = 200;
+const RETAIN_RARE_SERVICES_DEFAULT: bool = false;
+const VERIFIED_PEERS_THRESHOLD_DEFAULT: usize = 3;
+const DISCOVERY_DEDUP_WINDOW_SECS_DEFAULT: u64 = 0;
+const QUARANTINE_SECS_DEFAULT: u64 = 0;
+const QUARANTINE_VERIFICATIONS_DEFAULT: usize = 0;
+const QUERY_COOLDOWN_ROUNDS_DEFAULT: usize = 0;
+const PEER_ESTIMATED_TIME_DEFAULT: bool = false;
+const QUERY_BACKOFF_THRESHOLD_DEFAULT: usize = 0;
+const PEERS_PER_QUERY_DEFAULT: usize = 2;
+const MAX_ACTIVE_PEERS_DEFAULT: usize = 1000;
+const PEER_STALENESS_SECS_DEFAULT: u64 = 0;
+const DISCOVERY_TASK_TIMEOUT_SECS_DEFAULT: u64 = 10;
+const REVERIFICATION_MAX_FAILURES_DEFAULT: usize = 1;
+const REVERIFICATION_BACKOFF_SECS_DEFAULT: u64 = 10;
+const SCHEDULE_JITTER_PERCENT_DEFAULT: u8 = 0;
+const MAX_CONCURRENT_VERIFICATIONS_DEFAULT: usize = 8;
+
+/// Defines how the active peer to evict is picked when making room for another one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionMode {
+    /// Always evicts the least verified peer (the oldest, in case of a tie).
+    #[default]
+    Deterministic,
+    /// Evicts a random peer, with peers verified less often being more likely to be evicted.
+    Weighted,
+}
+
+/// Defines how the peers to query for new peers are selected among the verified ones, besides the latest verified
+/// peer, which is always queried.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStrategy {
+    /// Picks randomly among the peers that returned the most new peers the last time they were queried.
+    #[default]
+    LastNewPeers,
+    /// Picks peers spread across the ID space, i.e. at varying distances from the local peer.
+    Distance,
+    /// Picks peers spread across the ID space among the ones that returned the most new peers.
+    Hybrid,
+}
+
+/// Defines how verification requests from unknown peers are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownPeerPolicy {
+    /// Adds the peer to the replacement list like any other newly discovered peer, and verifies it in return.
+    #[default]
+    Add,
+    /// Never adds the peer, but still responds to its request.
+    Ignore,
+}
+
+/// Defines how peers are handled that turn out to belong to a different network during verification.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForeignPeerPolicy {
+    /// Keeps the peer until it is rejected on the gossip level.
+    #[default]
+    Keep,
+    /// Immediately removes the peer.
+    Evict,
+    /// Immediately removes the peer, and ignores it from then on.
+    Denylist,
+}
+
+/// Identifies peers in the peer allowlist or denylist, either by their base58 encoded public key, or by their IP
+/// address.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PeerFilterEntry {
+    /// The peer with that identity.
+    PeerId(Box<PeerId>),
+    /// All peers with that IP address.
+    IpAddr(IpAddr),
+}
+
+impl TryFrom<String> for PeerFilterEntry {
+    type Error = String;
+
+    fn try_from(entry: String) -> Result<Self, Self::Error> {
+        if let Ok(ip_addr) = entry.parse() {
+            Ok(Self::IpAddr(ip_addr))
+        } else if let Ok(public_key) = multiaddr::base58_to_pubkey(&entry) {
+            Ok(Self::PeerId(Box::new(PeerId::from_public_key(public_key))))
+        } else {
+            Err(format!("invalid peer filter entry: {}", entry))
+        }
+    }
+}
+
+impl From<PeerFilterEntry> for String {
+    fn from(entry: PeerFilterEntry) -> Self {
+        entry.to_string()
+    }
+}
+
+impl fmt::Display for PeerFilterEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PeerId(peer_id) => write!(f, "{}", multiaddr::pubkey_to_base58(peer_id.public_key())),
+            Self::IpAddr(ip_addr) => write!(f, "{}", ip_addr),
+        }
+    }
+}
+
+/// The weight of an entry node, written as `<public key>:<weight>`. Entry nodes with higher weights are preferred
+/// when entry nodes are queried for lack of verified peers, and entry nodes without a weight have a weight of 1.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct EntryNodeWeight {
+    peer_id: PeerId,
+    weight: u32,
+}
+
+impl EntryNodeWeight {
+    /// The identity of the entry node.
+    pub fn peer_id(&self) -> &PeerId {
+        &self.peer_id
+    }
+
+    /// The weight of the entry node, where 0 means it is never queried for lack of verified peers.
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+}
+
+impl TryFrom<String> for EntryNodeWeight {
+    type Error = String;
+
+    fn try_from(entry: String) -> Result<Self, Self::Error> {
+        entry
+            .split_once(':')
+            .and_then(|(public_key, weight)| {
+                Some(Self {
+                    peer_id: PeerId::from_public_key(multiaddr::base58_to_pubkey(public_key).ok()?),
+                    weight: weight.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| format!("invalid entry node weight: {}", entry))
+    }
+}
+
+impl From<EntryNodeWeight> for String {
+    fn from(entry: EntryNodeWeight) -> Self {
+        entry.to_string()
+    }
+}
+
+impl fmt::Display for EntryNodeWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}",
+            multiaddr::pubkey_to_base58(self.peer_id.public_key()),
+            self.weight
+        )
+    }
+}
 
 /// The autopeering config.
 #[derive(Clone, Debug)]
@@ -58,9 +225,37 @@ pub struct AutopeeringConfig {
     bind_addr: SocketAddr,
     entry_nodes: Vec<AutopeeringMultiaddr>,
     entry_nodes_prefer_ipv6: bool,
+    entry_node_weights: Vec<EntryNodeWeight>,
     run_as_entry_node: bool,
     drop_neighbors_on_salt_update: bool,
+    salt_lifetime_secs: u64,
     peer_storage_path: PathBuf,
+    verification_retries: usize,
+    verification_retry_timeout_millis: u64,
+    retain_rare_services: bool,
+    verified_peers_threshold: usize,
+    discovery_dedup_window_secs: u64,
+    quarantine_secs: u64,
+    quarantine_verifications: usize,
+    unknown_peer_policy: UnknownPeerPolicy,
+    eviction_mode: EvictionMode,
+    selection_strategy: SelectionStrategy,
+    foreign_peer_policy: ForeignPeerPolicy,
+    relay_addr: Option<SocketAddr>,
+    query_cooldown_rounds: usize,
+    peer_estimated_time: bool,
+    query_backoff_threshold: usize,
+    peers_per_query: usize,
+    max_active_peers: usize,
+    query_rng_seed: Option<u64>,
+    peer_staleness_secs: u64,
+    discovery_task_timeout_secs: u64,
+    reverification_max_failures: usize,
+    reverification_backoff_secs: u64,
+    schedule_jitter_percent: u8,
+    max_concurrent_verifications: usize,
+    peer_allowlist: Vec<PeerFilterEntry>,
+    peer_denylist: Vec<PeerFilterEntry>,
 }
 
 impl AutopeeringConfig {
@@ -84,6 +279,11 @@ impl AutopeeringConfig {
         self.entry_nodes_prefer_ipv6
     }
 
+    /// The weights of the entry nodes, preferring the ones with higher weights when querying them.
+    pub fn entry_node_weights(&self) -> &[EntryNodeWeight] {
+        &self.entry_node_weights
+    }
+
     /// Whether the node should run as an entry node.
     pub fn run_as_entry_node(&self) -> bool {
         self.run_as_entry_node
@@ -94,6 +294,11 @@ impl AutopeeringConfig {
         self.drop_neighbors_on_salt_update
     }
 
+    /// The number of seconds the salts are valid for, after which they are replaced by new ones.
+    pub fn salt_lifetime_secs(&self) -> u64 {
+        self.salt_lifetime_secs
+    }
+
     /// Reduces this config to its list of entry node addresses.
     pub fn into_entry_nodes(self) -> Vec<AutopeeringMultiaddr> {
         self.entry_nodes
@@ -104,6 +309,142 @@ impl AutopeeringConfig {
         &self.peer_storage_path
     }
 
+    /// The number of times a verification request is resent if the response doesn't arrive in time.
+    pub fn verification_retries(&self) -> usize {
+        self.verification_retries
+    }
+
+    /// The time in milliseconds a resent verification request waits for its response, which is usually shorter than
+    /// for the first request.
+    pub fn verification_retry_timeout_millis(&self) -> u64 {
+        self.verification_retry_timeout_millis
+    }
+
+    /// Whether peers offering rare services should be preferred when the active peer list is full.
+    pub fn retain_rare_services(&self) -> bool {
+        self.retain_rare_services
+    }
+
+    /// The number of verified peers below which the peer count is considered low.
+    pub fn verified_peers_threshold(&self) -> usize {
+        self.verified_peers_threshold
+    }
+
+    /// The time window in seconds within which identical discovery responses of a peer are not processed again.
+    pub fn discovery_dedup_window_secs(&self) -> u64 {
+        self.discovery_dedup_window_secs
+    }
+
+    /// The time in seconds newly discovered peers have to stay in quarantine before they can become active peers.
+    pub fn quarantine_secs(&self) -> u64 {
+        self.quarantine_secs
+    }
+
+    /// The number of verifications newly discovered peers have to pass in quarantine before they can become active
+    /// peers.
+    pub fn quarantine_verifications(&self) -> usize {
+        self.quarantine_verifications
+    }
+
+    /// How verification requests from unknown peers are handled.
+    pub fn unknown_peer_policy(&self) -> UnknownPeerPolicy {
+        self.unknown_peer_policy
+    }
+
+    /// How the active peer to evict is picked when making room for a peer offering a rare service.
+    pub fn eviction_mode(&self) -> EvictionMode {
+        self.eviction_mode
+    }
+
+    /// How the peers to query for new peers are selected.
+    pub fn selection_strategy(&self) -> SelectionStrategy {
+        self.selection_strategy
+    }
+
+    /// How peers belonging to a different network are handled.
+    pub fn foreign_peer_policy(&self) -> ForeignPeerPolicy {
+        self.foreign_peer_policy
+    }
+
+    /// The address of the relay discovery and verification requests are sent through, if any.
+    pub fn relay_addr(&self) -> Option<SocketAddr> {
+        self.relay_addr
+    }
+
+    /// The number of query rounds during which a queried peer is only selected again if there are too few other peers.
+    pub fn query_cooldown_rounds(&self) -> usize {
+        self.query_cooldown_rounds
+    }
+
+    /// Whether request timestamps are validated against the local time corrected by the clock offsets of known peers.
+    pub fn peer_estimated_time(&self) -> bool {
+        self.peer_estimated_time
+    }
+
+    /// The number of verified peers below which peers are queried for new peers less often.
+    pub fn query_backoff_threshold(&self) -> usize {
+        self.query_backoff_threshold
+    }
+
+    /// The maximum number of peers queried for new peers per query round: the latest verified peer and the others
+    /// among the peers that returned the most new peers.
+    pub fn peers_per_query(&self) -> usize {
+        self.peers_per_query
+    }
+
+    /// The maximum number of active peers, beyond which the least valuable ones are moved to the replacements.
+    pub fn max_active_peers(&self) -> usize {
+        self.max_active_peers
+    }
+
+    /// The seed of the random number generator selecting the peers to query and to evict, and jittering the discovery
+    /// schedules, if they should be reproducible.
+    pub fn query_rng_seed(&self) -> Option<u64> {
+        self.query_rng_seed
+    }
+
+    /// The time in seconds since their last verification after which stored peers are not restored on startup, or 0 to
+    /// restore all of them.
+    pub fn peer_staleness_secs(&self) -> u64 {
+        self.peer_staleness_secs
+    }
+
+    /// The time in seconds after which a background verification or discovery request is abandoned.
+    pub fn discovery_task_timeout_secs(&self) -> u64 {
+        self.discovery_task_timeout_secs
+    }
+
+    /// The number of reverifications of a peer that may fail in a row before it is removed.
+    pub fn reverification_max_failures(&self) -> usize {
+        self.reverification_max_failures
+    }
+
+    /// The backoff in seconds after a failed reverification of a peer, which doubles with every further failure.
+    pub fn reverification_backoff_secs(&self) -> u64 {
+        self.reverification_backoff_secs
+    }
+
+    /// The maximum deviation in percent by which the intervals of the reverifications and queries are randomly
+    /// lengthened or shortened, so that nodes started together don't send their requests in sync.
+    pub fn schedule_jitter_percent(&self) -> u8 {
+        self.schedule_jitter_percent
+    }
+
+    /// The maximum number of peers verified concurrently on demand.
+    pub fn max_concurrent_verifications(&self) -> usize {
+        self.max_concurrent_verifications
+    }
+
+    /// The peers that may be added when discovered, or all peers if empty.
+    pub fn peer_allowlist(&self) -> &[PeerFilterEntry] {
+        &self.peer_allowlist
+    }
+
+    /// The peers that are never added when discovered.
+    pub fn peer_denylist(&self) -> &[PeerFilterEntry] {
+        &self.peer_denylist
+    }
+
     /// Turns the [`AutopeeringConfig`] into its JSON representation.
     pub fn into_json_config(self) -> AutopeeringConfigJsonBuilder {
         AutopeeringConfigJsonBuilder {
@@ -111,9 +452,37 @@ impl AutopeeringConfig {
             bind_addr: self.bind_addr,
             entry_nodes: self.entry_nodes,
             entry_nodes_prefer_ipv6: Some(self.entry_nodes_prefer_ipv6),
+            entry_node_weights: self.entry_node_weights,
             run_as_entry_node: Some(self.run_as_entry_node),
             drop_neighbors_on_salt_update: Some(self.drop_neighbors_on_salt_update),
+            salt_lifetime_secs: Some(self.salt_lifetime_secs),
             peer_storage_path: Some(self.peer_storage_path),
+            verification_retries: Some(self.verification_retries),
+            verification_retry_timeout_millis: Some(self.verification_retry_timeout_millis),
+            retain_rare_services: Some(self.retain_rare_services),
+            verified_peers_threshold: Some(self.verified_peers_threshold),
+            discovery_dedup_window_secs: Some(self.discovery_dedup_window_secs),
+            quarantine_secs: Some(self.quarantine_secs),
+            quarantine_verifications: Some(self.quarantine_verifications),
+            unknown_peer_policy: Some(self.unknown_peer_policy),
+            eviction_mode: Some(self.eviction_mode),
+            selection_strategy: Some(self.selection_strategy),
+            foreign_peer_policy: Some(self.foreign_peer_policy),
+            relay_addr: self.relay_addr,
+            query_cooldown_rounds: Some(self.query_cooldown_rounds),
+            peer_estimated_time: Some(self.peer_estimated_time),
+            query_backoff_threshold: Some(self.query_backoff_threshold),
+            peers_per_query: Some(self.peers_per_query),
+            max_active_peers: Some(self.max_active_peers),
+            query_rng_seed: self.query_rng_seed,
+            peer_staleness_secs: Some(self.peer_staleness_secs),
+            discovery_task_timeout_secs: Some(self.discovery_task_timeout_secs),
+            reverification_max_failures: Some(self.reverification_max_failures),
+            reverification_backoff_secs: Some(self.reverification_backoff_secs),
+            schedule_jitter_percent: Some(self.schedule_jitter_percent),
+            max_concurrent_verifications: Some(self.max_concurrent_verifications),
+            peer_allowlist: self.peer_allowlist,
+            peer_denylist: self.peer_denylist,
         }
     }
 
@@ -124,9 +493,37 @@ impl AutopeeringConfig {
             bind_addr: self.bind_addr,
             entry_nodes: self.entry_nodes,
             entry_nodes_prefer_ipv6: Some(self.entry_nodes_prefer_ipv6),
+            entry_node_weights: self.entry_node_weights,
             run_as_entry_node: Some(self.run_as_entry_node),
             drop_neighbors_on_salt_update: Some(self.drop_neighbors_on_salt_update),
+            salt_lifetime_secs: Some(self.salt_lifetime_secs),
             peer_storage_path: Some(self.peer_storage_path),
+            verification_retries: Some(self.verification_retries),
+            verification_retry_timeout_millis: Some(self.verification_retry_timeout_millis),
+            retain_rare_services: Some(self.retain_rare_services),
+            verified_peers_threshold: Some(self.verified_peers_threshold),
+            discovery_dedup_window_secs: Some(self.discovery_dedup_window_secs),
+            quarantine_secs: Some(self.quarantine_secs),
+            quarantine_verifications: Some(self.quarantine_verifications),
+            unknown_peer_policy: Some(self.unknown_peer_policy),
+            eviction_mode: Some(self.eviction_mode),
+            selection_strategy: Some(self.selection_strategy),
+            foreign_peer_policy: Some(self.foreign_peer_policy),
+            relay_addr: self.relay_addr,
+            query_cooldown_rounds: Some(self.query_cooldown_rounds),
+            peer_estimated_time: Some(self.peer_estimated_time),
+            query_backoff_threshold: Some(self.query_backoff_threshold),
+            peers_per_query: Some(self.peers_per_query),
+            max_active_peers: Some(self.max_active_peers),
+            query_rng_seed: self.query_rng_seed,
+            peer_staleness_secs: Some(self.peer_staleness_secs),
+            discovery_task_timeout_secs: Some(self.discovery_task_timeout_secs),
+            reverification_max_failures: Some(self.reverification_max_failures),
+            reverification_backoff_secs: Some(self.reverification_backoff_secs),
+            schedule_jitter_percent: Some(self.schedule_jitter_percent),
+            max_concurrent_verifications: Some(self.max_concurrent_verifications),
+            peer_allowlist: self.peer_allowlist,
+            peer_denylist: self.peer_denylist,
         }
     }
 }
@@ -154,15 +551,102 @@ pub struct AutopeeringConfigJsonBuilder {
     /// Whether `Ipv4` or `Ipv6` should be preferred in case a hostname supports both.
     #[serde(rename = "entryNodesPreferIPv6")]
     pub entry_nodes_prefer_ipv6: Option<bool>,
+    /// The weights of the entry nodes, preferring the ones with higher weights when querying them.
+    #[serde(rename = "entryNodeWeights", default)]
+    pub entry_node_weights: Vec<EntryNodeWeight>,
     /// Whether the node should run as an entry node.
     #[serde(rename = "runAsEntryNode")]
     pub run_as_entry_node: Option<bool>,
     /// Whether all neighbors should be disconnected from when the salts are updated.
     #[serde(rename = "dropNeighborsOnSaltUpdate")]
     pub drop_neighbors_on_salt_update: Option<bool>,
+    /// The number of seconds the salts are valid for, after which they are replaced by new ones.
+    #[serde(rename = "saltLifetimeSecs")]
+    pub salt_lifetime_secs: Option<u64>,
     /// The peer storage path.
     #[serde(rename = "peerStoragePath")]
     pub peer_storage_path: Option<PathBuf>,
+    /// The number of times a verification request is resent if the response doesn't arrive in time.
+    #[serde(rename = "verificationRetries")]
+    pub verification_retries: Option<usize>,
+    /// The time in milliseconds a resent verification request waits for its response.
+    #[serde(rename = "verificationRetryTimeoutMillis")]
+    pub verification_retry_timeout_millis: Option<u64>,
+    /// Whether peers offering rare services should be preferred when the active peer list is full.
+    #[serde(rename = "retainRareServices")]
+    pub retain_rare_services: Option<bool>,
+    /// The number of verified peers below which the peer count is considered low.
+    #[serde(rename = "verifiedPeersThreshold")]
+    pub verified_peers_threshold: Option<usize>,
+    /// The time window in seconds within which identical discovery responses of a peer are not processed again.
+    #[serde(rename = "discoveryDedupWindowSecs")]
+    pub discovery_dedup_window_secs: Option<u64>,
+    /// The time in seconds newly discovered peers have to stay in quarantine before they can become active peers.
+    #[serde(rename = "quarantineSecs")]
+    pub quarantine_secs: Option<u64>,
+    /// The number of verifications newly discovered peers have to pass in quarantine.
+    #[serde(rename = "quarantineVerifications")]
+    pub quarantine_verifications: Option<usize>,
+    /// How verification requests from unknown peers are handled.
+    #[serde(rename = "unknownPeerPolicy")]
+    pub unknown_peer_policy: Option<UnknownPeerPolicy>,
+    /// How the active peer to evict is picked when making room for a peer offering a rare service.
+    #[serde(rename = "evictionMode")]
+    pub eviction_mode: Option<EvictionMode>,
+    /// How the peers to query for new peers are selected.
+    #[serde(rename = "selectionStrategy")]
+    pub selection_strategy: Option<SelectionStrategy>,
+    /// How peers belonging to a different network are handled.
+    #[serde(rename = "foreignPeerPolicy")]
+    pub foreign_peer_policy: Option<ForeignPeerPolicy>,
+    /// The address of the relay discovery and verification requests are sent through, if any.
+    #[serde(rename = "relayAddress")]
+    pub relay_addr: Option<SocketAddr>,
+    /// The number of query rounds during which a queried peer is only selected again if there are too few other peers.
+    #[serde(rename = "queryCooldownRounds")]
+    pub query_cooldown_rounds: Option<usize>,
+    /// Whether request timestamps are validated against the local time corrected by the clock offsets of known peers.
+    #[serde(rename = "peerEstimatedTime")]
+    pub peer_estimated_time: Option<bool>,
+    /// The number of verified peers below which peers are queried for new peers less often.
+    #[serde(rename = "queryBackoffThreshold")]
+    pub query_backoff_threshold: Option<usize>,
+    /// The maximum number of peers queried for new peers per query round.
+    #[serde(rename = "peersPerQuery")]
+    pub peers_per_query: Option<usize>,
+    /// The maximum number of active peers, beyond which the least valuable ones are moved to the replacements.
+    #[serde(rename = "maxActivePeers")]
+    pub max_active_peers: Option<usize>,
+    /// The seed of the random number generator selecting the peers to query and to evict, and jittering the discovery
+    /// schedules, if they should be reproducible.
+    #[serde(rename = "queryRngSeed")]
+    pub query_rng_seed: Option<u64>,
+    /// The time in seconds since their last verification after which stored peers are not restored on startup, or 0 to
+    /// restore all of them.
+    #[serde(rename = "peerStalenessSecs")]
+    pub peer_staleness_secs: Option<u64>,
+    /// The time in seconds after which a background verification or discovery request is abandoned.
+    #[serde(rename = "discoveryTaskTimeoutSecs")]
+    pub discovery_task_timeout_secs: Option<u64>,
+    /// The number of reverifications of a peer that may fail in a row before it is removed.
+    #[serde(rename = "reverificationMaxFailures")]
+    pub reverification_max_failures: Option<usize>,
+    /// The backoff in seconds after a failed reverification of a peer, which doubles with every further failure.
+    #[serde(rename = "reverificationBackoffSecs")]
+    pub reverification_backoff_secs: Option<u64>,
+    /// The maximum deviation in percent by which the intervals of the reverifications and queries are randomly
+    /// lengthened or shortened.
+    #[serde(rename = "scheduleJitterPercent")]
+    pub schedule_jitter_percent: Option<u8>,
+    /// The maximum number of peers verified concurrently on demand.
+    #[serde(rename = "maxConcurrentVerifications")]
+    pub max_concurrent_verifications: Option<usize>,
+    /// The peers that may be added when discovered, or all peers if empty.
+    #[serde(rename = "peerAllowlist", default)]
+    pub peer_allowlist: Vec<PeerFilterEntry>,
+    /// The peers that are never added when discovered.
+    #[serde(rename = "peerDenylist", default)]
+    pub peer_denylist: Vec<PeerFilterEntry>,
 }
 
 impl AutopeeringConfigJsonBuilder {
@@ -174,13 +658,60 @@ impl AutopeeringConfigJsonBuilder {
             bind_addr: self.bind_addr,
             entry_nodes: self.entry_nodes,
             entry_nodes_prefer_ipv6: self.entry_nodes_prefer_ipv6.unwrap_or(ENTRYNODES_PREFER_IPV6_DEFAULT),
+            entry_node_weights: self.entry_node_weights,
             run_as_entry_node: self.run_as_entry_node.unwrap_or(RUN_AS_ENTRYNODE_DEFAULT),
             drop_neighbors_on_salt_update: self
                 .drop_neighbors_on_salt_update
                 .unwrap_or(DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT),
+            salt_lifetime_secs: self.salt_lifetime_secs.unwrap_or(SALT_LIFETIME_SECS_DEFAULT),
             peer_storage_path: self
                 .peer_storage_path
                 .unwrap_or_else(|| PEER_STORAGE_PATH_DEFAULT.into()),
+            verification_retries: self.verification_retries.unwrap_or(VERIFICATION_RETRIES_DEFAULT),
+            verification_retry_timeout_millis: self
+                .verification_retry_timeout_millis
+                .unwrap_or(VERIFICATION_RETRY_TIMEOUT_MILLIS_DEFAULT),
+            retain_rare_services: self.retain_rare_services.unwrap_or(RETAIN_RARE_SERVICES_DEFAULT),
+            verified_peers_threshold: self
+                .verified_peers_threshold
+                .unwrap_or(VERIFIED_PEERS_THRESHOLD_DEFAULT),
+            discovery_dedup_window_secs: self
+                .discovery_dedup_window_secs
+                .unwrap_or(DISCOVERY_DEDUP_WINDOW_SECS_DEFAULT),
+            quarantine_secs: self.quarantine_secs.unwrap_or(QUARANTINE_SECS_DEFAULT),
+            quarantine_verifications: self
+                .quarantine_verifications
+                .unwrap_or(QUARANTINE_VERIFICATIONS_DEFAULT),
+            unknown_peer_policy: self.unknown_peer_policy.unwrap_or_default(),
+            eviction_mode: self.eviction_mode.unwrap_or_default(),
+            selection_strategy: self.selection_strategy.unwrap_or_default(),
+            foreign_peer_policy: self.foreign_peer_policy.unwrap_or_default(),
+            relay_addr: self.relay_addr,
+            query_cooldown_rounds: self.query_cooldown_rounds.unwrap_or(QUERY_COOLDOWN_ROUNDS_DEFAULT),
+            peer_estimated_time: self.peer_estimated_time.unwrap_or(PEER_ESTIMATED_TIME_DEFAULT),
+            query_backoff_threshold: self.query_backoff_threshold.unwrap_or(QUERY_BACKOFF_THRESHOLD_DEFAULT),
+            peers_per_query: self.peers_per_query.unwrap_or(PEERS_PER_QUERY_DEFAULT),
+            max_active_peers: self.max_active_peers.unwrap_or(MAX_ACTIVE_PEERS_DEFAULT),
+            query_rng_seed: self.query_rng_seed,
+            peer_staleness_secs: self.peer_staleness_secs.unwrap_or(PEER_STALENESS_SECS_DEFAULT),
+            discovery_task_timeout_secs: self
+                .discovery_task_timeout_secs
+                .unwrap_or(DISCOVERY_TASK_TIMEOUT_SECS_DEFAULT),
+            reverification_max_failures: self
+                .reverification_max_failures
+                .unwrap_or(REVERIFICATION_MAX_FAILURES_DEFAULT),
+            reverification_backoff_secs: self
+                .reverification_backoff_secs
+                .unwrap_or(REVERIFICATION_BACKOFF_SECS_DEFAULT),
+            schedule_jitter_percent: self
+                .schedule_jitter_percent
+                .unwrap_or(SCHEDULE_JITTER_PERCENT_DEFAULT)
+                .min(100),
+            max_concurrent_verifications: self
+                .max_concurrent_verifications
+                .unwrap_or(MAX_CONCURRENT_VERIFICATIONS_DEFAULT),
+            peer_allowlist: self.peer_allowlist,
+            peer_denylist: self.peer_denylist,
         }
     }
 }
@@ -192,9 +723,37 @@ impl Default for AutopeeringConfigJsonBuilder {
             bind_addr: SocketAddr::new(AUTOPEERING_BIND_ADDR_DEFAULT, AUTOPEERING_BIND_PORT_DEFAULT),
             entry_nodes: Vec::default(),
             entry_nodes_prefer_ipv6: Some(ENTRYNODES_PREFER_IPV6_DEFAULT),
+            entry_node_weights: Vec::default(),
             run_as_entry_node: Some(RUN_AS_ENTRYNODE_DEFAULT),
             drop_neighbors_on_salt_update: Some(DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT),
+            salt_lifetime_secs: Some(SALT_LIFETIME_SECS_DEFAULT),
             peer_storage_path: Some(PEER_STORAGE_PATH_DEFAULT.into()),
+            verification_retries: Some(VERIFICATION_RETRIES_DEFAULT),
+            verification_retry_timeout_millis: Some(VERIFICATION_RETRY_TIMEOUT_MILLIS_DEFAULT),
+            retain_rare_services: Some(RETAIN_RARE_SERVICES_DEFAULT),
+            verified_peers_threshold: Some(VERIFIED_PEERS_THRESHOLD_DEFAULT),
+            discovery_dedup_window_secs: Some(DISCOVERY_DEDUP_WINDOW_SECS_DEFAULT),
+            quarantine_secs: Some(QUARANTINE_SECS_DEFAULT),
+            quarantine_verifications: Some(QUARANTINE_VERIFICATIONS_DEFAULT),
+            unknown_peer_policy: Some(UnknownPeerPolicy::default()),
+            eviction_mode: Some(EvictionMode::default()),
+            selection_strategy: Some(SelectionStrategy::default()),
+            foreign_peer_policy: Some(ForeignPeerPolicy::default()),
+            relay_addr: None,
+            query_cooldown_rounds: Some(QUERY_COOLDOWN_ROUNDS_DEFAULT),
+            peer_estimated_time: Some(PEER_ESTIMATED_TIME_DEFAULT),
+            query_backoff_threshold: Some(QUERY_BACKOFF_THRESHOLD_DEFAULT),
+            peers_per_query: Some(PEERS_PER_QUERY_DEFAULT),
+            max_active_peers: Some(MAX_ACTIVE_PEERS_DEFAULT),
+            query_rng_seed: None,
+            peer_staleness_secs: Some(PEER_STALENESS_SECS_DEFAULT),
+            discovery_task_timeout_secs: Some(DISCOVERY_TASK_TIMEOUT_SECS_DEFAULT),
+            reverification_max_failures: Some(REVERIFICATION_MAX_FAILURES_DEFAULT),
+            reverification_backoff_secs: Some(REVERIFICATION_BACKOFF_SECS_DEFAULT),
+            schedule_jitter_percent: Some(SCHEDULE_JITTER_PERCENT_DEFAULT),
+            max_concurrent_verifications: Some(MAX_CONCURRENT_VERIFICATIONS_DEFAULT),
+            peer_allowlist: Vec::default(),
+            peer_denylist: Vec::default(),
         }
     }
 }
@@ -216,12 +775,75 @@ pub struct AutopeeringConfigTomlBuilder {
     pub entry_nodes: Vec<AutopeeringMultiaddr>,
     /// Whether `Ipv4` or `Ipv6` should be preferred in case a hostname supports both.
     pub entry_nodes_prefer_ipv6: Option<bool>,
+    /// The weights of the entry nodes, preferring the ones with higher weights when querying them.
+    #[serde(default)]
+    pub entry_node_weights: Vec<EntryNodeWeight>,
     /// Whether the node should run as an entry node.
     pub run_as_entry_node: Option<bool>,
     /// Whether all neighbors should be disconnected from when the salts are updated.
     pub drop_neighbors_on_salt_update: Option<bool>,
+    /// The number of seconds the salts are valid for, after which they are replaced by new ones.
+    pub salt_lifetime_secs: Option<u64>,
     /// The peer storage path.
     pub peer_storage_path: Option<PathBuf>,
+    /// The number of times a verification request is resent if the response doesn't arrive in time.
+    pub verification_retries: Option<usize>,
+    /// The time in milliseconds a resent verification request waits for its response.
+    pub verification_retry_timeout_millis: Option<u64>,
+    /// Whether peers offering rare services should be preferred when the active peer list is full.
+    pub retain_rare_services: Option<bool>,
+    /// The number of verified peers below which the peer count is considered low.
+    pub verified_peers_threshold: Option<usize>,
+    /// The time window in seconds within which identical discovery responses of a peer are not processed again.
+    pub discovery_dedup_window_secs: Option<u64>,
+    /// The time in seconds newly discovered peers have to stay in quarantine before they can become active peers.
+    pub quarantine_secs: Option<u64>,
+    /// The number of verifications newly discovered peers have to pass in quarantine.
+    pub quarantine_verifications: Option<usize>,
+    /// How verification requests from unknown peers are handled.
+    pub unknown_peer_policy: Option<UnknownPeerPolicy>,
+    /// How the active peer to evict is picked when making room for a peer offering a rare service.
+    pub eviction_mode: Option<EvictionMode>,
+    /// How the peers to query for new peers are selected.
+    pub selection_strategy: Option<SelectionStrategy>,
+    /// How peers belonging to a different network are handled.
+    pub foreign_peer_policy: Option<ForeignPeerPolicy>,
+    /// The address of the relay discovery and verification requests are sent through, if any.
+    #[serde(rename = "relay_address")]
+    pub relay_addr: Option<SocketAddr>,
+    /// The number of query rounds during which a queried peer is only selected again if there are too few other peers.
+    pub query_cooldown_rounds: Option<usize>,
+    /// Whether request timestamps are validated against the local time corrected by the clock offsets of known peers.
+    pub peer_estimated_time: Option<bool>,
+    /// The number of verified peers below which peers are queried for new peers less often.
+    pub query_backoff_threshold: Option<usize>,
+    /// The maximum number of peers queried for new peers per query round.
+    pub peers_per_query: Option<usize>,
+    /// The maximum number of active peers, beyond which the least valuable ones are moved to the replacements.
+    pub max_active_peers: Option<usize>,
+    /// The seed of the random number generator selecting the peers to query and to evict, and jittering the discovery
+    /// schedules, if they should be reproducible.
+    pub query_rng_seed: Option<u64>,
+    /// The time in seconds since their last verification after which stored peers are not restored on startup, or 0 to
+    /// restore all of them.
+    pub peer_staleness_secs: Option<u64>,
+    /// The time in seconds after which a background verification or discovery request is abandoned.
+    pub discovery_task_timeout_secs: Option<u64>,
+    /// The number of reverifications of a peer that may fail in a row before it is removed.
+    pub reverification_max_failures: Option<usize>,
+    /// The backoff in seconds after a failed reverification of a peer, which doubles with every further failure.
+    pub reverification_backoff_secs: Option<u64>,
+    /// The maximum deviation in percent by which the intervals of the reverifications and queries are randomly
+    /// lengthened or shortened.
+    pub schedule_jitter_percent: Option<u8>,
+    /// The maximum number of peers verified concurrently on demand.
+    pub max_concurrent_verifications: Option<usize>,
+    /// The peers that may be added when discovered, or all peers if empty.
+    #[serde(default)]
+    pub peer_allowlist: Vec<PeerFilterEntry>,
+    /// The peers that are never added when discovered.
+    #[serde(default)]
+    pub peer_denylist: Vec<PeerFilterEntry>,
 }
 
 impl AutopeeringConfigTomlBuilder {
@@ -232,13 +854,60 @@ impl AutopeeringConfigTomlBuilder {
             bind_addr: self.bind_addr,
             entry_nodes: self.entry_nodes,
             entry_nodes_prefer_ipv6: self.entry_nodes_prefer_ipv6.unwrap_or(ENTRYNODES_PREFER_IPV6_DEFAULT),
+            entry_node_weights: self.entry_node_weights,
             run_as_entry_node: self.run_as_entry_node.unwrap_or(RUN_AS_ENTRYNODE_DEFAULT),
             drop_neighbors_on_salt_update: self
                 .drop_neighbors_on_salt_update
                 .unwrap_or(DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT),
+            salt_lifetime_secs: self.salt_lifetime_secs.unwrap_or(SALT_LIFETIME_SECS_DEFAULT),
             peer_storage_path: self
                 .peer_storage_path
                 .unwrap_or_else(|| PEER_STORAGE_PATH_DEFAULT.into()),
+            verification_retries: self.verification_retries.unwrap_or(VERIFICATION_RETRIES_DEFAULT),
+            verification_retry_timeout_millis: self
+                .verification_retry_timeout_millis
+                .unwrap_or(VERIFICATION_RETRY_TIMEOUT_MILLIS_DEFAULT),
+            retain_rare_services: self.retain_rare_services.unwrap_or(RETAIN_RARE_SERVICES_DEFAULT),
+            verified_peers_threshold: self
+                .verified_peers_threshold
+                .unwrap_or(VERIFIED_PEERS_THRESHOLD_DEFAULT),
+            discovery_dedup_window_secs: self
+                .discovery_dedup_window_secs
+                .unwrap_or(DISCOVERY_DEDUP_WINDOW_SECS_DEFAULT),
+            quarantine_secs: self.quarantine_secs.unwrap_or(QUARANTINE_SECS_DEFAULT),
+            quarantine_verifications: self
+                .quarantine_verifications
+                .unwrap_or(QUARANTINE_VERIFICATIONS_DEFAULT),
+            unknown_peer_policy: self.unknown_peer_policy.unwrap_or_default(),
+            eviction_mode: self.eviction_mode.unwrap_or_default(),
+            selection_strategy: self.selection_strategy.unwrap_or_default(),
+            foreign_peer_policy: self.foreign_peer_policy.unwrap_or_default(),
+            relay_addr: self.relay_addr,
+            query_cooldown_rounds: self.query_cooldown_rounds.unwrap_or(QUERY_COOLDOWN_ROUNDS_DEFAULT),
+            peer_estimated_time: self.peer_estimated_time.unwrap_or(PEER_ESTIMATED_TIME_DEFAULT),
+            query_backoff_threshold: self.query_backoff_threshold.unwrap_or(QUERY_BACKOFF_THRESHOLD_DEFAULT),
+            peers_per_query: self.peers_per_query.unwrap_or(PEERS_PER_QUERY_DEFAULT),
+            max_active_peers: self.max_active_peers.unwrap_or(MAX_ACTIVE_PEERS_DEFAULT),
+            query_rng_seed: self.query_rng_seed,
+            peer_staleness_secs: self.peer_staleness_secs.unwrap_or(PEER_STALENESS_SECS_DEFAULT),
+            discovery_task_timeout_secs: self
+                .discovery_task_timeout_secs
+                .unwrap_or(DISCOVERY_TASK_TIMEOUT_SECS_DEFAULT),
+            reverification_max_failures: self
+                .reverification_max_failures
+                .unwrap_or(REVERIFICATION_MAX_FAILURES_DEFAULT),
+            reverification_backoff_secs: self
+                .reverification_backoff_secs
+                .unwrap_or(REVERIFICATION_BACKOFF_SECS_DEFAULT),
+            schedule_jitter_percent: self
+                .schedule_jitter_percent
+                .unwrap_or(SCHEDULE_JITTER_PERCENT_DEFAULT)
+                .min(100),
+            max_concurrent_verifications: self
+                .max_concurrent_verifications
+                .unwrap_or(MAX_CONCURRENT_VERIFICATIONS_DEFAULT),
+            peer_allowlist: self.peer_allowlist,
+            peer_denylist: self.peer_denylist,
         }
     }
 }
@@ -250,9 +919,37 @@ impl Default for AutopeeringConfigTomlBuilder {
             bind_addr: SocketAddr::new(AUTOPEERING_BIND_ADDR_DEFAULT, AUTOPEERING_BIND_PORT_DEFAULT),
             entry_nodes: Vec::default(),
             entry_nodes_prefer_ipv6: Some(ENTRYNODES_PREFER_IPV6_DEFAULT),
+            entry_node_weights: Vec::default(),
             run_as_entry_node: Some(RUN_AS_ENTRYNODE_DEFAULT),
             drop_neighbors_on_salt_update: Some(DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT),
+            salt_lifetime_secs: Some(SALT_LIFETIME_SECS_DEFAULT),
             peer_storage_path: Some(PEER_STORAGE_PATH_DEFAULT.into()),
+            verification_retries: Some(VERIFICATION_RETRIES_DEFAULT),
+            verification_retry_timeout_millis: Some(VERIFICATION_RETRY_TIMEOUT_MILLIS_DEFAULT),
+            retain_rare_services: Some(RETAIN_RARE_SERVICES_DEFAULT),
+            verified_peers_threshold: Some(VERIFIED_PEERS_THRESHOLD_DEFAULT),
+            discovery_dedup_window_secs: Some(DISCOVERY_DEDUP_WINDOW_SECS_DEFAULT),
+            quarantine_secs: Some(QUARANTINE_SECS_DEFAULT),
+            quarantine_verifications: Some(QUARANTINE_VERIFICATIONS_DEFAULT),
+            unknown_peer_policy: Some(UnknownPeerPolicy::default()),
+            eviction_mode: Some(EvictionMode::default()),
+            selection_strategy: Some(SelectionStrategy::default()),
+            foreign_peer_policy: Some(ForeignPeerPolicy::default()),
+            relay_addr: None,
+            query_cooldown_rounds: Some(QUERY_COOLDOWN_ROUNDS_DEFAULT),
+            peer_estimated_time: Some(PEER_ESTIMATED_TIME_DEFAULT),
+            query_backoff_threshold: Some(QUERY_BACKOFF_THRESHOLD_DEFAULT),
+            peers_per_query: Some(PEERS_PER_QUERY_DEFAULT),
+            max_active_peers: Some(MAX_ACTIVE_PEERS_DEFAULT),
+            query_rng_seed: None,
+            peer_staleness_secs: Some(PEER_STALENESS_SECS_DEFAULT),
+            discovery_task_timeout_secs: Some(DISCOVERY_TASK_TIMEOUT_SECS_DEFAULT),
+            reverification_max_failures: Some(REVERIFICATION_MAX_FAILURES_DEFAULT),
+            reverification_backoff_secs: Some(REVERIFICATION_BACKOFF_SECS_DEFAULT),
+            schedule_jitter_percent: Some(SCHEDULE_JITTER_PERCENT_DEFAULT),
+            max_concurrent_verifications: Some(MAX_CONCURRENT_VERIFICATIONS_DEFAULT),
+            peer_allowlist: Vec::default(),
+            peer_denylist: Vec::default(),
         }
     }
 }
@@ -286,9 +983,37 @@ mod tests {
                 "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
             ],
             "entryNodesPreferIPv6": true,
+            "entryNodeWeights": ["iotaPHdAn7eueBnXtikZMwhfPXaeGJGXDt4RBuLuGgb:3"],
             "runAsEntryNode": false,
             "dropNeighborsOnSaltUpdate": false,
-            "peerStoragePath": "./storage/mainnet/peers"
+            "saltLifetimeSecs": 3600,
+            "peerStoragePath": "./storage/mainnet/peers",
+            "verificationRetries": 2,
+            "verificationRetryTimeoutMillis": 100,
+            "retainRareServices": false,
+            "verifiedPeersThreshold": 3,
+            "discoveryDedupWindowSecs": 0,
+            "quarantineSecs": 0,
+            "quarantineVerifications": 0,
+            "unknownPeerPolicy": "ignore",
+            "evictionMode": "weighted",
+            "selectionStrategy": "distance",
+            "foreignPeerPolicy": "denylist",
+            "relayAddress": "10.0.0.1:14627",
+            "queryCooldownRounds": 2,
+            "peerEstimatedTime": true,
+            "queryBackoffThreshold": 2,
+            "peersPerQuery": 4,
+            "maxActivePeers": 500,
+            "queryRngSeed": 42,
+            "peerStalenessSecs": 86400,
+            "discoveryTaskTimeoutSecs": 20,
+            "reverificationMaxFailures": 3,
+            "reverificationBackoffSecs": 5,
+            "scheduleJitterPercent": 10,
+            "maxConcurrentVerifications": 4,
+            "peerAllowlist": ["4H6WV54tB29u8xCcEaMGQMn37LFvM1ynNpp27TTXaqNM", "10.0.0.2"],
+            "peerDenylist": ["10.0.0.3"]
         }"#;
 
         serde_json::from_str(config_json_str).expect("error deserializing json config")
@@ -305,9 +1030,37 @@ mod tests {
                 "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
             ]
             entry_nodes_prefer_ipv6 = true
+            entry_node_weights = ["iotaPHdAn7eueBnXtikZMwhfPXaeGJGXDt4RBuLuGgb:3"]
             run_as_entry_node = false
             drop_neighbors_on_salt_update = false
+            salt_lifetime_secs = 3600
             peer_storage_path = "./storage/mainnet/peers"
+            verification_retries = 2
+            verification_retry_timeout_millis = 100
+            retain_rare_services = false
+            verified_peers_threshold = 3
+            discovery_dedup_window_secs = 0
+            quarantine_secs = 0
+            quarantine_verifications = 0
+            unknown_peer_policy = "ignore"
+            eviction_mode = "weighted"
+            selection_strategy = "distance"
+            foreign_peer_policy = "denylist"
+            relay_address = "10.0.0.1:14627"
+            query_cooldown_rounds = 2
+            peer_estimated_time = true
+            query_backoff_threshold = 2
+            peers_per_query = 4
+            max_active_peers = 500
+            query_rng_seed = 42
+            peer_staleness_secs = 86400
+            discovery_task_timeout_secs = 20
+            reverification_max_failures = 3
+            reverification_backoff_secs = 5
+            schedule_jitter_percent = 10
+            max_concurrent_verifications = 4
+            peer_allowlist = ["4H6WV54tB29u8xCcEaMGQMn37LFvM1ynNpp27TTXaqNM", "10.0.0.2"]
+            peer_denylist = ["10.0.0.3"]
         "#;
 
         toml::from_str(toml_config_str).unwrap()
@@ -324,9 +1077,40 @@ mod tests {
                 "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC".parse().unwrap(),
             ],
             entry_nodes_prefer_ipv6: true,
+            entry_node_weights: vec!["iotaPHdAn7eueBnXtikZMwhfPXaeGJGXDt4RBuLuGgb:3".to_string().try_into().unwrap()],
             run_as_entry_node: false,
             drop_neighbors_on_salt_update: false,
-            peer_storage_path: "./storage/mainnet/peers".into()
+            salt_lifetime_secs: 3600,
+            peer_storage_path: "./storage/mainnet/peers".into(),
+            verification_retries: 2,
+            verification_retry_timeout_millis: 100,
+            retain_rare_services: false,
+            verified_peers_threshold: 3,
+            discovery_dedup_window_secs: 0,
+            quarantine_secs: 0,
+            quarantine_verifications: 0,
+            unknown_peer_policy: UnknownPeerPolicy::Ignore,
+            eviction_mode: EvictionMode::Weighted,
+            selection_strategy: SelectionStrategy::Distance,
+            foreign_peer_policy: ForeignPeerPolicy::Denylist,
+            relay_addr: Some("10.0.0.1:14627".parse().unwrap()),
+            query_cooldown_rounds: 2,
+            peer_estimated_time: true,
+            query_backoff_threshold: 2,
+            peers_per_query: 4,
+            max_active_peers: 500,
+            query_rng_seed: Some(42),
+            peer_staleness_secs: 86400,
+            discovery_task_timeout_secs: 20,
+            reverification_max_failures: 3,
+            reverification_backoff_secs: 5,
+            schedule_jitter_percent: 10,
+            max_concurrent_verifications: 4,
+            peer_allowlist: vec![
+                "4H6WV54tB29u8xCcEaMGQMn37LFvM1ynNpp27TTXaqNM".to_string().try_into().unwrap(),
+                PeerFilterEntry::IpAddr("10.0.0.2".parse().unwrap()),
+            ],
+            peer_denylist: vec![PeerFilterEntry::IpAddr("10.0.0.3".parse().unwrap())],
         }
     }
 
@@ -1,6 +1,10 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::discovery::query::QueryRng;
+
+use rand::Rng as _;
+
 use std::{
     sync::atomic::{AtomicU64, Ordering},
     time::Duration,
@@ -40,3 +44,77 @@ impl Iterator for ManualDelayFactory {
         Some(millis_to_delay(self.0.load(Ordering::Relaxed)))
     }
 }
+
+/// Randomly lengthens or shortens the delays of another delay iterator by up to a percentage of each delay.
+pub(crate) struct Jittered<D> {
+    delays: D,
+    percent: u8,
+    rng: QueryRng,
+}
+
+impl<D> Jittered<D> {
+    /// Creates a new `Jittered` from the delays to randomize, the maximum deviation in percent, and the source of
+    /// randomness, which can be seeded to make the delays reproducible.
+    pub(crate) fn new(delays: D, percent: u8, rng: QueryRng) -> Self {
+        Self {
+            delays,
+            percent: percent.min(100),
+            rng,
+        }
+    }
+}
+
+impl<D: Iterator<Item = Delay>> Iterator for Jittered<D> {
+    type Item = Delay;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let millis = delay_to_millis(self.delays.next()?);
+        let max_deviation = millis * self.percent as u64 / 100;
+
+        if max_deviation == 0 {
+            return Some(millis_to_delay(millis));
+        }
+
+        Some(millis_to_delay(
+            millis - max_deviation + self.rng.with(|rng| rng.gen_range(0..=2 * max_deviation)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::iter;
+
+    #[test]
+    fn jittered_delays_vary_within_bound() {
+        let delays = Jittered::new(iter::repeat(Delay::from_secs(10)), 20, QueryRng::default())
+            .take(100)
+            .collect::<Vec<_>>();
+
+        assert!(delays
+            .iter()
+            .all(|delay| (Delay::from_secs(8)..=Delay::from_secs(12)).contains(delay)));
+        assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn zero_jitter_keeps_delays() {
+        let delays = Jittered::new(iter::repeat(Delay::from_secs(10)), 0, QueryRng::default()).take(10);
+
+        assert!(delays.into_iter().all(|delay| delay == Delay::from_secs(10)));
+    }
+
+    #[test]
+    fn seeded_jitter_is_reproducible() {
+        let delays = |seed| {
+            Jittered::new(iter::repeat(Delay::from_secs(10)), 20, QueryRng::seeded(seed))
+                .take(10)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(delays(42), delays(42));
+        assert_ne!(delays(42), delays(43));
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A handle to the running peer discovery.
+
+use crate::{
+    discovery::{
+        manager::{DiscoveryResponseCache, ForeignPeers, UnknownPeerVerifications},
+        query::{self, QueryContext},
+    },
+    hash,
+    local::services::ServiceMap,
+    peer::{lists::ActivePeersList, Peer, PeerId},
+    task::NextTick,
+    time::{self, TimeSource},
+};
+
+use tokio_stream::wrappers::WatchStream;
+
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// The result of verifying a single peer on demand.
+#[derive(Debug)]
+pub enum VerificationResult {
+    /// The peer responded in time and offers the contained services.
+    Verified {
+        /// The services offered by the peer.
+        services: ServiceMap,
+    },
+    /// The peer didn't respond in time.
+    Failed,
+    /// The peer is not an active peer, hence it can't be verified.
+    Unknown,
+}
+
+impl VerificationResult {
+    /// Returns whether the verification was successful.
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Self::Verified { .. })
+    }
+}
+
+/// The verification report of a single peer.
+#[derive(Debug)]
+pub struct VerificationReport {
+    /// The identity of the verified peer.
+    pub peer_id: PeerId,
+    /// The result of the verification.
+    pub result: VerificationResult,
+}
+
+/// A snapshot of the scheduling state of the peer discovery.
+#[derive(Debug)]
+pub struct DiscoveryScheduleState {
+    /// When the next query for new peers is scheduled, if any.
+    pub next_query: Option<Instant>,
+    /// When the next reverification is scheduled, if any.
+    pub next_reverify: Option<Instant>,
+    /// The peer that would be reverified next.
+    pub reverify_candidate: Option<PeerId>,
+}
+
+/// The number of verification requests from unknown peers per outcome of the configured policy.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UnknownPeerVerificationCounts {
+    /// The number of unknown peers that were added.
+    pub accepted: u64,
+    /// The number of unknown peers that were not added because the peer filter denies them.
+    pub rejected: u64,
+    /// The number of unknown peers that were ignored.
+    pub ignored: u64,
+}
+
+/// The number of queries and reverifications of the peer discovery, and their outcomes, since it started.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DiscoveryMetrics {
+    /// The number of queries for new peers that were sent.
+    pub queries: u64,
+    /// The number of queries that were answered.
+    pub successful_queries: u64,
+    /// The number of queries that failed or timed out.
+    pub failed_queries: u64,
+    /// The number of new peers received in query responses.
+    pub discovered_peers: u64,
+    /// The number of regular reverifications that were attempted.
+    pub reverifications: u64,
+    /// The number of peers that were removed because a query or reverification failed.
+    pub removed_peers: u64,
+}
+
+/// An active peer along with its distance to the local peer in the ID space.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerDistance {
+    /// The identity of the peer.
+    pub peer_id: PeerId,
+    /// The XOR distance between the IDs of the peer and the local peer.
+    pub distance: [u8; hash::SHA256_LEN],
+    /// The number of new peers the peer returned when it was queried the last time.
+    pub last_new_peers: usize,
+    /// The time since the peer last responded to a verification request, if it ever did.
+    pub verification_age: Option<Duration>,
+}
+
+/// An alternative transport to check the liveness of peers, e.g. an application-level ping over an existing gossip
+/// connection.
+#[async_trait::async_trait]
+pub trait LivenessProbe: Send + Sync + 'static {
+    /// Checks whether the peer is alive.
+    ///
+    /// Returns `None` if the peer can't be reached over this transport, in which case it is verified over the
+    /// autopeering transport instead.
+    async fn probe(&self, peer_id: &PeerId) -> Option<bool>;
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct LivenessProbeSlot(Arc<RwLock<Option<Arc<dyn LivenessProbe>>>>);
+
+impl LivenessProbeSlot {
+    pub(crate) fn get(&self) -> Option<Arc<dyn LivenessProbe>> {
+        // Panic: we don't allow poisoned locks.
+        self.0.read().expect("error getting read access").clone()
+    }
+
+    pub(crate) fn set(&self, probe: Arc<dyn LivenessProbe>) {
+        // Panic: we don't allow poisoned locks.
+        self.0.write().expect("error getting write access").replace(probe);
+    }
+}
+
+/// Allows to interact with the running peer discovery, e.g. for diagnostic purposes.
+#[derive(Clone)]
+pub struct DiscoveryHandle {
+    local_id: PeerId,
+    ctx: QueryContext,
+    next_query: NextTick,
+    next_reverify: NextTick,
+    response_cache: DiscoveryResponseCache,
+    unknown_verifications: UnknownPeerVerifications,
+    foreign_peers: ForeignPeers,
+    time_source: Arc<dyn TimeSource>,
+}
+
+impl DiscoveryHandle {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        local_id: PeerId,
+        ctx: QueryContext,
+        next_query: NextTick,
+        next_reverify: NextTick,
+        response_cache: DiscoveryResponseCache,
+        unknown_verifications: UnknownPeerVerifications,
+        foreign_peers: ForeignPeers,
+        time_source: Arc<dyn TimeSource>,
+    ) -> Self {
+        Self {
+            local_id,
+            ctx,
+            next_query,
+            next_reverify,
+            response_cache,
+            unknown_verifications,
+            foreign_peers,
+            time_source,
+        }
+    }
+
+    /// Returns the number of identical discovery responses that were not processed again.
+    pub fn duplicate_discovery_responses(&self) -> u64 {
+        self.response_cache.duplicates()
+    }
+
+    /// Returns the number of verification requests from unknown peers per outcome of the configured policy.
+    pub fn unknown_peer_verifications(&self) -> UnknownPeerVerificationCounts {
+        self.unknown_verifications.counts()
+    }
+
+    /// Returns the number of peers that were removed because they belong to a different network.
+    pub fn foreign_peer_evictions(&self) -> u64 {
+        self.foreign_peers.evictions()
+    }
+
+    /// Returns the number of queries and reverifications, and their outcomes.
+    pub fn metrics(&self) -> DiscoveryMetrics {
+        self.ctx.counters.metrics()
+    }
+
+    /// Returns the source of the time the timestamps of requests are validated against, which might be corrected by
+    /// the clocks of the peers.
+    pub fn time_source(&self) -> Arc<dyn TimeSource> {
+        self.time_source.clone()
+    }
+
+    /// Returns a snapshot of the current scheduling state of the peer discovery.
+    pub fn schedule_state(&self) -> DiscoveryScheduleState {
+        DiscoveryScheduleState {
+            next_query: self.next_query.get(),
+            next_reverify: self.next_reverify.get(),
+            reverify_candidate: query::peer_to_reverify(&self.ctx.active_peers),
+        }
+    }
+
+    /// Returns the active peers sorted by their distance to the local peer, closest first.
+    pub fn distance_table(&self) -> Vec<PeerDistance> {
+        distance_table(&self.local_id, &self.ctx.active_peers)
+    }
+
+    /// Sets a probe to verify peers over an alternative transport whenever it can reach them.
+    ///
+    /// Peers the probe can't reach are still verified over the autopeering transport.
+    pub fn set_liveness_probe<P: LivenessProbe>(&self, probe: P) {
+        self.ctx.liveness_probe.set(Arc::new(probe));
+    }
+
+    /// Verifies several peers concurrently, at most as many at once as configured, and reports the result for each of
+    /// them in the given order.
+    ///
+    /// Peers that fail the verification are **not** removed.
+    pub async fn verify_peers(&self, peer_ids: Vec<PeerId>) -> Vec<VerificationReport> {
+        query::verify_peers(peer_ids, &self.ctx).await
+    }
+
+    /// Reverifies up to `max_peers` of the least recently verified peers right away, in addition to the regular
+    /// reverification, and returns the number of peers being reverified.
+    ///
+    /// Peers that fail the verification are backed off from or removed, just like after a scheduled reverification.
+    /// Must be called from within a Tokio runtime.
+    pub fn reverify_burst(&self, max_peers: usize) -> usize {
+        query::reverify_burst(&self.ctx, max_peers)
+    }
+
+    /// Returns a stream yielding the currently verified peers, and then again whenever a peer gets verified for the
+    /// first time or is removed.
+    pub fn verified_peers(&self) -> WatchStream<Vec<Peer>> {
+        self.ctx.peer_count.verified_peers()
+    }
+
+    /// Reverifies an active peer right away, and returns the services it offers if it responded.
+    ///
+    /// Just like the regular reverification, a peer that fails it is backed off from or removed.
+    pub async fn reverify_peer(&self, peer_id: PeerId) -> Option<ServiceMap> {
+        query::reverify_peer(peer_id, &self.ctx).await
+    }
+}
+
+fn distance_table(local_id: &PeerId, active_peers: &ActivePeersList) -> Vec<PeerDistance> {
+    let mut table = active_peers
+        .read()
+        .iter()
+        .map(|p| {
+            let last_verif_response = p.metrics().last_verif_response_timestamp();
+
+            PeerDistance {
+                peer_id: *p.peer_id(),
+                distance: local_id.distance(p.peer_id()),
+                last_new_peers: p.metrics().last_new_peers(),
+                // A peer that never responded has no timestamp.
+                verification_age: if last_verif_response == 0 {
+                    None
+                } else {
+                    time::since(last_verif_response).map(Duration::from_secs)
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    table.sort_unstable_by_key(|entry| entry.distance);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event,
+        packet::OutgoingPacket,
+        peer::{
+            lists::{ActivePeer, ActivePeersList},
+            Peer,
+        },
+        server::server_chan,
+    };
+
+    use std::time::Duration;
+
+    fn create_handle(active_peers: ActivePeersList) -> DiscoveryHandle {
+        let (server_tx, _) = server_chan::<OutgoingPacket>();
+        let (event_tx, _) = event::event_chan();
+
+        let ctx = QueryContext::new_test(active_peers, server_tx, event_tx);
+
+        DiscoveryHandle::new(
+            PeerId::new_static(),
+            ctx,
+            NextTick::default(),
+            NextTick::default(),
+            DiscoveryResponseCache::new(Duration::ZERO),
+            UnknownPeerVerifications::new(Default::default()),
+            ForeignPeers::new(Default::default()),
+            Arc::new(time::SystemTimeSource),
+        )
+    }
+
+    #[test]
+    fn schedule_state_reports_reverify_candidate() {
+        let active_peers = ActivePeersList::default();
+        let handle = create_handle(active_peers.clone());
+
+        let state = handle.schedule_state();
+        assert!(state.reverify_candidate.is_none());
+        assert!(state.next_query.is_none());
+        assert!(state.next_reverify.is_none());
+
+        for i in 0..3 {
+            active_peers.write().insert(ActivePeer::new(Peer::new_test_peer(i)));
+        }
+
+        let state = handle.schedule_state();
+        assert!(state.reverify_candidate.is_some());
+        assert_eq!(state.reverify_candidate, query::peer_to_reverify(&active_peers));
+    }
+
+    #[test]
+    fn distance_table_is_sorted_by_distance() {
+        let active_peers = ActivePeersList::default();
+        let handle = create_handle(active_peers.clone());
+        assert!(handle.distance_table().is_empty());
+
+        for i in 0..10 {
+            let mut peer = ActivePeer::new(Peer::new_test_peer(i));
+            peer.metrics_mut().set_last_new_peers(i as usize);
+            if i % 2 == 0 {
+                peer.metrics_mut().set_last_verif_response_timestamp();
+            }
+            active_peers.write().insert(peer);
+        }
+
+        let table = handle.distance_table();
+        assert_eq!(table.len(), 10);
+
+        let local_id = PeerId::new_static();
+        let mut expected = active_peers
+            .read()
+            .iter()
+            .map(|p| (local_id.distance(p.peer_id()), *p.peer_id()))
+            .collect::<Vec<_>>();
+        expected.sort_unstable_by_key(|(distance, _)| *distance);
+
+        assert_eq!(
+            table
+                .iter()
+                .map(|entry| (entry.distance, entry.peer_id))
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        for entry in table {
+            let peer = active_peers.read().find(&entry.peer_id).unwrap().clone();
+            assert_eq!(entry.last_new_peers, peer.metrics().last_new_peers());
+            // Only the peers that responded to a verification request have a verification age.
+            assert_eq!(entry.verification_age.is_some(), entry.last_new_peers % 2 == 0);
+        }
+    }
+}
@@ -2,9 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::AutopeeringConfig,
-    discovery::messages::{DiscoveryRequest, DiscoveryResponse, VerificationRequest, VerificationResponse},
-    event::{Event, EventTx},
+    config::{AutopeeringConfig, EvictionMode, ForeignPeerPolicy, PeerFilterEntry, UnknownPeerPolicy},
+    discovery::{
+        handle::UnknownPeerVerificationCounts,
+        messages::{DiscoveryRequest, DiscoveryResponse, VerificationRequest, VerificationResponse},
+        quarantine::Quarantine,
+        query::QueryRng,
+    },
+    event::{Event, EventTx, TransitionReason},
     hash::message_hash,
     local::{
         services::{ServiceMap, ServicePort, ServiceProtocol, AUTOPEERING_SERVICE_NAME},
@@ -22,29 +27,50 @@ use crate::{
     request::{self, RequestManager, RequestValue, ResponseTx, RESPONSE_TIMEOUT},
     server::{ServerRx, ServerSocket, ServerTx},
     task::{Runnable, ShutdownRx, TaskManager},
-    time::{HOUR, SECOND},
+    time::{self, TimeSource, HOUR, SECOND},
 };
 
-use rand::{seq::index, Rng as _};
-
-use std::{net::SocketAddr, time::Duration};
+use rand::seq::index;
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 // Time interval after which the next peer is reverified.
 pub(crate) const REVERIFY_INTERVAL_DEFAULT: Duration = Duration::from_secs(10 * SECOND);
 // Time interval after which peers are queried for new peers.
 pub(crate) const QUERY_INTERVAL_DEFAULT: Duration = Duration::from_secs(60 * SECOND);
+// Time interval after which peers are queried for new peers while there are too few verified peers.
+pub(crate) const QUERY_BACKOFF_INTERVAL: Duration = Duration::from_secs(5 * 60 * SECOND);
 // Is the time until a peer verification expires (12 hours).
 pub(crate) const VERIFICATION_EXPIRATION: Duration = Duration::from_secs(12 * HOUR);
 // Is the maximum number of peers returned in DiscoveryResponse.
 const MAX_PEERS_IN_RESPONSE: usize = 6;
 // Is the minimum number of verifications required to be selected in DiscoveryResponse.
 const MIN_VERIFIED_IN_RESPONSE: usize = 1;
+// The weight of entry peers that weren't given one in the config.
+const DEFAULT_ENTRY_PEER_WEIGHT: u32 = 1;
 
 pub(crate) struct DiscoveryManagerConfig {
     pub(crate) entry_nodes: Vec<AutopeeringMultiaddr>,
     pub(crate) entry_nodes_prefer_ipv6: bool,
+    pub(crate) entry_node_weights: HashMap<PeerId, u32>,
     pub(crate) version: u32,
     pub(crate) network_id: u32,
+    pub(crate) retain_rare_services: bool,
+    pub(crate) eviction_mode: EvictionMode,
+    pub(crate) peer_staleness: Option<Duration>,
+    pub(crate) peer_filter: PeerFilter,
 }
 
 impl DiscoveryManagerConfig {
@@ -52,8 +78,20 @@ impl DiscoveryManagerConfig {
         Self {
             entry_nodes: config.entry_nodes().to_vec(),
             entry_nodes_prefer_ipv6: config.entry_nodes_prefer_ipv6(),
+            entry_node_weights: config
+                .entry_node_weights()
+                .iter()
+                .map(|entry| (*entry.peer_id(), entry.weight()))
+                .collect(),
             version,
             network_id,
+            retain_rare_services: config.retain_rare_services(),
+            eviction_mode: config.eviction_mode(),
+            peer_staleness: match config.peer_staleness_secs() {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+            peer_filter: PeerFilter::new(config.peer_allowlist(), config.peer_denylist()),
         }
     }
 }
@@ -77,6 +115,20 @@ pub(crate) struct DiscoveryManager<S: PeerStore> {
     active_peers: ActivePeersList,
     // The list of replacement peers.
     replacements: ReplacementPeersList,
+    // Watches the number of verified peers.
+    peer_count: PeerCountWatcher,
+    // Remembers recent discovery responses.
+    response_cache: DiscoveryResponseCache,
+    // Keeps track of newly discovered peers in quarantine.
+    quarantine: Quarantine,
+    // Decides about verification requests from unknown peers.
+    unknown_verifications: UnknownPeerVerifications,
+    // Removes peers belonging to a different network.
+    foreign_peers: ForeignPeers,
+    // Provides the time to validate request timestamps against.
+    time_source: Arc<dyn TimeSource>,
+    // Picks the active peers to evict.
+    rng: QueryRng,
 }
 
 impl<S: PeerStore + 'static> DiscoveryManager<S> {
@@ -91,6 +143,13 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
         active_peers: ActivePeersList,
         replacements: ReplacementPeersList,
         event_tx: EventTx,
+        peer_count: PeerCountWatcher,
+        response_cache: DiscoveryResponseCache,
+        quarantine: Quarantine,
+        unknown_verifications: UnknownPeerVerifications,
+        foreign_peers: ForeignPeers,
+        time_source: Arc<dyn TimeSource>,
+        rng: QueryRng,
     ) -> Self {
         Self {
             config,
@@ -102,6 +161,13 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
             entry_peers,
             active_peers,
             replacements,
+            peer_count,
+            response_cache,
+            quarantine,
+            unknown_verifications,
+            foreign_peers,
+            time_source,
+            rng,
         }
     }
 
@@ -116,28 +182,45 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
             entry_peers,
             active_peers,
             replacements,
+            peer_count,
+            response_cache,
+            quarantine,
+            unknown_verifications,
+            foreign_peers,
+            time_source,
+            rng,
         } = self;
 
         let DiscoveryManagerConfig {
             mut entry_nodes,
             entry_nodes_prefer_ipv6,
+            entry_node_weights,
             version,
             network_id,
+            retain_rare_services,
+            eviction_mode,
+            peer_staleness,
+            peer_filter,
         } = config;
 
         let ServerSocket { server_rx, server_tx } = socket;
 
         // Add previously discovered peers from the peer store.
-        if add_peers_from_store(&peer_store, &active_peers, &replacements)? == 0 {
+        if add_peers_from_store(&peer_store, &active_peers, &replacements, peer_staleness, &peer_filter)? == 0 {
             // Add entry peers from the config **only** if we start with an empty peer store,
             // otherwise entry nodes would be contacted each time a node starts.
             if add_entry_peers(
                 &mut entry_nodes,
                 entry_nodes_prefer_ipv6,
+                &entry_node_weights,
                 &local,
                 &entry_peers,
                 &active_peers,
                 &replacements,
+                retain_rare_services,
+                eviction_mode,
+                &rng,
+                &event_tx,
             )
             .await
                 == 0
@@ -154,8 +237,19 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
             network_id,
             request_mngr: request_mngr.clone(),
             event_tx,
+            entry_peers,
             active_peers: active_peers.clone(),
             replacements,
+            retain_rare_services,
+            eviction_mode,
+            peer_count,
+            response_cache,
+            quarantine,
+            unknown_verifications,
+            foreign_peers,
+            peer_filter,
+            time_source,
+            rng,
         };
 
         task_mngr.run::<DiscoveryRecvHandler>(discovery_recv_handler);
@@ -172,8 +266,19 @@ struct DiscoveryRecvHandler {
     network_id: u32,
     request_mngr: RequestManager,
     event_tx: EventTx,
+    entry_peers: EntryPeersList,
     active_peers: ActivePeersList,
     replacements: ReplacementPeersList,
+    retain_rare_services: bool,
+    eviction_mode: EvictionMode,
+    peer_count: PeerCountWatcher,
+    response_cache: DiscoveryResponseCache,
+    quarantine: Quarantine,
+    unknown_verifications: UnknownPeerVerifications,
+    foreign_peers: ForeignPeers,
+    peer_filter: PeerFilter,
+    time_source: Arc<dyn TimeSource>,
+    rng: QueryRng,
 }
 
 #[async_trait::async_trait]
@@ -192,9 +297,19 @@ impl Runnable for DiscoveryRecvHandler {
             network_id,
             request_mngr,
             event_tx,
+            entry_peers,
             active_peers,
             replacements,
-            ..
+            retain_rare_services,
+            eviction_mode,
+            peer_count,
+            response_cache,
+            quarantine,
+            unknown_verifications,
+            foreign_peers,
+            peer_filter,
+            time_source,
+            rng,
         } = self;
 
         // Event loop.
@@ -211,6 +326,11 @@ impl Runnable for DiscoveryRecvHandler {
                         peer_id,
                     }) = p
                     {
+                        if foreign_peers.is_denied(&peer_id) {
+                            log::trace!("Ignoring packet from denylisted {}.", &peer_id);
+                            continue 'recv;
+                        }
+
                         let ctx = RecvContext {
                             peer_id: &peer_id,
                             msg_bytes: &msg_bytes,
@@ -219,8 +339,18 @@ impl Runnable for DiscoveryRecvHandler {
                             request_mngr: &request_mngr,
                             peer_addr,
                             event_tx: &event_tx,
+                            entry_peers: &entry_peers,
                             active_peers: &active_peers,
                             replacements: &replacements,
+                            retain_rare_services,
+                            eviction_mode,
+                            peer_count: &peer_count,
+                            response_cache: &response_cache,
+                            quarantine: &quarantine,
+                            unknown_verifications: &unknown_verifications,
+                            foreign_peers: &foreign_peers,
+                            peer_filter: &peer_filter,
+                            rng: &rng,
                         };
 
                         match msg_type {
@@ -232,13 +362,23 @@ impl Runnable for DiscoveryRecvHandler {
                                     continue 'recv;
                                 };
 
-                                if let Err(e) = validate_verification_request(&verif_req, version, network_id) {
-                                    log::debug!("Received invalid verification request from {}. Reason: {}", &peer_id, e);
-                                    continue 'recv;
-                                } else {
-                                    log::trace!("Received valid verification request from {}.", &peer_id);
+                                record_peer_time(&peer_id, verif_req.timestamp(), &active_peers, &*time_source);
 
-                                    handle_verification_request(verif_req, ctx);
+                                match validate_verification_request(&verif_req, version, network_id, &*time_source) {
+                                    Ok(()) => {
+                                        log::trace!("Received valid verification request from {}.", &peer_id);
+
+                                        handle_verification_request(verif_req, ctx);
+                                    }
+                                    Err(e @ ValidationError::NetworkIdMismatch { received, .. }) => {
+                                        log::debug!("Received invalid verification request from {}. Reason: {}", &peer_id, e);
+
+                                        handle_foreign_peer(received, ctx);
+                                    }
+                                    Err(e) => {
+                                        log::debug!("Received invalid verification request from {}. Reason: {}", &peer_id, e);
+                                        continue 'recv;
+                                    }
                                 }
                             }
                             MessageType::VerificationResponse => {
@@ -269,7 +409,9 @@ impl Runnable for DiscoveryRecvHandler {
                                     continue 'recv;
                                 };
 
-                                if let Err(e) = validate_discovery_request(&disc_req) {
+                                record_peer_time(&peer_id, disc_req.timestamp(), &active_peers, &*time_source);
+
+                                if let Err(e) = validate_discovery_request(&disc_req, &*time_source) {
                                     log::debug!("Received invalid discovery request from {}. Reason: {:?}", &peer_id, e);
                                     continue 'recv;
                                 } else {
@@ -311,37 +453,73 @@ fn add_peers_from_store<S: PeerStore>(
     peer_store: &S,
     active_peers: &ActivePeersList,
     replacements: &ReplacementPeersList,
+    staleness: Option<Duration>,
+    peer_filter: &PeerFilter,
 ) -> Result<usize, S::Error> {
     let mut num_added = 0;
+    let mut num_stale = 0;
+
+    // Peers that weren't verified within the staleness window are likely gone, so they are not restored.
+    let verified_since = staleness.map(|staleness| time::unix_now_secs().saturating_sub(staleness.as_secs()));
+
+    // Peers beyond the maximum number of active peers, e.g. after lowering it, are restored as replacements.
+    let mut demoted = Vec::new();
 
     let mut write = active_peers.write();
     for active_peer in peer_store.fetch_all_active()? {
-        if write.insert(active_peer) {
-            num_added += 1;
+        if !peer_filter.accepts_peer(active_peer.peer()) {
+            continue;
+        }
+        if matches!(verified_since, Some(since) if !active_peer.metrics().is_verified_since(since)) {
+            num_stale += 1;
+            continue;
+        }
+        match write.insert_or_displace(active_peer) {
+            (true, None) => num_added += 1,
+            (_, Some(displaced)) => demoted.push(displaced),
+            (false, None) => {}
         }
     }
     drop(write);
 
+    let num_demoted = demoted.len();
+
     let mut write = replacements.write();
-    for replacement in peer_store.fetch_all_replacements()? {
+    for replacement in peer_store
+        .fetch_all_replacements()?
+        .into_iter()
+        .filter(|p| peer_filter.accepts_peer(p))
+        .chain(demoted.into_iter().map(ActivePeer::into_peer))
+    {
         if write.insert(replacement) {
             num_added += 1;
         }
     }
     drop(write);
 
-    log::debug!("Restored {} peer/s.", num_added);
+    log::debug!(
+        "Restored {} peer/s, dropped {} stale peer/s, demoted {} peer/s.",
+        num_added,
+        num_stale,
+        num_demoted
+    );
 
     Ok(num_added)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn add_entry_peers(
     entry_nodes: &mut Vec<AutopeeringMultiaddr>,
     entry_nodes_prefer_ipv6: bool,
+    entry_node_weights: &HashMap<PeerId, u32>,
     local: &Local,
     entry_peers: &EntryPeersList,
     active_peers: &ActivePeersList,
     replacements: &ReplacementPeersList,
+    retain_rare_services: bool,
+    eviction_mode: EvictionMode,
+    rng: &QueryRng,
+    event_tx: &EventTx,
 ) -> usize {
     let mut num_added = 0;
 
@@ -381,10 +559,23 @@ async fn add_entry_peers(
         let mut peer = Peer::new(entry_socketaddr.ip(), *entry_addr.public_key());
         peer.add_service(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, entry_socketaddr.port());
 
-        entry_peers.write().insert(*peer.peer_id());
+        let weight = entry_node_weights
+            .get(peer.peer_id())
+            .copied()
+            .unwrap_or(DEFAULT_ENTRY_PEER_WEIGHT);
+        entry_peers.write().insert(*peer.peer_id(), weight);
 
         // Also add it as a regular peer.
-        if let Some(peer_id) = add_peer::<false>(peer, local, active_peers, replacements) {
+        if let Some(peer_id) = add_peer::<false>(
+            peer,
+            local,
+            active_peers,
+            replacements,
+            retain_rare_services,
+            eviction_mode,
+            rng,
+            event_tx,
+        ) {
             log::debug!("Added {}.", peer_id);
             num_added += 1;
         }
@@ -397,27 +588,67 @@ async fn add_entry_peers(
 
 /// Attempts to add a new peer to a peer list (preferably as active).
 /// If the peer is added inbound, the "last verification timestamp" is added.
+///
+/// If `retain_rare_services` is set, and the active peer list is full, a peer offering a rare service replaces the
+/// least valuable active peer, which is moved to the replacement list instead. That peer is picked according to the
+/// `eviction_mode`, drawing from `rng` if needed.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn add_peer<const ON_REQUEST: bool>(
     peer: Peer,
     local: &Local,
     active_peers: &ActivePeersList,
     replacements: &ReplacementPeersList,
+    retain_rare_services: bool,
+    eviction_mode: EvictionMode,
+    rng: &QueryRng,
+    event_tx: &EventTx,
 ) -> Option<PeerId> {
     // Only add new peers.
     if peer::is_known(peer.peer_id(), local, active_peers, replacements) {
         None
     } else {
         let peer_id = *peer.peer_id();
-        // First try to add it to the active peer list. If that list is full, add it to the replacement list.
-        if !active_peers.read().is_full() {
-            let active_peer = if ON_REQUEST {
+        let into_active = |peer: Peer| {
+            if ON_REQUEST {
                 let mut active = ActivePeer::from(peer);
                 active.metrics_mut().set_last_verif_request_timestamp();
                 active
             } else {
                 ActivePeer::from(peer)
-            };
-            if active_peers.write().insert(active_peer) {
+            }
+        };
+        // First try to add it to the active peer list. If that list is full, add it to the replacement list.
+        if !active_peers.read().is_full() {
+            if active_peers.write().insert(into_active(peer)) {
+                Some(peer_id)
+            } else {
+                None
+            }
+        } else if retain_rare_services && active_peers.read().offers_rare_service(&peer) {
+            let mut active_peers = active_peers.write();
+
+            if let Some(evicted) = rng
+                .with(|rng| active_peers.eviction_candidate(true, eviction_mode, rng))
+                .and_then(|evicted_id| active_peers.remove(&evicted_id))
+            {
+                log::trace!(
+                    "Moving {} to the replacements in favor of {}.",
+                    evicted.peer_id(),
+                    peer_id
+                );
+                let evicted_id = *evicted.peer_id();
+                if replacements.write().insert(evicted.into_peer()) {
+                    // Panic: we don't allow channel send errors.
+                    event_tx
+                        .send(Event::PeerDemoted {
+                            peer_id: evicted_id,
+                            reason: TransitionReason::Promotion,
+                        })
+                        .expect("error sending `PeerDemoted` event");
+                }
+            }
+
+            if active_peers.insert(into_active(peer)) {
                 Some(peer_id)
             } else {
                 None
@@ -433,53 +664,85 @@ pub(crate) fn add_peer<const ON_REQUEST: bool>(
 // Note: this function is dead-lock danger zone!
 /// Deletes a peer from the active peerlist if it's not an entry peer, and replaces it by a peer
 /// from the replacement list.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn remove_peer_from_active_list(
     peer_id: &PeerId,
+    reason: TransitionReason,
     entry_peers: &EntryPeersList,
     active_peers: &ActivePeersList,
     replacements: &ReplacementPeersList,
     event_tx: &EventTx,
+    peer_count: &PeerCountWatcher,
+    quarantine: &Quarantine,
 ) {
-    let mut active_peers = active_peers.write();
-
-    if let Some(mut removed_peer) = active_peers.remove(peer_id) {
-        // entry peers are never removed
-        if entry_peers.read().contains(removed_peer.peer_id()) {
-            // hive.go: reset verifiedCount and re-add them
-            removed_peer.metrics_mut().reset_verified_count();
-            active_peers.insert(removed_peer);
-        } else {
-            // TODO: why is the event only triggered for verified peers?
-            // ```go
-            // if mp.verifiedCount.Load() > 0 {
-            //     m.events.PeerDeleted.Trigger(&DeletedEvent{Peer: unwrapPeer(mp)})
-            // }
-            // ```
-            if removed_peer.metrics().verified_count() > 0 {
+    {
+        let mut active_peers = active_peers.write();
+
+        if let Some(mut removed_peer) = active_peers.remove(peer_id) {
+            // entry peers are never removed
+            if entry_peers.read().contains_key(removed_peer.peer_id()) {
+                // hive.go: reset verifiedCount and re-add them
+                removed_peer.metrics_mut().reset_verified_count();
+                active_peers.insert(removed_peer);
+            } else {
+                // TODO: why is the event only triggered for verified peers?
+                // ```go
+                // if mp.verifiedCount.Load() > 0 {
+                //     m.events.PeerDeleted.Trigger(&DeletedEvent{Peer: unwrapPeer(mp)})
+                // }
+                // ```
+                if removed_peer.metrics().verified_count() > 0 {
+                    // Panic: we don't allow channel send errors.
+                    event_tx
+                        .send(Event::PeerDeleted { peer_id: *peer_id })
+                        .expect("error sending `PeerDeleted` event");
+                }
+
                 // Panic: we don't allow channel send errors.
                 event_tx
-                    .send(Event::PeerDeleted { peer_id: *peer_id })
-                    .expect("error sending `PeerDeleted` event");
-            }
-
-            // ```go
-            // if len(m.replacements) > 0 {
-            // 	var r *mpeer
-            // 	m.replacements, r = deletePeer(m.replacements, rand.Intn(len(m.replacements)))
-            // 	m.active = pushPeer(m.active, r, maxManaged)
-            // }
-            // ```
-            // Pick a random peer from the replacement list (if not empty)
-            if !replacements.read().is_empty() {
-                let index = rand::thread_rng().gen_range(0..replacements.read().len());
-                // Panic: unwrapping is fine, because we checked that the list isn't empty, and `index` must be in
-                // range.
-                let peer = replacements.write().remove_at(index).unwrap();
-
-                active_peers.insert(peer.into());
+                    .send(Event::PeerRemoved {
+                        peer_id: *peer_id,
+                        reason,
+                    })
+                    .expect("error sending `PeerRemoved` event");
+
+                // ```go
+                // if len(m.replacements) > 0 {
+                // 	var r *mpeer
+                // 	m.replacements, r = deletePeer(m.replacements, rand.Intn(len(m.replacements)))
+                // 	m.active = pushPeer(m.active, r, maxManaged)
+                // }
+                // ```
+                // Promote the best peer that is not in quarantine from the replacement list (if any): the one that
+                // passed the most verifications in quarantine, and among those the most recently added
+                // one. Both lists stay locked, so that neither the opened slot nor the replacement can
+                // be taken in the meantime.
+                let mut replacements = replacements.write();
+
+                let candidate = replacements
+                    .iter()
+                    .map(|p| *p.peer_id())
+                    .enumerate()
+                    .filter(|(_, peer_id)| quarantine.is_cleared(peer_id))
+                    .max_by_key(|(index, peer_id)| (quarantine.verifications(peer_id), Reverse(*index)))
+                    .map(|(_, peer_id)| peer_id);
+
+                if let Some(peer) = candidate.and_then(|peer_id| replacements.remove(&peer_id)) {
+                    let peer_id = *peer.peer_id();
+
+                    quarantine.release(&peer_id);
+                    active_peers.insert(peer.into());
+
+                    // Panic: we don't allow channel send errors.
+                    event_tx
+                        .send(Event::PeerPromoted { peer_id, reason })
+                        .expect("error sending `PeerPromoted` event");
+                }
             }
         }
     }
+
+    peer_count.update(active_peers, event_tx);
 }
 
 pub(crate) struct RecvContext<'a> {
@@ -490,8 +753,26 @@ pub(crate) struct RecvContext<'a> {
     request_mngr: &'a RequestManager,
     peer_addr: SocketAddr,
     event_tx: &'a EventTx,
+    entry_peers: &'a EntryPeersList,
     active_peers: &'a ActivePeersList,
     replacements: &'a ReplacementPeersList,
+    retain_rare_services: bool,
+    eviction_mode: EvictionMode,
+    peer_count: &'a PeerCountWatcher,
+    response_cache: &'a DiscoveryResponseCache,
+    quarantine: &'a Quarantine,
+    unknown_verifications: &'a UnknownPeerVerifications,
+    foreign_peers: &'a ForeignPeers,
+    peer_filter: &'a PeerFilter,
+    rng: &'a QueryRng,
+}
+
+// Only requests of already known peers contribute to the time estimate, and regardless of whether they turn out to be
+// expired, since that is judged by the estimate itself.
+fn record_peer_time(peer_id: &PeerId, peer_ts: u64, active_peers: &ActivePeersList, time_source: &dyn TimeSource) {
+    if active_peers.read().contains(peer_id) {
+        time_source.record(peer_id, peer_ts);
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -527,6 +808,7 @@ fn validate_verification_request(
     verif_req: &VerificationRequest,
     version: u32,
     network_id: u32,
+    time_source: &dyn TimeSource,
 ) -> Result<(), ValidationError> {
     use ValidationError::*;
 
@@ -540,7 +822,7 @@ fn validate_verification_request(
             expected: network_id,
             received: verif_req.network_id(),
         })
-    } else if request::is_expired(verif_req.timestamp()) {
+    } else if request::is_expired(verif_req.timestamp(), time_source) {
         Err(RequestExpired)
     } else {
         // NOTE: the validity of the transmitted source and target addresses is ensured through the
@@ -582,10 +864,13 @@ fn validate_verification_response(
     }
 }
 
-fn validate_discovery_request(disc_req: &DiscoveryRequest) -> Result<(), ValidationError> {
+fn validate_discovery_request(
+    disc_req: &DiscoveryRequest,
+    time_source: &dyn TimeSource,
+) -> Result<(), ValidationError> {
     use ValidationError::*;
 
-    if request::is_expired(disc_req.timestamp()) {
+    if request::is_expired(disc_req.timestamp(), time_source) {
         Err(RequestExpired)
     } else {
         Ok(())
@@ -638,13 +923,27 @@ fn handle_verification_request(verif_req: VerificationRequest, ctx: RecvContext)
         if let Some(peer) = ctx.active_peers.write().find_mut(ctx.peer_id) {
             peer.metrics_mut().set_last_verif_request_timestamp();
         }
+    } else if !ctx
+        .unknown_verifications
+        .admit(ctx.peer_filter.accepts(ctx.peer_id, ctx.peer_addr.ip()))
+    {
+        log::trace!("Not adding unknown {}.", ctx.peer_id);
     } else {
         // Add it as a new peer with autopeering service.
         let mut peer = Peer::new(ctx.peer_addr.ip(), *ctx.peer_id.public_key());
         peer.add_service(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, ctx.peer_addr.port());
 
-        if let Some(peer_id) = add_peer::<true>(peer, ctx.local, ctx.active_peers, ctx.replacements) {
-            log::debug!("Added unknown and unverified {}.", peer_id);
+        // Like any other newly learned peer, it is quarantined in the replacement list, if enabled.
+        if ctx.quarantine.is_enabled() {
+            if let Some(peer_id) = add_quarantined_peer(peer, &ctx) {
+                log::debug!("Added unknown (quarantined): {}.", peer_id);
+            }
+            return;
+        }
+
+        // Otherwise it waits in the replacement list, so it can't displace verified active peers.
+        if ctx.replacements.write().insert(peer) {
+            log::debug!("Added unknown and unverified {} to the replacements.", ctx.peer_id);
         }
 
         // Peer is unknown, thus still unverified.
@@ -676,7 +975,11 @@ fn handle_verification_response(verif_res: VerificationResponse, verif_reqval: R
             ctx.event_tx
                 .send(Event::PeerDiscovered { peer_id: *ctx.peer_id })
                 .expect("error publishing peer-discovered event");
+
+            ctx.peer_count.update(ctx.active_peers, ctx.event_tx);
         }
+    } else if ctx.quarantine.record_verification(ctx.peer_id) {
+        log::trace!("Verified quarantined {}.", ctx.peer_id);
     }
 
     // Send the response notification.
@@ -712,11 +1015,47 @@ fn handle_discovery_response(disc_res: DiscoveryResponse, disc_reqval: RequestVa
     log::trace!("Handling discovery response from {}.", ctx.peer_id);
 
     let mut num_added = 0;
+    let peers = disc_res.into_peers();
+
+    // Identical responses are acknowledged, but not processed again.
+    let peers = if ctx.response_cache.is_duplicate(ctx.peer_id, &peers) {
+        log::trace!("Skipping duplicate discovery response from {}.", ctx.peer_id);
+        Vec::new()
+    } else {
+        peers
+    };
 
     // Add discovered peers to the peer list and peer store.
-    for peer in disc_res.into_peers() {
+    for peer in peers {
+        if ctx.foreign_peers.is_denied(peer.peer_id()) {
+            continue;
+        }
+
+        if !ctx.peer_filter.accepts_peer(&peer) {
+            log::trace!("Not adding filtered {}.", peer.peer_id());
+            continue;
+        }
+
+        // Newly discovered peers are quarantined in the replacement list, if enabled.
+        if ctx.quarantine.is_enabled() {
+            if let Some(peer_id) = add_quarantined_peer(peer, &ctx) {
+                log::debug!("Added (quarantined): {}.", peer_id);
+                num_added += 1;
+            }
+            continue;
+        }
+
         // Note: we only fire `PeerDiscovered` if it also can be verified, so we don't fire it just yet.
-        if let Some(peer_id) = add_peer::<false>(peer, ctx.local, ctx.active_peers, ctx.replacements) {
+        if let Some(peer_id) = add_peer::<false>(
+            peer,
+            ctx.local,
+            ctx.active_peers,
+            ctx.replacements,
+            ctx.retain_rare_services,
+            ctx.eviction_mode,
+            ctx.rng,
+            ctx.event_tx,
+        ) {
             log::debug!("Added (unverified): {}.", peer_id);
             num_added += 1;
 
@@ -741,44 +1080,136 @@ fn handle_discovery_response(disc_res: DiscoveryResponse, disc_reqval: RequestVa
     }
 }
 
+// Removes a peer that reported to belong to a different network, and denylists it if configured.
+fn handle_foreign_peer(network_id: u32, ctx: RecvContext) {
+    if !ctx.foreign_peers.is_enabled() {
+        return;
+    }
+
+    let removed = if ctx.active_peers.read().contains(ctx.peer_id) {
+        remove_peer_from_active_list(
+            ctx.peer_id,
+            TransitionReason::ForeignNetwork,
+            ctx.entry_peers,
+            ctx.active_peers,
+            ctx.replacements,
+            ctx.event_tx,
+            ctx.peer_count,
+            ctx.quarantine,
+        );
+        // Note: entry peers are never removed.
+        !ctx.active_peers.read().contains(ctx.peer_id)
+    } else if ctx.replacements.write().remove(ctx.peer_id).is_some() {
+        // Panic: we don't allow channel send errors.
+        ctx.event_tx
+            .send(Event::PeerRemoved {
+                peer_id: *ctx.peer_id,
+                reason: TransitionReason::ForeignNetwork,
+            })
+            .expect("error sending `PeerRemoved` event");
+        true
+    } else {
+        false
+    };
+
+    if ctx.foreign_peers.deny(ctx.peer_id) {
+        log::debug!("Denylisted {} belonging to network {}.", ctx.peer_id, network_id);
+    }
+
+    if removed {
+        log::debug!("Removed {} belonging to network {}.", ctx.peer_id, network_id);
+
+        ctx.foreign_peers.record_eviction();
+
+        // Panic: we don't allow channel send errors.
+        ctx.event_tx
+            .send(Event::ForeignPeerEvicted {
+                peer_id: *ctx.peer_id,
+                network_id,
+            })
+            .expect("error publishing foreign-peer-evicted event");
+    }
+}
+
+// Adds a newly discovered peer to the replacement list in quarantine, and immediatedly tries to verify it.
+fn add_quarantined_peer(peer: Peer, ctx: &RecvContext) -> Option<PeerId> {
+    if peer::is_known(peer.peer_id(), ctx.local, ctx.active_peers, ctx.replacements) {
+        return None;
+    }
+
+    let peer_id = *peer.peer_id();
+    let peer_addr = peer.service_socketaddr(AUTOPEERING_SERVICE_NAME)?;
+
+    if !ctx.replacements.write().insert(peer) {
+        return None;
+    }
+
+    // Forget about quarantined peers that were dropped from the replacement list in the meantime.
+    let replacements = ctx.replacements.read();
+    ctx.quarantine.retain(|peer_id| replacements.contains(peer_id));
+    drop(replacements);
+
+    ctx.quarantine.admit(peer_id);
+
+    send_verification_request_to_addr(peer_addr, &peer_id, ctx.request_mngr, ctx.server_tx, None);
+
+    Some(peer_id)
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
 // SENDING
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Initiates a verification request to a peer waiting for the peer's response, which must arrive in time.
 ///
+/// If the response doesn't arrive in time, the request is resent up to `retries` times before the verification is
+/// considered failed.
+///
 /// Returns `Some(ServiceMap)` if the request was successful, otherwise `None`.
 pub(crate) async fn begin_verification(
     peer_id: &PeerId,
     active_peers: &ActivePeersList,
     request_mngr: &RequestManager,
     server_tx: &ServerTx,
+    retries: usize,
+    retry_timeout: Duration,
 ) -> Option<ServiceMap> {
-    let (response_tx, response_rx) = request::response_chan();
+    for attempt in 0..=retries {
+        let (response_tx, response_rx) = request::response_chan();
 
-    send_verification_request_to_peer(peer_id, active_peers, request_mngr, server_tx, Some(response_tx));
+        send_verification_request_to_peer(peer_id, active_peers, request_mngr, server_tx, Some(response_tx));
 
-    match tokio::time::timeout(RESPONSE_TIMEOUT, response_rx).await {
-        Ok(Ok(bytes)) => match VerificationResponse::from_protobuf(&bytes).map(|r| r.into_services()) {
-            Ok(services) => Some(services),
+        // Resent requests only wait briefly, so that a lost request doesn't hold up the verification for long.
+        let timeout = if attempt == 0 { RESPONSE_TIMEOUT } else { retry_timeout };
+
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(bytes)) => match VerificationResponse::from_protobuf(&bytes).map(|r| r.into_services()) {
+                Ok(services) => return Some(services),
+                Err(e) => {
+                    log::debug!("Verification response decode error for {}: {}", peer_id, e);
+                    return None;
+                }
+            },
+            Ok(Err(e)) => {
+                log::debug!("Verification response error for {}: {}", peer_id, e);
+                return None;
+            }
             Err(e) => {
-                log::debug!("Verification response decode error for {}: {}", peer_id, e);
-                None
+                log::debug!(
+                    "Verification response timeout for {} (attempt {}/{}): {}",
+                    peer_id,
+                    attempt + 1,
+                    retries + 1,
+                    e
+                );
+
+                // The response didn't arrive in time => remove the request.
+                let _ = request_mngr.remove_request::<VerificationRequest>(peer_id);
             }
-        },
-        Ok(Err(e)) => {
-            log::debug!("Verification response error for {}: {}", peer_id, e);
-            None
-        }
-        Err(e) => {
-            log::debug!("Verification response timeout for {}: {}", peer_id, e);
-
-            // The response didn't arrive in time => remove the request.
-            let _ = request_mngr.remove_request::<VerificationRequest>(peer_id);
-
-            None
         }
     }
+
+    None
 }
 
 /// Sends a verification request to a peer.
@@ -1008,3 +1439,1132 @@ pub(crate) fn get_verified_peers(active_peers: &ActivePeersList) -> Vec<ActivePe
 
     peers
 }
+
+/// Remembers the fingerprints of the recent discovery responses of each peer, to detect identical ones.
+#[derive(Clone)]
+pub(crate) struct DiscoveryResponseCache {
+    window: Duration,
+    fingerprints: Arc<Mutex<HashMap<PeerId, (u64, Instant)>>>,
+    duplicates: Arc<AtomicU64>,
+}
+
+impl DiscoveryResponseCache {
+    /// Creates a new cache; a zero `window` disables the detection.
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            fingerprints: Arc::new(Mutex::new(HashMap::new())),
+            duplicates: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    // Returns whether the same peer sent an identical response within the window, and remembers it otherwise.
+    pub(crate) fn is_duplicate(&self, peer_id: &PeerId, peers: &[Peer]) -> bool {
+        if self.window.is_zero() {
+            return false;
+        }
+
+        let fingerprint = response_fingerprint(peers);
+        let now = Instant::now();
+
+        // Panic: we don't allow poisoned locks.
+        let mut fingerprints = self.fingerprints.lock().expect("error getting lock");
+        fingerprints.retain(|_, (_, timestamp)| now.duration_since(*timestamp) < self.window);
+
+        if matches!(fingerprints.get(peer_id), Some((f, _)) if *f == fingerprint) {
+            self.duplicates.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            fingerprints.insert(*peer_id, (fingerprint, now));
+            false
+        }
+    }
+
+    /// Returns the number of duplicate responses that were skipped.
+    pub(crate) fn duplicates(&self) -> u64 {
+        self.duplicates.load(Ordering::Relaxed)
+    }
+}
+
+/// Applies the configured policy to verification requests from unknown peers, and counts the outcomes.
+#[derive(Clone)]
+pub(crate) struct UnknownPeerVerifications {
+    policy: UnknownPeerPolicy,
+    accepted: Arc<AtomicU64>,
+    rejected: Arc<AtomicU64>,
+    ignored: Arc<AtomicU64>,
+}
+
+impl UnknownPeerVerifications {
+    pub(crate) fn new(policy: UnknownPeerPolicy) -> Self {
+        Self {
+            policy,
+            accepted: Arc::new(AtomicU64::new(0)),
+            rejected: Arc::new(AtomicU64::new(0)),
+            ignored: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    // Returns whether the unknown peer requesting verification may be added, given whether the peer filter accepts it.
+    pub(crate) fn admit(&self, accepted_by_filter: bool) -> bool {
+        let (admitted, counter) = match self.policy {
+            _ if !accepted_by_filter => (false, &self.rejected),
+            UnknownPeerPolicy::Add => (true, &self.accepted),
+            UnknownPeerPolicy::Ignore => (false, &self.ignored),
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        admitted
+    }
+
+    /// Returns the number of verification requests from unknown peers per outcome.
+    pub(crate) fn counts(&self) -> UnknownPeerVerificationCounts {
+        UnknownPeerVerificationCounts {
+            accepted: self.accepted.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            ignored: self.ignored.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ForeignPeers {
+    policy: ForeignPeerPolicy,
+    denylist: Arc<Mutex<HashSet<PeerId>>>,
+    evictions: Arc<AtomicU64>,
+}
+
+impl ForeignPeers {
+    pub(crate) fn new(policy: ForeignPeerPolicy) -> Self {
+        Self {
+            policy,
+            denylist: Arc::new(Mutex::new(HashSet::new())),
+            evictions: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    // Returns whether peers belonging to a different network are removed.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.policy != ForeignPeerPolicy::Keep
+    }
+
+    // Returns whether the peer is denylisted.
+    pub(crate) fn is_denied(&self, peer_id: &PeerId) -> bool {
+        // Panic: we don't allow poisoned locks.
+        self.denylist.lock().expect("error getting lock").contains(peer_id)
+    }
+
+    // Denylists the peer if configured, and returns whether it wasn't denylisted before.
+    fn deny(&self, peer_id: &PeerId) -> bool {
+        if self.policy == ForeignPeerPolicy::Denylist {
+            // Panic: we don't allow poisoned locks.
+            self.denylist.lock().expect("error getting lock").insert(*peer_id)
+        } else {
+            false
+        }
+    }
+
+    fn record_eviction(&self) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of peers that were removed because they belong to a different network.
+    pub(crate) fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+}
+
+// Decides which peers may be added, based on the configured allowlist and denylist.
+#[derive(Default)]
+pub(crate) struct PeerFilter {
+    allowlist: PeerSet,
+    denylist: PeerSet,
+}
+
+#[derive(Default)]
+struct PeerSet {
+    peer_ids: HashSet<PeerId>,
+    ip_addrs: HashSet<IpAddr>,
+}
+
+impl PeerSet {
+    fn new(entries: &[PeerFilterEntry]) -> Self {
+        let mut set = Self::default();
+
+        for entry in entries {
+            match entry {
+                PeerFilterEntry::PeerId(peer_id) => {
+                    set.peer_ids.insert(**peer_id);
+                }
+                PeerFilterEntry::IpAddr(ip_addr) => {
+                    set.ip_addrs.insert(*ip_addr);
+                }
+            }
+        }
+
+        set
+    }
+
+    fn is_empty(&self) -> bool {
+        self.peer_ids.is_empty() && self.ip_addrs.is_empty()
+    }
+
+    fn contains(&self, peer_id: &PeerId, ip_addr: &IpAddr) -> bool {
+        self.peer_ids.contains(peer_id) || self.ip_addrs.contains(ip_addr)
+    }
+}
+
+impl PeerFilter {
+    pub(crate) fn new(allowlist: &[PeerFilterEntry], denylist: &[PeerFilterEntry]) -> Self {
+        Self {
+            allowlist: PeerSet::new(allowlist),
+            denylist: PeerSet::new(denylist),
+        }
+    }
+
+    // Returns whether a peer with that identity and IP address may be added.
+    pub(crate) fn accepts(&self, peer_id: &PeerId, ip_addr: IpAddr) -> bool {
+        !self.denylist.contains(peer_id, &ip_addr)
+            && (self.allowlist.is_empty() || self.allowlist.contains(peer_id, &ip_addr))
+    }
+
+    pub(crate) fn accepts_peer(&self, peer: &Peer) -> bool {
+        self.accepts(peer.peer_id(), peer.ip_address())
+    }
+}
+
+// Computes a fingerprint of a discovered peer list that doesn't depend on the order of the peers.
+fn response_fingerprint(peers: &[Peer]) -> u64 {
+    let mut peer_hashes = peers
+        .iter()
+        .map(|peer| {
+            let mut hasher = DefaultHasher::new();
+            peer.peer_id().hash(&mut hasher);
+            peer.ip_address().hash(&mut hasher);
+            let mut services = peer
+                .services()
+                .names()
+                .map(|name| (name, peer.services().get(name).map(|endpoint| endpoint.port())))
+                .collect::<Vec<_>>();
+            services.sort_unstable();
+            services.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect::<Vec<_>>();
+    peer_hashes.sort_unstable();
+
+    let mut hasher = DefaultHasher::new();
+    peer_hashes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Watches the number of verified peers, and publishes an event whenever it crosses the configured threshold.
+///
+/// The count is assumed to be low initially, hence nothing is published until the threshold is reached once.
+///
+/// Also keeps track of the verified peers themselves, which can be subscribed to.
+#[derive(Clone)]
+pub(crate) struct PeerCountWatcher {
+    threshold: usize,
+    low: Arc<AtomicBool>,
+    verified_tx: Arc<watch::Sender<Vec<Peer>>>,
+    // Note: keeps the channel open even without subscribers.
+    verified_rx: watch::Receiver<Vec<Peer>>,
+}
+
+impl PeerCountWatcher {
+    pub(crate) fn new(threshold: usize) -> Self {
+        let (verified_tx, verified_rx) = watch::channel(Vec::new());
+
+        Self {
+            threshold,
+            low: Arc::new(AtomicBool::new(true)),
+            verified_tx: Arc::new(verified_tx),
+            verified_rx,
+        }
+    }
+
+    /// Returns a stream yielding the current verified peers, and then the verified peers whenever they change.
+    pub(crate) fn verified_peers(&self) -> WatchStream<Vec<Peer>> {
+        WatchStream::new(self.verified_rx.clone())
+    }
+
+    // Note: must not be called while holding a lock on the active peer list.
+    pub(crate) fn update(&self, active_peers: &ActivePeersList, event_tx: &EventTx) {
+        let verified = active_peers
+            .read()
+            .iter()
+            .filter(|p| p.metrics().verified_count() > 0)
+            .map(|p| p.peer().clone())
+            .collect::<Vec<_>>();
+        let verified_peers = verified.len();
+        let low = verified_peers < self.threshold;
+
+        let changed = {
+            let current = self.verified_rx.borrow();
+            let current_ids = current.iter().map(Peer::peer_id).collect::<HashSet<_>>();
+            current.len() != verified_peers || verified.iter().any(|p| !current_ids.contains(p.peer_id()))
+        };
+        if changed {
+            // Panic: the channel is kept open by our own receiver.
+            self.verified_tx
+                .send(verified)
+                .expect("error publishing verified peers");
+        }
+
+        if self.low.swap(low, Ordering::Relaxed) != low {
+            let threshold = self.threshold;
+            let event = if low {
+                Event::PeerCountLow {
+                    verified_peers,
+                    threshold,
+                }
+            } else {
+                Event::PeerCountRecovered {
+                    verified_peers,
+                    threshold,
+                }
+            };
+
+            // Panic: we don't allow channel send errors.
+            event_tx.send(event).expect("error publishing peer-count event");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        server::server_chan,
+        time::{PeerEstimatedTimeSource, SystemTimeSource},
+    };
+
+    #[tokio::test]
+    async fn begin_verification_retries_after_loss() {
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+
+        let active_peers = ActivePeersList::default();
+        active_peers.write().insert(ActivePeer::new(peer.clone()));
+
+        let request_mngr = RequestManager::new(0, 0, "127.0.0.1:14626".parse().unwrap());
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+
+        // Drop the first verification request, and respond to all subsequent ones.
+        let request_mngr_ = request_mngr.clone();
+        let responder = tokio::spawn(async move {
+            let mut num_requests = 0;
+            while let Some(packet) = server_rx.recv().await {
+                num_requests += 1;
+                if num_requests == 1 {
+                    continue;
+                }
+                if let Some(value) = request_mngr_.remove_request::<VerificationRequest>(&peer_id) {
+                    let response =
+                        VerificationResponse::new(value.request_hash, peer.services().clone(), packet.peer_addr.ip());
+                    let _ = value.response_tx.unwrap().send(response.to_protobuf().to_vec());
+                }
+            }
+            num_requests
+        });
+
+        assert!(begin_verification(
+            &peer_id,
+            &active_peers,
+            &request_mngr,
+            &server_tx,
+            1,
+            Duration::from_millis(100)
+        )
+        .await
+        .is_some());
+
+        drop(server_tx);
+        assert_eq!(responder.await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn begin_verification_retries_wait_for_the_retry_timeout() {
+        // The clock only advances by the timeouts waited for.
+        tokio::time::pause();
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+
+        let active_peers = ActivePeersList::default();
+        active_peers.write().insert(ActivePeer::new(peer));
+
+        let request_mngr = RequestManager::new(0, 0, "127.0.0.1:14627".parse().unwrap());
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+
+        // Never respond, so that every attempt times out.
+        let silent = tokio::spawn(async move {
+            let mut num_requests = 0;
+            while server_rx.recv().await.is_some() {
+                num_requests += 1;
+            }
+            num_requests
+        });
+
+        let start = tokio::time::Instant::now();
+        assert!(begin_verification(
+            &peer_id,
+            &active_peers,
+            &request_mngr,
+            &server_tx,
+            2,
+            Duration::from_millis(20)
+        )
+        .await
+        .is_none());
+
+        // Only the first attempt waits the full response timeout, each timeout being rounded up to the next
+        // millisecond by the timer.
+        let expected = RESPONSE_TIMEOUT + 2 * Duration::from_millis(20);
+        assert!(start.elapsed() >= expected);
+        assert!(start.elapsed() <= expected + Duration::from_millis(3));
+
+        drop(server_tx);
+        assert_eq!(silent.await.unwrap(), 3);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn stale_peers_are_not_restored() {
+        use crate::peer::stores::{SledPeerStore, SledPeerStoreConfig};
+
+        let fresh = Peer::new_test_peer(0);
+        let stale = Peer::new_test_peer(1);
+        let replacement = Peer::new_test_peer(2);
+
+        let active_peers = ActivePeersList::default();
+        let mut fresh_active = ActivePeer::new(fresh.clone());
+        fresh_active.metrics_mut().set_last_verif_response_timestamp();
+        fresh_active.metrics_mut().increment_verified_count();
+        active_peers.write().insert(fresh_active);
+        active_peers.write().insert(ActivePeer::new(stale.clone()));
+        let replacements = ReplacementPeersList::default();
+        replacements.write().insert(replacement.clone());
+
+        let peer_store = SledPeerStore::new(SledPeerStoreConfig::new().temporary(true)).unwrap();
+        peer_store.store_all_active(&active_peers).unwrap();
+        peer_store.store_all_replacements(&replacements).unwrap();
+
+        // Without a staleness window all peers are restored.
+        let restored_active = ActivePeersList::default();
+        let restored_replacements = ReplacementPeersList::default();
+        assert_eq!(
+            add_peers_from_store(
+                &peer_store,
+                &restored_active,
+                &restored_replacements,
+                None,
+                &PeerFilter::default()
+            )
+            .unwrap(),
+            3
+        );
+
+        let restored_active = ActivePeersList::default();
+        let restored_replacements = ReplacementPeersList::default();
+        assert_eq!(
+            add_peers_from_store(
+                &peer_store,
+                &restored_active,
+                &restored_replacements,
+                Some(Duration::from_secs(HOUR)),
+                &PeerFilter::default()
+            )
+            .unwrap(),
+            2
+        );
+
+        assert!(restored_active.read().find(stale.peer_id()).is_none());
+        assert_eq!(
+            restored_active
+                .read()
+                .find(fresh.peer_id())
+                .unwrap()
+                .metrics()
+                .verified_count(),
+            1
+        );
+        // Replacements are never verified, so they are restored regardless.
+        assert!(restored_replacements.read().find(replacement.peer_id()).is_some());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn restored_peers_beyond_the_cap_are_demoted() {
+        use crate::peer::stores::{SledPeerStore, SledPeerStoreConfig};
+
+        let active_peers = ActivePeersList::default();
+        for i in 0..4 {
+            let mut active_peer = ActivePeer::new(Peer::new_test_peer(i));
+            active_peer.metrics_mut().set_last_new_peers(i as usize);
+            active_peers.write().insert(active_peer);
+        }
+
+        let peer_store = SledPeerStore::new(SledPeerStoreConfig::new().temporary(true)).unwrap();
+        peer_store.store_all_active(&active_peers).unwrap();
+
+        let restored_active = ActivePeersList::new(2);
+        let restored_replacements = ReplacementPeersList::default();
+        assert_eq!(
+            add_peers_from_store(
+                &peer_store,
+                &restored_active,
+                &restored_replacements,
+                None,
+                &PeerFilter::default()
+            )
+            .unwrap(),
+            4
+        );
+
+        // The peers that returned the most new peers stay active.
+        assert_eq!(restored_active.read().len(), 2);
+        assert!(restored_active.read().iter().all(|p| p.metrics().last_new_peers() >= 2));
+        assert_eq!(restored_replacements.read().len(), 2);
+    }
+
+    #[test]
+    fn discovered_peers_beyond_the_cap_become_replacements() {
+        let local = Local::generate();
+        let active_peers = ActivePeersList::new(2);
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, _event_rx) = crate::event::event_chan();
+
+        for i in 0..5 {
+            assert!(add_peer::<false>(
+                Peer::new_test_peer(i),
+                &local,
+                &active_peers,
+                &replacements,
+                false,
+                EvictionMode::default(),
+                &QueryRng::default(),
+                &event_tx
+            )
+            .is_some());
+        }
+
+        assert_eq!(active_peers.read().len(), 2);
+        assert_eq!(replacements.read().len(), 3);
+    }
+
+    #[test]
+    fn peer_count_crosses_threshold() {
+        let active_peers = ActivePeersList::default();
+        let entry_peers = EntryPeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, mut event_rx) = crate::event::event_chan();
+        let peer_count = PeerCountWatcher::new(2);
+
+        let peer_ids = (0..3)
+            .map(|i| {
+                let peer = Peer::new_test_peer(i);
+                let peer_id = *peer.peer_id();
+                active_peers.write().insert(ActivePeer::new(peer));
+                peer_id
+            })
+            .collect::<Vec<_>>();
+
+        // Verify peers one by one until the threshold is reached.
+        for peer_id in peer_ids.iter() {
+            peer::set_front_and_update(peer_id, &active_peers);
+            peer_count.update(&active_peers, &event_tx);
+        }
+
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerCountRecovered {
+                verified_peers: 2,
+                threshold: 2
+            })
+        ));
+        assert!(event_rx.try_recv().is_err());
+
+        // Remove verified peers until the count drops below the threshold.
+        for peer_id in peer_ids.iter().take(2) {
+            remove_peer_from_active_list(
+                peer_id,
+                TransitionReason::ReverificationFailure,
+                &entry_peers,
+                &active_peers,
+                &replacements,
+                &event_tx,
+                &peer_count,
+                &Quarantine::new(Duration::ZERO, 0),
+            );
+        }
+
+        for peer_id in peer_ids.iter().take(2) {
+            assert!(matches!(event_rx.try_recv(), Ok(Event::PeerDeleted { peer_id: id }) if id == *peer_id));
+            assert!(matches!(
+                event_rx.try_recv(),
+                Ok(Event::PeerRemoved {
+                    peer_id: id,
+                    reason: TransitionReason::ReverificationFailure
+                }) if id == *peer_id
+            ));
+        }
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerCountLow {
+                verified_peers: 1,
+                threshold: 2
+            })
+        ));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn list_transitions_are_published() {
+        use crate::local::services::ServiceProtocol;
+
+        let local = Local::generate();
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, mut event_rx) = crate::event::event_chan();
+        let peer_count = PeerCountWatcher::new(1);
+        let quarantine = Quarantine::new(Duration::ZERO, 0);
+
+        let mut i = 0u8;
+        while !active_peers.read().is_full() {
+            active_peers.write().insert(ActivePeer::new(Peer::new_test_peer(i)));
+            i = i.wrapping_add(1);
+        }
+        let oldest_id = *active_peers.read().iter().last().unwrap().peer_id();
+
+        // A peer offering a rare service takes the place of the oldest active peer, which is demoted.
+        let mut rare_peer = Peer::new_test_peer(0);
+        rare_peer.add_service("rare", ServiceProtocol::Tcp, 15600);
+        let rare_peer_id = *rare_peer.peer_id();
+
+        assert_eq!(
+            add_peer::<false>(
+                rare_peer,
+                &local,
+                &active_peers,
+                &replacements,
+                true,
+                EvictionMode::Deterministic,
+                &QueryRng::default(),
+                &event_tx
+            ),
+            Some(rare_peer_id)
+        );
+        assert!(replacements.read().contains(&oldest_id));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerDemoted {
+                peer_id: id,
+                reason: TransitionReason::Promotion
+            }) if id == oldest_id
+        ));
+
+        // Removing an active peer promotes the demoted peer again.
+        remove_peer_from_active_list(
+            &rare_peer_id,
+            TransitionReason::QueryFailure,
+            &entry_peers,
+            &active_peers,
+            &replacements,
+            &event_tx,
+            &peer_count,
+            &quarantine,
+        );
+
+        assert!(active_peers.read().contains(&oldest_id));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerRemoved {
+                peer_id: id,
+                reason: TransitionReason::QueryFailure
+            }) if id == rare_peer_id
+        ));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerPromoted {
+                peer_id: id,
+                reason: TransitionReason::QueryFailure
+            }) if id == oldest_id
+        ));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn best_replacement_is_promoted_on_removal() {
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, mut event_rx) = crate::event::event_chan();
+        let peer_count = PeerCountWatcher::new(1);
+        let quarantine = Quarantine::new(Duration::ZERO, 1);
+
+        let removed = Peer::new_test_peer(0);
+        let removed_id = *removed.peer_id();
+        active_peers.write().insert(ActivePeer::new(removed));
+
+        // The oldest replacement passed its verification in quarantine, the next one is still in quarantine, and the
+        // newest one was never quarantined.
+        let replacement_ids = (1..4)
+            .map(|i| {
+                let peer = Peer::new_test_peer(i);
+                let peer_id = *peer.peer_id();
+                replacements.write().insert(peer);
+                peer_id
+            })
+            .collect::<Vec<_>>();
+        quarantine.admit(replacement_ids[0]);
+        quarantine.record_verification(&replacement_ids[0]);
+        quarantine.admit(replacement_ids[1]);
+
+        remove_peer_from_active_list(
+            &removed_id,
+            TransitionReason::QueryFailure,
+            &entry_peers,
+            &active_peers,
+            &replacements,
+            &event_tx,
+            &peer_count,
+            &quarantine,
+        );
+
+        assert_eq!(active_peers.read().len(), 1);
+        assert!(active_peers.read().contains(&replacement_ids[0]));
+        assert!(!replacements.read().contains(&replacement_ids[0]));
+        assert_eq!(quarantine.verifications(&replacement_ids[0]), 0);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::PeerRemoved { peer_id, .. }) if peer_id == removed_id));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerPromoted {
+                peer_id,
+                reason: TransitionReason::QueryFailure
+            }) if peer_id == replacement_ids[0]
+        ));
+
+        // Without verifications in quarantine, the most recently added replacement is promoted next.
+        remove_peer_from_active_list(
+            &replacement_ids[0],
+            TransitionReason::QueryFailure,
+            &entry_peers,
+            &active_peers,
+            &replacements,
+            &event_tx,
+            &peer_count,
+            &quarantine,
+        );
+
+        assert!(active_peers.read().contains(&replacement_ids[2]));
+        assert_eq!(replacements.read().len(), 1);
+    }
+
+    #[test]
+    fn removal_without_replacements_promotes_nothing() {
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, mut event_rx) = crate::event::event_chan();
+        let peer_count = PeerCountWatcher::new(1);
+        let quarantine = Quarantine::new(Duration::from_secs(60), 0);
+
+        let peer_ids = (0..2)
+            .map(|i| {
+                let peer = Peer::new_test_peer(i);
+                let peer_id = *peer.peer_id();
+                active_peers.write().insert(ActivePeer::new(peer));
+                peer_id
+            })
+            .collect::<Vec<_>>();
+
+        let remove = |peer_id: &PeerId| {
+            remove_peer_from_active_list(
+                peer_id,
+                TransitionReason::ReverificationFailure,
+                &entry_peers,
+                &active_peers,
+                &replacements,
+                &event_tx,
+                &peer_count,
+                &quarantine,
+            )
+        };
+
+        remove(&peer_ids[0]);
+        assert_eq!(active_peers.read().len(), 1);
+        assert!(matches!(event_rx.try_recv(), Ok(Event::PeerRemoved { peer_id, .. }) if peer_id == peer_ids[0]));
+        assert!(event_rx.try_recv().is_err());
+
+        // A replacement still in quarantine isn't promoted either.
+        let quarantined = Peer::new_test_peer(2);
+        let quarantined_id = *quarantined.peer_id();
+        replacements.write().insert(quarantined);
+        quarantine.admit(quarantined_id);
+
+        remove(&peer_ids[1]);
+        assert!(active_peers.read().is_empty());
+        assert!(replacements.read().contains(&quarantined_id));
+        assert!(matches!(event_rx.try_recv(), Ok(Event::PeerRemoved { peer_id, .. }) if peer_id == peer_ids[1]));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn verified_peers_are_streamed() {
+        use tokio_stream::StreamExt as _;
+
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let (event_tx, _event_rx) = crate::event::event_chan();
+        let peer_count = PeerCountWatcher::new(1);
+
+        let mut verified_peers = peer_count.verified_peers();
+        assert!(verified_peers.next().await.unwrap().is_empty());
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+        active_peers.write().insert(ActivePeer::new(peer));
+        active_peers.write().insert(ActivePeer::new(Peer::new_test_peer(1)));
+
+        // Only verified peers are streamed.
+        peer::set_front_and_update(&peer_id, &active_peers);
+        peer_count.update(&active_peers, &event_tx);
+
+        let peers = verified_peers.next().await.unwrap();
+        assert_eq!(peers.iter().map(|p| *p.peer_id()).collect::<Vec<_>>(), vec![peer_id]);
+
+        remove_peer_from_active_list(
+            &peer_id,
+            TransitionReason::ReverificationFailure,
+            &entry_peers,
+            &active_peers,
+            &replacements,
+            &event_tx,
+            &peer_count,
+            &Quarantine::new(Duration::ZERO, 0),
+        );
+
+        assert!(verified_peers.next().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicate_discovery_responses_are_skipped() {
+        let cache = DiscoveryResponseCache::new(Duration::from_secs(60));
+
+        let peer_id = *Peer::new_test_peer(0).peer_id();
+        let other_peer_id = *Peer::new_test_peer(1).peer_id();
+        let peers = (2..5).map(Peer::new_test_peer).collect::<Vec<_>>();
+        let mut reordered = peers.clone();
+        reordered.reverse();
+
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+        // Identical responses are detected regardless of the order of the peers.
+        assert!(cache.is_duplicate(&peer_id, &peers));
+        assert!(cache.is_duplicate(&peer_id, &reordered));
+        // Responses are remembered per peer.
+        assert!(!cache.is_duplicate(&other_peer_id, &peers));
+        // A different response is processed, and replaces the previous one.
+        assert!(!cache.is_duplicate(&peer_id, &peers[1..]));
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+        assert_eq!(cache.duplicates(), 2);
+    }
+
+    #[test]
+    fn duplicate_discovery_responses_expire() {
+        let cache = DiscoveryResponseCache::new(Duration::from_millis(10));
+        let peer_id = *Peer::new_test_peer(0).peer_id();
+        let peers = (1..3).map(Peer::new_test_peer).collect::<Vec<_>>();
+
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+
+        // A zero window disables the detection.
+        let cache = DiscoveryResponseCache::new(Duration::ZERO);
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+        assert!(!cache.is_duplicate(&peer_id, &peers));
+        assert_eq!(cache.duplicates(), 0);
+    }
+
+    #[test]
+    fn unknown_peer_policy_add() {
+        let verifications = UnknownPeerVerifications::new(UnknownPeerPolicy::Add);
+
+        assert!(verifications.admit(true));
+        assert!(verifications.admit(true));
+        assert!(!verifications.admit(false));
+        assert_eq!(
+            verifications.counts(),
+            UnknownPeerVerificationCounts {
+                accepted: 2,
+                rejected: 1,
+                ignored: 0
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_peer_policy_ignore() {
+        let verifications = UnknownPeerVerifications::new(UnknownPeerPolicy::Ignore);
+
+        assert!(!verifications.admit(true));
+        assert!(!verifications.admit(true));
+        assert!(!verifications.admit(false));
+        assert_eq!(
+            verifications.counts(),
+            UnknownPeerVerificationCounts {
+                accepted: 0,
+                rejected: 1,
+                ignored: 2
+            }
+        );
+    }
+
+    // Handles a verification request from an unknown peer, and returns the resulting peer lists and the peer's id.
+    fn handle_unknown_verification_request(quarantine: &Quarantine) -> (ActivePeersList, ReplacementPeersList, PeerId) {
+        let local = Local::generate();
+        let request_mngr = RequestManager::new(0, 1, "127.0.0.1:14626".parse().unwrap());
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = crate::event::event_chan();
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let peer_count = PeerCountWatcher::new(1);
+        let response_cache = DiscoveryResponseCache::new(Duration::ZERO);
+        let unknown_verifications = UnknownPeerVerifications::new(UnknownPeerPolicy::Add);
+        let foreign_peers = ForeignPeers::new(ForeignPeerPolicy::Keep);
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+        let peer_addr = peer.service_socketaddr(AUTOPEERING_SERVICE_NAME).unwrap();
+
+        let verif_req = VerificationRequest::new(0, 1, peer_addr, "127.0.0.1".parse().unwrap());
+        let msg_bytes = verif_req.to_protobuf().to_vec();
+
+        let ctx = RecvContext {
+            peer_id: &peer_id,
+            msg_bytes: &msg_bytes,
+            server_tx: &server_tx,
+            local: &local,
+            request_mngr: &request_mngr,
+            peer_addr,
+            event_tx: &event_tx,
+            entry_peers: &entry_peers,
+            active_peers: &active_peers,
+            replacements: &replacements,
+            retain_rare_services: false,
+            eviction_mode: EvictionMode::default(),
+            peer_count: &peer_count,
+            response_cache: &response_cache,
+            quarantine,
+            unknown_verifications: &unknown_verifications,
+            foreign_peers: &foreign_peers,
+            peer_filter: &PeerFilter::default(),
+            rng: &QueryRng::default(),
+        };
+        handle_verification_request(verif_req, ctx);
+
+        (active_peers, replacements, peer_id)
+    }
+
+    #[test]
+    fn unknown_verifying_peers_are_added_to_the_replacements() {
+        let quarantine = Quarantine::new(Duration::ZERO, 0);
+        let (active_peers, replacements, peer_id) = handle_unknown_verification_request(&quarantine);
+
+        assert!(!active_peers.read().contains(&peer_id));
+        assert!(replacements.read().contains(&peer_id));
+        assert!(quarantine.is_cleared(&peer_id));
+    }
+
+    #[test]
+    fn unknown_verifying_peers_are_quarantined() {
+        let quarantine = Quarantine::new(Duration::from_secs(60), 0);
+        let (active_peers, replacements, peer_id) = handle_unknown_verification_request(&quarantine);
+
+        assert!(!active_peers.read().contains(&peer_id));
+        assert!(replacements.read().contains(&peer_id));
+        assert!(!quarantine.is_cleared(&peer_id));
+    }
+
+    #[test]
+    fn foreign_peer_is_evicted_and_denylisted() {
+        let local = Local::generate();
+        let request_mngr = RequestManager::new(0, 1, "127.0.0.1:14626".parse().unwrap());
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, mut event_rx) = crate::event::event_chan();
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let peer_count = PeerCountWatcher::new(1);
+        let response_cache = DiscoveryResponseCache::new(Duration::ZERO);
+        let quarantine = Quarantine::new(Duration::ZERO, 0);
+        let unknown_verifications = UnknownPeerVerifications::new(UnknownPeerPolicy::Add);
+        let foreign_peers = ForeignPeers::new(ForeignPeerPolicy::Denylist);
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+        let peer_addr = peer.service_socketaddr(AUTOPEERING_SERVICE_NAME).unwrap();
+        active_peers.write().insert(ActivePeer::new(peer));
+
+        // The peer claims to belong to network 2, while we belong to network 1.
+        let verif_req = VerificationRequest::new(0, 2, peer_addr, "127.0.0.1".parse().unwrap());
+        let msg_bytes = verif_req.to_protobuf().to_vec();
+        let network_id = match validate_verification_request(&verif_req, 0, 1, &SystemTimeSource) {
+            Err(ValidationError::NetworkIdMismatch { received, .. }) => received,
+            _ => panic!("expected a network id mismatch"),
+        };
+
+        let ctx = RecvContext {
+            peer_id: &peer_id,
+            msg_bytes: &msg_bytes,
+            server_tx: &server_tx,
+            local: &local,
+            request_mngr: &request_mngr,
+            peer_addr,
+            event_tx: &event_tx,
+            entry_peers: &entry_peers,
+            active_peers: &active_peers,
+            replacements: &replacements,
+            retain_rare_services: false,
+            eviction_mode: EvictionMode::default(),
+            peer_count: &peer_count,
+            response_cache: &response_cache,
+            quarantine: &quarantine,
+            unknown_verifications: &unknown_verifications,
+            foreign_peers: &foreign_peers,
+            peer_filter: &PeerFilter::default(),
+            rng: &QueryRng::default(),
+        };
+        handle_foreign_peer(network_id, ctx);
+
+        assert!(!active_peers.read().contains(&peer_id));
+        assert!(foreign_peers.is_denied(&peer_id));
+        assert_eq!(foreign_peers.evictions(), 1);
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::PeerRemoved {
+                peer_id: id,
+                reason: TransitionReason::ForeignNetwork
+            }) if id == peer_id
+        ));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(Event::ForeignPeerEvicted { peer_id: id, network_id: 2 }) if id == peer_id
+        ));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn discovered_peers_are_filtered() {
+        let local = Local::generate();
+        let request_mngr = RequestManager::new(0, 1, "127.0.0.1:14626".parse().unwrap());
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = crate::event::event_chan();
+        let entry_peers = EntryPeersList::default();
+        let active_peers = ActivePeersList::default();
+        let replacements = ReplacementPeersList::default();
+        let peer_count = PeerCountWatcher::new(1);
+        let response_cache = DiscoveryResponseCache::new(Duration::ZERO);
+        let quarantine = Quarantine::new(Duration::ZERO, 0);
+        let unknown_verifications = UnknownPeerVerifications::new(UnknownPeerPolicy::Add);
+        let foreign_peers = ForeignPeers::new(ForeignPeerPolicy::Keep);
+
+        let queried = Peer::new_test_peer(0);
+        let queried_id = *queried.peer_id();
+        let peer_addr = queried.service_socketaddr(AUTOPEERING_SERVICE_NAME).unwrap();
+        active_peers.write().insert(ActivePeer::new(queried));
+
+        // Test peers use the IP address `127.0.0.<index>`.
+        let denied = Peer::new_test_peer(1);
+        let allowed = Peer::new_test_peer(2);
+        let unlisted = Peer::new_test_peer(3);
+
+        // The denylist takes precedence over the allowlist.
+        let peer_filter = PeerFilter::new(
+            &[
+                PeerFilterEntry::PeerId(Box::new(*denied.peer_id())),
+                PeerFilterEntry::PeerId(Box::new(*allowed.peer_id())),
+            ],
+            &[PeerFilterEntry::IpAddr(denied.ip_address())],
+        );
+
+        let disc_res = DiscoveryResponse::new([0; 32], vec![denied.clone(), allowed.clone(), unlisted.clone()]);
+        let msg_bytes = disc_res.to_protobuf().to_vec();
+        let disc_reqval = RequestValue {
+            request_hash: [0; 32],
+            issue_time: 0,
+            response_tx: None,
+        };
+
+        let ctx = RecvContext {
+            peer_id: &queried_id,
+            msg_bytes: &msg_bytes,
+            server_tx: &server_tx,
+            local: &local,
+            request_mngr: &request_mngr,
+            peer_addr,
+            event_tx: &event_tx,
+            entry_peers: &entry_peers,
+            active_peers: &active_peers,
+            replacements: &replacements,
+            retain_rare_services: false,
+            eviction_mode: EvictionMode::default(),
+            peer_count: &peer_count,
+            response_cache: &response_cache,
+            quarantine: &quarantine,
+            unknown_verifications: &unknown_verifications,
+            foreign_peers: &foreign_peers,
+            peer_filter: &peer_filter,
+            rng: &QueryRng::default(),
+        };
+        handle_discovery_response(disc_res, disc_reqval, ctx);
+
+        assert!(active_peers.read().contains(allowed.peer_id()));
+        assert!(!active_peers.read().contains(denied.peer_id()));
+        assert!(!active_peers.read().contains(unlisted.peer_id()));
+        assert!(replacements.read().is_empty());
+        assert_eq!(
+            active_peers
+                .read()
+                .find(&queried_id)
+                .unwrap()
+                .metrics()
+                .last_new_peers(),
+            1
+        );
+    }
+
+    #[test]
+    fn requests_are_validated_against_the_time_source() {
+        let active_peers = ActivePeersList::default();
+        let time_source = PeerEstimatedTimeSource::default();
+        let disc_req = DiscoveryRequest::new();
+
+        assert!(validate_discovery_request(&disc_req, &time_source).is_ok());
+
+        // The clocks of the known peers are a minute ahead of the local clock.
+        for i in 0..3 {
+            let peer = Peer::new_test_peer(i);
+            let peer_id = *peer.peer_id();
+            active_peers.write().insert(ActivePeer::new(peer));
+
+            record_peer_time(&peer_id, disc_req.timestamp() + 60, &active_peers, &time_source);
+        }
+
+        // Unknown peers are ignored.
+        for _ in 0..3 {
+            record_peer_time(&PeerId::generate(), disc_req.timestamp(), &active_peers, &time_source);
+        }
+
+        assert!(matches!(
+            validate_discovery_request(&disc_req, &time_source),
+            Err(ValidationError::RequestExpired)
+        ));
+        assert!(validate_discovery_request(&disc_req, &SystemTimeSource).is_ok());
+    }
+}
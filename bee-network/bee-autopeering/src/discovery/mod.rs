@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod handle;
 pub(crate) mod manager;
 pub(crate) mod messages;
+pub(crate) mod quarantine;
 pub(crate) mod query;
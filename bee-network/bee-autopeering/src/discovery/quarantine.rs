@@ -0,0 +1,158 @@
+// Copyright 2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quarantine of newly discovered peers before they can become active peers.
+
+use crate::peer::PeerId;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+struct QuarantineEntry {
+    since: Instant,
+    verifications: usize,
+}
+
+/// Keeps track of the peers in quarantine, i.e. newly discovered peers that have to stay in the replacement list for
+/// some time, and have to be verified several times, before they are allowed to be promoted to the active peer list.
+#[derive(Clone)]
+pub(crate) struct Quarantine {
+    duration: Duration,
+    required_verifications: usize,
+    entries: Arc<Mutex<HashMap<PeerId, QuarantineEntry>>>,
+}
+
+impl Quarantine {
+    pub(crate) fn new(duration: Duration, required_verifications: usize) -> Self {
+        Self {
+            duration,
+            required_verifications,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whether newly discovered peers need to be quarantined at all.
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.duration.is_zero() || self.required_verifications > 0
+    }
+
+    /// Puts a peer into quarantine.
+    pub(crate) fn admit(&self, peer_id: PeerId) {
+        self.entries().insert(
+            peer_id,
+            QuarantineEntry {
+                since: Instant::now(),
+                verifications: 0,
+            },
+        );
+    }
+
+    /// Records a successful verification of a quarantined peer, and returns whether the peer is in quarantine.
+    pub(crate) fn record_verification(&self, peer_id: &PeerId) -> bool {
+        if let Some(entry) = self.entries().get_mut(peer_id) {
+            entry.verifications += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether a peer may be promoted to the active peer list. Peers not in quarantine always may.
+    pub(crate) fn is_cleared(&self, peer_id: &PeerId) -> bool {
+        self.is_cleared_at(peer_id, Instant::now())
+    }
+
+    fn is_cleared_at(&self, peer_id: &PeerId, now: Instant) -> bool {
+        match self.entries().get(peer_id) {
+            Some(entry) => {
+                now.saturating_duration_since(entry.since) >= self.duration
+                    && entry.verifications >= self.required_verifications
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the number of verifications a peer passed in quarantine, which is 0 for peers not in quarantine.
+    pub(crate) fn verifications(&self, peer_id: &PeerId) -> usize {
+        self.entries().get(peer_id).map_or(0, |entry| entry.verifications)
+    }
+
+    /// Returns the quarantined peer that lacks the most verifications, if any lacks one.
+    pub(crate) fn next_to_verify(&self) -> Option<PeerId> {
+        self.entries()
+            .iter()
+            .filter(|(_, entry)| entry.verifications < self.required_verifications)
+            .min_by_key(|(_, entry)| (entry.verifications, entry.since))
+            .map(|(peer_id, _)| *peer_id)
+    }
+
+    /// Releases a peer from quarantine.
+    pub(crate) fn release(&self, peer_id: &PeerId) {
+        self.entries().remove(peer_id);
+    }
+
+    /// Releases all peers that don't satisfy the predicate, e.g. because they were dropped from the replacement list.
+    pub(crate) fn retain(&self, mut f: impl FnMut(&PeerId) -> bool) {
+        self.entries().retain(|peer_id, _| f(peer_id));
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<PeerId, QuarantineEntry>> {
+        // Panic: we don't allow poisoned locks.
+        self.entries.lock().expect("error getting lock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::Peer;
+
+    #[test]
+    fn promotes_only_after_time_and_verifications() {
+        let quarantine = Quarantine::new(Duration::from_secs(60), 2);
+        assert!(quarantine.is_enabled());
+
+        let peer_id = *Peer::new_test_peer(0).peer_id();
+        let other_peer_id = *Peer::new_test_peer(1).peer_id();
+        quarantine.admit(peer_id);
+
+        let now = Instant::now();
+        let later = now + Duration::from_secs(61);
+
+        // Neither requirement is met.
+        assert!(!quarantine.is_cleared_at(&peer_id, now));
+        assert_eq!(quarantine.next_to_verify(), Some(peer_id));
+
+        // Only the time requirement is met.
+        assert!(!quarantine.is_cleared_at(&peer_id, later));
+
+        // Only the verification requirement is met.
+        assert!(quarantine.record_verification(&peer_id));
+        assert!(!quarantine.is_cleared_at(&peer_id, later));
+        assert!(quarantine.record_verification(&peer_id));
+        assert!(!quarantine.is_cleared_at(&peer_id, now));
+        assert_eq!(quarantine.next_to_verify(), None);
+
+        // Both requirements are met.
+        assert!(quarantine.is_cleared_at(&peer_id, later));
+
+        // Peers that aren't quarantined are always cleared.
+        assert!(!quarantine.record_verification(&other_peer_id));
+        assert!(quarantine.is_cleared(&other_peer_id));
+        quarantine.release(&peer_id);
+        assert!(quarantine.is_cleared(&peer_id));
+    }
+
+    #[test]
+    fn disabled_quarantine() {
+        let quarantine = Quarantine::new(Duration::ZERO, 0);
+        assert!(!quarantine.is_enabled());
+
+        let peer_id = *Peer::new_test_peer(0).peer_id();
+        quarantine.admit(peer_id);
+        assert!(quarantine.is_cleared(&peer_id));
+    }
+}
@@ -2,18 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    discovery::manager,
-    event::EventTx,
+    config::SelectionStrategy,
+    discovery::{
+        handle::{DiscoveryMetrics, LivenessProbeSlot, VerificationReport, VerificationResult},
+        manager::{self, PeerCountWatcher, QUERY_BACKOFF_INTERVAL, QUERY_INTERVAL_DEFAULT},
+        messages::{DiscoveryRequest, VerificationRequest},
+        quarantine::Quarantine,
+    },
+    event::{EventTx, TransitionReason},
+    local::services::{ServiceMap, AUTOPEERING_SERVICE_NAME},
     peer::{
-        lists::{ActivePeer, ActivePeersList, EntryPeersList, ReplacementPeersList},
+        self,
+        lists::{ActivePeersList, EntryPeersList, ReplacementPeersList},
         PeerId,
     },
     request::RequestManager,
     server::ServerTx,
-    task::Repeat,
+    task::{Repeat, UnsupervisedTask},
+    time,
 };
 
-use rand::{thread_rng, Rng as _};
+use rand::{rngs::StdRng, seq::SliceRandom as _, thread_rng, Rng, RngCore, SeedableRng};
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    iter,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 #[derive(Clone)]
 pub(crate) struct QueryContext {
@@ -23,6 +44,196 @@ pub(crate) struct QueryContext {
     pub(crate) replacements: ReplacementPeersList,
     pub(crate) server_tx: ServerTx,
     pub(crate) event_tx: EventTx,
+    pub(crate) verification_retries: usize,
+    pub(crate) verification_retry_timeout: Duration,
+    pub(crate) liveness_probe: LivenessProbeSlot,
+    pub(crate) peer_count: PeerCountWatcher,
+    pub(crate) quarantine: Quarantine,
+    pub(crate) query_cooldown: QueryCooldown,
+    pub(crate) peers_per_query: usize,
+    pub(crate) selection: PeerSelection,
+    pub(crate) rng: QueryRng,
+    pub(crate) task_timeout: Duration,
+    pub(crate) max_reverification_failures: usize,
+    pub(crate) reverification_backoff: Duration,
+    pub(crate) counters: DiscoveryCounters,
+    // Bounds the number of peers verified concurrently on demand, across all batches.
+    pub(crate) verification_slots: Arc<Semaphore>,
+}
+
+#[cfg(test)]
+impl QueryContext {
+    // Creates a context for tests, whose settings can be overridden with the struct update syntax.
+    pub(crate) fn new_test(active_peers: ActivePeersList, server_tx: ServerTx, event_tx: EventTx) -> Self {
+        Self {
+            request_mngr: RequestManager::new(0, 0, "127.0.0.1:14626".parse().unwrap()),
+            entry_peers: EntryPeersList::default(),
+            active_peers,
+            replacements: ReplacementPeersList::default(),
+            server_tx,
+            event_tx,
+            verification_retries: 0,
+            verification_retry_timeout: Duration::from_millis(100),
+            liveness_probe: Default::default(),
+            quarantine: Quarantine::new(Duration::ZERO, 0),
+            peer_count: PeerCountWatcher::new(3),
+            query_cooldown: QueryCooldown::new(0),
+            peers_per_query: 2,
+            selection: PeerSelection::LastNewPeers,
+            rng: QueryRng::default(),
+            task_timeout: Duration::from_secs(10),
+            max_reverification_failures: 1,
+            reverification_backoff: Duration::ZERO,
+            counters: DiscoveryCounters::default(),
+            verification_slots: Arc::new(Semaphore::new(8)),
+        }
+    }
+}
+
+/// Counts the queries and reverifications of the peer discovery, and their outcomes.
+#[derive(Clone, Default)]
+pub(crate) struct DiscoveryCounters(Arc<DiscoveryCountersInner>);
+
+#[derive(Default)]
+struct DiscoveryCountersInner {
+    queries: AtomicU64,
+    successful_queries: AtomicU64,
+    failed_queries: AtomicU64,
+    discovered_peers: AtomicU64,
+    reverifications: AtomicU64,
+    removed_peers: AtomicU64,
+}
+
+impl DiscoveryCounters {
+    fn query_sent(&self) {
+        self.0.queries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn query_succeeded(&self, new_peers: usize) {
+        self.0.successful_queries.fetch_add(1, Ordering::Relaxed);
+        self.0.discovered_peers.fetch_add(new_peers as u64, Ordering::Relaxed);
+    }
+
+    fn query_failed(&self) {
+        self.0.failed_queries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn reverification_sent(&self) {
+        self.0.reverifications.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn peer_removed(&self) {
+        self.0.removed_peers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current values of all counters.
+    pub(crate) fn metrics(&self) -> DiscoveryMetrics {
+        DiscoveryMetrics {
+            queries: self.0.queries.load(Ordering::Relaxed),
+            successful_queries: self.0.successful_queries.load(Ordering::Relaxed),
+            failed_queries: self.0.failed_queries.load(Ordering::Relaxed),
+            discovered_peers: self.0.discovered_peers.load(Ordering::Relaxed),
+            reverifications: self.0.reverifications.load(Ordering::Relaxed),
+            removed_peers: self.0.removed_peers.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Keeps track of the query round in which each peer was last queried, so that peers queried during the last few
+/// rounds are only selected again if there are too few other peers.
+#[derive(Clone)]
+pub(crate) struct QueryCooldown {
+    rounds: usize,
+    inner: Arc<Mutex<QueryRounds>>,
+}
+
+#[derive(Default)]
+struct QueryRounds {
+    current: u64,
+    last_queried: HashMap<PeerId, u64>,
+}
+
+impl QueryCooldown {
+    pub(crate) fn new(rounds: usize) -> Self {
+        Self {
+            rounds,
+            inner: Arc::new(Mutex::new(QueryRounds::default())),
+        }
+    }
+
+    /// Whether a peer was queried during the last rounds.
+    pub(crate) fn is_cooling_down(&self, peer_id: &PeerId) -> bool {
+        let inner = self.inner.lock().expect("error locking query rounds");
+
+        match inner.last_queried.get(peer_id) {
+            Some(round) => inner.current - round <= self.rounds as u64,
+            None => false,
+        }
+    }
+
+    /// Records the peers queried in the current round, and starts the next round.
+    pub(crate) fn record(&self, peer_ids: &[PeerId]) {
+        if self.rounds == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("error locking query rounds");
+        let current = inner.current;
+
+        for peer_id in peer_ids {
+            inner.last_queried.insert(*peer_id, current);
+        }
+
+        inner.current += 1;
+
+        // Forget about the peers that cooled down already.
+        let rounds = self.rounds as u64;
+        inner.last_queried.retain(|_, round| current + 1 - *round <= rounds);
+    }
+}
+
+/// The source of randomness used to select the peers to query, which can be seeded to make the selection reproducible.
+#[derive(Clone, Default)]
+pub(crate) enum QueryRng {
+    /// Uses the thread-local generator.
+    #[default]
+    Thread,
+    /// Uses a generator shared by all clones.
+    Seeded(Arc<Mutex<StdRng>>),
+}
+
+impl QueryRng {
+    pub(crate) fn seeded(seed: u64) -> Self {
+        Self::Seeded(Arc::new(Mutex::new(StdRng::seed_from_u64(seed))))
+    }
+
+    /// Provides the generator to the given closure.
+    pub(crate) fn with<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        match self {
+            Self::Thread => f(&mut thread_rng()),
+            Self::Seeded(rng) => f(&mut *rng.lock().expect("error locking query rng")),
+        }
+    }
+}
+
+/// How the peers to query are selected, see [`SelectionStrategy`].
+#[derive(Clone, Copy)]
+pub(crate) enum PeerSelection {
+    LastNewPeers,
+    /// Spreads the selected peers across the distance buckets around the given local peer.
+    Distance(PeerId),
+    /// Like `Distance`, but only among the heaviest peers.
+    Hybrid(PeerId),
+}
+
+impl PeerSelection {
+    pub(crate) fn new(strategy: SelectionStrategy, local_id: PeerId) -> Self {
+        match strategy {
+            SelectionStrategy::LastNewPeers => Self::LastNewPeers,
+            SelectionStrategy::Distance => Self::Distance(local_id),
+            SelectionStrategy::Hybrid => Self::Hybrid(local_id),
+        }
+    }
 }
 
 // Hive.go: pings the oldest active peer.
@@ -30,150 +241,476 @@ pub(crate) fn reverify_fn() -> Repeat<QueryContext> {
     Box::new(|ctx| {
         // Determine the next peer to re/verifiy.
         if let Some(peer_id) = peer_to_reverify(&ctx.active_peers) {
-            let ctx_ = ctx.clone();
-
-            // TODO: introduce `UnsupervisedTask` type, that always finishes after a timeout.
-            let _ = tokio::spawn(async move {
-                if let Some(services) =
-                    manager::begin_verification(&peer_id, &ctx_.active_peers, &ctx_.request_mngr, &ctx_.server_tx).await
-                {
-                    // Hive.go: no need to do anything here, as the peer is bumped when handling the pong
-                    log::debug!(
-                        "Reverified {}. Peer offers {} service/s: {}",
-                        &peer_id,
-                        services.len(),
-                        services
-                    );
-                } else {
-                    log::debug!("Failed to reverify {}. Removing peer.", peer_id);
-
-                    manager::remove_peer_from_active_list(
-                        &peer_id,
-                        &ctx_.entry_peers,
-                        &ctx_.active_peers,
-                        &ctx_.replacements,
-                        &ctx_.event_tx,
-                    )
-                }
-            });
+            spawn_reverification(peer_id, ctx);
         } else {
             log::debug!("Currently no peers to reverify.");
         }
+
+        // Verify a quarantined peer as well, if any still lacks verifications.
+        if let Some(peer_id) = ctx.quarantine.next_to_verify() {
+            let peer_addr = ctx
+                .replacements
+                .read()
+                .find(&peer_id)
+                .and_then(|p| p.service_socketaddr(AUTOPEERING_SERVICE_NAME));
+
+            if let Some(peer_addr) = peer_addr {
+                manager::send_verification_request_to_addr(
+                    peer_addr,
+                    &peer_id,
+                    &ctx.request_mngr,
+                    &ctx.server_tx,
+                    None,
+                );
+            } else {
+                ctx.quarantine.release(&peer_id);
+            }
+        }
     })
 }
 
+/// Verifies a batch of peers concurrently without removing those that fail.
+pub(crate) async fn verify_peers(peer_ids: Vec<PeerId>, ctx: &QueryContext) -> Vec<VerificationReport> {
+    let handles = peer_ids
+        .into_iter()
+        .map(|peer_id| {
+            let ctx = ctx.clone();
+
+            tokio::spawn(async move {
+                // Panic: the semaphore is never closed.
+                let _permit = ctx
+                    .verification_slots
+                    .acquire()
+                    .await
+                    .expect("error acquiring verification permit");
+
+                let result = if ctx.active_peers.read().contains(&peer_id) {
+                    match verify(&peer_id, &ctx).await {
+                        Some(services) => VerificationResult::Verified { services },
+                        None => VerificationResult::Failed,
+                    }
+                } else {
+                    VerificationResult::Unknown
+                };
+
+                VerificationReport { peer_id, result }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut reports = Vec::with_capacity(handles.len());
+    for handle in handles {
+        // Panic: verification tasks don't panic.
+        reports.push(handle.await.expect("error joining verification task"));
+    }
+
+    reports
+}
+
+// Verifies a peer over the liveness probe if it can reach the peer, and over the autopeering transport otherwise.
+async fn verify(peer_id: &PeerId, ctx: &QueryContext) -> Option<ServiceMap> {
+    if let Some(probe) = ctx.liveness_probe.get() {
+        if let Some(alive) = probe.probe(peer_id).await {
+            log::trace!("Probed {}: alive = {}.", peer_id, alive);
+
+            if !alive {
+                return None;
+            }
+
+            // Bump the peer just like a verification response would.
+            let verified_count = peer::set_front_and_update(peer_id, &ctx.active_peers)?;
+            if verified_count == 1 {
+                ctx.peer_count.update(&ctx.active_peers, &ctx.event_tx);
+            }
+
+            return ctx
+                .active_peers
+                .read()
+                .find(peer_id)
+                .map(|p| p.peer().services().clone());
+        }
+    }
+
+    manager::begin_verification(
+        peer_id,
+        &ctx.active_peers,
+        &ctx.request_mngr,
+        &ctx.server_tx,
+        ctx.verification_retries,
+        ctx.verification_retry_timeout,
+    )
+    .await
+}
+
 // Hive.go: returns the oldest peer, or nil if empty.
-fn peer_to_reverify(active_peers: &ActivePeersList) -> Option<PeerId> {
-    active_peers.read().get_oldest().map(|p| *p.peer_id())
+// Here, peers backing off after failed reverifications are skipped.
+pub(crate) fn peer_to_reverify(active_peers: &ActivePeersList) -> Option<PeerId> {
+    let now = time::unix_now_secs();
+
+    active_peers
+        .read()
+        .iter()
+        .rev()
+        .find(|p| p.metrics().is_reverification_due(now))
+        .map(|p| *p.peer_id())
+}
+
+// Returns the time a peer isn't reverified after the given number of failed reverifications in a row.
+pub(crate) fn reverification_backoff(base: Duration, failures: usize) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(failures.saturating_sub(1) as u32))
+}
+
+// Removes a peer once its reverification failed `max_reverification_failures` times in a row, and backs off from
+// reverifying it otherwise.
+fn handle_reverification_failure(peer_id: &PeerId, ctx: &QueryContext) {
+    let failures = {
+        let mut active_peers = ctx.active_peers.write();
+
+        let metrics = match active_peers.find_mut(peer_id) {
+            Some(active_peer) => active_peer.metrics_mut(),
+            None => return,
+        };
+
+        let failures = metrics.increment_reverification_failures();
+        if failures < ctx.max_reverification_failures {
+            let backoff = reverification_backoff(ctx.reverification_backoff, failures);
+            metrics.set_reverify_after(time::unix_now_secs() + backoff.as_secs());
+
+            log::debug!(
+                "Failed to reverify {} ({}/{}). Backing off for {:?}.",
+                peer_id,
+                failures,
+                ctx.max_reverification_failures,
+                backoff
+            );
+        }
+
+        failures
+    };
+
+    if failures >= ctx.max_reverification_failures {
+        log::debug!(
+            "Failed to reverify {} {} time/s in a row. Removing peer.",
+            peer_id,
+            failures
+        );
+
+        remove_active_peer(peer_id, TransitionReason::ReverificationFailure, ctx);
+    }
+}
+
+// Returns at most `max_peers` active peers, starting with the oldest one.
+pub(crate) fn peers_to_reverify(active_peers: &ActivePeersList, max_peers: usize) -> Vec<PeerId> {
+    let peer_ids = active_peers.read().iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+
+    peer_ids.into_iter().rev().take(max_peers).collect()
+}
+
+// Reverifies up to `max_peers` of the oldest active peers right away, handling those that fail just like after a
+// scheduled reverification. Returns the number of peers being reverified.
+pub(crate) fn reverify_burst(ctx: &QueryContext, max_peers: usize) -> usize {
+    let peer_ids = peers_to_reverify(&ctx.active_peers, max_peers);
+    let num_peers = peer_ids.len();
+
+    if num_peers == 0 {
+        log::debug!("Currently no peers to reverify.");
+        return 0;
+    }
+
+    log::debug!("Reverifying {} peer/s off schedule...", num_peers);
+
+    let ctx = ctx.clone();
+    tokio::spawn(async move { reverify_batch(peer_ids, &ctx).await });
+
+    num_peers
+}
+
+// Reverifies a batch of peers concurrently, counting and handling each reverification like a scheduled one.
+async fn reverify_batch(peer_ids: Vec<PeerId>, ctx: &QueryContext) {
+    peer_ids.iter().for_each(|_| ctx.counters.reverification_sent());
+
+    for VerificationReport { peer_id, result } in verify_peers(peer_ids, ctx).await {
+        if let VerificationResult::Failed = result {
+            handle_reverification_failure(&peer_id, ctx);
+        }
+    }
 }
 
 // Hive.go:
 // The current strategy is to always select the latest verified peer and one of
 // the peers that returned the most number of peers the last time it was queried.
+// Here, more than one of the latter are selected if more than 2 peers are to be queried per round.
 pub(crate) fn query_fn() -> Repeat<QueryContext> {
     Box::new(|ctx| {
-        let peers = select_peers_to_query(&ctx.active_peers);
+        let peers = ctx.rng.with(|rng| {
+            select_peers_to_query(
+                &ctx.active_peers,
+                &ctx.entry_peers,
+                &ctx.query_cooldown,
+                ctx.peers_per_query,
+                &ctx.selection,
+                rng,
+            )
+        });
+        ctx.query_cooldown.record(&peers);
+
         if peers.is_empty() {
             log::debug!("No peers to query.");
         } else {
             log::debug!("Querying {} peer/s...", peers.len());
 
             for peer_id in peers.into_iter() {
-                let ctx_ = ctx.clone();
-
-                // TODO: introduce `UnsupervisedTask` type, that always finishes after a timeout.
-                tokio::spawn(async move {
-                    if let Some(peers) =
-                        manager::begin_discovery(&peer_id, &ctx_.active_peers, &ctx_.request_mngr, &ctx_.server_tx)
-                            .await
-                    {
-                        log::debug!("Query successful. Received {} peers.", peers.len());
-                    } else {
-                        log::debug!("Query unsuccessful. Removing peer {}.", peer_id);
-
-                        manager::remove_peer_from_active_list(
-                            &peer_id,
-                            &ctx_.entry_peers,
-                            &ctx_.active_peers,
-                            &ctx_.replacements,
-                            &ctx_.event_tx,
-                        )
-                    }
-                });
+                spawn_query(peer_id, ctx);
             }
         }
     })
 }
 
-// Hive.go: selects the peers that should be queried.
-fn select_peers_to_query(active_peers: &ActivePeersList) -> Vec<PeerId> {
-    let mut verif_peers = manager::get_verified_peers(active_peers);
+// Reverifies a specific active peer right away, just like a scheduled reverification, and returns the services it
+// offers if it responded.
+pub(crate) async fn reverify_peer(peer_id: PeerId, ctx: &QueryContext) -> Option<ServiceMap> {
+    if !ctx.active_peers.read().contains(&peer_id) {
+        log::debug!("Not reverifying unknown {}.", peer_id);
+        return None;
+    }
+
+    // Panic: reverification tasks don't panic.
+    spawn_reverification(peer_id, ctx)
+        .await
+        .expect("error joining reverification task")
+        .flatten()
+}
+
+// Reverifies a peer in the background, treating a reverification that doesn't finish in time as failed.
+fn spawn_reverification(peer_id: PeerId, ctx: &QueryContext) -> JoinHandle<Option<Option<ServiceMap>>> {
+    let ctx_ = ctx.clone();
+    let ctx_timeout = ctx.clone();
 
-    // If we have less than 3 verified peers, then we use those for the query.
-    if verif_peers.len() < 3 {
-        verif_peers.into_iter().map(|ap| *ap.peer_id()).collect::<Vec<_>>()
+    ctx.counters.reverification_sent();
+
+    UnsupervisedTask::new("Reverification", ctx.task_timeout).spawn(
+        async move {
+            if let Some(services) = verify(&peer_id, &ctx_).await {
+                // Hive.go: no need to do anything here, as the peer is bumped when handling the pong
+                log::debug!(
+                    "Reverified {}. Peer offers {} service/s: {}",
+                    &peer_id,
+                    services.len(),
+                    services
+                );
+                Some(services)
+            } else {
+                handle_reverification_failure(&peer_id, &ctx_);
+                None
+            }
+        },
+        move || {
+            log::debug!("Reverification of {} timed out.", peer_id);
+
+            let _ = ctx_timeout.request_mngr.remove_request::<VerificationRequest>(&peer_id);
+            handle_reverification_failure(&peer_id, &ctx_timeout);
+        },
+    )
+}
+
+// Queries a peer for other peers in the background, and removes it if it fails or doesn't finish in time.
+fn spawn_query(peer_id: PeerId, ctx: &QueryContext) -> JoinHandle<Option<()>> {
+    let ctx_ = ctx.clone();
+    let ctx_timeout = ctx.clone();
+
+    ctx.counters.query_sent();
+
+    UnsupervisedTask::new("Query", ctx.task_timeout).spawn(
+        async move {
+            if let Some(peers) =
+                manager::begin_discovery(&peer_id, &ctx_.active_peers, &ctx_.request_mngr, &ctx_.server_tx).await
+            {
+                log::debug!("Query successful. Received {} peers.", peers.len());
+
+                // The response handler recorded how many of the received peers were new.
+                let new_peers = ctx_
+                    .active_peers
+                    .read()
+                    .find(&peer_id)
+                    .map_or(0, |p| p.metrics().last_new_peers());
+                ctx_.counters.query_succeeded(new_peers);
+            } else {
+                log::debug!("Query unsuccessful. Removing peer {}.", peer_id);
+
+                ctx_.counters.query_failed();
+                remove_active_peer(&peer_id, TransitionReason::QueryFailure, &ctx_);
+            }
+        },
+        move || {
+            log::debug!("Query of {} timed out. Removing peer.", peer_id);
+
+            ctx_timeout.counters.query_failed();
+            let _ = ctx_timeout.request_mngr.remove_request::<DiscoveryRequest>(&peer_id);
+            remove_active_peer(&peer_id, TransitionReason::QueryFailure, &ctx_timeout);
+        },
+    )
+}
+
+fn remove_active_peer(peer_id: &PeerId, reason: TransitionReason, ctx: &QueryContext) {
+    let was_active = ctx.active_peers.read().contains(peer_id);
+
+    manager::remove_peer_from_active_list(
+        peer_id,
+        reason,
+        &ctx.entry_peers,
+        &ctx.active_peers,
+        &ctx.replacements,
+        &ctx.event_tx,
+        &ctx.peer_count,
+        &ctx.quarantine,
+    );
+
+    // Note: entry peers are never removed.
+    if was_active && !ctx.active_peers.read().contains(peer_id) {
+        ctx.counters.peer_removed();
+    }
+}
+
+// Returns the time until the next query, which is longer while there are fewer verified peers than `backoff_threshold`,
+// so that the few peers known, e.g. right after booting with only entry peers, aren't queried over and over again.
+pub(crate) fn query_interval(active_peers: &ActivePeersList, backoff_threshold: usize) -> Duration {
+    let num_verified = manager::get_verified_peers(active_peers).len();
+
+    if num_verified < backoff_threshold {
+        log::debug!(
+            "Only {} verified peer/s, backing off queries for {:?}.",
+            num_verified,
+            QUERY_BACKOFF_INTERVAL
+        );
+        QUERY_BACKOFF_INTERVAL
     } else {
-        // Note: this macro is useful to remove some noise from the pattern matching rules.
-        macro_rules! num {
-            ($t:expr) => {
-                // Panic: we made sure, that unwrap is always okay.
-                $t.as_ref().unwrap().metrics().last_new_peers()
-            };
-        }
+        QUERY_INTERVAL_DEFAULT
+    }
+}
+
+// Hive.go: selects the peers that should be queried.
+fn select_peers_to_query<R: Rng + ?Sized>(
+    active_peers: &ActivePeersList,
+    entry_peers: &EntryPeersList,
+    cooldown: &QueryCooldown,
+    peers_per_query: usize,
+    selection: &PeerSelection,
+    rng: &mut R,
+) -> Vec<PeerId> {
+    if peers_per_query == 0 {
+        return Vec::new();
+    }
+
+    let verif_peers = manager::get_verified_peers(active_peers);
 
+    // Peers that are still cooling down are only considered if there are too few other peers, and then they come last,
+    // so that they are never selected as the latest verified peer if there is any other.
+    let (mut verif_peers, cooling_peers): (Vec<_>, Vec<_>) = verif_peers
+        .into_iter()
+        .partition(|p| !cooldown.is_cooling_down(p.peer_id()));
+
+    if verif_peers.len() <= peers_per_query {
+        verif_peers.extend(cooling_peers);
+    }
+
+    // If we have no more verified peers than we query per round, then we use those for the query, and fall back to the
+    // entry peers for the rest.
+    if verif_peers.len() <= peers_per_query {
+        let mut peers = verif_peers.into_iter().map(|ap| *ap.peer_id()).collect::<Vec<_>>();
+        let missing = peers_per_query - peers.len();
+        peers.extend(select_entry_peers(active_peers, entry_peers, &peers, missing, rng));
+        peers
+    } else {
         let latest = *verif_peers.remove(0).peer_id();
-        let len = verif_peers.len().min(3);
-
-        // Note: This loop finds the three "heaviest" peers with one iteration over an unsorted vec of verified peers.
-        let heaviest3 = verif_peers.into_iter().fold(
-            (None, None, None),
-            |(x, y, z): (Option<ActivePeer>, Option<ActivePeer>, Option<ActivePeer>), p| {
-                let n = p.metrics().last_new_peers();
-
-                match (&x, &y, &z) {
-                    // set 1st
-                    (None, _, _) => (Some(p), y, z),
-                    // shift-right + set 1st
-                    (t, None, _) if n < num!(t) => (Some(p), t.clone(), z),
-                    // set 2nd
-                    (t, None, _) if n >= num!(t) => (x, Some(p), z),
-                    // shift-right + shift-right + set 1st
-                    (s, t, None) if n < num!(s) => (Some(p), s.clone(), t.clone()),
-                    // shift-right + set 1st
-                    (_, t, None) if n < num!(t) => (x, Some(p), t.clone()),
-                    // set 3rd
-                    (_, t, None) if n >= num!(t) => (x, y, Some(p)),
-                    // no-op
-                    (t, _, _) if n < num!(t) => (x, y, z),
-                    // set 1st
-                    (_, t, _) if n < num!(t) => (Some(p), y, z),
-                    // shift-left + set 2nd
-                    (_, _, t) if n < num!(t) => (y, Some(p), z),
-                    // shift-left + shift-left + set 3rd
-                    (_, _, _) => (y, z, Some(p)),
-                }
-            },
-        );
 
-        let r = thread_rng().gen_range(0..len);
-        let heaviest = *match r {
-            0 => heaviest3.0,
-            1 => heaviest3.1,
-            2 => heaviest3.2,
-            _ => unreachable!(),
+        // Unless spread across the ID space, the other peers are picked randomly among the "heaviest" ones, i.e. the
+        // ones that returned the most new peers the last time they were queried, with two more candidates than peers to
+        // pick.
+        if !matches!(selection, PeerSelection::Distance(_)) {
+            verif_peers.sort_by_key(|p| Reverse(p.metrics().last_new_peers()));
+            verif_peers.truncate(peers_per_query + 1);
         }
-        // Panic: we made sure that the unwrap is always possible.
-        .unwrap()
-        .peer_id();
 
-        vec![latest, heaviest]
+        let others = match selection {
+            PeerSelection::LastNewPeers => verif_peers
+                .choose_multiple(rng, peers_per_query - 1)
+                .map(|p| *p.peer_id())
+                .collect(),
+            PeerSelection::Distance(local_id) | PeerSelection::Hybrid(local_id) => spread_across_buckets(
+                local_id,
+                &latest,
+                verif_peers.iter().map(|p| *p.peer_id()).collect(),
+                peers_per_query - 1,
+                rng,
+            ),
+        };
+
+        iter::once(latest).chain(others).collect()
     }
 }
 
+// Picks up to `n` of the active entry peers that aren't selected yet at random, the chance of each of them being
+// picked being proportional to its weight. Entry peers with a weight of 0 are never picked.
+fn select_entry_peers<R: Rng + ?Sized>(
+    active_peers: &ActivePeersList,
+    entry_peers: &EntryPeersList,
+    selected: &[PeerId],
+    n: usize,
+    rng: &mut R,
+) -> Vec<PeerId> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut candidates = {
+        let active_peers = active_peers.read();
+        entry_peers
+            .read()
+            .iter()
+            .filter(|(peer_id, weight)| **weight > 0 && !selected.contains(peer_id) && active_peers.contains(peer_id))
+            .map(|(peer_id, weight)| (*peer_id, *weight))
+            .collect::<Vec<_>>()
+    };
+
+    // Keep seeded selections reproducible despite the arbitrary order of the entry peers.
+    candidates.sort_unstable_by_key(|(peer_id, _)| *peer_id.id_bytes());
+
+    candidates
+        .choose_multiple_weighted(rng, n, |(_, weight)| *weight)
+        // Panic: all weights are positive and finite.
+        .expect("invalid entry peer weights")
+        .map(|(peer_id, _)| *peer_id)
+        .collect()
+}
+
+// Picks `n` of the candidates at random, preferring ones whose distance bucket relative to the local peer is neither
+// the one of the latest peer nor of an already picked candidate.
+fn spread_across_buckets<R: Rng + ?Sized>(
+    local_id: &PeerId,
+    latest: &PeerId,
+    mut candidates: Vec<PeerId>,
+    n: usize,
+    rng: &mut R,
+) -> Vec<PeerId> {
+    candidates.shuffle(rng);
+
+    let mut covered = HashSet::from([local_id.common_prefix_len(latest)]);
+    let (mut picked, others): (Vec<_>, Vec<_>) = candidates
+        .into_iter()
+        .partition(|p| covered.insert(local_id.common_prefix_len(p)));
+
+    picked.truncate(n);
+    let missing = n - picked.len();
+    picked.extend(others.into_iter().take(missing));
+    picked
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
     use crate::peer::{lists::ActivePeer, Peer};
+    use std::time::Duration;
 
     fn create_peerlist_of_size(n: usize) -> ActivePeersList {
         // Create a set of active peer entries.
@@ -193,11 +730,55 @@ mod tests {
         peerlist
     }
 
+    #[test]
+    fn oldest_peers_are_reverified_first() {
+        let peerlist = create_peerlist_of_size(5);
+        let peer_id = |i: usize| *peerlist.read().get(i).unwrap().peer_id();
+
+        assert_eq!(peers_to_reverify(&peerlist, 2), vec![peer_id(4), peer_id(3)]);
+        assert_eq!(peers_to_reverify(&peerlist, 10).len(), 5);
+        assert!(peers_to_reverify(&peerlist, 0).is_empty());
+    }
+
+    #[test]
+    fn queries_back_off_below_the_threshold() {
+        let peerlist = create_peerlist_of_size(2);
+
+        assert_eq!(query_interval(&peerlist, 3), QUERY_BACKOFF_INTERVAL);
+
+        // Unverified peers don't count.
+        peerlist.write().insert(ActivePeer::new(Peer::new_test_peer(2)));
+        assert_eq!(query_interval(&peerlist, 3), QUERY_BACKOFF_INTERVAL);
+
+        // Without a threshold queries never back off.
+        assert_eq!(query_interval(&ActivePeersList::default(), 0), QUERY_INTERVAL_DEFAULT);
+    }
+
+    #[test]
+    fn queries_resume_normal_cadence_above_the_threshold() {
+        let peerlist = create_peerlist_of_size(2);
+        assert_eq!(query_interval(&peerlist, 3), QUERY_BACKOFF_INTERVAL);
+
+        let mut peer = ActivePeer::new(Peer::new_test_peer(2));
+        peer.metrics_mut().increment_verified_count();
+        peerlist.write().insert(peer);
+
+        assert_eq!(query_interval(&peerlist, 3), QUERY_INTERVAL_DEFAULT);
+        assert_eq!(query_interval(&create_peerlist_of_size(10), 3), QUERY_INTERVAL_DEFAULT);
+    }
+
     #[test]
     fn find_peers_to_query_in_peerlist_1() {
         let peerlist = create_peerlist_of_size(1);
 
-        let selected = select_peers_to_query(&peerlist);
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
         assert_eq!(1, selected.len());
     }
 
@@ -205,7 +786,14 @@ mod tests {
     fn find_peers_to_query_in_peerlist_2() {
         let peerlist = create_peerlist_of_size(2);
 
-        let selected = select_peers_to_query(&peerlist);
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
         assert_eq!(2, selected.len());
     }
 
@@ -214,10 +802,19 @@ mod tests {
         let peerlist = create_peerlist_of_size(3);
 
         macro_rules! equal {
-            ($a:expr, $b:expr) => {{ $a == peerlist.read().get($b).unwrap().peer_id() }};
+            ($a:expr, $b:expr) => {{
+                $a == peerlist.read().get($b).unwrap().peer_id()
+            }};
         }
 
-        let selected = select_peers_to_query(&peerlist);
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
         assert_eq!(2, selected.len());
 
         assert!(equal!(&selected[0], 0));
@@ -229,14 +826,23 @@ mod tests {
         let peerlist = create_peerlist_of_size(10);
 
         macro_rules! equal {
-            ($a:expr, $b:expr) => {{ $a == peerlist.read().get($b).unwrap().peer_id() }};
+            ($a:expr, $b:expr) => {{
+                $a == peerlist.read().get($b).unwrap().peer_id()
+            }};
         }
 
         // 0 1 2 3 4 ... 7 8 9 (index)
         // 0 1 2 3 4 ... 7 8 9 (last_new_peers)
         // ^             ^ ^ ^
         // 0             1 1 1 (expected)
-        let selected = select_peers_to_query(&peerlist);
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
         assert_eq!(2, selected.len());
 
         // Always the newest peer (index 0) is selected.
@@ -251,10 +857,846 @@ mod tests {
         peerlist.write().rotate_forwards();
         peerlist.write().rotate_forwards();
 
-        let selected = select_peers_to_query(&peerlist);
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
         assert_eq!(2, selected.len());
 
         assert!(equal!(&selected[0], 0));
         assert!(equal!(&selected[1], 1) || equal!(&selected[1], 8) || equal!(&selected[1], 9));
     }
+
+    #[test]
+    fn seeded_selection_is_reproducible() {
+        let peerlist = create_peerlist_of_size(10);
+        let peer_id = |i: usize| *peerlist.read().get(i).unwrap().peer_id();
+        let select = |rng: &QueryRng| {
+            rng.with(|rng| {
+                select_peers_to_query(
+                    &peerlist,
+                    &EntryPeersList::default(),
+                    &QueryCooldown::new(0),
+                    2,
+                    &PeerSelection::LastNewPeers,
+                    rng,
+                )
+            })
+        };
+
+        let rng = QueryRng::seeded(42);
+        let selected = (0..4).map(|_| select(&rng)[1]).collect::<Vec<_>>();
+
+        assert_eq!(selected, vec![peer_id(9), peer_id(8), peer_id(9), peer_id(8)]);
+
+        // The same seed reproduces the same sequence.
+        let rng = QueryRng::seeded(42);
+        assert_eq!((0..4).map(|_| select(&rng)[1]).collect::<Vec<_>>(), selected);
+    }
+
+    #[test]
+    fn only_the_latest_peer_is_queried_if_one_per_query() {
+        let peerlist = create_peerlist_of_size(10);
+
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            1,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(selected, vec![*peerlist.read().get(0).unwrap().peer_id()]);
+
+        assert_eq!(
+            1,
+            select_peers_to_query(
+                &create_peerlist_of_size(1),
+                &EntryPeersList::default(),
+                &QueryCooldown::new(0),
+                1,
+                &PeerSelection::LastNewPeers,
+                &mut thread_rng()
+            )
+            .len()
+        );
+        assert!(select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            0,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn find_four_peers_to_query() {
+        let peerlist = create_peerlist_of_size(10);
+        let peer_id = |i: usize| *peerlist.read().get(i).unwrap().peer_id();
+
+        // 0 1 2 3 4 5 6 7 8 9 (index)
+        // 0 1 2 3 4 5 6 7 8 9 (last_new_peers)
+        // ^         ^ ^ ^ ^ ^
+        // 0         1 1 1 1 1 (expected)
+        let selected = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &QueryCooldown::new(0),
+            4,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(4, selected.len());
+
+        assert_eq!(selected[0], peer_id(0));
+        let heaviest = (5..10).map(peer_id).collect::<Vec<_>>();
+        assert!(selected[1..].iter().all(|p| heaviest.contains(p)));
+        assert!(selected[1..]
+            .iter()
+            .enumerate()
+            .all(|(i, p)| !selected[i + 2..].contains(p)));
+
+        // With no more verified peers than queried per round, all of them are queried.
+        assert_eq!(
+            4,
+            select_peers_to_query(
+                &create_peerlist_of_size(4),
+                &EntryPeersList::default(),
+                &QueryCooldown::new(0),
+                4,
+                &PeerSelection::LastNewPeers,
+                &mut thread_rng()
+            )
+            .len()
+        );
+        assert_eq!(
+            4,
+            select_peers_to_query(
+                &create_peerlist_of_size(5),
+                &EntryPeersList::default(),
+                &QueryCooldown::new(0),
+                4,
+                &PeerSelection::LastNewPeers,
+                &mut thread_rng()
+            )
+            .len()
+        );
+    }
+
+    #[test]
+    fn queried_peers_are_deprioritized() {
+        let peerlist = create_peerlist_of_size(10);
+        let cooldown = QueryCooldown::new(2);
+
+        let peer_id = |i: usize| *peerlist.read().get(i).unwrap().peer_id();
+
+        let first = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &cooldown,
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(first[0], peer_id(0));
+        cooldown.record(&first);
+
+        // The latest verified peer and the heaviest peer were just queried, so neither is selected in the next round.
+        let second = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &cooldown,
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(second[0], peer_id(1));
+        assert!(!second.contains(&first[1]));
+        cooldown.record(&second);
+
+        let third = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &cooldown,
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(third[0], peer_id(2));
+        cooldown.record(&third);
+
+        // The first queried peers cooled down after two rounds.
+        assert!(!cooldown.is_cooling_down(&first[0]));
+        assert!(cooldown.is_cooling_down(&third[0]));
+        assert_eq!(
+            select_peers_to_query(
+                &peerlist,
+                &EntryPeersList::default(),
+                &cooldown,
+                2,
+                &PeerSelection::LastNewPeers,
+                &mut thread_rng()
+            )[0],
+            peer_id(0)
+        );
+    }
+
+    #[test]
+    fn queried_peers_are_selected_if_too_few_others() {
+        let peerlist = create_peerlist_of_size(3);
+        let cooldown = QueryCooldown::new(2);
+
+        let first = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &cooldown,
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        cooldown.record(&first);
+
+        // Only one peer is not cooling down, so the others are still considered.
+        let second = select_peers_to_query(
+            &peerlist,
+            &EntryPeersList::default(),
+            &cooldown,
+            2,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(2, second.len());
+        assert!(!first.contains(&second[0]));
+    }
+
+    #[test]
+    fn distance_selection_spreads_across_the_id_space() {
+        let local_id = PeerId::generate();
+
+        // Collect six peers in the farthest distance bucket, and one peer in each of the next three.
+        let mut peers = Vec::new();
+        let mut spread = Vec::new();
+        for i in 0..=u8::MAX {
+            let peer = Peer::new_test_peer(i);
+            match local_id.common_prefix_len(peer.peer_id()) {
+                0 if peers.len() < 6 => peers.push(peer),
+                b @ 1..=3 if !spread.iter().any(|(sb, _)| *sb == b) => spread.push((b, peer)),
+                _ => {}
+            }
+        }
+        assert_eq!(spread.len(), 3);
+
+        // The spread peers come last in the peerlist, and returned no new peers so far.
+        let peerlist = ActivePeersList::default();
+        for (peer, last_new_peers) in spread
+            .into_iter()
+            .map(|(_, peer)| (peer, 0))
+            .chain(peers.into_iter().map(|peer| (peer, 10)))
+        {
+            let mut entry = ActivePeer::new(peer);
+            entry.metrics_mut().set_last_new_peers(last_new_peers);
+            entry.metrics_mut().increment_verified_count();
+            peerlist.write().insert(entry);
+        }
+
+        let bucket = |peer_id: &PeerId| local_id.common_prefix_len(peer_id);
+        let select = |selection| {
+            select_peers_to_query(
+                &peerlist,
+                &EntryPeersList::default(),
+                &QueryCooldown::new(0),
+                4,
+                &selection,
+                &mut thread_rng(),
+            )
+        };
+
+        // Picking the heaviest peers only queries the farthest bucket.
+        let selected = select(PeerSelection::LastNewPeers);
+        assert!(selected.iter().all(|p| bucket(p) == 0));
+
+        // Spreading queries one peer per bucket, besides the latest peer.
+        let selected = select(PeerSelection::Distance(local_id));
+        assert_eq!(selected[0], *peerlist.read().get(0).unwrap().peer_id());
+        let mut buckets = selected.iter().map(bucket).collect::<Vec<_>>();
+        buckets.sort_unstable();
+        assert_eq!(buckets, vec![0, 1, 2, 3]);
+
+        // Spreading only among the heaviest peers queries the farthest bucket again.
+        let selected = select(PeerSelection::Hybrid(local_id));
+        assert!(selected.iter().all(|p| bucket(p) == 0));
+    }
+
+    // Adds unverified entry peers with the given weights to the active peers.
+    fn add_entry_peers(active_peers: &ActivePeersList, weights: &[u32]) -> (EntryPeersList, Vec<PeerId>) {
+        let entry_peers = EntryPeersList::default();
+        let peer_ids = weights
+            .iter()
+            .enumerate()
+            .map(|(i, weight)| {
+                let peer = Peer::new_test_peer(100 + i as u8);
+                let peer_id = *peer.peer_id();
+                active_peers.write().insert(ActivePeer::new(peer));
+                entry_peers.write().insert(peer_id, *weight);
+                peer_id
+            })
+            .collect();
+
+        (entry_peers, peer_ids)
+    }
+
+    #[test]
+    fn entry_peers_fill_up_missing_verified_peers() {
+        let peerlist = create_peerlist_of_size(1);
+        let verified = *peerlist.read().get(0).unwrap().peer_id();
+        let (entry_peers, entry_peer_ids) = add_entry_peers(&peerlist, &[1]);
+        let select = |peers_per_query| {
+            select_peers_to_query(
+                &peerlist,
+                &entry_peers,
+                &QueryCooldown::new(0),
+                peers_per_query,
+                &PeerSelection::LastNewPeers,
+                &mut thread_rng(),
+            )
+        };
+
+        assert_eq!(select(1), vec![verified]);
+        assert_eq!(select(2), vec![verified, entry_peer_ids[0]]);
+        // There are no more entry peers to fill up with.
+        assert_eq!(select(3), vec![verified, entry_peer_ids[0]]);
+    }
+
+    #[test]
+    fn weighted_entry_peers_are_preferred() {
+        const ROUNDS: usize = 5000;
+
+        let peerlist = ActivePeersList::default();
+        let (entry_peers, entry_peer_ids) = add_entry_peers(&peerlist, &[1, 4, 0]);
+        let rng = QueryRng::seeded(42);
+
+        let mut selections = HashMap::<PeerId, usize>::new();
+        for _ in 0..ROUNDS {
+            let selected = rng.with(|rng| {
+                select_peers_to_query(
+                    &peerlist,
+                    &entry_peers,
+                    &QueryCooldown::new(0),
+                    1,
+                    &PeerSelection::LastNewPeers,
+                    rng,
+                )
+            });
+            assert_eq!(selected.len(), 1);
+            *selections.entry(selected[0]).or_default() += 1;
+        }
+
+        // The entry peer weighing 4 times as much is selected about 4 times as often, and the one weighing nothing
+        // never.
+        let heavy_share = selections[&entry_peer_ids[1]] as f64 / ROUNDS as f64;
+        assert!(
+            (0.75..0.85).contains(&heavy_share),
+            "share of the heavy entry peer: {}",
+            heavy_share
+        );
+        assert_eq!(selections[&entry_peer_ids[0]] + selections[&entry_peer_ids[1]], ROUNDS);
+        assert!(!selections.contains_key(&entry_peer_ids[2]));
+
+        // With room for two, both entry peers with a weight are selected.
+        let selected = select_peers_to_query(
+            &peerlist,
+            &entry_peers,
+            &QueryCooldown::new(0),
+            3,
+            &PeerSelection::LastNewPeers,
+            &mut thread_rng(),
+        );
+        assert_eq!(selected.len(), 2);
+        assert!(!selected.contains(&entry_peer_ids[2]));
+    }
+
+    #[tokio::test]
+    async fn verify_reachable_and_unreachable_peers() {
+        use crate::{
+            discovery::messages::{VerificationRequest, VerificationResponse},
+            event,
+            packet::OutgoingPacket,
+            server::server_chan,
+        };
+
+        let active_peers = create_peerlist_of_size(4);
+        let peers = active_peers.read().iter().map(|p| p.peer().clone()).collect::<Vec<_>>();
+
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext::new_test(active_peers.clone(), server_tx, event_tx);
+        let request_mngr = ctx.request_mngr.clone();
+
+        // Only peers with an even index respond.
+        let reachable = peers.iter().step_by(2).cloned().collect::<Vec<_>>();
+        tokio::spawn(async move {
+            while let Some(packet) = server_rx.recv().await {
+                if let Some(peer) = reachable.iter().find(|p| p.ip_address() == packet.peer_addr.ip()) {
+                    if let Some(value) = request_mngr.remove_request::<VerificationRequest>(peer.peer_id()) {
+                        let response =
+                            VerificationResponse::new(value.request_hash, peer.services().clone(), peer.ip_address());
+                        let _ = value.response_tx.unwrap().send(response.to_protobuf().to_vec());
+                    }
+                }
+            }
+        });
+
+        let unknown_peer_id = *Peer::new_test_peer(42).peer_id();
+        let mut peer_ids = peers.iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+        peer_ids.push(unknown_peer_id);
+
+        let reports = verify_peers(peer_ids.clone(), &ctx).await;
+        assert_eq!(5, reports.len());
+
+        for (i, report) in reports.iter().enumerate() {
+            assert_eq!(peer_ids[i], report.peer_id);
+            match i {
+                0 | 2 => assert!(report.result.is_verified()),
+                1 | 3 => assert!(matches!(report.result, VerificationResult::Failed)),
+                _ => assert!(matches!(report.result, VerificationResult::Unknown)),
+            }
+        }
+
+        // Failed verifications must not remove any peers.
+        assert_eq!(4, active_peers.read().len());
+    }
+
+    #[tokio::test]
+    async fn reverify_responsive_and_silent_peer() {
+        use crate::{
+            discovery::messages::{VerificationRequest, VerificationResponse},
+            event,
+            packet::OutgoingPacket,
+            server::server_chan,
+        };
+
+        let active_peers = create_peerlist_of_size(2);
+        let responsive = active_peers.read().get(0).unwrap().peer().clone();
+        let silent_id = *active_peers.read().get(1).unwrap().peer_id();
+
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext::new_test(active_peers.clone(), server_tx, event_tx);
+        let request_mngr = ctx.request_mngr.clone();
+
+        let responsive_ = responsive.clone();
+        tokio::spawn(async move {
+            while let Some(packet) = server_rx.recv().await {
+                if packet.peer_addr.ip() != responsive_.ip_address() {
+                    continue;
+                }
+                if let Some(value) = request_mngr.remove_request::<VerificationRequest>(responsive_.peer_id()) {
+                    let response = VerificationResponse::new(
+                        value.request_hash,
+                        responsive_.services().clone(),
+                        responsive_.ip_address(),
+                    );
+                    let _ = value.response_tx.unwrap().send(response.to_protobuf().to_vec());
+                }
+            }
+        });
+
+        let services = reverify_peer(*responsive.peer_id(), &ctx).await.unwrap();
+        assert!(services.get(AUTOPEERING_SERVICE_NAME).is_some());
+        assert!(active_peers.read().contains(responsive.peer_id()));
+
+        // The silent peer is removed, just like after a scheduled reverification.
+        assert!(reverify_peer(silent_id, &ctx).await.is_none());
+        assert!(!active_peers.read().contains(&silent_id));
+
+        // Unknown peers aren't reverified at all.
+        assert!(reverify_peer(silent_id, &ctx).await.is_none());
+
+        let metrics = ctx.counters.metrics();
+        assert_eq!(metrics.reverifications, 2);
+        assert_eq!(metrics.removed_peers, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_verifications_are_bounded_across_batches() {
+        use crate::{discovery::handle::LivenessProbe, event, packet::OutgoingPacket, server::server_chan};
+
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Default)]
+        struct SlowProbe {
+            in_flight: AtomicUsize,
+            max_in_flight: AtomicUsize,
+        }
+
+        #[async_trait::async_trait]
+        impl LivenessProbe for Arc<SlowProbe> {
+            async fn probe(&self, _: &PeerId) -> Option<bool> {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                Some(true)
+            }
+        }
+
+        let active_peers = create_peerlist_of_size(6);
+        let peer_ids = active_peers.read().iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            verification_slots: Arc::new(Semaphore::new(2)),
+            ..QueryContext::new_test(active_peers, server_tx, event_tx)
+        };
+        let probe = Arc::new(SlowProbe::default());
+        ctx.liveness_probe.set(Arc::new(probe.clone()));
+
+        // Two batches verified at the same time share the same bound.
+        let (first, second) = tokio::join!(
+            verify_peers(peer_ids[..3].to_vec(), &ctx),
+            verify_peers(peer_ids[3..].to_vec(), &ctx)
+        );
+
+        assert!(first
+            .iter()
+            .chain(second.iter())
+            .all(|report| report.result.is_verified()));
+        assert_eq!(probe.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn verify_peers_over_liveness_probe() {
+        use crate::{
+            discovery::{
+                handle::LivenessProbe,
+                messages::{VerificationRequest, VerificationResponse},
+            },
+            event,
+            packet::OutgoingPacket,
+            server::server_chan,
+        };
+
+        use std::collections::HashMap;
+
+        struct MockProbe(HashMap<PeerId, bool>);
+
+        #[async_trait::async_trait]
+        impl LivenessProbe for MockProbe {
+            async fn probe(&self, peer_id: &PeerId) -> Option<bool> {
+                self.0.get(peer_id).copied()
+            }
+        }
+
+        let active_peers = create_peerlist_of_size(3);
+        let peers = active_peers.read().iter().map(|p| p.peer().clone()).collect::<Vec<_>>();
+        let peer_ids = peers.iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext::new_test(active_peers.clone(), server_tx, event_tx);
+        let request_mngr = ctx.request_mngr.clone();
+
+        // The first peer is alive, the second one dead, and the third one can't be probed.
+        ctx.liveness_probe.set(Arc::new(MockProbe(
+            vec![(peer_ids[0], true), (peer_ids[1], false)].into_iter().collect(),
+        )));
+
+        // Every peer responds over the autopeering transport.
+        let responder = tokio::spawn(async move {
+            let mut contacted = Vec::new();
+            while let Some(packet) = server_rx.recv().await {
+                if let Some(peer) = peers.iter().find(|p| p.ip_address() == packet.peer_addr.ip()) {
+                    contacted.push(*peer.peer_id());
+                    if let Some(value) = request_mngr.remove_request::<VerificationRequest>(peer.peer_id()) {
+                        let response =
+                            VerificationResponse::new(value.request_hash, peer.services().clone(), peer.ip_address());
+                        let _ = value.response_tx.unwrap().send(response.to_protobuf().to_vec());
+                    }
+                }
+            }
+            contacted
+        });
+
+        let reports = verify_peers(peer_ids.clone(), &ctx).await;
+
+        assert!(reports[0].result.is_verified());
+        assert!(matches!(reports[1].result, VerificationResult::Failed));
+        assert!(reports[2].result.is_verified());
+
+        // The probed peer was bumped like after a verification response.
+        assert_eq!(
+            2,
+            active_peers
+                .read()
+                .find(&peer_ids[0])
+                .unwrap()
+                .metrics()
+                .verified_count()
+        );
+
+        // Only the peer the probe couldn't reach was contacted over the autopeering transport.
+        drop(ctx);
+        assert_eq!(responder.await.unwrap(), vec![peer_ids[2]]);
+    }
+
+    #[tokio::test]
+    async fn stalled_reverification_is_abandoned() {
+        use crate::{event, packet::OutgoingPacket, server::server_chan};
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+
+        let active_peers = ActivePeersList::default();
+        active_peers.write().insert(ActivePeer::new(peer));
+
+        // Nobody responds, so the verification keeps retrying for much longer than the task timeout.
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, mut event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            verification_retries: 10,
+            task_timeout: Duration::from_millis(100),
+            ..QueryContext::new_test(active_peers.clone(), server_tx, event_tx)
+        };
+
+        assert!(spawn_reverification(peer_id, &ctx).await.unwrap().is_none());
+        assert!(!active_peers.read().contains(&peer_id));
+        assert!(ctx
+            .request_mngr
+            .remove_request::<VerificationRequest>(&peer_id)
+            .is_none());
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(crate::event::Event::PeerRemoved {
+                peer_id: id,
+                reason: TransitionReason::ReverificationFailure
+            }) if id == peer_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn stalled_query_is_abandoned() {
+        use crate::{event, packet::OutgoingPacket, server::server_chan};
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+
+        let active_peers = ActivePeersList::default();
+        let mut active_peer = ActivePeer::new(peer);
+        active_peer.metrics_mut().increment_verified_count();
+        active_peers.write().insert(active_peer);
+
+        // Nobody responds, and the response timeout is longer than the task timeout.
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, mut event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            task_timeout: Duration::from_millis(100),
+            ..QueryContext::new_test(active_peers.clone(), server_tx, event_tx)
+        };
+
+        assert!(spawn_query(peer_id, &ctx).await.unwrap().is_none());
+        assert!(!active_peers.read().contains(&peer_id));
+        assert!(ctx.request_mngr.remove_request::<DiscoveryRequest>(&peer_id).is_none());
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(crate::event::Event::PeerDeleted { peer_id: id }) if id == peer_id
+        ));
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(crate::event::Event::PeerRemoved {
+                peer_id: id,
+                reason: TransitionReason::QueryFailure
+            }) if id == peer_id
+        ));
+    }
+
+    #[test]
+    fn reverification_backoff_doubles_with_every_failure() {
+        let base = Duration::from_secs(10);
+
+        assert_eq!(reverification_backoff(base, 1), Duration::from_secs(10));
+        assert_eq!(reverification_backoff(base, 2), Duration::from_secs(20));
+        assert_eq!(reverification_backoff(base, 4), Duration::from_secs(80));
+    }
+
+    #[test]
+    fn flaky_peer_is_removed_after_too_many_failures() {
+        use crate::{event, packet::OutgoingPacket, server::server_chan};
+
+        let active_peers = create_peerlist_of_size(2);
+        let flaky = *active_peers.read().get(1).unwrap().peer_id();
+        let other = *active_peers.read().get(0).unwrap().peer_id();
+
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            max_reverification_failures: 3,
+            reverification_backoff: Duration::from_secs(60),
+            ..QueryContext::new_test(active_peers.clone(), server_tx, event_tx)
+        };
+
+        let failures = |peer_id: &PeerId| {
+            active_peers
+                .read()
+                .find(peer_id)
+                .map(|p| p.metrics().reverification_failures())
+        };
+
+        assert_eq!(peer_to_reverify(&active_peers), Some(flaky));
+
+        // A single failure keeps the peer, but it isn't reverified again before the backoff elapsed.
+        handle_reverification_failure(&flaky, &ctx);
+        assert_eq!(failures(&flaky), Some(1));
+        assert_eq!(peer_to_reverify(&active_peers), Some(other));
+
+        // A successful verification resets the failures.
+        peer::set_front_and_update(&flaky, &active_peers);
+        assert_eq!(failures(&flaky), Some(0));
+
+        // Failing too often in a row removes the peer.
+        handle_reverification_failure(&flaky, &ctx);
+        handle_reverification_failure(&flaky, &ctx);
+        assert_eq!(failures(&flaky), Some(2));
+
+        handle_reverification_failure(&flaky, &ctx);
+        assert_eq!(failures(&flaky), None);
+        assert!(active_peers.read().contains(&other));
+    }
+
+    #[tokio::test]
+    async fn burst_failures_back_off_like_scheduled_ones() {
+        use crate::{discovery::handle::LivenessProbe, event, packet::OutgoingPacket, server::server_chan};
+
+        struct DeadProbe;
+
+        #[async_trait::async_trait]
+        impl LivenessProbe for DeadProbe {
+            async fn probe(&self, _: &PeerId) -> Option<bool> {
+                Some(false)
+            }
+        }
+
+        let active_peers = create_peerlist_of_size(2);
+        let peer_ids = active_peers.read().iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            max_reverification_failures: 2,
+            reverification_backoff: Duration::from_secs(60),
+            ..QueryContext::new_test(active_peers.clone(), server_tx, event_tx)
+        };
+        ctx.liveness_probe.set(Arc::new(DeadProbe));
+
+        // A first failure only backs off from the peers.
+        reverify_batch(peer_ids.clone(), &ctx).await;
+        assert_eq!(active_peers.read().len(), 2);
+        assert!(active_peers
+            .read()
+            .iter()
+            .all(|p| p.metrics().reverification_failures() == 1));
+        assert_eq!(peer_to_reverify(&active_peers), None);
+
+        // A second failure in a row removes them.
+        reverify_batch(peer_ids, &ctx).await;
+        assert!(active_peers.read().is_empty());
+
+        let metrics = ctx.counters.metrics();
+        assert_eq!(metrics.reverifications, 4);
+        assert_eq!(metrics.removed_peers, 2);
+    }
+
+    #[tokio::test]
+    async fn metrics_count_queries_and_reverifications() {
+        use crate::{
+            discovery::messages::{DiscoveryResponse, VerificationResponse},
+            event,
+            packet::OutgoingPacket,
+            server::server_chan,
+        };
+
+        let active_peers = create_peerlist_of_size(2);
+        let peers = active_peers.read().iter().map(|p| p.peer().clone()).collect::<Vec<_>>();
+
+        let (server_tx, mut server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, _event_rx) = event::event_chan();
+
+        let ctx = QueryContext {
+            task_timeout: Duration::from_millis(100),
+            ..QueryContext::new_test(active_peers.clone(), server_tx, event_tx)
+        };
+        let request_mngr = ctx.request_mngr.clone();
+
+        // Only the first peer responds, and every discovery response contains a single new peer.
+        let responsive = peers[0].clone();
+        let active_peers_ = active_peers.clone();
+        tokio::spawn(async move {
+            while let Some(packet) = server_rx.recv().await {
+                if packet.peer_addr.ip() != responsive.ip_address() {
+                    continue;
+                }
+
+                let peer_id = responsive.peer_id();
+                let response = if let Some(value) = request_mngr.remove_request::<DiscoveryRequest>(peer_id) {
+                    // Record the new peers just like the discovery response handler does.
+                    if let Some(p) = active_peers_.write().find_mut(peer_id) {
+                        p.metrics_mut().set_last_new_peers(1);
+                    }
+
+                    let response = DiscoveryResponse::new(value.request_hash, vec![Peer::new_test_peer(42)]);
+                    value.response_tx.map(|tx| (tx, response.to_protobuf()))
+                } else if let Some(value) = request_mngr.remove_request::<VerificationRequest>(peer_id) {
+                    let response = VerificationResponse::new(
+                        value.request_hash,
+                        responsive.services().clone(),
+                        responsive.ip_address(),
+                    );
+                    value.response_tx.map(|tx| (tx, response.to_protobuf()))
+                } else {
+                    None
+                };
+
+                if let Some((tx, bytes)) = response {
+                    let _ = tx.send(bytes.to_vec());
+                }
+            }
+        });
+
+        // The first query cycle removes the unresponsive peer, so the second one only queries the responsive one.
+        let query = query_fn();
+        for _ in 0..2 {
+            query(&ctx);
+            tokio::time::sleep(Duration::from_millis(300)).await;
+        }
+
+        reverify_fn()(&ctx);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert_eq!(
+            ctx.counters.metrics(),
+            DiscoveryMetrics {
+                queries: 3,
+                successful_queries: 2,
+                failed_queries: 1,
+                discovered_peers: 2,
+                reverifications: 1,
+                removed_peers: 1,
+            }
+        );
+        assert_eq!(1, active_peers.read().len());
+    }
 }
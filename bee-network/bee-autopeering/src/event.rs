@@ -25,6 +25,34 @@ pub enum Event {
         /// The identity of the deleted peer.
         peer_id: PeerId,
     },
+    /// A replacement peer has been promoted to the active peer list.
+    PeerPromoted {
+        /// The identity of the promoted peer.
+        peer_id: PeerId,
+        /// Why the active peer it took the place of was removed.
+        reason: TransitionReason,
+    },
+    /// An active peer has been moved to the replacement peer list.
+    PeerDemoted {
+        /// The identity of the demoted peer.
+        peer_id: PeerId,
+        /// Why the peer was demoted.
+        reason: TransitionReason,
+    },
+    /// A peer has been removed from the active or replacement peer list.
+    PeerRemoved {
+        /// The identity of the removed peer.
+        peer_id: PeerId,
+        /// Why the peer was removed.
+        reason: TransitionReason,
+    },
+    /// A peer has been removed because it belongs to a different network.
+    ForeignPeerEvicted {
+        /// The identity of the removed peer.
+        peer_id: PeerId,
+        /// The network id the peer reported.
+        network_id: u32,
+    },
     /// Private and public salt were updated.
     SaltUpdated {
         /// Lifetime of the public salt.
@@ -51,6 +79,44 @@ pub enum Event {
         /// The identity of the dropped peer.
         peer_id: PeerId,
     },
+    /// The number of verified peers dropped below the configured threshold.
+    PeerCountLow {
+        /// The current number of verified peers.
+        verified_peers: usize,
+        /// The configured threshold.
+        threshold: usize,
+    },
+    /// The number of verified peers recovered to at least the configured threshold.
+    PeerCountRecovered {
+        /// The current number of verified peers.
+        verified_peers: usize,
+        /// The configured threshold.
+        threshold: usize,
+    },
+}
+
+/// Why a peer moved between the peer lists.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransitionReason {
+    /// The peer failed to respond to reverifications.
+    ReverificationFailure,
+    /// The peer failed to respond to a discovery query.
+    QueryFailure,
+    /// Another peer was promoted to the active peer list in its place.
+    Promotion,
+    /// The peer belongs to a different network.
+    ForeignNetwork,
+}
+
+impl fmt::Display for TransitionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReverificationFailure => write!(f, "reverification failure"),
+            Self::QueryFailure => write!(f, "query failure"),
+            Self::Promotion => write!(f, "promotion"),
+            Self::ForeignNetwork => write!(f, "foreign network"),
+        }
+    }
 }
 
 /// Exposes autopeering related events.
@@ -68,6 +134,12 @@ impl fmt::Display for Event {
         match self {
             PeerDiscovered { peer_id } => write!(f, "Discovered: {}.", peer_id),
             PeerDeleted { peer_id } => write!(f, "Removed offline: {}.", peer_id),
+            PeerPromoted { peer_id, reason } => write!(f, "Promoted: {} ({}).", peer_id, reason),
+            PeerDemoted { peer_id, reason } => write!(f, "Demoted: {} ({}).", peer_id, reason),
+            PeerRemoved { peer_id, reason } => write!(f, "Removed: {} ({}).", peer_id, reason),
+            ForeignPeerEvicted { peer_id, network_id } => {
+                write!(f, "Removed foreign: {} (network id: {}).", peer_id, network_id)
+            }
             SaltUpdated {
                 public_salt_lifetime,
                 private_salt_lifetime,
@@ -79,6 +151,22 @@ impl fmt::Display for Event {
             OutgoingPeering { peer, .. } => write!(f, "Peered: {} (outgoing).", peer.peer_id()),
             IncomingPeering { peer, .. } => write!(f, "Peered: {} (incoming).", peer.peer_id()),
             PeeringDropped { peer_id } => write!(f, "Dropped: {}.", peer_id),
+            PeerCountLow {
+                verified_peers,
+                threshold,
+            } => write!(
+                f,
+                "Peer count low: {} verified (threshold: {}).",
+                verified_peers, threshold
+            ),
+            PeerCountRecovered {
+                verified_peers,
+                threshold,
+            } => write!(
+                f,
+                "Peer count recovered: {} verified (threshold: {}).",
+                verified_peers, threshold
+            ),
         }
     }
 }
@@ -7,12 +7,17 @@ use crate::{
     config::AutopeeringConfig,
     delay,
     discovery::{
-        manager::{DiscoveryManager, DiscoveryManagerConfig, QUERY_INTERVAL_DEFAULT, REVERIFY_INTERVAL_DEFAULT},
-        query::{self, QueryContext},
+        handle::DiscoveryHandle,
+        manager::{
+            DiscoveryManager, DiscoveryManagerConfig, DiscoveryResponseCache, ForeignPeers, PeerCountWatcher,
+            UnknownPeerVerifications, REVERIFY_INTERVAL_DEFAULT,
+        },
+        quarantine::Quarantine,
+        query::{self, DiscoveryCounters, PeerSelection, QueryContext, QueryCooldown, QueryRng},
     },
     event::{self, EventRx},
     hash,
-    local::Local,
+    local::{salt::Salt, Local},
     multiaddr,
     packet::IncomingPacket,
     peer::{
@@ -21,17 +26,19 @@ use crate::{
     },
     peering::{
         filter::NeighborFilter,
-        manager::{InboundNeighborhood, OutboundNeighborhood, PeeringManager, SaltUpdateContext, SALT_UPDATE_SECS},
+        manager::{InboundNeighborhood, OutboundNeighborhood, PeeringManager, SaltUpdateContext},
         update::{self, UpdateContext, OPEN_OUTBOUND_NBH_UPDATE_SECS},
         NeighborValidator,
     },
     request::{self, RequestManager, EXPIRED_REQUEST_REMOVAL_INTERVAL},
     server::{server_chan, IncomingPacketSenders, Server, ServerConfig, ServerSocket},
-    task::{TaskManager, MAX_SHUTDOWN_PRIORITY},
-    time::SECOND,
+    task::{NextTick, TaskManager, MAX_SHUTDOWN_PRIORITY},
+    time::{PeerEstimatedTimeSource, SystemTimeSource, TimeSource, SECOND},
 };
 
-use std::{error, future::Future, iter, time::Duration};
+use tokio::sync::Semaphore;
+
+use std::{error, future::Future, iter, sync::Arc, time::Duration};
 
 const NUM_TASKS: usize = 9;
 const BOOTSTRAP_MAX_VERIFICATIONS: usize = 10;
@@ -49,6 +56,35 @@ pub async fn init<S, I, Q, V>(
     term_signal: Q,
     neighbor_validator: V,
 ) -> Result<EventRx, Box<dyn error::Error>>
+where
+    S: PeerStore + 'static,
+    I: AsRef<str>,
+    Q: Future + Send + 'static,
+    V: NeighborValidator + 'static,
+{
+    init_with_handle::<S, I, Q, V>(
+        config,
+        version,
+        network_name,
+        local,
+        peer_store_config,
+        term_signal,
+        neighbor_validator,
+    )
+    .await
+    .map(|(event_rx, _)| event_rx)
+}
+
+/// Initializes the autopeering service, and additionally returns a handle to interact with the peer discovery.
+pub async fn init_with_handle<S, I, Q, V>(
+    config: AutopeeringConfig,
+    version: u32,
+    network_name: I,
+    local: Local,
+    peer_store_config: <S as PeerStore>::Config,
+    term_signal: Q,
+    neighbor_validator: V,
+) -> Result<(EventRx, DiscoveryHandle), Box<dyn error::Error>>
 where
     S: PeerStore + 'static,
     I: AsRef<str>,
@@ -72,7 +108,7 @@ where
 
     // Create peer lists.
     let entry_peers = EntryPeersList::default();
-    let active_peers = ActivePeersList::default();
+    let active_peers = ActivePeersList::new(config.max_active_peers());
     let replacements = ReplacementPeersList::default();
 
     // Create a task manager to have good control over the tokio task spawning business.
@@ -98,9 +134,25 @@ where
     // Create a request manager that creates and keeps track of outgoing requests.
     let request_mngr = RequestManager::new(version, network_id, config.bind_addr());
 
+    // Provides the time to validate the timestamps of incoming requests against.
+    let time_source: Arc<dyn TimeSource> = if config.peer_estimated_time() {
+        Arc::new(PeerEstimatedTimeSource::default())
+    } else {
+        Arc::new(SystemTimeSource)
+    };
+
     // Create the discovery manager handling the discovery request/response protocol.
     let discovery_config = DiscoveryManagerConfig::new(&config, version, network_id);
     let discovery_socket = ServerSocket::new(discovery_rx, server_tx.clone());
+    let peer_count = PeerCountWatcher::new(config.verified_peers_threshold());
+    let response_cache = DiscoveryResponseCache::new(Duration::from_secs(config.discovery_dedup_window_secs()));
+    let quarantine = Quarantine::new(
+        Duration::from_secs(config.quarantine_secs()),
+        config.quarantine_verifications(),
+    );
+    let unknown_verifications = UnknownPeerVerifications::new(config.unknown_peer_policy());
+    let foreign_peers = ForeignPeers::new(config.foreign_peer_policy());
+    let rng = config.query_rng_seed().map(QueryRng::seeded).unwrap_or_default();
 
     let discovery_mngr = DiscoveryManager::new(
         discovery_config,
@@ -112,6 +164,13 @@ where
         active_peers.clone(),
         replacements.clone(),
         event_tx.clone(),
+        peer_count.clone(),
+        response_cache.clone(),
+        quarantine.clone(),
+        unknown_verifications.clone(),
+        foreign_peers.clone(),
+        time_source.clone(),
+        rng.clone(),
     );
     discovery_mngr.init(&mut task_mngr).await?;
 
@@ -132,6 +191,7 @@ where
         inbound_nbh.clone(),
         outbound_nbh.clone(),
         nb_filter.clone(),
+        time_source.clone(),
     );
     task_mngr.run(peering_mngr);
 
@@ -151,9 +211,13 @@ where
         event_tx.clone(),
     );
 
-    // Update salts regularly.
-    let f = crate::peering::manager::update_salts_fn(config.drop_neighbors_on_salt_update());
-    let delay = iter::repeat(SALT_UPDATE_SECS);
+    // Update salts regularly, starting with salts valid for the configured lifetime.
+    let salt_lifetime = Duration::from_secs(config.salt_lifetime_secs());
+    local.set_private_salt(Salt::new(salt_lifetime));
+    local.set_public_salt(Salt::new(salt_lifetime));
+
+    let f = crate::peering::manager::update_salts_fn(config.drop_neighbors_on_salt_update(), salt_lifetime);
+    let delay = iter::repeat(crate::peering::manager::salt_update_interval(salt_lifetime));
     task_mngr.repeat(f, delay, ctx, "Salt-Update", MAX_SHUTDOWN_PRIORITY);
 
     let ctx = QueryContext {
@@ -163,19 +227,59 @@ where
         replacements: replacements.clone(),
         server_tx: server_tx.clone(),
         event_tx: event_tx.clone(),
+        verification_retries: config.verification_retries(),
+        verification_retry_timeout: Duration::from_millis(config.verification_retry_timeout_millis()),
+        liveness_probe: Default::default(),
+        quarantine,
+        peer_count,
+        query_cooldown: QueryCooldown::new(config.query_cooldown_rounds()),
+        peers_per_query: config.peers_per_query(),
+        selection: PeerSelection::new(config.selection_strategy(), local.peer_id()),
+        rng,
+        task_timeout: Duration::from_secs(config.discovery_task_timeout_secs()),
+        max_reverification_failures: config.reverification_max_failures(),
+        reverification_backoff: Duration::from_secs(config.reverification_backoff_secs()),
+        counters: DiscoveryCounters::default(),
+        verification_slots: Arc::new(Semaphore::new(config.max_concurrent_verifications().max(1))),
     };
 
+    let next_query = NextTick::default();
+    let next_reverify = NextTick::default();
+    let discovery_handle = DiscoveryHandle::new(
+        local.peer_id(),
+        ctx.clone(),
+        next_query.clone(),
+        next_reverify.clone(),
+        response_cache,
+        unknown_verifications,
+        foreign_peers,
+        time_source,
+    );
+
     // Reverify old peers regularly.
     let f = query::reverify_fn();
     let delay = iter::repeat(BOOTSTRAP_VERIFICATION_DELAY)
         .take(BOOTSTRAP_MAX_VERIFICATIONS.min(active_peers.read().len()))
         .chain(iter::repeat(REVERIFY_INTERVAL_DEFAULT));
-    task_mngr.repeat(f, delay, ctx.clone(), "Reverification", MAX_SHUTDOWN_PRIORITY);
+    let delay = delay::Jittered::new(delay, config.schedule_jitter_percent(), ctx.rng.clone());
+    task_mngr.repeat_tracked(
+        f,
+        delay,
+        ctx.clone(),
+        "Reverification",
+        MAX_SHUTDOWN_PRIORITY,
+        next_reverify,
+    );
 
-    // Discover new peers regularly.
+    // Discover new peers regularly (interval depends on whether there are enough verified peers or not).
     let f = query::query_fn();
-    let delay = iter::once(BOOTSTRAP_QUERY_DELAY).chain(iter::repeat(QUERY_INTERVAL_DEFAULT));
-    task_mngr.repeat(f, delay, ctx, "Discovery", MAX_SHUTDOWN_PRIORITY);
+    let query_peers = ctx.active_peers.clone();
+    let query_backoff_threshold = config.query_backoff_threshold();
+    let delay = iter::once(BOOTSTRAP_QUERY_DELAY).chain(iter::repeat_with(move || {
+        query::query_interval(&query_peers, query_backoff_threshold)
+    }));
+    let delay = delay::Jittered::new(delay, config.schedule_jitter_percent(), ctx.rng.clone());
+    task_mngr.repeat_tracked(f, delay, ctx, "Discovery", MAX_SHUTDOWN_PRIORITY, next_query);
 
     let ctx = UpdateContext {
         local,
@@ -200,5 +304,5 @@ where
 
     log::debug!("Autopeering initialized.");
 
-    Ok(event_rx)
+    Ok((event_rx, discovery_handle))
 }
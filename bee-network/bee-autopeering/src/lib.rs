@@ -138,12 +138,17 @@ pub mod config;
 pub mod event;
 pub mod init;
 
-pub use config::AutopeeringConfig;
-pub use event::Event;
-pub use init::init;
+pub use config::{AutopeeringConfig, EntryNodeWeight, PeerFilterEntry};
+pub use discovery::handle::{
+    DiscoveryHandle, DiscoveryMetrics, DiscoveryScheduleState, LivenessProbe, PeerDistance,
+    UnknownPeerVerificationCounts, VerificationReport, VerificationResult,
+};
+pub use event::{Event, TransitionReason};
+pub use init::{init, init_with_handle};
 pub use local::{
     services::{ServiceEndpoint, ServiceMap, ServiceName, ServiceProtocol, AUTOPEERING_SERVICE_NAME},
     Local,
 };
 pub use peer::{peer_id, peer_id::PeerId, stores, Peer};
 pub use peering::{Distance, NeighborValidator, Status};
+pub use time::{SystemTimeSource, TimeSource};
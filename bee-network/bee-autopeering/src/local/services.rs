@@ -35,6 +35,11 @@ impl ServiceMap {
     pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Returns the names of the services.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &ServiceName> {
+        self.0.keys()
+    }
 }
 
 impl TryFrom<proto::ServiceMap> for ServiceMap {
@@ -4,10 +4,12 @@
 use super::{peer_id::PeerId, Peer};
 
 use crate::{
+    config::EvictionMode,
     discovery::manager::VERIFICATION_EXPIRATION,
     time::{self, Timestamp},
 };
 
+use rand::{seq::SliceRandom as _, Rng};
 use serde::{
     de::{SeqAccess, Visitor},
     ser::SerializeStruct,
@@ -15,7 +17,7 @@ use serde::{
 };
 
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, VecDeque},
     fmt,
     sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
@@ -24,10 +26,13 @@ use std::{
 const DEFAULT_MAX_MANAGED: usize = 1000;
 // Maximum number of peers kept in the replacement list.
 const DEFAULT_MAX_REPLACEMENTS: usize = 10;
+// Maximum number of active peers offering a service for it to be considered rare.
+const MAX_RARE_SERVICE_PROVIDERS: usize = 2;
 
 type ActivePeersListInner = PeerRing<ActivePeer, DEFAULT_MAX_MANAGED>;
 type ReplacementPeersListInner = PeerRing<Peer, DEFAULT_MAX_REPLACEMENTS>;
-type EntryPeersListInner = HashSet<PeerId>;
+// Maps each entry peer to its weight when selecting the entry peers to query.
+type EntryPeersListInner = HashMap<PeerId, u32>;
 
 #[derive(Clone)]
 pub struct ActivePeer {
@@ -168,6 +173,13 @@ pub struct ActivePeersList {
 }
 
 impl ActivePeersList {
+    /// Creates an active peer list holding at most `max_len` peers.
+    pub(crate) fn new(max_len: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(PeerRing::with_max_len(max_len))),
+        }
+    }
+
     pub(crate) fn read(&self) -> RwLockReadGuard<ActivePeersListInner> {
         // Panic: we don't allow poisened locks.
         self.inner.read().expect("error getting read access")
@@ -219,6 +231,12 @@ pub(crate) struct PeerMetrics {
     last_verif_request: Timestamp,
     // timestamp of last verification request received
     last_verif_response: Timestamp,
+    // number of reverifications that failed in a row
+    #[serde(skip)]
+    reverification_failures: usize,
+    // timestamp before which the peer isn't reverified again
+    #[serde(skip)]
+    reverify_after: Timestamp,
 }
 
 impl PeerMetrics {
@@ -252,9 +270,43 @@ impl PeerMetrics {
         self.last_verif_response = time::unix_now_secs();
     }
 
+    pub(crate) fn last_verif_response_timestamp(&self) -> Timestamp {
+        self.last_verif_response
+    }
+
+    /// Returns whether the peer sent a verification response at or after the given timestamp.
+    pub(crate) fn is_verified_since(&self, timestamp: Timestamp) -> bool {
+        self.last_verif_response >= timestamp
+    }
+
     pub(crate) fn is_verified(&self) -> bool {
         time::since(self.last_verif_response).expect("system clock error") < VERIFICATION_EXPIRATION.as_secs()
     }
+
+    #[cfg(test)]
+    pub(crate) fn reverification_failures(&self) -> usize {
+        self.reverification_failures
+    }
+
+    /// Increments the number of reverifications that failed in a row, and returns the new value.
+    pub(crate) fn increment_reverification_failures(&mut self) -> usize {
+        self.reverification_failures += 1;
+        self.reverification_failures
+    }
+
+    pub(crate) fn reset_reverification_failures(&mut self) {
+        self.reverification_failures = 0;
+        self.reverify_after = 0;
+    }
+
+    pub(crate) fn set_reverify_after(&mut self, timestamp: Timestamp) {
+        self.reverify_after = timestamp;
+    }
+
+    /// Returns whether the peer may be reverified at the given timestamp, i.e. isn't backing off.
+    pub(crate) fn is_reverification_due(&self, now: Timestamp) -> bool {
+        now >= self.reverify_after
+    }
 }
 
 impl fmt::Debug for PeerMetrics {
@@ -264,13 +316,19 @@ impl fmt::Debug for PeerMetrics {
             .field("last_new_peers", &self.last_new_peers)
             .field("last_verif_request", &self.last_verif_request)
             .field("last_verif_response", &self.last_verif_response)
+            .field("reverification_failures", &self.reverification_failures)
+            .field("reverify_after", &self.reverify_after)
             .finish()
     }
 }
 
 /// TODO: consider using `IndexMap` for faster search.
 #[derive(Clone)]
-pub(crate) struct PeerRing<P, const N: usize>(VecDeque<P>);
+pub(crate) struct PeerRing<P, const N: usize> {
+    items: VecDeque<P>,
+    // The number of items the ring is considered full at, which is at most `N`.
+    max_len: usize,
+}
 
 impl<P: AsRef<PeerId>, const N: usize> PeerRing<P, N> {
     /// Returns `false`, if the list already contains the id, otherwise `true`.
@@ -283,13 +341,13 @@ impl<P: AsRef<PeerId>, const N: usize> PeerRing<P, N> {
             if self.is_full() {
                 self.remove_oldest();
             }
-            self.0.push_front(item);
+            self.items.push_front(item);
             true
         }
     }
 
     pub(crate) fn remove_oldest(&mut self) -> Option<P> {
-        self.0.pop_back()
+        self.items.pop_back()
     }
 
     pub(crate) fn remove(&mut self, peer_id: &PeerId) -> Option<P> {
@@ -301,15 +359,15 @@ impl<P: AsRef<PeerId>, const N: usize> PeerRing<P, N> {
     }
 
     pub(crate) fn remove_at(&mut self, index: usize) -> Option<P> {
-        self.0.remove(index)
+        self.items.remove(index)
     }
 
     pub(crate) fn contains(&self, peer_id: &PeerId) -> bool {
-        self.0.iter().any(|v| v.as_ref() == peer_id)
+        self.items.iter().any(|v| v.as_ref() == peer_id)
     }
 
     pub(crate) fn find_index(&self, peer_id: &PeerId) -> Option<usize> {
-        self.0.iter().position(|v| v.as_ref() == peer_id)
+        self.items.iter().position(|v| v.as_ref() == peer_id)
     }
 
     pub(crate) fn find(&self, peer_id: &PeerId) -> Option<&P> {
@@ -326,22 +384,22 @@ impl<P: AsRef<PeerId>, const N: usize> PeerRing<P, N> {
     }
 
     pub(crate) fn get(&self, index: usize) -> Option<&P> {
-        self.0.get(index)
+        self.items.get(index)
     }
 
     pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut P> {
-        self.0.get_mut(index)
+        self.items.get_mut(index)
     }
 
     pub(crate) fn get_newest_mut(&mut self) -> Option<&mut P> {
-        self.0.get_mut(0)
+        self.items.get_mut(0)
     }
 
     // NOTE: need to be atomic operations
     pub(crate) fn set_newest_and_get_mut(&mut self, peer_id: &PeerId) -> Option<&mut P> {
         if let Some(mid) = self.find_index(peer_id) {
             if mid > 0 {
-                self.0.rotate_left(mid);
+                self.items.rotate_left(mid);
             }
             self.get_newest_mut()
         } else {
@@ -350,37 +408,299 @@ impl<P: AsRef<PeerId>, const N: usize> PeerRing<P, N> {
     }
 
     pub(crate) fn get_oldest(&self) -> Option<&P> {
-        if self.0.is_empty() {
+        if self.items.is_empty() {
             None
         } else {
-            self.0.get(self.0.len() - 1)
+            self.items.get(self.items.len() - 1)
         }
     }
 
     pub(crate) fn len(&self) -> usize {
-        self.0.len()
+        self.items.len()
     }
 
     pub(crate) fn is_full(&self) -> bool {
-        self.len() >= N
+        self.len() >= self.max_len
     }
 
+    #[cfg(test)]
     pub(crate) fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.items.is_empty()
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = &P> {
-        self.0.iter()
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = &P> {
+        self.items.iter()
     }
 
     #[cfg(test)]
     pub(crate) fn rotate_forwards(&mut self) {
-        self.0.rotate_right(1);
+        self.items.rotate_right(1);
+    }
+}
+
+impl<const N: usize> PeerRing<ActivePeer, N> {
+    /// Returns whether the peer offers a rare service, i.e. one that - including this peer - would be offered by at
+    /// most `MAX_RARE_SERVICE_PROVIDERS` active peers.
+    pub(crate) fn offers_rare_service(&self, peer: &Peer) -> bool {
+        // The stored copy of the peer may offer other services than the given one, so it is left out of the count.
+        let counts = self.service_counts(Some(peer.peer_id()));
+
+        peer.services()
+            .names()
+            .any(|name| counts.get(name.as_str()).copied().unwrap_or(0) < MAX_RARE_SERVICE_PROVIDERS)
+    }
+
+    /// Returns the peer that should be evicted next to make room for another one.
+    ///
+    /// If `retain_rare_services` is set, peers offering a rare service are only evicted if there is no other choice.
+    /// Among the remaining peers, `EvictionMode::Deterministic` picks the one verified the least number of times (the
+    /// oldest, in case of a tie), and `EvictionMode::Weighted` picks one at random, with a probability inversely
+    /// proportional to the number of times it was verified.
+    pub(crate) fn eviction_candidate<R: Rng + ?Sized>(
+        &self,
+        retain_rare_services: bool,
+        eviction_mode: EvictionMode,
+        rng: &mut R,
+    ) -> Option<PeerId> {
+        let candidates = if retain_rare_services {
+            let counts = self.service_counts(None);
+
+            self.items
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| {
+                    !p.peer()
+                        .services()
+                        .names()
+                        .any(|name| counts.get(name.as_str()).copied().unwrap_or(0) <= MAX_RARE_SERVICE_PROVIDERS)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            self.items.iter().enumerate().collect::<Vec<_>>()
+        };
+
+        let candidate = match eviction_mode {
+            EvictionMode::Deterministic => candidates
+                .iter()
+                .min_by_key(|(index, p)| (p.metrics().verified_count(), usize::MAX - index)),
+            EvictionMode::Weighted => candidates
+                .choose_weighted(rng, |(_, p)| 1.0 / (p.metrics().verified_count() as f64 + 1.0))
+                .ok(),
+        };
+
+        candidate
+            .map(|(_, p)| *p.peer_id())
+            .or_else(|| self.get_oldest().map(|p| *p.peer_id()))
+    }
+
+    /// Inserts a peer like `insert`, but if the ring is full, the least valuable peer - i.e. the one that returned the
+    /// fewest new peers the last time it was queried, the oldest in case of a tie - is removed and returned instead of
+    /// just dropping the oldest one. This might be the given peer itself.
+    pub(crate) fn insert_or_displace(&mut self, peer: ActivePeer) -> (bool, Option<ActivePeer>) {
+        if self.contains(peer.peer_id()) {
+            return (false, None);
+        }
+
+        if !self.is_full() {
+            self.items.push_front(peer);
+            return (true, None);
+        }
+
+        // Panic: a full ring isn't empty.
+        let (index, least_valuable) = self
+            .items
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by_key(|(_, p)| p.metrics().last_new_peers())
+            .expect("full ring is empty");
+
+        if peer.metrics().last_new_peers() < least_valuable.metrics().last_new_peers() {
+            (false, Some(peer))
+        } else {
+            let displaced = self.items.remove(index);
+            self.items.push_front(peer);
+            (true, displaced)
+        }
+    }
+
+    // Counts the active peers offering each service, except for the `excluded` one.
+    fn service_counts(&self, excluded: Option<&PeerId>) -> HashMap<&str, usize> {
+        let mut counts = HashMap::new();
+        for name in self
+            .items
+            .iter()
+            .filter(|p| Some(p.peer_id()) != excluded)
+            .flat_map(|p| p.peer().services().names())
+        {
+            *counts.entry(name.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl<P, const N: usize> PeerRing<P, N> {
+    /// Creates a ring that is full at `max_len` items, which is clamped to `1..=N`.
+    pub(crate) fn with_max_len(max_len: usize) -> Self {
+        let max_len = max_len.clamp(1, N);
+
+        Self {
+            items: VecDeque::with_capacity(max_len),
+            max_len,
+        }
     }
 }
 
 impl<P, const N: usize> Default for PeerRing<P, N> {
     fn default() -> Self {
-        Self(VecDeque::with_capacity(N))
+        Self::with_max_len(N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::services::ServiceProtocol;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn rare_service_peer_survives_eviction() {
+        let mut ring = PeerRing::<ActivePeer, 4>::default();
+
+        // The oldest peer offers a rare service, but has never been verified.
+        let mut rare_peer = Peer::new_test_peer(0);
+        rare_peer.add_service("rare", ServiceProtocol::Tcp, 15600);
+        let rare_peer_id = *rare_peer.peer_id();
+        ring.insert(ActivePeer::new(rare_peer));
+
+        let mut common_peer_ids = Vec::new();
+        for i in 1..4 {
+            let mut active_peer = ActivePeer::new(Peer::new_test_peer(i));
+            active_peer.metrics_mut().increment_verified_count();
+            active_peer.metrics_mut().increment_verified_count();
+            common_peer_ids.push(*active_peer.peer_id());
+            ring.insert(active_peer);
+        }
+
+        assert!(ring.offers_rare_service(ring.find(&rare_peer_id).unwrap().peer()));
+        assert!(!ring.offers_rare_service(ring.find(&common_peer_ids[0]).unwrap().peer()));
+
+        // A peer may have started offering a service since it was stored.
+        let mut updated_peer = ring.find(&common_peer_ids[0]).unwrap().peer().clone();
+        updated_peer.add_service("new", ServiceProtocol::Tcp, 15601);
+        assert!(ring.offers_rare_service(&updated_peer));
+
+        let mut rng = rand::thread_rng();
+
+        // By default the least verified peer is evicted.
+        assert_eq!(
+            ring.eviction_candidate(false, EvictionMode::Deterministic, &mut rng),
+            Some(rare_peer_id)
+        );
+        // Otherwise the oldest of the common peers is evicted.
+        assert_eq!(
+            ring.eviction_candidate(true, EvictionMode::Deterministic, &mut rng),
+            Some(common_peer_ids[0])
+        );
+    }
+
+    #[test]
+    fn least_verified_peer_is_evicted_before_older_ones() {
+        let mut ring = PeerRing::<ActivePeer, 4>::default();
+
+        // The oldest peers were verified more often than the newest one.
+        let peer_ids = [2, 2, 1, 0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, verified_count)| {
+                let mut active_peer = ActivePeer::new(Peer::new_test_peer(i as u8));
+                for _ in 0..verified_count {
+                    active_peer.metrics_mut().increment_verified_count();
+                }
+                let peer_id = *active_peer.peer_id();
+                ring.insert(active_peer);
+                peer_id
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ring.eviction_candidate(false, EvictionMode::Deterministic, &mut rand::thread_rng()),
+            Some(peer_ids[3])
+        );
+    }
+
+    #[test]
+    fn weak_peers_are_evicted_more_often() {
+        let mut ring = PeerRing::<ActivePeer, 4>::default();
+
+        // Peers verified 0, 1, 3 and 7 times, i.e. with eviction weights of 1, 1/2, 1/4 and 1/8.
+        let peer_ids = [0, 1, 3, 7]
+            .into_iter()
+            .enumerate()
+            .map(|(i, verified_count)| {
+                let mut active_peer = ActivePeer::new(Peer::new_test_peer(i as u8));
+                for _ in 0..verified_count {
+                    active_peer.metrics_mut().increment_verified_count();
+                }
+                let peer_id = *active_peer.peer_id();
+                ring.insert(active_peer);
+                peer_id
+            })
+            .collect::<Vec<_>>();
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut evictions = HashMap::new();
+
+        for _ in 0..10000 {
+            let evicted = ring
+                .eviction_candidate(false, EvictionMode::Weighted, &mut rng)
+                .unwrap();
+            *evictions.entry(evicted).or_insert(0usize) += 1;
+        }
+
+        let counts = peer_ids
+            .iter()
+            .map(|peer_id| evictions.get(peer_id).copied().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        // Every peer may be evicted, but the weaker a peer the more often it is.
+        assert!(counts.iter().all(|count| *count > 0));
+        assert!(counts.windows(2).all(|pair| pair[0] > pair[1]));
+        // The least verified peer is expected to be evicted 8/15 of the time.
+        assert!((5000..5700).contains(&counts[0]));
+    }
+
+    #[test]
+    fn least_valuable_peer_is_displaced_beyond_max_len() {
+        let mut ring = PeerRing::<ActivePeer, 4>::with_max_len(3);
+
+        let new_peer = |i: u8, last_new_peers: usize| {
+            let mut active_peer = ActivePeer::new(Peer::new_test_peer(i));
+            active_peer.metrics_mut().set_last_new_peers(last_new_peers);
+            active_peer
+        };
+
+        // The two oldest peers returned the fewest new peers.
+        let peers = [new_peer(0, 1), new_peer(1, 1), new_peer(2, 3)];
+        let peer_ids = peers.iter().map(|p| *p.peer_id()).collect::<Vec<_>>();
+        for peer in peers {
+            assert!(matches!(ring.insert_or_displace(peer), (true, None)));
+        }
+        assert!(ring.is_full());
+
+        // The oldest of the least valuable peers makes room.
+        let (inserted, displaced) = ring.insert_or_displace(new_peer(3, 1));
+        assert!(inserted);
+        assert_eq!(displaced.map(|p| *p.peer_id()), Some(peer_ids[0]));
+        assert_eq!(ring.len(), 3);
+
+        // A peer less valuable than all others is displaced itself.
+        let peer = new_peer(4, 0);
+        let peer_id = *peer.peer_id();
+        let (inserted, displaced) = ring.insert_or_displace(peer);
+        assert!(!inserted);
+        assert_eq!(displaced.map(|p| *p.peer_id()), Some(peer_id));
+        assert_eq!(ring.len(), 3);
     }
 }
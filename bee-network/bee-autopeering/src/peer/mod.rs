@@ -317,6 +317,7 @@ pub(crate) fn set_front_and_update(peer_id: &PeerId, active_peers: &ActivePeersL
     if let Some(p) = active_peers.write().set_newest_and_get_mut(peer_id) {
         let metrics = p.metrics_mut();
         metrics.set_last_verif_response_timestamp();
+        metrics.reset_reverification_failures();
         let new_count = metrics.increment_verified_count();
 
         Some(new_count)
@@ -57,6 +57,25 @@ impl PeerId {
     pub fn libp2p_peer_id(&self) -> libp2p_core::PeerId {
         libp2p_peer_id(self.public_key())
     }
+
+    /// Returns the XOR distance to another identity, to be compared as a big-endian number.
+    pub fn distance(&self, other: &PeerId) -> [u8; hash::SHA256_LEN] {
+        let mut distance = [0; hash::SHA256_LEN];
+        for (d, (a, b)) in distance.iter_mut().zip(self.id_bytes.iter().zip(other.id_bytes.iter())) {
+            *d = a ^ b;
+        }
+        distance
+    }
+
+    /// Returns the number of leading bits this identity shares with another one, i.e. the index of the distance
+    /// bucket the other identity falls into.
+    pub fn common_prefix_len(&self, other: &PeerId) -> usize {
+        let distance = self.distance(other);
+        distance
+            .iter()
+            .position(|&b| b != 0)
+            .map_or(distance.len() * 8, |i| i * 8 + distance[i].leading_zeros() as usize)
+    }
 }
 
 /// Creates the corresponding `libp2p_core::PeerId` from a crypto.rs ED25519 public key.
@@ -197,4 +216,16 @@ mod tests {
         let peer_id = PeerId::new_static();
         let _ = peer_id.libp2p_peer_id();
     }
+
+    #[test]
+    fn distance_is_symmetric() {
+        let a = PeerId::new_static();
+        let b = PeerId::generate();
+
+        assert_eq!(a.distance(&b), b.distance(&a));
+        assert_eq!(a.distance(&a), [0; hash::SHA256_LEN]);
+        assert_eq!(a.common_prefix_len(&b), b.common_prefix_len(&a));
+        assert_eq!(a.common_prefix_len(&a), hash::SHA256_LEN * 8);
+        assert!(a.common_prefix_len(&b) < hash::SHA256_LEN * 8);
+    }
 }
@@ -11,7 +11,7 @@ use crate::{
     event::{Event, EventTx},
     hash::message_hash,
     local::{
-        salt::{self, Salt, SALT_LIFETIME_SECS},
+        salt::{self, Salt},
         services::AUTOPEERING_SERVICE_NAME,
         Local,
     },
@@ -21,14 +21,12 @@ use crate::{
     request::{self, RequestManager, RequestValue, ResponseTx, RESPONSE_TIMEOUT},
     server::{ServerSocket, ServerTx},
     task::{Repeat, Runnable, ShutdownRx},
-    time::SECOND,
+    time::{TimeSource, SECOND},
     NeighborValidator,
 };
 
-use std::{net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-/// Salt update interval.
-pub(crate) const SALT_UPDATE_SECS: Duration = Duration::from_secs(SALT_LIFETIME_SECS.as_secs() - SECOND);
 const INBOUND: bool = true;
 const OUTBOUND: bool = false;
 
@@ -55,6 +53,8 @@ pub(crate) struct PeeringManager<V: NeighborValidator> {
     outbound_nbh: OutboundNeighborhood,
     // The peer rejection filter.
     nb_filter: NeighborFilter<V>,
+    // Provides the time to validate request timestamps against.
+    time_source: Arc<dyn TimeSource>,
 }
 
 impl<V: NeighborValidator> PeeringManager<V> {
@@ -68,6 +68,7 @@ impl<V: NeighborValidator> PeeringManager<V> {
         inbound_nbh: InboundNeighborhood,
         outbound_nbh: OutboundNeighborhood,
         nb_filter: NeighborFilter<V>,
+        time_source: Arc<dyn TimeSource>,
     ) -> Self {
         Self {
             local,
@@ -78,6 +79,7 @@ impl<V: NeighborValidator> PeeringManager<V> {
             inbound_nbh,
             outbound_nbh,
             nb_filter,
+            time_source,
         }
     }
 }
@@ -99,6 +101,7 @@ impl<V: NeighborValidator> Runnable for PeeringManager<V> {
             inbound_nbh,
             outbound_nbh,
             nb_filter,
+            time_source,
         } = self;
 
         let ServerSocket {
@@ -130,6 +133,7 @@ impl<V: NeighborValidator> Runnable for PeeringManager<V> {
                             event_tx: &event_tx,
                             inbound_nbh: &inbound_nbh,
                             outbound_nbh: &outbound_nbh,
+                            time_source: &*time_source,
                         };
 
                         match msg_type {
@@ -207,6 +211,7 @@ pub(crate) struct RecvContext<'a> {
     event_tx: &'a EventTx,
     inbound_nbh: &'a InboundNeighborhood,
     outbound_nbh: &'a OutboundNeighborhood,
+    time_source: &'a dyn TimeSource,
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -230,7 +235,7 @@ pub(crate) enum ValidationError {
 fn validate_peering_request(peer_req: &PeeringRequest, ctx: &RecvContext) -> Result<(), ValidationError> {
     use ValidationError::*;
 
-    if request::is_expired(peer_req.timestamp()) {
+    if request::is_expired(peer_req.timestamp(), ctx.time_source) {
         Err(RequestExpired)
     } else if !peer::is_verified(ctx.peer_id, ctx.active_peers) {
         Err(PeerNotVerified)
@@ -255,10 +260,10 @@ fn validate_peering_response(peer_res: &PeeringResponse, ctx: &RecvContext) -> R
     }
 }
 
-fn validate_drop_request(drop_req: &DropPeeringRequest, _: &RecvContext) -> Result<(), ValidationError> {
+fn validate_drop_request(drop_req: &DropPeeringRequest, ctx: &RecvContext) -> Result<(), ValidationError> {
     use ValidationError::*;
 
-    if request::is_expired(drop_req.timestamp()) {
+    if request::is_expired(drop_req.timestamp(), ctx.time_source) {
         Err(RequestExpired)
     } else {
         Ok(())
@@ -687,13 +692,23 @@ impl<V: NeighborValidator> SaltUpdateContext<V> {
     }
 }
 
+/// Returns the salt update interval for salts valid for `salt_lifetime`, so that new salts are in place before the
+/// current ones expire.
+pub(crate) fn salt_update_interval(salt_lifetime: Duration) -> Duration {
+    salt_lifetime
+        .saturating_sub(Duration::from_secs(SECOND))
+        .max(Duration::from_secs(SECOND))
+}
+
 // Regularly update the salts of the local peer.
 pub(crate) fn update_salts_fn<V: NeighborValidator>(
     drop_neighbors_on_salt_update: bool,
+    salt_lifetime: Duration,
 ) -> Repeat<SaltUpdateContext<V>> {
     Box::new(move |ctx| {
         update_salts(
             drop_neighbors_on_salt_update,
+            salt_lifetime,
             &ctx.local,
             &ctx.nb_filter,
             &ctx.inbound_nbh,
@@ -704,8 +719,12 @@ pub(crate) fn update_salts_fn<V: NeighborValidator>(
     })
 }
 
+// Note: open peering requests keep their hash, which includes the previous public salt, so responses to them are still
+// accepted until the requests expire.
+#[allow(clippy::too_many_arguments)]
 fn update_salts<V: NeighborValidator>(
     drop_neighbors_on_salt_update: bool,
+    salt_lifetime: Duration,
     local: &Local,
     nb_filter: &NeighborFilter<V>,
     inbound_nbh: &InboundNeighborhood,
@@ -714,12 +733,12 @@ fn update_salts<V: NeighborValidator>(
     event_tx: &EventTx,
 ) {
     // Create a new private salt.
-    let private_salt = Salt::new(SALT_LIFETIME_SECS);
+    let private_salt = Salt::new(salt_lifetime);
     let private_salt_lifetime = private_salt.expiration_time();
     local.set_private_salt(private_salt);
 
     // Create a new public salt.
-    let public_salt = Salt::new(SALT_LIFETIME_SECS);
+    let public_salt = Salt::new(salt_lifetime);
     let public_salt_lifetime = public_salt.expiration_time();
     local.set_public_salt(public_salt);
 
@@ -780,3 +799,85 @@ pub(crate) fn add_or_replace_neighbor<const IS_INBOUND: bool>(
         outbound_nbh.insert_neighbor(peer, local)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event, request::REQUEST_EXPIRATION, server::server_chan, time::SystemTimeSource};
+
+    #[derive(Clone)]
+    struct AnyValidator;
+    impl NeighborValidator for AnyValidator {
+        fn is_valid<P: AsRef<Peer>>(&self, _: P) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn salt_update_precedes_expiration() {
+        assert_eq!(salt_update_interval(Duration::from_secs(60)), Duration::from_secs(59));
+        assert_eq!(salt_update_interval(Duration::ZERO), Duration::from_secs(SECOND));
+    }
+
+    #[test]
+    fn responses_to_requests_with_the_previous_salt_are_accepted() {
+        let local = Local::generate();
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+        let peer_addr = peer.service_socketaddr(AUTOPEERING_SERVICE_NAME).unwrap();
+        let request_mngr = RequestManager::new(0, 0, "127.0.0.1:14626".parse().unwrap());
+        let (server_tx, _server_rx) = server_chan::<OutgoingPacket>();
+        let (event_tx, mut event_rx) = event::event_chan();
+        let active_peers = ActivePeersList::default();
+        let inbound_nbh = InboundNeighborhood::new();
+        let outbound_nbh = OutboundNeighborhood::new();
+
+        let peer_req = request_mngr.create_peering_request(peer_id, None, &local);
+        let request_hash = message_hash(MessageType::PeeringRequest, &peer_req.to_protobuf());
+        let previous_salt = local.public_salt();
+
+        update_salts(
+            false,
+            Duration::from_secs(60),
+            &local,
+            &NeighborFilter::new(local.peer_id(), AnyValidator),
+            &inbound_nbh,
+            &outbound_nbh,
+            &server_tx,
+            &event_tx,
+        );
+
+        assert_ne!(local.public_salt().bytes(), previous_salt.bytes());
+        assert!(local.public_salt().expiration_time() < previous_salt.expiration_time());
+        assert!(matches!(event_rx.try_recv(), Ok(Event::SaltUpdated { .. })));
+
+        let ctx = RecvContext {
+            peer_id: &peer_id,
+            msg_bytes: &[],
+            server_tx: &server_tx,
+            local: &local,
+            active_peers: &active_peers,
+            request_mngr: &request_mngr,
+            peer_addr,
+            event_tx: &event_tx,
+            inbound_nbh: &inbound_nbh,
+            outbound_nbh: &outbound_nbh,
+            time_source: &SystemTimeSource,
+        };
+
+        // The request issued with the previous salt is still open.
+        let peer_res = PeeringResponse::new(request_hash, true);
+        assert!(validate_peering_response(&peer_res, &ctx).is_ok());
+
+        // Once expired, it isn't anymore.
+        let peer_req = request_mngr.create_peering_request(peer_id, None, &local);
+        let request_hash = message_hash(MessageType::PeeringRequest, &peer_req.to_protobuf());
+        request_mngr.remove_expired_requests(peer_req.timestamp() + REQUEST_EXPIRATION.as_secs());
+
+        let peer_res = PeeringResponse::new(request_hash, true);
+        assert!(matches!(
+            validate_peering_response(&peer_res, &ctx),
+            Err(ValidationError::NoCorrespondingRequestOrTimeout)
+        ));
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
     peer::peer_id::PeerId,
     peering::messages::PeeringRequest,
     task::Repeat,
-    time::{self, Timestamp},
+    time::{self, TimeSource, Timestamp},
 };
 
 use tokio::sync::oneshot;
@@ -177,8 +177,8 @@ impl RequestManager {
     }
 }
 
-pub(crate) fn is_expired(past_ts: Timestamp) -> bool {
-    is_expired_internal(past_ts, time::unix_now_secs())
+pub(crate) fn is_expired(past_ts: Timestamp, time_source: &dyn TimeSource) -> bool {
+    is_expired_internal(past_ts, time_source.now_secs())
 }
 
 fn is_expired_internal(past_ts: Timestamp, now_ts: Timestamp) -> bool {
@@ -14,7 +14,10 @@ use crate::{
 
 use tokio::{net::UdpSocket, sync::mpsc};
 
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 pub(crate) use tokio::sync::mpsc::unbounded_channel as server_chan;
 
@@ -28,16 +31,73 @@ type OutgoingPacketRx = mpsc::UnboundedReceiver<OutgoingPacket>;
 
 pub(crate) struct ServerConfig {
     pub bind_addr: SocketAddr,
+    pub relay_addr: Option<SocketAddr>,
 }
 
 impl ServerConfig {
     pub(crate) fn new(config: &AutopeeringConfig) -> Self {
         Self {
             bind_addr: config.bind_addr(),
+            relay_addr: config.relay_addr(),
         }
     }
 }
 
+/// Relays outgoing packets to peers that can't be reached directly, e.g. because UDP is blocked.
+pub(crate) trait Relay: Send + Sync + 'static {
+    /// Returns the address to send the bytes of a packet to and the bytes to send so that the packet reaches
+    /// `peer_addr`, or `None` if the packet can't be relayed.
+    fn wrap(&self, bytes: &[u8], peer_addr: SocketAddr) -> Option<(SocketAddr, Vec<u8>)>;
+}
+
+/// A [`Relay`] forwarding UDP datagrams, which are prefixed with the IPv4 address and the port of their destination.
+///
+/// The relay is expected to forward the responses back to the bind address of the node.
+pub(crate) struct UdpRelay {
+    relay_addr: SocketAddr,
+}
+
+impl UdpRelay {
+    pub(crate) fn new(relay_addr: SocketAddr) -> Self {
+        Self { relay_addr }
+    }
+}
+
+impl Relay for UdpRelay {
+    fn wrap(&self, bytes: &[u8], peer_addr: SocketAddr) -> Option<(SocketAddr, Vec<u8>)> {
+        let ip = match peer_addr.ip() {
+            IpAddr::V4(ip) => ip,
+            IpAddr::V6(_) => return None,
+        };
+
+        let mut wrapped = Vec::with_capacity(6 + bytes.len());
+        wrapped.extend_from_slice(&ip.octets());
+        wrapped.extend_from_slice(&peer_addr.port().to_be_bytes());
+        wrapped.extend_from_slice(bytes);
+
+        Some((self.relay_addr, wrapped))
+    }
+}
+
+// Returns the address to send the bytes of an outgoing packet to and the bytes to send. Discovery and verification
+// requests go through the relay if there is one that can relay them, everything else goes directly to the peer.
+fn route(
+    relay: Option<&dyn Relay>,
+    msg_type: MessageType,
+    bytes: Vec<u8>,
+    peer_addr: SocketAddr,
+) -> (SocketAddr, Vec<u8>) {
+    let relayed = matches!(
+        msg_type,
+        MessageType::VerificationRequest | MessageType::DiscoveryRequest
+    );
+
+    match relay {
+        Some(relay) if relayed => relay.wrap(&bytes, peer_addr).unwrap_or((peer_addr, bytes)),
+        _ => (peer_addr, bytes),
+    }
+}
+
 pub(crate) struct IncomingPacketSenders {
     pub(crate) discovery_tx: IncomingPacketTx,
     pub(crate) peering_tx: IncomingPacketTx,
@@ -95,6 +155,9 @@ impl Server {
             outgoing_rx,
             local,
             bind_addr: config.bind_addr,
+            relay: config
+                .relay_addr
+                .map(|relay_addr| Box::new(UdpRelay::new(relay_addr)) as Box<dyn Relay>),
         };
 
         task_mngr.run::<IncomingPacketHandler>(incoming_packet_handler);
@@ -113,6 +176,7 @@ struct OutgoingPacketHandler {
     outgoing_rx: OutgoingPacketRx,
     local: Local,
     bind_addr: SocketAddr,
+    relay: Option<Box<dyn Relay>>,
 }
 
 // Note: Invalid packets from peers are not logged as warnings because the fault is not on our side.
@@ -231,6 +295,7 @@ impl Runnable for OutgoingPacketHandler {
             mut outgoing_rx,
             local,
             bind_addr,
+            relay,
         } = self;
 
         'recv: loop {
@@ -269,9 +334,11 @@ impl Runnable for OutgoingPacketHandler {
                             continue 'recv;
                         }
 
-                        let n = outgoing_socket.send_to(&bytes, peer_addr).await.expect("socket send error");
+                        let (target_addr, bytes) = route(relay.as_deref(), msg_type, bytes.to_vec(), peer_addr);
+
+                        let n = outgoing_socket.send_to(&bytes, target_addr).await.expect("socket send error");
 
-                        log::trace!("Sent {} bytes to {}.", n, peer_addr);
+                        log::trace!("Sent {} bytes to {} via {}.", n, peer_addr, target_addr);
                     } else {
                         // All `outgoing_tx` message senders were dropped.
                         break 'recv;
@@ -313,3 +380,94 @@ impl ServerSocket {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    // Records the packets it relays, relaying nothing if unavailable.
+    struct MockRelay {
+        available: bool,
+        relayed: Mutex<Vec<SocketAddr>>,
+    }
+
+    impl MockRelay {
+        fn new(available: bool) -> Self {
+            Self {
+                available,
+                relayed: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Relay for MockRelay {
+        fn wrap(&self, bytes: &[u8], peer_addr: SocketAddr) -> Option<(SocketAddr, Vec<u8>)> {
+            if !self.available {
+                return None;
+            }
+
+            self.relayed.lock().unwrap().push(peer_addr);
+
+            Some(("10.0.0.1:14627".parse().unwrap(), bytes.to_vec()))
+        }
+    }
+
+    #[test]
+    fn requests_are_sent_via_relay() {
+        let relay = MockRelay::new(true);
+        let relay_addr: SocketAddr = "10.0.0.1:14627".parse().unwrap();
+        let peer_addr: SocketAddr = "10.0.0.2:14626".parse().unwrap();
+
+        assert_eq!(
+            route(Some(&relay), MessageType::VerificationRequest, vec![1], peer_addr),
+            (relay_addr, vec![1])
+        );
+        assert_eq!(
+            route(Some(&relay), MessageType::DiscoveryRequest, vec![2], peer_addr),
+            (relay_addr, vec![2])
+        );
+        // Responses and peering packets are sent directly.
+        assert_eq!(
+            route(Some(&relay), MessageType::VerificationResponse, vec![3], peer_addr),
+            (peer_addr, vec![3])
+        );
+        assert_eq!(
+            route(Some(&relay), MessageType::PeeringRequest, vec![4], peer_addr),
+            (peer_addr, vec![4])
+        );
+
+        assert_eq!(*relay.relayed.lock().unwrap(), vec![peer_addr, peer_addr]);
+    }
+
+    #[test]
+    fn requests_fall_back_to_direct() {
+        let peer_addr: SocketAddr = "10.0.0.2:14626".parse().unwrap();
+
+        assert_eq!(
+            route(None, MessageType::DiscoveryRequest, vec![1], peer_addr),
+            (peer_addr, vec![1])
+        );
+        assert_eq!(
+            route(
+                Some(&MockRelay::new(false)),
+                MessageType::DiscoveryRequest,
+                vec![2],
+                peer_addr
+            ),
+            (peer_addr, vec![2])
+        );
+    }
+
+    #[test]
+    fn udp_relay_prefixes_destination() {
+        let relay = UdpRelay::new("10.0.0.1:14627".parse().unwrap());
+
+        let (relay_addr, bytes) = relay.wrap(&[42], "1.2.3.4:258".parse().unwrap()).unwrap();
+
+        assert_eq!(relay_addr, "10.0.0.1:14627".parse().unwrap());
+        assert_eq!(bytes, vec![1, 2, 3, 4, 1, 2, 42]);
+        assert!(relay.wrap(&[42], "[::1]:258".parse().unwrap()).is_none());
+    }
+}
@@ -12,7 +12,12 @@ use crate::{
 use priority_queue::PriorityQueue;
 use tokio::{sync::oneshot, task::JoinHandle, time};
 
-use std::{collections::HashMap, future::Future, time::Duration};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 pub(crate) const MAX_SHUTDOWN_PRIORITY: u8 = 255;
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5 * SECOND);
@@ -22,6 +27,56 @@ type ShutdownTx = oneshot::Sender<()>;
 
 pub(crate) type Repeat<T> = Box<dyn for<'a> Fn(&'a T) + Send>;
 
+/// Keeps track of when a repeated command is executed next.
+#[derive(Clone, Default)]
+pub(crate) struct NextTick(Arc<RwLock<Option<Instant>>>);
+
+impl NextTick {
+    /// Returns the point in time of the next execution, or `None` if there is none scheduled.
+    pub(crate) fn get(&self) -> Option<Instant> {
+        // Panic: we don't allow poisened locks.
+        *self.0.read().expect("error getting read access")
+    }
+
+    fn set(&self, next_tick: Option<Instant>) {
+        // Panic: we don't allow poisened locks.
+        *self.0.write().expect("error getting write access") = next_tick;
+    }
+}
+
+/// A fire-and-forget task, that is abandoned if it doesn't finish within its timeout.
+pub(crate) struct UnsupervisedTask {
+    name: &'static str,
+    timeout: Duration,
+}
+
+impl UnsupervisedTask {
+    pub(crate) fn new(name: &'static str, timeout: Duration) -> Self {
+        Self { name, timeout }
+    }
+
+    /// Spawns the future, and runs `on_timeout` if it's abandoned. The returned handle resolves to `None` in that case.
+    pub(crate) fn spawn<F, C>(self, future: F, on_timeout: C) -> JoinHandle<Option<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+        C: FnOnce() + Send + 'static,
+    {
+        let UnsupervisedTask { name, timeout } = self;
+
+        tokio::spawn(async move {
+            match time::timeout(timeout, future).await {
+                Ok(output) => Some(output),
+                Err(_) => {
+                    log::debug!("`{}` didn't finish within {:?}. Abandoning it.", name, timeout);
+                    on_timeout();
+                    None
+                }
+            }
+        })
+    }
+}
+
 // TODO: @thibault-martinez mentioned that we should consider using `backstage` instead.
 /// Represents types driving an event loop.
 #[async_trait::async_trait]
@@ -74,21 +129,40 @@ impl<S: PeerStore, const N: usize> TaskManager<S, N> {
 
     /// Repeats a command in certain intervals provided a context `T`. Will be shut down gracefully with the rest of
     /// all spawned tasks by specifying a `name` and a `shutdown_priority`.
-    pub(crate) fn repeat<T, D>(&mut self, f: Repeat<T>, mut delay: D, ctx: T, name: &str, shutdown_priority: u8)
+    pub(crate) fn repeat<T, D>(&mut self, f: Repeat<T>, delay: D, ctx: T, name: &str, shutdown_priority: u8)
     where
         T: Send + Sync + 'static,
         D: Iterator<Item = Duration> + Send + 'static,
+    {
+        self.repeat_tracked(f, delay, ctx, name, shutdown_priority, NextTick::default());
+    }
+
+    /// Like `repeat`, but additionally publishes the point in time of the next execution to `next_tick`.
+    pub(crate) fn repeat_tracked<T, D>(
+        &mut self,
+        f: Repeat<T>,
+        mut delay: D,
+        ctx: T,
+        name: &str,
+        shutdown_priority: u8,
+        next_tick: NextTick,
+    ) where
+        T: Send + Sync + 'static,
+        D: Iterator<Item = Duration> + Send + 'static,
     {
         let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
         self.shutdown_senders.insert(name.into(), shutdown_tx);
 
         let handle = tokio::spawn(async move {
             for duration in &mut delay {
+                next_tick.set(Some(Instant::now() + duration));
+
                 tokio::select! {
                     _ = &mut shutdown_rx => break,
                     _ = time::sleep(duration) => f(&ctx),
                 }
             }
+            next_tick.set(None);
         });
         log::trace!("`{}` repeating.", name);
 
@@ -1,7 +1,13 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::peer::peer_id::PeerId;
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub(crate) type Timestamp = u64;
 pub(crate) type Timespan = u64;
@@ -33,3 +39,126 @@ pub(crate) fn until(future_ts: Timestamp) -> Option<Timespan> {
 pub(crate) fn delta(older_ts: Timestamp, newer_ts: Timestamp) -> Option<Timespan> {
     newer_ts.checked_sub(older_ts)
 }
+
+/// The number of peers whose clock offsets are remembered.
+const MAX_TIME_SAMPLES: usize = 32;
+/// The number of peers required before the local clock gets adjusted.
+const MIN_TIME_SAMPLES: usize = 3;
+
+/// A source of the current time used to validate the timestamps of incoming requests.
+pub trait TimeSource: Send + Sync + 'static {
+    /// Returns the current time in seconds since the Unix epoch.
+    fn now_secs(&self) -> Timestamp;
+
+    /// Takes note of the timestamp a peer put into one of its requests.
+    fn record(&self, _peer_id: &PeerId, _peer_ts: Timestamp) {}
+}
+
+/// Uses the local system clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource {
+    fn now_secs(&self) -> Timestamp {
+        unix_now_secs()
+    }
+}
+
+/// Corrects the local system clock by the median offset of the peers' clocks, as observed from the timestamps of
+/// their requests.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PeerEstimatedTimeSource {
+    // The latest clock offset of each peer, from the oldest to the newest sample.
+    samples: Arc<Mutex<VecDeque<(PeerId, i64)>>>,
+}
+
+impl PeerEstimatedTimeSource {
+    /// Takes note of the timestamp a peer put into one of its requests, given the local time of its arrival.
+    pub(crate) fn record_at(&self, peer_id: &PeerId, peer_ts: Timestamp, local_ts: Timestamp) {
+        let offset = peer_ts as i64 - local_ts as i64;
+        let mut samples = self.samples.lock().expect("lock");
+
+        samples.retain(|(id, _)| id != peer_id);
+        samples.push_back((*peer_id, offset));
+
+        if samples.len() > MAX_TIME_SAMPLES {
+            samples.pop_front();
+        }
+    }
+
+    /// Returns the estimated offset of the local clock in seconds, which is zero until enough peers were observed.
+    pub(crate) fn offset(&self) -> i64 {
+        let samples = self.samples.lock().expect("lock");
+
+        if samples.len() < MIN_TIME_SAMPLES {
+            return 0;
+        }
+
+        let mut offsets = samples.iter().map(|(_, offset)| *offset).collect::<Vec<_>>();
+        offsets.sort_unstable();
+
+        offsets[offsets.len() / 2]
+    }
+
+    pub(crate) fn now_secs_at(&self, local_ts: Timestamp) -> Timestamp {
+        (local_ts as i64).saturating_add(self.offset()).max(0) as Timestamp
+    }
+}
+
+impl TimeSource for PeerEstimatedTimeSource {
+    fn now_secs(&self) -> Timestamp {
+        self.now_secs_at(unix_now_secs())
+    }
+
+    fn record(&self, peer_id: &PeerId, peer_ts: Timestamp) {
+        self.record_at(peer_id, peer_ts, unix_now_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_TS: Timestamp = 1_000_000;
+
+    #[test]
+    fn local_time_is_kept_until_enough_peers_were_observed() {
+        let time_source = PeerEstimatedTimeSource::default();
+
+        time_source.record_at(&PeerId::generate(), LOCAL_TS + 30, LOCAL_TS);
+        time_source.record_at(&PeerId::generate(), LOCAL_TS + 30, LOCAL_TS);
+
+        assert_eq!(time_source.now_secs_at(LOCAL_TS), LOCAL_TS);
+    }
+
+    #[test]
+    fn local_time_is_corrected_by_the_median_offset() {
+        let time_source = PeerEstimatedTimeSource::default();
+
+        // The local clock is 30 seconds behind, and one peer's clock is far off.
+        for offset in [28, 30, 31, 3600] {
+            time_source.record_at(&PeerId::generate(), LOCAL_TS + offset, LOCAL_TS);
+        }
+        time_source.record_at(&PeerId::generate(), LOCAL_TS - 3600, LOCAL_TS);
+
+        assert_eq!(time_source.offset(), 30);
+        assert_eq!(time_source.now_secs_at(LOCAL_TS), LOCAL_TS + 30);
+    }
+
+    #[test]
+    fn only_the_latest_sample_of_a_peer_counts() {
+        let time_source = PeerEstimatedTimeSource::default();
+        let peer_id = PeerId::new_static();
+
+        for _ in 0..MIN_TIME_SAMPLES {
+            time_source.record_at(&peer_id, LOCAL_TS - 60, LOCAL_TS);
+        }
+        assert_eq!(time_source.offset(), 0);
+
+        for _ in 0..MAX_TIME_SAMPLES + 1 {
+            time_source.record_at(&PeerId::generate(), LOCAL_TS - 10, LOCAL_TS);
+        }
+        assert_eq!(time_source.samples.lock().unwrap().len(), MAX_TIME_SAMPLES);
+        assert_eq!(time_source.offset(), -10);
+    }
+}
@@ -84,7 +84,8 @@ pub enum Command {
 pub struct NetworkCommandSender(CommandSender);
 
 impl NetworkCommandSender {
-    pub(crate) fn new(inner: CommandSender) -> Self {
+    /// Creates a new `NetworkCommandSender` from the sending half of a command channel.
+    pub fn new(inner: CommandSender) -> Self {
         Self(inner)
     }
 
@@ -17,9 +17,10 @@ use crate::plugins::Dashboard;
 
 use bee_autopeering::{
     stores::{Options as RocksDbPeerStoreConfigOptions, RocksDbPeerStore, RocksDbPeerStoreConfig},
-    NeighborValidator, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
+    DiscoveryHandle, NeighborValidator, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
 };
 use bee_gossip::{Keypair, NetworkEventReceiver, Protocol};
+use bee_protocol::workers::ProtocolExtensions;
 use bee_runtime::{
     event::Bus,
     node::{Node, NodeBuilder},
@@ -150,9 +151,9 @@ impl<S: NodeStorageBackend> NodeBuilder<FullNode<S>> for FullNodeBuilder<S> {
 
         // Initialize everything.
         let (gossip_rx, builder) = initialize_gossip_layer(builder).await?;
-        let (autopeering_rx, builder) = initialize_autopeering(builder).await?;
+        let (autopeering, builder) = initialize_autopeering(builder).await?;
         let builder = initialize_ledger(builder);
-        let builder = initialize_protocol(builder, gossip_rx, autopeering_rx);
+        let builder = initialize_protocol(builder, gossip_rx, autopeering);
         let builder = initialize_api(builder).await;
         let builder = initialize_tangle(builder);
 
@@ -285,7 +286,7 @@ fn initialize_ledger<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> Full
 fn initialize_protocol<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
     gossip_events: NetworkEventReceiver,
-    autopeering_events: Option<bee_autopeering::event::EventRx>,
+    autopeering: Option<(bee_autopeering::event::EventRx, DiscoveryHandle)>,
 ) -> FullNodeBuilder<S> {
     log::info!("Initializing protocol layer...");
 
@@ -299,11 +300,24 @@ fn initialize_protocol<S: NodeStorageBackend>(
 
     let protocol_cfg = config.protocol_config.clone();
 
+    // Discovered peers are reverified on new milestones, and the clocks of the peers correct the local one, if
+    // autopeering is enabled.
+    let (autopeering_events, extensions) = match autopeering {
+        Some((autopeering_events, discovery_handle)) => (
+            Some(autopeering_events),
+            ProtocolExtensions::default()
+                .with_time_source(discovery_handle.time_source())
+                .with_discovery_handle(discovery_handle),
+        ),
+        None => (None, ProtocolExtensions::default()),
+    };
+
     bee_protocol::workers::init::<FullNode<S>>(
         protocol_cfg,
         (network_name, network_id),
         gossip_events,
         autopeering_events,
+        extensions,
         builder,
     )
 }
@@ -311,7 +325,13 @@ fn initialize_protocol<S: NodeStorageBackend>(
 /// Initializes the (optional) autopeering service.
 async fn initialize_autopeering<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
-) -> Result<(Option<bee_autopeering::event::EventRx>, FullNodeBuilder<S>), FullNodeError> {
+) -> Result<
+    (
+        Option<(bee_autopeering::event::EventRx, DiscoveryHandle)>,
+        FullNodeBuilder<S>,
+    ),
+    FullNodeError,
+> {
     let config = builder.config();
 
     if !config.autopeering_config.enabled() {
@@ -337,7 +357,7 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
 
         let quit_signal = tokio::signal::ctrl_c();
 
-        let autopeering_rx = bee_autopeering::init::<RocksDbPeerStore, _, _, _>(
+        let autopeering = bee_autopeering::init_with_handle::<RocksDbPeerStore, _, _, _>(
             autopeering_cfg,
             AUTOPEERING_VERSION,
             network_name,
@@ -349,7 +369,7 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
         .await
         .map_err(|e| FullNodeError::AutopeeringInitialization(e))?;
 
-        Ok((Some(autopeering_rx), builder))
+        Ok((Some(autopeering), builder))
     }
 }
 
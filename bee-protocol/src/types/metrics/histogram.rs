@@ -0,0 +1,135 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module that provides a type to hold a histogram of latencies.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Upper bounds of the buckets of a `LatencyHistogram`, in the unit of the recorded latencies.
+pub const LATENCY_BUCKETS: [u64; 12] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000];
+
+/// Holds a histogram of latencies, e.g. in milliseconds or microseconds depending on what is measured.
+#[derive(Default, Debug)]
+pub struct LatencyHistogram {
+    // One bucket per upper bound plus one for the latencies above the highest upper bound.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    sum: AtomicU64,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Records a latency in the `LatencyHistogram`.
+    pub fn observe(&self, latency: u64) {
+        let index = LATENCY_BUCKETS.partition_point(|bound| *bound < latency);
+
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(latency, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Resets the `LatencyHistogram`.
+    pub fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.sum.store(0, Ordering::Relaxed);
+        self.count.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the cumulative number of latencies per upper bound of the `LatencyHistogram`, `None` standing for an
+    /// infinite upper bound.
+    pub fn buckets(&self) -> Vec<(Option<u64>, u64)> {
+        let mut cumulative = 0;
+
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(index, bucket)| {
+                cumulative += bucket.load(Ordering::Relaxed);
+                (LATENCY_BUCKETS.get(index).copied(), cumulative)
+            })
+            .collect()
+    }
+
+    /// Returns an estimate of a quantile, between 0 and 1, of the `LatencyHistogram` as the upper bound of the bucket
+    /// it falls in, or `None` if the `LatencyHistogram` is empty. Quantiles above the highest upper bound are
+    /// estimated as the highest upper bound.
+    pub fn quantile(&self, quantile: f64) -> Option<u64> {
+        let buckets = self.buckets();
+        let count = buckets.last().map_or(0, |(_, count)| *count);
+
+        if count == 0 {
+            return None;
+        }
+
+        let rank = ((quantile * count as f64).ceil() as u64).clamp(1, count);
+
+        buckets
+            .into_iter()
+            .find(|(_, cumulative)| *cumulative >= rank)
+            .map(|(bound, _)| bound.unwrap_or(LATENCY_BUCKETS[LATENCY_BUCKETS.len() - 1]))
+    }
+
+    /// Returns the sum of the latencies of the `LatencyHistogram`.
+    pub fn sum(&self) -> u64 {
+        self.sum.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of latencies of the `LatencyHistogram`.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn cumulative_buckets() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.observe(0);
+        histogram.observe(5);
+        histogram.observe(6);
+        histogram.observe(1000);
+        histogram.observe(40000);
+
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.sum(), 41011);
+
+        let buckets = histogram.buckets();
+
+        assert_eq!(buckets.len(), LATENCY_BUCKETS.len() + 1);
+        assert_eq!(buckets[0], (Some(5), 2));
+        assert_eq!(buckets[1], (Some(10), 3));
+        assert_eq!(buckets[7], (Some(1000), 4));
+        assert_eq!(buckets[11], (Some(30000), 4));
+        assert_eq!(buckets[12], (None, 5));
+    }
+
+    #[test]
+    fn quantiles() {
+        let histogram = LatencyHistogram::default();
+
+        assert_eq!(histogram.quantile(0.5), None);
+
+        for latency in 1..=100 {
+            histogram.observe(latency);
+        }
+
+        assert_eq!(histogram.quantile(0.0), Some(5));
+        assert_eq!(histogram.quantile(0.1), Some(10));
+        assert_eq!(histogram.quantile(0.5), Some(50));
+        assert_eq!(histogram.quantile(0.95), Some(100));
+        assert_eq!(histogram.quantile(0.99), Some(100));
+
+        for _ in 0..100 {
+            histogram.observe(40000);
+        }
+
+        assert_eq!(histogram.quantile(0.5), Some(100));
+        assert_eq!(histogram.quantile(0.99), Some(30000));
+    }
+}
@@ -3,8 +3,12 @@
 
 //! A module that provides types to hold metrics related to other components.
 
+pub mod histogram;
 pub mod node;
 pub mod peer;
+pub mod snapshot;
 
+pub use histogram::LatencyHistogram;
 pub use node::NodeMetrics;
 pub use peer::PeerMetrics;
+pub use snapshot::MetricsSnapshot;
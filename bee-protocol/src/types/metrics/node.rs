@@ -3,6 +3,8 @@
 
 //! Module that provides a type to hold metrics related to nodes.
 
+use crate::types::metrics::LatencyHistogram;
+
 use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Holds metrics related to a node.
@@ -21,9 +23,40 @@ pub struct NodeMetrics {
     heartbeats_sent: AtomicU64,
 
     invalid_messages: AtomicU64,
+    invalid_parse: AtomicU64,
+    invalid_network_id: AtomicU64,
+    invalid_pow: AtomicU64,
+    invalid_parents: AtomicU64,
+    invalid_timestamp: AtomicU64,
+    oversized_messages: AtomicU64,
+    rejected_messages: AtomicU64,
+    blocked_index_messages: AtomicU64,
     new_messages: AtomicU64,
     known_messages: AtomicU64,
+    corrupt_messages: AtomicU64,
+    future_milestone_messages: AtomicU64,
+    rate_limited_messages: AtomicU64,
+    message_cache_hits: AtomicU64,
+    message_cache_size: AtomicU64,
+    notifiers_gone: AtomicU64,
+    message_requests_suppressed: AtomicU64,
+    message_requests_abandoned: AtomicU64,
+    message_requests_deferred: AtomicU64,
     messages_average_latency: AtomicU64,
+    solidification_latency: LatencyHistogram,
+    unpack_time: LatencyHistogram,
+    pow_check_time: LatencyHistogram,
+    insert_time: LatencyHistogram,
+    open_circuit_breakers: AtomicU64,
+    broadcasts_suppressed: AtomicU64,
+    stale_broadcasts_suppressed: AtomicU64,
+    milestone_queue_depth: AtomicU64,
+    milestone_payloads_dropped: AtomicU64,
+    milestone_forward_retries: AtomicU64,
+    processor_queue_depth: AtomicU64,
+    processor_queue_full: AtomicU64,
+    audit_records_dropped: AtomicU64,
+    tips: AtomicU64,
 
     referenced_messages: AtomicU64,
     excluded_no_transaction_messages: AtomicU64,
@@ -47,6 +80,65 @@ impl NodeMetrics {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Resets the counters and histograms of the `NodeMetrics`, leaving the gauges untouched.
+    pub fn reset_counters(&self) {
+        for counter in [
+            &self.invalid_packets,
+            &self.milestone_requests_received,
+            &self.messages_received,
+            &self.message_requests_received,
+            &self.heartbeats_received,
+            &self.milestone_requests_sent,
+            &self.messages_sent,
+            &self.message_requests_sent,
+            &self.heartbeats_sent,
+            &self.invalid_messages,
+            &self.invalid_parse,
+            &self.invalid_network_id,
+            &self.invalid_pow,
+            &self.invalid_parents,
+            &self.invalid_timestamp,
+            &self.oversized_messages,
+            &self.rejected_messages,
+            &self.blocked_index_messages,
+            &self.new_messages,
+            &self.known_messages,
+            &self.corrupt_messages,
+            &self.future_milestone_messages,
+            &self.rate_limited_messages,
+            &self.message_cache_hits,
+            &self.notifiers_gone,
+            &self.message_requests_suppressed,
+            &self.message_requests_abandoned,
+            &self.message_requests_deferred,
+            &self.broadcasts_suppressed,
+            &self.stale_broadcasts_suppressed,
+            &self.milestone_payloads_dropped,
+            &self.milestone_forward_retries,
+            &self.processor_queue_full,
+            &self.audit_records_dropped,
+            &self.referenced_messages,
+            &self.excluded_no_transaction_messages,
+            &self.excluded_conflicting_messages,
+            &self.included_messages,
+            &self.created_outputs,
+            &self.consumed_outputs,
+            &self.receipts,
+            &self.transaction_payloads,
+            &self.milestone_payloads,
+            &self.indexation_payloads,
+            &self.snapshots,
+            &self.prunings,
+        ] {
+            counter.store(0, Ordering::SeqCst);
+        }
+
+        self.solidification_latency.reset();
+        self.unpack_time.reset();
+        self.pow_check_time.reset();
+        self.insert_time.reset();
+    }
 }
 
 impl NodeMetrics {
@@ -150,6 +242,86 @@ impl NodeMetrics {
         self.invalid_messages.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of invalid messages that could not be unpacked of the `NodeMetrics`.
+    pub fn invalid_parse(&self) -> u64 {
+        self.invalid_parse.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages that could not be unpacked of the `NodeMetrics`.
+    pub fn invalid_parse_inc(&self) -> u64 {
+        self.invalid_parse.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages of another network of the `NodeMetrics`.
+    pub fn invalid_network_id(&self) -> u64 {
+        self.invalid_network_id.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages of another network of the `NodeMetrics`.
+    pub fn invalid_network_id_inc(&self) -> u64 {
+        self.invalid_network_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with an insufficient PoW score of the `NodeMetrics`.
+    pub fn invalid_pow(&self) -> u64 {
+        self.invalid_pow.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with an insufficient PoW score of the `NodeMetrics`.
+    pub fn invalid_pow_inc(&self) -> u64 {
+        self.invalid_pow.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with duplicate or unsorted parents of the `NodeMetrics`.
+    pub fn invalid_parents(&self) -> u64 {
+        self.invalid_parents.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with duplicate or unsorted parents of the `NodeMetrics`.
+    pub fn invalid_parents_inc(&self) -> u64 {
+        self.invalid_parents.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with a timestamp too far in the future of the `NodeMetrics`.
+    pub fn invalid_timestamp(&self) -> u64 {
+        self.invalid_timestamp.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with a timestamp too far in the future of the `NodeMetrics`.
+    pub fn invalid_timestamp_inc(&self) -> u64 {
+        self.invalid_timestamp.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages rejected for exceeding the maximum length of the `NodeMetrics`.
+    pub fn oversized_messages(&self) -> u64 {
+        self.oversized_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages rejected for exceeding the maximum length of the `NodeMetrics`.
+    pub fn oversized_messages_inc(&self) -> u64 {
+        self.oversized_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages rejected by the validators provided by the operator of the `NodeMetrics`.
+    pub fn rejected_messages(&self) -> u64 {
+        self.rejected_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages rejected by the validators provided by the operator of the `NodeMetrics`.
+    pub fn rejected_messages_inc(&self) -> u64 {
+        self.rejected_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages rejected for an index matching a blocked prefix of the `NodeMetrics`.
+    pub fn blocked_index_messages(&self) -> u64 {
+        self.blocked_index_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages rejected for an index matching a blocked prefix of the `NodeMetrics`.
+    pub fn blocked_index_messages_inc(&self) -> u64 {
+        self.blocked_index_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Returns the number of new messages of the `NodeMetrics`.
     pub fn new_messages(&self) -> u64 {
         self.new_messages.load(Ordering::Relaxed)
@@ -170,6 +342,96 @@ impl NodeMetrics {
         self.known_messages.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of known messages received with different bytes than the stored ones of the `NodeMetrics`.
+    pub fn corrupt_messages(&self) -> u64 {
+        self.corrupt_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of known messages received with different bytes than the stored ones of the `NodeMetrics`.
+    pub fn corrupt_messages_inc(&self) -> u64 {
+        self.corrupt_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages - referencing a milestone index too far ahead - of the `NodeMetrics`.
+    pub fn future_milestone_messages(&self) -> u64 {
+        self.future_milestone_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages - referencing a milestone index too far ahead - of the `NodeMetrics`.
+    pub fn future_milestone_messages_inc(&self) -> u64 {
+        self.future_milestone_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages dropped for exceeding the rate limit of their peer of the `NodeMetrics`.
+    pub fn rate_limited_messages(&self) -> u64 {
+        self.rate_limited_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages dropped for exceeding the rate limit of their peer of the `NodeMetrics`.
+    pub fn rate_limited_messages_inc(&self) -> u64 {
+        self.rate_limited_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of message cache hits of the `NodeMetrics`.
+    pub fn message_cache_hits(&self) -> u64 {
+        self.message_cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of message cache hits of the `NodeMetrics`.
+    pub fn message_cache_hits_inc(&self) -> u64 {
+        self.message_cache_hits.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the message cache size of the `NodeMetrics`.
+    pub fn message_cache_size(&self) -> u64 {
+        self.message_cache_size.load(Ordering::Relaxed)
+    }
+
+    /// Sets the message cache size of the `NodeMetrics`.
+    pub fn message_cache_size_set(&self, val: u64) {
+        self.message_cache_size.store(val, Ordering::Relaxed)
+    }
+
+    /// Returns the number of notifiers whose receiver was gone of the `NodeMetrics`.
+    pub fn notifiers_gone(&self) -> u64 {
+        self.notifiers_gone.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of notifiers whose receiver was gone of the `NodeMetrics`.
+    pub fn notifiers_gone_inc(&self) -> u64 {
+        self.notifiers_gone.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of suppressed duplicate message requests of the `NodeMetrics`.
+    pub fn message_requests_suppressed(&self) -> u64 {
+        self.message_requests_suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of suppressed duplicate message requests of the `NodeMetrics`.
+    pub fn message_requests_suppressed_inc(&self) -> u64 {
+        self.message_requests_suppressed.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of message requests given up on after exhausting their retries of the `NodeMetrics`.
+    pub fn message_requests_abandoned(&self) -> u64 {
+        self.message_requests_abandoned.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of message requests given up on after exhausting their retries of the `NodeMetrics`.
+    pub fn message_requests_abandoned_inc(&self) -> u64 {
+        self.message_requests_abandoned.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of requested messages whose parents were left to the solidifier of the `NodeMetrics`.
+    pub fn message_requests_deferred(&self) -> u64 {
+        self.message_requests_deferred.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of requested messages whose parents were left to the solidifier of the `NodeMetrics`.
+    pub fn message_requests_deferred_inc(&self) -> u64 {
+        self.message_requests_deferred.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Returns the average messages latency of the `NodeMetrics`.
     pub fn messages_average_latency(&self) -> u64 {
         self.messages_average_latency.load(Ordering::Relaxed)
@@ -180,6 +442,151 @@ impl NodeMetrics {
         self.messages_average_latency.store(val, Ordering::Relaxed)
     }
 
+    /// Returns the latencies, in milliseconds, from arrival to solidification of messages of the `NodeMetrics`.
+    pub fn solidification_latency(&self) -> &LatencyHistogram {
+        &self.solidification_latency
+    }
+
+    /// Records a latency from arrival to solidification of a message of the `NodeMetrics`.
+    pub fn solidification_latency_observe(&self, val: u64) {
+        self.solidification_latency.observe(val)
+    }
+
+    /// Returns the times, in microseconds, spent unpacking received messages of the `NodeMetrics`.
+    pub fn unpack_time(&self) -> &LatencyHistogram {
+        &self.unpack_time
+    }
+
+    /// Records a time, in microseconds, spent unpacking a received message of the `NodeMetrics`.
+    pub fn unpack_time_observe(&self, val: u64) {
+        self.unpack_time.observe(val)
+    }
+
+    /// Returns the times, in microseconds, spent checking the PoW score of received messages of the `NodeMetrics`.
+    pub fn pow_check_time(&self) -> &LatencyHistogram {
+        &self.pow_check_time
+    }
+
+    /// Records a time, in microseconds, spent checking the PoW score of a received message of the `NodeMetrics`.
+    pub fn pow_check_time_observe(&self, val: u64) {
+        self.pow_check_time.observe(val)
+    }
+
+    /// Returns the times, in microseconds, spent inserting received messages into the tangle of the `NodeMetrics`.
+    pub fn insert_time(&self) -> &LatencyHistogram {
+        &self.insert_time
+    }
+
+    /// Records a time, in microseconds, spent inserting a received message into the tangle of the `NodeMetrics`.
+    pub fn insert_time_observe(&self, val: u64) {
+        self.insert_time.observe(val)
+    }
+
+    /// Returns the number of open circuit breakers of the `NodeMetrics`.
+    pub fn open_circuit_breakers(&self) -> u64 {
+        self.open_circuit_breakers.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of open circuit breakers of the `NodeMetrics`.
+    pub fn open_circuit_breakers_set(&self, val: u64) {
+        self.open_circuit_breakers.store(val, Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages that would have been broadcast if broadcasting was enabled of the `NodeMetrics`.
+    pub fn broadcasts_suppressed(&self) -> u64 {
+        self.broadcasts_suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages that would have been broadcast if broadcasting was enabled of the
+    /// `NodeMetrics`.
+    pub fn broadcasts_suppressed_inc(&self) -> u64 {
+        self.broadcasts_suppressed.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of new messages not broadcast for referencing an old milestone of the `NodeMetrics`.
+    pub fn stale_broadcasts_suppressed(&self) -> u64 {
+        self.stale_broadcasts_suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of new messages not broadcast for referencing an old milestone of the `NodeMetrics`.
+    pub fn stale_broadcasts_suppressed_inc(&self) -> u64 {
+        self.stale_broadcasts_suppressed.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of milestone payloads waiting to be validated of the `NodeMetrics`.
+    pub fn milestone_queue_depth(&self) -> u64 {
+        self.milestone_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of milestone payloads waiting to be validated of the `NodeMetrics`.
+    pub fn milestone_queue_depth_set(&self, val: u64) {
+        self.milestone_queue_depth.store(val, Ordering::Relaxed)
+    }
+
+    /// Returns the number of invalid looking milestone payloads dropped because the milestone queue was full of the
+    /// `NodeMetrics`.
+    pub fn milestone_payloads_dropped(&self) -> u64 {
+        self.milestone_payloads_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid looking milestone payloads dropped because the milestone queue was full of the
+    /// `NodeMetrics`.
+    pub fn milestone_payloads_dropped_inc(&self) -> u64 {
+        self.milestone_payloads_dropped.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of milestone payloads held back to be retried because the milestone queue was full of the
+    /// `NodeMetrics`.
+    pub fn milestone_forward_retries(&self) -> u64 {
+        self.milestone_forward_retries.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of milestone payloads held back to be retried because the milestone queue was full of
+    /// the `NodeMetrics`.
+    pub fn milestone_forward_retries_inc(&self) -> u64 {
+        self.milestone_forward_retries.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages waiting to be processed of the `NodeMetrics`.
+    pub fn processor_queue_depth(&self) -> u64 {
+        self.processor_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of messages waiting to be processed of the `NodeMetrics`.
+    pub fn processor_queue_depth_set(&self, val: u64) {
+        self.processor_queue_depth.store(val, Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages dropped because the processor queue was full of the `NodeMetrics`.
+    pub fn processor_queue_full(&self) -> u64 {
+        self.processor_queue_full.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages dropped because the processor queue was full of the `NodeMetrics`.
+    pub fn processor_queue_full_inc(&self) -> u64 {
+        self.processor_queue_full.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of audit records dropped because the audit sink fell behind of the `NodeMetrics`.
+    pub fn audit_records_dropped(&self) -> u64 {
+        self.audit_records_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of audit records dropped because the audit sink fell behind of the `NodeMetrics`.
+    pub fn audit_records_dropped_inc(&self) -> u64 {
+        self.audit_records_dropped.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of tips of the `NodeMetrics`.
+    pub fn tips(&self) -> u64 {
+        self.tips.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of tips of the `NodeMetrics`.
+    pub fn tips_set(&self, val: u64) {
+        self.tips.store(val, Ordering::Relaxed)
+    }
+
     /// Returns the number of referenced messages of the `NodeMetrics`.
     pub fn referenced_messages(&self) -> u64 {
         self.referenced_messages.load(Ordering::Relaxed)
@@ -320,9 +727,40 @@ mod tests {
         assert_eq!(metrics.message_requests_sent(), 0);
         assert_eq!(metrics.heartbeats_sent(), 0);
         assert_eq!(metrics.invalid_messages(), 0);
+        assert_eq!(metrics.invalid_parse(), 0);
+        assert_eq!(metrics.invalid_network_id(), 0);
+        assert_eq!(metrics.invalid_pow(), 0);
+        assert_eq!(metrics.invalid_parents(), 0);
+        assert_eq!(metrics.invalid_timestamp(), 0);
+        assert_eq!(metrics.oversized_messages(), 0);
+        assert_eq!(metrics.rejected_messages(), 0);
+        assert_eq!(metrics.blocked_index_messages(), 0);
         assert_eq!(metrics.new_messages(), 0);
         assert_eq!(metrics.known_messages(), 0);
+        assert_eq!(metrics.corrupt_messages(), 0);
+        assert_eq!(metrics.future_milestone_messages(), 0);
+        assert_eq!(metrics.rate_limited_messages(), 0);
+        assert_eq!(metrics.message_cache_hits(), 0);
+        assert_eq!(metrics.message_cache_size(), 0);
+        assert_eq!(metrics.notifiers_gone(), 0);
+        assert_eq!(metrics.message_requests_suppressed(), 0);
+        assert_eq!(metrics.message_requests_abandoned(), 0);
+        assert_eq!(metrics.message_requests_deferred(), 0);
         assert_eq!(metrics.messages_average_latency(), 0);
+        assert_eq!(metrics.solidification_latency().count(), 0);
+        assert_eq!(metrics.unpack_time().count(), 0);
+        assert_eq!(metrics.pow_check_time().count(), 0);
+        assert_eq!(metrics.insert_time().count(), 0);
+        assert_eq!(metrics.open_circuit_breakers(), 0);
+        assert_eq!(metrics.broadcasts_suppressed(), 0);
+        assert_eq!(metrics.stale_broadcasts_suppressed(), 0);
+        assert_eq!(metrics.milestone_queue_depth(), 0);
+        assert_eq!(metrics.milestone_payloads_dropped(), 0);
+        assert_eq!(metrics.milestone_forward_retries(), 0);
+        assert_eq!(metrics.processor_queue_depth(), 0);
+        assert_eq!(metrics.processor_queue_full(), 0);
+        assert_eq!(metrics.audit_records_dropped(), 0);
+        assert_eq!(metrics.tips(), 0);
         assert_eq!(metrics.referenced_messages(), 0);
         assert_eq!(metrics.excluded_no_transaction_messages(), 0);
         assert_eq!(metrics.excluded_conflicting_messages(), 0);
@@ -346,9 +784,40 @@ mod tests {
         metrics.message_requests_sent_inc();
         metrics.heartbeats_sent_inc();
         metrics.invalid_messages_inc();
+        metrics.invalid_parse_inc();
+        metrics.invalid_network_id_inc();
+        metrics.invalid_pow_inc();
+        metrics.invalid_parents_inc();
+        metrics.invalid_timestamp_inc();
+        metrics.oversized_messages_inc();
+        metrics.rejected_messages_inc();
+        metrics.blocked_index_messages_inc();
         metrics.new_messages_inc();
         metrics.known_messages_inc();
+        metrics.corrupt_messages_inc();
+        metrics.future_milestone_messages_inc();
+        metrics.rate_limited_messages_inc();
+        metrics.message_cache_hits_inc();
+        metrics.message_cache_size_set(42);
+        metrics.notifiers_gone_inc();
+        metrics.message_requests_suppressed_inc();
+        metrics.message_requests_abandoned_inc();
+        metrics.message_requests_deferred_inc();
         metrics.messages_average_latency_set(42);
+        metrics.solidification_latency_observe(42);
+        metrics.unpack_time_observe(42);
+        metrics.pow_check_time_observe(42);
+        metrics.insert_time_observe(42);
+        metrics.open_circuit_breakers_set(42);
+        metrics.broadcasts_suppressed_inc();
+        metrics.stale_broadcasts_suppressed_inc();
+        metrics.milestone_queue_depth_set(42);
+        metrics.milestone_payloads_dropped_inc();
+        metrics.milestone_forward_retries_inc();
+        metrics.processor_queue_depth_set(42);
+        metrics.processor_queue_full_inc();
+        metrics.audit_records_dropped_inc();
+        metrics.tips_set(42);
         metrics.referenced_messages_inc(1);
         metrics.excluded_no_transaction_messages_inc(1);
         metrics.excluded_conflicting_messages_inc(1);
@@ -372,9 +841,44 @@ mod tests {
         assert_eq!(metrics.message_requests_sent(), 1);
         assert_eq!(metrics.heartbeats_sent(), 1);
         assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(metrics.invalid_parse(), 1);
+        assert_eq!(metrics.invalid_network_id(), 1);
+        assert_eq!(metrics.invalid_pow(), 1);
+        assert_eq!(metrics.invalid_parents(), 1);
+        assert_eq!(metrics.invalid_timestamp(), 1);
+        assert_eq!(metrics.oversized_messages(), 1);
+        assert_eq!(metrics.rejected_messages(), 1);
+        assert_eq!(metrics.blocked_index_messages(), 1);
         assert_eq!(metrics.new_messages(), 1);
         assert_eq!(metrics.known_messages(), 1);
+        assert_eq!(metrics.corrupt_messages(), 1);
+        assert_eq!(metrics.future_milestone_messages(), 1);
+        assert_eq!(metrics.rate_limited_messages(), 1);
+        assert_eq!(metrics.message_cache_hits(), 1);
+        assert_eq!(metrics.message_cache_size(), 42);
+        assert_eq!(metrics.notifiers_gone(), 1);
+        assert_eq!(metrics.message_requests_suppressed(), 1);
+        assert_eq!(metrics.message_requests_abandoned(), 1);
+        assert_eq!(metrics.message_requests_deferred(), 1);
         assert_eq!(metrics.messages_average_latency(), 42);
+        assert_eq!(metrics.solidification_latency().count(), 1);
+        assert_eq!(metrics.solidification_latency().sum(), 42);
+        assert_eq!(metrics.unpack_time().count(), 1);
+        assert_eq!(metrics.unpack_time().sum(), 42);
+        assert_eq!(metrics.pow_check_time().count(), 1);
+        assert_eq!(metrics.pow_check_time().sum(), 42);
+        assert_eq!(metrics.insert_time().count(), 1);
+        assert_eq!(metrics.insert_time().sum(), 42);
+        assert_eq!(metrics.open_circuit_breakers(), 42);
+        assert_eq!(metrics.broadcasts_suppressed(), 1);
+        assert_eq!(metrics.stale_broadcasts_suppressed(), 1);
+        assert_eq!(metrics.milestone_queue_depth(), 42);
+        assert_eq!(metrics.milestone_payloads_dropped(), 1);
+        assert_eq!(metrics.milestone_forward_retries(), 1);
+        assert_eq!(metrics.processor_queue_depth(), 42);
+        assert_eq!(metrics.processor_queue_full(), 1);
+        assert_eq!(metrics.audit_records_dropped(), 1);
+        assert_eq!(metrics.tips(), 42);
         assert_eq!(metrics.referenced_messages(), 1);
         assert_eq!(metrics.excluded_no_transaction_messages(), 1);
         assert_eq!(metrics.excluded_conflicting_messages(), 1);
@@ -388,4 +892,35 @@ mod tests {
         assert_eq!(metrics.snapshots(), 1);
         assert_eq!(metrics.prunings(), 1);
     }
+
+    #[test]
+    fn reset_counters() {
+        let metrics = NodeMetrics::default();
+
+        metrics.messages_received_inc();
+        metrics.new_messages_inc();
+        metrics.referenced_messages_inc(3);
+        metrics.prunings_inc(1);
+        metrics.solidification_latency_observe(42);
+        metrics.unpack_time_observe(42);
+        metrics.pow_check_time_observe(42);
+        metrics.insert_time_observe(42);
+        metrics.message_cache_size_set(42);
+        metrics.open_circuit_breakers_set(2);
+
+        metrics.reset_counters();
+
+        assert_eq!(metrics.messages_received(), 0);
+        assert_eq!(metrics.new_messages(), 0);
+        assert_eq!(metrics.referenced_messages(), 0);
+        assert_eq!(metrics.prunings(), 0);
+        assert_eq!(metrics.solidification_latency().count(), 0);
+        assert_eq!(metrics.solidification_latency().sum(), 0);
+        assert!(metrics.unpack_time().buckets().iter().all(|(_, count)| *count == 0));
+        assert_eq!(metrics.pow_check_time().count(), 0);
+        assert_eq!(metrics.insert_time().count(), 0);
+        // Gauges describe the current state and are kept.
+        assert_eq!(metrics.message_cache_size(), 42);
+        assert_eq!(metrics.open_circuit_breakers(), 2);
+    }
 }
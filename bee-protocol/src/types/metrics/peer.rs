@@ -9,8 +9,17 @@ use std::sync::atomic::{AtomicU64, Ordering};
 #[derive(Default)]
 pub struct PeerMetrics {
     invalid_messages: AtomicU64,
+    invalid_parse: AtomicU64,
+    invalid_network_id: AtomicU64,
+    invalid_pow: AtomicU64,
+    invalid_parents: AtomicU64,
+    invalid_timestamp: AtomicU64,
+    oversized_messages: AtomicU64,
     new_messages: AtomicU64,
     known_messages: AtomicU64,
+    corrupt_messages: AtomicU64,
+    requested_messages: AtomicU64,
+    rate_limited_messages: AtomicU64,
     invalid_packets: AtomicU64,
     milestone_requests_received: AtomicU64,
     messages_received: AtomicU64,
@@ -33,6 +42,66 @@ impl PeerMetrics {
         self.invalid_messages.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of invalid messages that could not be unpacked of the `PeerMetrics`.
+    pub fn invalid_parse(&self) -> u64 {
+        self.invalid_parse.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages that could not be unpacked of the `PeerMetrics`.
+    pub fn invalid_parse_inc(&self) -> u64 {
+        self.invalid_parse.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages of another network of the `PeerMetrics`.
+    pub fn invalid_network_id(&self) -> u64 {
+        self.invalid_network_id.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages of another network of the `PeerMetrics`.
+    pub fn invalid_network_id_inc(&self) -> u64 {
+        self.invalid_network_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with an insufficient PoW score of the `PeerMetrics`.
+    pub fn invalid_pow(&self) -> u64 {
+        self.invalid_pow.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with an insufficient PoW score of the `PeerMetrics`.
+    pub fn invalid_pow_inc(&self) -> u64 {
+        self.invalid_pow.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with duplicate or unsorted parents of the `PeerMetrics`.
+    pub fn invalid_parents(&self) -> u64 {
+        self.invalid_parents.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with duplicate or unsorted parents of the `PeerMetrics`.
+    pub fn invalid_parents_inc(&self) -> u64 {
+        self.invalid_parents.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of invalid messages with a timestamp too far in the future of the `PeerMetrics`.
+    pub fn invalid_timestamp(&self) -> u64 {
+        self.invalid_timestamp.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of invalid messages with a timestamp too far in the future of the `PeerMetrics`.
+    pub fn invalid_timestamp_inc(&self) -> u64 {
+        self.invalid_timestamp.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages rejected for exceeding the maximum length of the `PeerMetrics`.
+    pub fn oversized_messages(&self) -> u64 {
+        self.oversized_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages rejected for exceeding the maximum length of the `PeerMetrics`.
+    pub fn oversized_messages_inc(&self) -> u64 {
+        self.oversized_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Returns the number of new messages of the `PeerMetrics`.
     pub fn new_messages(&self) -> u64 {
         self.new_messages.load(Ordering::Relaxed)
@@ -53,6 +122,36 @@ impl PeerMetrics {
         self.known_messages.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of known messages received with different bytes than the stored ones of the `PeerMetrics`.
+    pub fn corrupt_messages(&self) -> u64 {
+        self.corrupt_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of known messages received with different bytes than the stored ones of the `PeerMetrics`.
+    pub fn corrupt_messages_inc(&self) -> u64 {
+        self.corrupt_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of new messages received that were requested by this node of the `PeerMetrics`.
+    pub fn requested_messages(&self) -> u64 {
+        self.requested_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of new messages received that were requested by this node of the `PeerMetrics`.
+    pub fn requested_messages_inc(&self) -> u64 {
+        self.requested_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages dropped for exceeding the rate limit of the `PeerMetrics`.
+    pub fn rate_limited_messages(&self) -> u64 {
+        self.rate_limited_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages dropped for exceeding the rate limit of the `PeerMetrics`.
+    pub fn rate_limited_messages_inc(&self) -> u64 {
+        self.rate_limited_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Returns the number of invalid packets of the `PeerMetrics`.
     pub fn invalid_packets(&self) -> u64 {
         self.invalid_packets.load(Ordering::Relaxed)
@@ -154,16 +253,43 @@ mod tests {
         let metrics = PeerMetrics::default();
 
         assert_eq!(metrics.invalid_messages(), 0);
+        assert_eq!(metrics.invalid_parse(), 0);
+        assert_eq!(metrics.invalid_network_id(), 0);
+        assert_eq!(metrics.invalid_pow(), 0);
+        assert_eq!(metrics.invalid_parents(), 0);
+        assert_eq!(metrics.invalid_timestamp(), 0);
+        assert_eq!(metrics.oversized_messages(), 0);
         assert_eq!(metrics.new_messages(), 0);
         assert_eq!(metrics.known_messages(), 0);
+        assert_eq!(metrics.corrupt_messages(), 0);
+        assert_eq!(metrics.requested_messages(), 0);
+        assert_eq!(metrics.rate_limited_messages(), 0);
 
         metrics.invalid_messages_inc();
+        metrics.invalid_parse_inc();
+        metrics.invalid_network_id_inc();
+        metrics.invalid_pow_inc();
+        metrics.invalid_parents_inc();
+        metrics.invalid_timestamp_inc();
+        metrics.oversized_messages_inc();
         metrics.new_messages_inc();
         metrics.known_messages_inc();
+        metrics.corrupt_messages_inc();
+        metrics.requested_messages_inc();
+        metrics.rate_limited_messages_inc();
 
         assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(metrics.invalid_parse(), 1);
+        assert_eq!(metrics.invalid_network_id(), 1);
+        assert_eq!(metrics.invalid_pow(), 1);
+        assert_eq!(metrics.invalid_parents(), 1);
+        assert_eq!(metrics.invalid_timestamp(), 1);
+        assert_eq!(metrics.oversized_messages(), 1);
         assert_eq!(metrics.new_messages(), 1);
         assert_eq!(metrics.known_messages(), 1);
+        assert_eq!(metrics.corrupt_messages(), 1);
+        assert_eq!(metrics.requested_messages(), 1);
+        assert_eq!(metrics.rate_limited_messages(), 1);
     }
 
     #[test]
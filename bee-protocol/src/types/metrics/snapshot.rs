@@ -0,0 +1,669 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module that provides a point-in-time snapshot of the metrics and its rendering in the OpenMetrics text format.
+
+use crate::types::metrics::{LatencyHistogram, NodeMetrics, PeerMetrics};
+
+use std::fmt::Write;
+
+const METRIC_PREFIX: &str = "bee";
+
+/// The kind of a metric family.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetricKind {
+    /// A monotonically increasing value.
+    Counter,
+    /// A value that can arbitrarily go up and down.
+    Gauge,
+    /// A distribution of observed values over buckets.
+    Histogram,
+    /// Quantiles of observed values.
+    Summary,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+            Self::Summary => "summary",
+        }
+    }
+}
+
+/// A single sample of a metric family.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricSample {
+    suffix: &'static str,
+    labels: Vec<(&'static str, String)>,
+    value: u64,
+}
+
+impl MetricSample {
+    /// Returns the name suffix of the `MetricSample`, e.g. `_bucket` for histograms.
+    pub fn suffix(&self) -> &'static str {
+        self.suffix
+    }
+
+    /// Returns the labels of the `MetricSample`.
+    pub fn labels(&self) -> &[(&'static str, String)] {
+        &self.labels
+    }
+
+    /// Returns the value of the `MetricSample`.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// A named group of samples sharing the same kind and description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricFamily {
+    name: &'static str,
+    help: &'static str,
+    kind: MetricKind,
+    samples: Vec<MetricSample>,
+}
+
+impl MetricFamily {
+    /// Returns the name of the `MetricFamily`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the kind of the `MetricFamily`.
+    pub fn kind(&self) -> MetricKind {
+        self.kind
+    }
+
+    /// Returns the samples of the `MetricFamily`.
+    pub fn samples(&self) -> &[MetricSample] {
+        &self.samples
+    }
+}
+
+/// A point-in-time snapshot of the node and peer metrics.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MetricsSnapshot {
+    families: Vec<MetricFamily>,
+}
+
+impl MetricsSnapshot {
+    /// Creates a new `MetricsSnapshot` from the node metrics and the metrics of each peer, identified by a label.
+    pub fn new<'a, I, S>(node: &NodeMetrics, peers: I) -> Self
+    where
+        I: IntoIterator<Item = (S, &'a PeerMetrics)>,
+        S: ToString,
+    {
+        let mut snapshot = Self::default();
+
+        macro_rules! node_metric {
+            ($kind:ident, $name:ident, $help:literal) => {
+                snapshot.push(
+                    stringify!($name),
+                    $help,
+                    MetricKind::$kind,
+                    MetricSample {
+                        suffix: "",
+                        labels: Vec::new(),
+                        value: node.$name(),
+                    },
+                )
+            };
+        }
+
+        node_metric!(Counter, invalid_packets, "Number of invalid packets.");
+        node_metric!(
+            Counter,
+            milestone_requests_received,
+            "Number of received milestone requests."
+        );
+        node_metric!(Counter, messages_received, "Number of received messages.");
+        node_metric!(
+            Counter,
+            message_requests_received,
+            "Number of received message requests."
+        );
+        node_metric!(Counter, heartbeats_received, "Number of received heartbeats.");
+        node_metric!(Counter, milestone_requests_sent, "Number of sent milestone requests.");
+        node_metric!(Counter, messages_sent, "Number of sent messages.");
+        node_metric!(Counter, message_requests_sent, "Number of sent message requests.");
+        node_metric!(Counter, heartbeats_sent, "Number of sent heartbeats.");
+        node_metric!(Counter, invalid_messages, "Number of invalid messages.");
+        node_metric!(
+            Counter,
+            invalid_parse,
+            "Number of invalid messages that could not be unpacked."
+        );
+        node_metric!(
+            Counter,
+            invalid_network_id,
+            "Number of invalid messages of another network."
+        );
+        node_metric!(
+            Counter,
+            invalid_pow,
+            "Number of invalid messages with an insufficient PoW score."
+        );
+        node_metric!(
+            Counter,
+            invalid_parents,
+            "Number of invalid messages with duplicate or unsorted parents."
+        );
+        node_metric!(
+            Counter,
+            invalid_timestamp,
+            "Number of invalid messages with a timestamp too far in the future."
+        );
+        node_metric!(
+            Counter,
+            oversized_messages,
+            "Number of messages rejected for exceeding the maximum length."
+        );
+        node_metric!(
+            Counter,
+            rejected_messages,
+            "Number of messages rejected by the validators provided by the operator."
+        );
+        node_metric!(
+            Counter,
+            blocked_index_messages,
+            "Number of messages rejected for an index matching a blocked prefix."
+        );
+        node_metric!(Counter, new_messages, "Number of new messages.");
+        node_metric!(Counter, known_messages, "Number of known messages.");
+        node_metric!(
+            Counter,
+            corrupt_messages,
+            "Number of known messages received with different bytes than the stored ones."
+        );
+        node_metric!(
+            Counter,
+            future_milestone_messages,
+            "Number of messages referencing a milestone index too far ahead."
+        );
+        node_metric!(
+            Counter,
+            rate_limited_messages,
+            "Number of messages dropped for exceeding the rate limit of their peer."
+        );
+        node_metric!(Counter, message_cache_hits, "Number of message cache hits.");
+        node_metric!(Gauge, message_cache_size, "Number of entries in the message cache.");
+        node_metric!(
+            Counter,
+            notifiers_gone,
+            "Number of submitters gone before being notified."
+        );
+        node_metric!(
+            Counter,
+            message_requests_suppressed,
+            "Number of suppressed duplicate message requests."
+        );
+        node_metric!(
+            Counter,
+            message_requests_abandoned,
+            "Number of message requests given up on after exhausting their retries."
+        );
+        node_metric!(
+            Counter,
+            message_requests_deferred,
+            "Number of requested messages whose parents were left to the solidifier."
+        );
+        node_metric!(
+            Gauge,
+            messages_average_latency,
+            "Average latency of requested messages in milliseconds."
+        );
+        snapshot.push_histogram(
+            "solidification_latency",
+            "Latency from arrival to solidification of messages in milliseconds.",
+            node.solidification_latency(),
+        );
+        snapshot.push_histogram(
+            "unpack_time_microseconds",
+            "Time spent unpacking received messages in microseconds.",
+            node.unpack_time(),
+        );
+        snapshot.push_quantiles(
+            "unpack_time_quantiles_microseconds",
+            "Quantiles of the time spent unpacking received messages in microseconds.",
+            node.unpack_time(),
+        );
+        snapshot.push_histogram(
+            "pow_check_time_microseconds",
+            "Time spent checking the PoW score of received messages in microseconds.",
+            node.pow_check_time(),
+        );
+        snapshot.push_quantiles(
+            "pow_check_time_quantiles_microseconds",
+            "Quantiles of the time spent checking the PoW score of received messages in microseconds.",
+            node.pow_check_time(),
+        );
+        snapshot.push_histogram(
+            "insert_time_microseconds",
+            "Time spent inserting received messages into the tangle in microseconds.",
+            node.insert_time(),
+        );
+        snapshot.push_quantiles(
+            "insert_time_quantiles_microseconds",
+            "Quantiles of the time spent inserting received messages into the tangle in microseconds.",
+            node.insert_time(),
+        );
+        node_metric!(
+            Gauge,
+            open_circuit_breakers,
+            "Number of peers skipped by the broadcaster after repeated send failures."
+        );
+        node_metric!(
+            Counter,
+            broadcasts_suppressed,
+            "Number of messages not broadcast because broadcasting is disabled."
+        );
+        node_metric!(
+            Counter,
+            stale_broadcasts_suppressed,
+            "Number of new messages not broadcast for referencing an old milestone."
+        );
+        node_metric!(
+            Gauge,
+            milestone_queue_depth,
+            "Number of milestone payloads waiting to be validated."
+        );
+        node_metric!(
+            Counter,
+            milestone_payloads_dropped,
+            "Number of invalid looking milestone payloads dropped because the milestone queue was full."
+        );
+        node_metric!(
+            Counter,
+            milestone_forward_retries,
+            "Number of milestone payloads held back to be retried because the milestone queue was full."
+        );
+        node_metric!(
+            Gauge,
+            processor_queue_depth,
+            "Number of messages waiting to be processed."
+        );
+        node_metric!(
+            Counter,
+            processor_queue_full,
+            "Number of messages dropped because the processor queue was full."
+        );
+        node_metric!(
+            Counter,
+            audit_records_dropped,
+            "Number of audit records dropped because the audit sink fell behind."
+        );
+        node_metric!(Gauge, tips, "Number of tips in the tip pool.");
+        node_metric!(Counter, referenced_messages, "Number of referenced messages.");
+        node_metric!(
+            Counter,
+            excluded_no_transaction_messages,
+            "Number of messages excluded because without transaction."
+        );
+        node_metric!(
+            Counter,
+            excluded_conflicting_messages,
+            "Number of messages excluded because conflicting."
+        );
+        node_metric!(Counter, included_messages, "Number of included messages.");
+        node_metric!(Counter, created_outputs, "Number of created outputs.");
+        node_metric!(Counter, consumed_outputs, "Number of consumed outputs.");
+        node_metric!(Counter, receipts, "Number of receipts.");
+        node_metric!(Counter, transaction_payloads, "Number of transaction payloads.");
+        node_metric!(Counter, milestone_payloads, "Number of milestone payloads.");
+        node_metric!(Counter, indexation_payloads, "Number of indexation payloads.");
+        node_metric!(Counter, snapshots, "Number of snapshots.");
+        node_metric!(Counter, prunings, "Number of prunings.");
+
+        for (peer, metrics) in peers {
+            let peer = peer.to_string();
+
+            macro_rules! peer_metric {
+                ($name:ident, $help:literal) => {
+                    snapshot.push(
+                        concat!("peer_", stringify!($name)),
+                        $help,
+                        MetricKind::Counter,
+                        MetricSample {
+                            suffix: "",
+                            labels: vec![("peer", peer.clone())],
+                            value: metrics.$name(),
+                        },
+                    )
+                };
+            }
+
+            peer_metric!(invalid_messages, "Number of invalid messages per peer.");
+            peer_metric!(
+                invalid_parse,
+                "Number of invalid messages that could not be unpacked per peer."
+            );
+            peer_metric!(
+                invalid_network_id,
+                "Number of invalid messages of another network per peer."
+            );
+            peer_metric!(
+                invalid_pow,
+                "Number of invalid messages with an insufficient PoW score per peer."
+            );
+            peer_metric!(
+                invalid_parents,
+                "Number of invalid messages with duplicate or unsorted parents per peer."
+            );
+            peer_metric!(
+                invalid_timestamp,
+                "Number of invalid messages with a timestamp too far in the future per peer."
+            );
+            peer_metric!(
+                oversized_messages,
+                "Number of messages rejected for exceeding the maximum length per peer."
+            );
+            peer_metric!(new_messages, "Number of new messages per peer.");
+            peer_metric!(known_messages, "Number of known messages per peer.");
+            peer_metric!(
+                corrupt_messages,
+                "Number of known messages received with different bytes than the stored ones per peer."
+            );
+            peer_metric!(
+                requested_messages,
+                "Number of new messages received that were requested per peer."
+            );
+            peer_metric!(
+                rate_limited_messages,
+                "Number of messages dropped for exceeding the rate limit per peer."
+            );
+            peer_metric!(invalid_packets, "Number of invalid packets per peer.");
+            peer_metric!(
+                milestone_requests_received,
+                "Number of received milestone requests per peer."
+            );
+            peer_metric!(messages_received, "Number of received messages per peer.");
+            peer_metric!(
+                message_requests_received,
+                "Number of received message requests per peer."
+            );
+            peer_metric!(heartbeats_received, "Number of received heartbeats per peer.");
+            peer_metric!(milestone_requests_sent, "Number of sent milestone requests per peer.");
+            peer_metric!(messages_sent, "Number of sent messages per peer.");
+            peer_metric!(message_requests_sent, "Number of sent message requests per peer.");
+            peer_metric!(heartbeats_sent, "Number of sent heartbeats per peer.");
+        }
+
+        snapshot
+    }
+
+    /// Returns the metric families of the `MetricsSnapshot`.
+    pub fn families(&self) -> &[MetricFamily] {
+        &self.families
+    }
+
+    /// Renders the `MetricsSnapshot` in the OpenMetrics text exposition format.
+    pub fn to_openmetrics(&self) -> String {
+        let mut out = String::new();
+
+        for family in self.families.iter() {
+            let name = format!("{}_{}", METRIC_PREFIX, family.name);
+            let suffix = match family.kind {
+                MetricKind::Counter => "_total",
+                MetricKind::Gauge | MetricKind::Histogram | MetricKind::Summary => "",
+            };
+
+            // Panic: writing to a `String` can't fail.
+            writeln!(out, "# HELP {} {}", name, escape_help(family.help)).unwrap();
+            writeln!(out, "# TYPE {} {}", name, family.kind.as_str()).unwrap();
+
+            for sample in family.samples.iter() {
+                out.push_str(&name);
+                out.push_str(suffix);
+                out.push_str(sample.suffix);
+
+                if !sample.labels.is_empty() {
+                    let labels = sample
+                        .labels
+                        .iter()
+                        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    out.push('{');
+                    out.push_str(&labels);
+                    out.push('}');
+                }
+
+                writeln!(out, " {}", sample.value).unwrap();
+            }
+        }
+
+        out.push_str("# EOF\n");
+
+        out
+    }
+
+    fn push(&mut self, name: &'static str, help: &'static str, kind: MetricKind, sample: MetricSample) {
+        match self.families.iter_mut().find(|family| family.name == name) {
+            Some(family) => family.samples.push(sample),
+            None => self.families.push(MetricFamily {
+                name,
+                help,
+                kind,
+                samples: vec![sample],
+            }),
+        }
+    }
+
+    // Pushes the p50, p95 and p99 estimates of a histogram as a summary, 0 standing for an empty histogram.
+    fn push_quantiles(&mut self, name: &'static str, help: &'static str, histogram: &LatencyHistogram) {
+        for (label, quantile) in [("0.5", 0.5), ("0.95", 0.95), ("0.99", 0.99)] {
+            self.push(
+                name,
+                help,
+                MetricKind::Summary,
+                MetricSample {
+                    suffix: "",
+                    labels: vec![("quantile", label.to_string())],
+                    value: histogram.quantile(quantile).unwrap_or_default(),
+                },
+            );
+        }
+        self.push(
+            name,
+            help,
+            MetricKind::Summary,
+            MetricSample {
+                suffix: "_sum",
+                labels: Vec::new(),
+                value: histogram.sum(),
+            },
+        );
+        self.push(
+            name,
+            help,
+            MetricKind::Summary,
+            MetricSample {
+                suffix: "_count",
+                labels: Vec::new(),
+                value: histogram.count(),
+            },
+        );
+    }
+
+    fn push_histogram(&mut self, name: &'static str, help: &'static str, histogram: &LatencyHistogram) {
+        for (bound, count) in histogram.buckets() {
+            self.push(
+                name,
+                help,
+                MetricKind::Histogram,
+                MetricSample {
+                    suffix: "_bucket",
+                    labels: vec![(
+                        "le",
+                        bound.map_or_else(|| "+Inf".to_string(), |bound| bound.to_string()),
+                    )],
+                    value: count,
+                },
+            );
+        }
+        self.push(
+            name,
+            help,
+            MetricKind::Histogram,
+            MetricSample {
+                suffix: "_sum",
+                labels: Vec::new(),
+                value: histogram.sum(),
+            },
+        );
+        self.push(
+            name,
+            help,
+            MetricKind::Histogram,
+            MetricSample {
+                suffix: "_count",
+                labels: Vec::new(),
+                value: histogram.count(),
+            },
+        );
+    }
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn openmetrics_fixture() {
+        let mut snapshot = MetricsSnapshot::default();
+
+        snapshot.push(
+            "messages_received",
+            "Number of received messages.",
+            MetricKind::Counter,
+            MetricSample {
+                suffix: "",
+                labels: Vec::new(),
+                value: 42,
+            },
+        );
+        snapshot.push(
+            "message_cache_size",
+            "Number of entries in the message cache.",
+            MetricKind::Gauge,
+            MetricSample {
+                suffix: "",
+                labels: Vec::new(),
+                value: 7,
+            },
+        );
+        snapshot.push(
+            "peer_new_messages",
+            "Number of new messages per peer.",
+            MetricKind::Counter,
+            MetricSample {
+                suffix: "",
+                labels: vec![("peer", "12D3KooW".to_string())],
+                value: 3,
+            },
+        );
+        snapshot.push(
+            "peer_new_messages",
+            "Number of new messages per peer.",
+            MetricKind::Counter,
+            MetricSample {
+                suffix: "",
+                labels: vec![("peer", "a\"b\\c\nd".to_string())],
+                value: 1,
+            },
+        );
+
+        let expected = "\
+# HELP bee_messages_received Number of received messages.
+# TYPE bee_messages_received counter
+bee_messages_received_total 42
+# HELP bee_message_cache_size Number of entries in the message cache.
+# TYPE bee_message_cache_size gauge
+bee_message_cache_size 7
+# HELP bee_peer_new_messages Number of new messages per peer.
+# TYPE bee_peer_new_messages counter
+bee_peer_new_messages_total{peer=\"12D3KooW\"} 3
+bee_peer_new_messages_total{peer=\"a\\\"b\\\\c\\nd\"} 1
+# EOF
+";
+
+        assert_eq!(snapshot.to_openmetrics(), expected);
+    }
+
+    #[test]
+    fn snapshot_from_metrics() {
+        let node = NodeMetrics::default();
+        node.messages_received_inc();
+        node.message_cache_size_set(5);
+
+        let first = PeerMetrics::default();
+        first.known_messages_inc();
+        let second = PeerMetrics::default();
+
+        let snapshot = MetricsSnapshot::new(&node, vec![("first", &first), ("second", &second)]);
+        let rendered = snapshot.to_openmetrics();
+
+        assert!(rendered.contains("bee_messages_received_total 1\n"));
+        assert!(rendered.contains("bee_message_cache_size 5\n"));
+        assert!(rendered.contains("bee_peer_known_messages_total{peer=\"first\"} 1\n"));
+        assert!(rendered.contains("bee_peer_known_messages_total{peer=\"second\"} 0\n"));
+        assert_eq!(rendered.matches("# TYPE bee_peer_known_messages counter\n").count(), 1);
+        assert!(rendered.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn histogram_rendering() {
+        let node = NodeMetrics::default();
+        node.solidification_latency_observe(3);
+        node.solidification_latency_observe(40);
+
+        let rendered = MetricsSnapshot::new(&node, Vec::<(&str, &PeerMetrics)>::new()).to_openmetrics();
+
+        assert!(rendered.contains("# TYPE bee_solidification_latency histogram\n"));
+        assert!(rendered.contains("bee_solidification_latency_bucket{le=\"5\"} 1\n"));
+        assert!(rendered.contains("bee_solidification_latency_bucket{le=\"50\"} 2\n"));
+        assert!(rendered.contains("bee_solidification_latency_bucket{le=\"+Inf\"} 2\n"));
+        assert!(rendered.contains("bee_solidification_latency_sum 43\n"));
+        assert!(rendered.contains("bee_solidification_latency_count 2\n"));
+    }
+
+    #[test]
+    fn help_is_escaped() {
+        assert_eq!(escape_help("a \"b\"\\\nc"), "a \\\"b\\\"\\\\\\nc");
+    }
+
+    #[test]
+    fn quantile_rendering() {
+        let node = NodeMetrics::default();
+
+        let rendered = MetricsSnapshot::new(&node, Vec::<(&str, &PeerMetrics)>::new()).to_openmetrics();
+
+        assert!(rendered.contains("# TYPE bee_insert_time_quantiles_microseconds summary\n"));
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds{quantile=\"0.5\"} 0\n"));
+
+        for latency in [3, 8, 20, 40, 400] {
+            node.insert_time_observe(latency);
+        }
+
+        let rendered = MetricsSnapshot::new(&node, Vec::<(&str, &PeerMetrics)>::new()).to_openmetrics();
+
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds{quantile=\"0.5\"} 25\n"));
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds{quantile=\"0.95\"} 500\n"));
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds{quantile=\"0.99\"} 500\n"));
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds_sum 471\n"));
+        assert!(rendered.contains("bee_insert_time_quantiles_microseconds_count 5\n"));
+    }
+}
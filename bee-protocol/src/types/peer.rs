@@ -9,7 +9,7 @@ use bee_gossip::{Multiaddr, PeerId, PeerInfo, PeerRelation};
 use bee_message::milestone::MilestoneIndex;
 
 use std::{
-    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
+    sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicU64, AtomicU8, Ordering},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -28,6 +28,7 @@ pub struct Peer {
     synced_peers: AtomicU8,
     heartbeat_sent_timestamp: AtomicU64,
     heartbeat_received_timestamp: AtomicU64,
+    reputation: AtomicI64,
 }
 
 impl Peer {
@@ -45,6 +46,7 @@ impl Peer {
             synced_peers: AtomicU8::new(0),
             heartbeat_sent_timestamp: AtomicU64::new(0),
             heartbeat_received_timestamp: AtomicU64::new(0),
+            reputation: AtomicI64::new(0),
         }
     }
 
@@ -88,6 +90,16 @@ impl Peer {
         self.solid_milestone_index.store(*index, Ordering::Relaxed);
     }
 
+    /// Returns the reputation of the `Peer`, which its valid messages raise and its invalid ones lower.
+    pub fn reputation(&self) -> i64 {
+        self.reputation.load(Ordering::Relaxed)
+    }
+
+    // Adds the given delta to the reputation of the `Peer` and returns the previous reputation.
+    pub(crate) fn adjust_reputation(&self, delta: i64) -> i64 {
+        self.reputation.fetch_add(delta, Ordering::Relaxed)
+    }
+
     /// Returns the solid milestone index of the `Peer`.
     pub fn solid_milestone_index(&self) -> MilestoneIndex {
         self.solid_milestone_index.load(Ordering::Relaxed).into()
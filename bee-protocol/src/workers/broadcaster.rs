@@ -9,54 +9,299 @@ use crate::{
 use bee_gossip::PeerId;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 
+use async_priority_queue::PriorityQueue;
 use async_trait::async_trait;
 use futures::stream::StreamExt;
-use log::info;
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use log::{debug, info};
+use parking_lot::Mutex;
 
-use std::{any::TypeId, convert::Infallible};
+use std::{
+    any::TypeId,
+    cmp::{Ord, Ordering, PartialOrd},
+    collections::HashMap,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 pub(crate) struct BroadcasterWorkerEvent {
     pub(crate) source: Option<PeerId>,
     pub(crate) message: MessagePacket,
+    pub(crate) priority: bool,
+}
+
+// Prioritized events are broadcast first, events of the same priority in the order they were queued.
+struct QueuedBroadcast {
+    sequence: u64,
+    event: BroadcasterWorkerEvent,
+}
+
+impl Ord for QueuedBroadcast {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.event
+            .priority
+            .cmp(&other.event.priority)
+            .then_with(|| self.sequence.cmp(&other.sequence).reverse())
+    }
+}
+
+impl PartialOrd for QueuedBroadcast {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedBroadcast {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedBroadcast {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BreakerState {
+    // Messages are sent to the peer, counting the consecutive failures.
+    Closed { failures: u32 },
+    // Messages are not sent to the peer until the cooldown is over.
+    Open { until: Instant },
+    // The next message is sent to the peer to test whether it recovered.
+    HalfOpen,
+}
+
+// Skips peers after a number of consecutive send failures for a cooldown, before testing them again.
+struct CircuitBreakers {
+    threshold: Option<u32>,
+    cooldown: Duration,
+    states: Mutex<HashMap<PeerId, BreakerState>>,
 }
 
+impl CircuitBreakers {
+    fn new(threshold: Option<u32>, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Returns whether a message may be sent to the peer, half-opening its breaker if the cooldown is over.
+    fn allows(&self, peer_id: &PeerId, now: Instant) -> bool {
+        let mut states = self.states.lock();
+
+        match states.get(peer_id) {
+            Some(BreakerState::Open { until }) if now < *until => false,
+            Some(BreakerState::Open { .. }) => {
+                states.insert(*peer_id, BreakerState::HalfOpen);
+                true
+            }
+            _ => true,
+        }
+    }
+
+    // Records the outcome of sending a message to the peer.
+    fn record(&self, peer_id: &PeerId, sent: bool, now: Instant) {
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => return,
+        };
+        let mut states = self.states.lock();
+
+        if sent {
+            states.remove(peer_id);
+            return;
+        }
+
+        let state = states.entry(*peer_id).or_insert(BreakerState::Closed { failures: 0 });
+
+        let failures = match state {
+            BreakerState::Closed { failures } => *failures + 1,
+            _ => threshold,
+        };
+
+        if failures >= threshold {
+            debug!(
+                "Skipping {} for {:?} after {} failed sends.",
+                peer_id, self.cooldown, failures
+            );
+            *state = BreakerState::Open {
+                until: now + self.cooldown,
+            };
+        } else {
+            *state = BreakerState::Closed { failures };
+        }
+    }
+
+    // Returns the number of peers currently skipped.
+    fn open(&self) -> usize {
+        self.states
+            .lock()
+            .values()
+            .filter(|state| matches!(state, BreakerState::Open { .. }))
+            .count()
+    }
+}
+
+#[derive(Clone)]
 pub(crate) struct BroadcasterWorker {
-    pub(crate) tx: mpsc::UnboundedSender<BroadcasterWorkerEvent>,
+    queue: Arc<PriorityQueue<QueuedBroadcast>>,
+    sequence: Arc<AtomicU64>,
+}
+
+impl BroadcasterWorker {
+    pub(crate) fn new() -> Self {
+        Self {
+            queue: Arc::new(PriorityQueue::new()),
+            sequence: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub(crate) fn broadcast(&self, event: BroadcasterWorkerEvent) {
+        let sequence = self.sequence.fetch_add(1, AtomicOrdering::Relaxed);
+
+        self.queue.push(QueuedBroadcast { sequence, event });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn queued(&self) -> usize {
+        self.queue.len()
+    }
 }
 
 #[async_trait]
 impl<N: Node> Worker<N> for BroadcasterWorker {
-    type Config = ();
+    type Config = (Option<u32>, u64);
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
         vec![TypeId::of::<PeerManagerResWorker>(), TypeId::of::<MetricsWorker>()].leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
-        let (tx, rx) = mpsc::unbounded_channel();
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let broadcaster = Self::new();
+        let breakers = CircuitBreakers::new(config.0, Duration::from_secs(config.1));
 
         let peer_manager = node.resource::<PeerManager>();
         let metrics = node.resource::<NodeMetrics>();
 
-        node.spawn::<Self, _, _>(|shutdown| async move {
-            info!("Running.");
+        node.spawn::<Self, _, _>({
+            let queue = broadcaster.queue.clone();
+            |shutdown| async move {
+                info!("Running.");
 
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+                let mut receiver = ShutdownStream::new(shutdown, queue.incoming());
 
-            while let Some(BroadcasterWorkerEvent { source, message }) = receiver.next().await {
-                peer_manager.for_each(|peer_id, _| {
-                    if source.map_or(true, |ref source| peer_id != source) {
-                        Sender::<MessagePacket>::send(&message, peer_id, &peer_manager, &metrics);
-                    }
-                });
-            }
+                while let Some(QueuedBroadcast {
+                    event: BroadcasterWorkerEvent { source, message, .. },
+                    ..
+                }) = receiver.next().await
+                {
+                    let now = Instant::now();
+
+                    peer_manager.for_each(|peer_id, _| {
+                        if source.map_or(true, |ref source| peer_id != source)
+                            && peer_manager.is_connected(peer_id)
+                            && breakers.allows(peer_id, now)
+                        {
+                            let sent = Sender::<MessagePacket>::send(&message, peer_id, &peer_manager, &metrics);
+                            breakers.record(peer_id, sent, now);
+                        }
+                    });
 
-            info!("Stopped.");
+                    metrics.open_circuit_breakers_set(breakers.open() as u64);
+                }
+
+                info!("Stopped.");
+            }
         });
 
-        Ok(Self { tx })
+        Ok(broadcaster)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    impl CircuitBreakers {
+        fn state(&self, peer_id: &PeerId) -> BreakerState {
+            self.states
+                .lock()
+                .get(peer_id)
+                .copied()
+                .unwrap_or(BreakerState::Closed { failures: 0 })
+        }
+    }
+
+    fn event(source: Option<PeerId>, byte: u8) -> BroadcasterWorkerEvent {
+        BroadcasterWorkerEvent {
+            source,
+            message: MessagePacket::new(vec![byte]),
+            priority: source.is_none(),
+        }
+    }
+
+    #[test]
+    fn submitted_message_is_broadcast_before_relays() {
+        let broadcaster = BroadcasterWorker::new();
+        let relayer = PeerId::random();
+
+        broadcaster.broadcast(event(Some(relayer), 1));
+        broadcaster.broadcast(event(Some(relayer), 2));
+        broadcaster.broadcast(event(None, 3));
+        broadcaster.broadcast(event(Some(relayer), 4));
+        broadcaster.broadcast(event(None, 5));
+
+        let order = std::iter::from_fn(|| broadcaster.queue.try_pop())
+            .map(|queued| queued.event.message.bytes[0])
+            .collect::<Vec<_>>();
+
+        assert_eq!(order, vec![3, 5, 1, 2, 4]);
+    }
+
+    #[test]
+    fn circuit_breaker_transitions() {
+        let breakers = CircuitBreakers::new(Some(2), Duration::from_secs(10));
+        let peer_id = PeerId::random();
+        let start = Instant::now();
+
+        // Closed: failures below the threshold are tolerated.
+        assert!(breakers.allows(&peer_id, start));
+        breakers.record(&peer_id, false, start);
+        assert_eq!(breakers.state(&peer_id), BreakerState::Closed { failures: 1 });
+
+        // Open: the peer is skipped during the cooldown.
+        assert!(breakers.allows(&peer_id, start));
+        breakers.record(&peer_id, false, start);
+        assert!(matches!(breakers.state(&peer_id), BreakerState::Open { .. }));
+        assert_eq!(breakers.open(), 1);
+        assert!(!breakers.allows(&peer_id, start + Duration::from_secs(5)));
+
+        // Half-open: the peer is tested again after the cooldown.
+        assert!(breakers.allows(&peer_id, start + Duration::from_secs(10)));
+        assert_eq!(breakers.state(&peer_id), BreakerState::HalfOpen);
+        assert_eq!(breakers.open(), 0);
+
+        // Closed: a successful send resets the breaker.
+        breakers.record(&peer_id, true, start + Duration::from_secs(10));
+        assert_eq!(breakers.state(&peer_id), BreakerState::Closed { failures: 0 });
+        assert!(breakers.allows(&peer_id, start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn circuit_breaker_reopens_after_failed_test() {
+        let breakers = CircuitBreakers::new(Some(1), Duration::from_secs(10));
+        let peer_id = PeerId::random();
+        let start = Instant::now();
+
+        breakers.record(&peer_id, false, start);
+        assert!(breakers.allows(&peer_id, start + Duration::from_secs(10)));
+        breakers.record(&peer_id, false, start + Duration::from_secs(10));
+
+        assert!(!breakers.allows(&peer_id, start + Duration::from_secs(15)));
     }
 }
@@ -1,18 +1,92 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::types::milestone_key_range::MilestoneKeyRange;
+use crate::{
+    types::milestone_key_range::MilestoneKeyRange,
+    workers::peer::{ReputationPolicy, StrictPeeringPolicy},
+};
 
-use bee_message::milestone::MilestoneIndex;
+use bee_gossip::PeerId;
+use bee_message::{milestone::MilestoneIndex, MESSAGE_LENGTH_MAX};
 
 use serde::Deserialize;
 
+use std::{collections::HashMap, time::Duration};
+
 const DEFAULT_MINIMUM_POW_SCORE: f64 = 4000.0;
 const DEFAULT_COO_PUBLIC_KEY_COUNT: usize = 2;
 const DEFAULT_COO_PUBLIC_KEY_RANGES: [(&str, MilestoneIndex, MilestoneIndex); 0] = [];
 const DEFAULT_MESSAGE_WORKER_CACHE: usize = 10000;
 const DEFAULT_STATUS_INTERVAL: u64 = 10;
 const DEFAULT_MILESTONE_SYNC_COUNT: u32 = 200;
+const DEFAULT_MESSAGE_TRACE_SAMPLE_RATE: u64 = 1;
+const DEFAULT_SKIP_CANCELED_NOTIFIERS: bool = false;
+const DEFAULT_SOLIDIFICATION_LATENCY: bool = false;
+const DEFAULT_MIN_TIPS: usize = 4;
+const DEFAULT_UNPACK_TIME: bool = false;
+const DEFAULT_PROCESSING_TIME: bool = false;
+const DEFAULT_MESSAGE_REQUEST_TIMEOUT: u64 = 2500;
+pub(crate) const DEFAULT_UNAVAILABLE_MESSAGE_CAPACITY: usize = 10000;
+const DEFAULT_CIRCUIT_BREAKER_COOLDOWN: u64 = 30;
+const DEFAULT_STORE_RAW_MESSAGES: bool = false;
+const DEFAULT_PRIORITIZE_KNOWN_PEERS: bool = false;
+const DEFAULT_BROADCAST_ENABLED: bool = true;
+const DEFAULT_VERIFY_KNOWN_MESSAGES: bool = false;
+const DEFAULT_BAN_CORRUPT_PEERS: bool = false;
+const DEFAULT_DETECT_REATTACHMENTS: bool = false;
+const DEFAULT_RESET_METRICS_ON_SYNC: bool = false;
+const DEFAULT_MILESTONE_REVERIFY_INTERVAL: u64 = 60;
+const DEFAULT_POW_SCORE_MPS_THRESHOLD: u64 = 1000;
+const DEFAULT_PROCESSOR_TASKS: usize = 16;
+const DEFAULT_PROCESSOR_QUEUE_CAPACITY: usize = 10000;
+const DEFAULT_STRICT_PEERING: bool = false;
+const DEFAULT_MILESTONE_RETRY_QUEUE_CAPACITY: usize = 64;
+const DEFAULT_PROCESSOR_DRAIN: bool = false;
+const DEFAULT_PROCESSOR_DRAIN_TIMEOUT: u64 = 10;
+
+/// Defines how the message worker cache behaves once it reached its capacity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageCachePolicy {
+    /// Evicts the oldest entry to make room for the new one.
+    #[default]
+    EvictOldest,
+    /// Stops caching new entries, letting them through without deduplication.
+    Bypass,
+}
+
+/// Defines how milestone payloads are handled once the milestone queue reached its capacity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneQueuePolicy {
+    /// Waits for room in the queue.
+    #[default]
+    Block,
+    /// Drops the milestone payloads that look invalid, and waits for room in the queue for the others.
+    DropInvalid,
+}
+
+/// Defines how incoming messages are handled once the processor queue reached its capacity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessorQueuePolicy {
+    /// Waits for room in the queue.
+    #[default]
+    Block,
+    /// Drops the messages.
+    Drop,
+}
+
+/// Defines how the minimum PoW score of incoming messages is determined.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowScorePolicyKind {
+    /// Always requires the minimum PoW score.
+    #[default]
+    Static,
+    /// Raises the minimum PoW score in proportion to the incoming message rate, once it exceeds a threshold.
+    Adaptive,
+}
 
 #[derive(Default, Deserialize)]
 #[must_use]
@@ -25,8 +99,58 @@ struct ProtocolCoordinatorConfigBuilder {
 #[must_use]
 struct ProtocolWorkersConfigBuilder {
     message_worker_cache: Option<usize>,
+    message_worker_cache_policy: Option<MessageCachePolicy>,
+    message_worker_cache_ttl: Option<u64>,
     status_interval: Option<u64>,
     milestone_sync_count: Option<u32>,
+    message_trace_sample_rate: Option<u64>,
+    milestone_lookahead: Option<u32>,
+    max_clock_drift: Option<u64>,
+    blocked_index_prefixes: Option<Vec<String>>,
+    max_message_length: Option<usize>,
+    skip_canceled_notifiers: Option<bool>,
+    solidification_latency: Option<bool>,
+    min_tips: Option<usize>,
+    unpack_time: Option<bool>,
+    processing_time: Option<bool>,
+    message_request_timeout: Option<u64>,
+    message_request_retries: Option<u32>,
+    unavailable_message_capacity: Option<usize>,
+    unavailable_message_ttl: Option<u64>,
+    max_parent_request_depth: Option<u32>,
+    circuit_breaker_threshold: Option<u32>,
+    circuit_breaker_cooldown: Option<u64>,
+    store_raw_messages: Option<bool>,
+    prioritize_known_peers: Option<bool>,
+    broadcast_enabled: Option<bool>,
+    stale_broadcast_threshold: Option<u32>,
+    verify_known_messages: Option<bool>,
+    ban_corrupt_peers: Option<bool>,
+    detect_reattachments: Option<bool>,
+    reset_metrics_on_sync: Option<bool>,
+    milestone_queue_capacity: Option<usize>,
+    milestone_queue_policy: Option<MilestoneQueuePolicy>,
+    milestone_retry_queue_capacity: Option<usize>,
+    processor_queue_capacity: Option<usize>,
+    processor_queue_policy: Option<ProcessorQueuePolicy>,
+    processor_tasks: Option<usize>,
+    decision_log_capacity: Option<usize>,
+    processor_drain: Option<bool>,
+    processor_drain_timeout: Option<u64>,
+    milestone_reverify_interval: Option<u64>,
+    pow_score_policy: Option<PowScorePolicyKind>,
+    pow_score_mps_threshold: Option<u64>,
+    peer_rate_limit: Option<u32>,
+    peer_rate_limits: Option<HashMap<String, u32>>,
+    reputation_new_message: Option<i64>,
+    reputation_known_message: Option<i64>,
+    reputation_invalid_message: Option<i64>,
+    reputation_low_pow_message: Option<i64>,
+    reputation_threshold: Option<i64>,
+    strict_peering: Option<bool>,
+    strict_peering_max_invalid_ratio: Option<f64>,
+    strict_peering_window: Option<u64>,
+    strict_peering_min_samples: Option<usize>,
 }
 
 /// Builder for a `ProtocolConfig`.
@@ -68,6 +192,20 @@ impl ProtocolConfigBuilder {
         self
     }
 
+    /// Sets the message worker cache policy of the `ProtocolConfigBuilder`.
+    pub fn message_worker_cache_policy(mut self, message_worker_cache_policy: MessageCachePolicy) -> Self {
+        self.workers
+            .message_worker_cache_policy
+            .replace(message_worker_cache_policy);
+        self
+    }
+
+    /// Sets the maximum age, in seconds, of the entries of the message worker cache of the `ProtocolConfigBuilder`.
+    pub fn message_worker_cache_ttl(mut self, message_worker_cache_ttl: u64) -> Self {
+        self.workers.message_worker_cache_ttl.replace(message_worker_cache_ttl);
+        self
+    }
+
     /// Sets the status interval of the `ProtocolConfigBuilder`.
     pub fn status_interval(mut self, status_interval: u64) -> Self {
         self.workers.status_interval.replace(status_interval);
@@ -80,6 +218,337 @@ impl ProtocolConfigBuilder {
         self
     }
 
+    /// Sets the message trace sample rate of the `ProtocolConfigBuilder`.
+    pub fn message_trace_sample_rate(mut self, message_trace_sample_rate: u64) -> Self {
+        self.workers
+            .message_trace_sample_rate
+            .replace(message_trace_sample_rate);
+        self
+    }
+
+    /// Sets the milestone lookahead of the `ProtocolConfigBuilder`.
+    pub fn milestone_lookahead(mut self, milestone_lookahead: u32) -> Self {
+        self.workers.milestone_lookahead.replace(milestone_lookahead);
+        self
+    }
+
+    /// Sets how many seconds the timestamp of a milestone may be ahead of the local time of the
+    /// `ProtocolConfigBuilder`.
+    pub fn max_clock_drift(mut self, max_clock_drift: u64) -> Self {
+        self.workers.max_clock_drift.replace(max_clock_drift);
+        self
+    }
+
+    /// Sets the prefixes of the indexes, as UTF-8 strings, whose indexation payloads are rejected of the
+    /// `ProtocolConfigBuilder`.
+    pub fn blocked_index_prefixes(mut self, blocked_index_prefixes: Vec<String>) -> Self {
+        self.workers.blocked_index_prefixes.replace(blocked_index_prefixes);
+        self
+    }
+
+    /// Sets the maximum length, in bytes, of the messages that are not rejected before being unpacked of the
+    /// `ProtocolConfigBuilder`.
+    pub fn max_message_length(mut self, max_message_length: usize) -> Self {
+        self.workers.max_message_length.replace(max_message_length);
+        self
+    }
+
+    /// Sets whether canceled notifiers are skipped by the `ProtocolConfigBuilder`.
+    pub fn skip_canceled_notifiers(mut self, skip_canceled_notifiers: bool) -> Self {
+        self.workers.skip_canceled_notifiers.replace(skip_canceled_notifiers);
+        self
+    }
+
+    /// Sets whether the solidification latency is measured by the `ProtocolConfigBuilder`.
+    pub fn solidification_latency(mut self, solidification_latency: bool) -> Self {
+        self.workers.solidification_latency.replace(solidification_latency);
+        self
+    }
+
+    /// Sets the number of tips below which a warning is logged of the `ProtocolConfigBuilder`.
+    pub fn min_tips(mut self, min_tips: usize) -> Self {
+        self.workers.min_tips.replace(min_tips);
+        self
+    }
+
+    /// Sets whether the time spent unpacking messages is measured by the `ProtocolConfigBuilder`.
+    pub fn unpack_time(mut self, unpack_time: bool) -> Self {
+        self.workers.unpack_time.replace(unpack_time);
+        self
+    }
+
+    /// Sets whether the time spent unpacking, checking the PoW score of and inserting messages is measured by the
+    /// `ProtocolConfigBuilder`.
+    pub fn processing_time(mut self, processing_time: bool) -> Self {
+        self.workers.processing_time.replace(processing_time);
+        self
+    }
+
+    /// Sets the time, in milliseconds, after which an unanswered message request is issued again of the
+    /// `ProtocolConfigBuilder`.
+    pub fn message_request_timeout(mut self, message_request_timeout: u64) -> Self {
+        self.workers.message_request_timeout.replace(message_request_timeout);
+        self
+    }
+
+    /// Sets the message request retries of the `ProtocolConfigBuilder`.
+    pub fn message_request_retries(mut self, message_request_retries: u32) -> Self {
+        self.workers.message_request_retries.replace(message_request_retries);
+        self
+    }
+
+    /// Sets how many messages that were given up on are remembered of the `ProtocolConfigBuilder`.
+    pub fn unavailable_message_capacity(mut self, unavailable_message_capacity: usize) -> Self {
+        self.workers
+            .unavailable_message_capacity
+            .replace(unavailable_message_capacity);
+        self
+    }
+
+    /// Sets the time, in seconds, for which a message that was given up on is remembered of the
+    /// `ProtocolConfigBuilder`.
+    pub fn unavailable_message_ttl(mut self, unavailable_message_ttl: u64) -> Self {
+        self.workers.unavailable_message_ttl.replace(unavailable_message_ttl);
+        self
+    }
+
+    /// Sets how many generations of parents of a requested message are requested before leaving them to the solidifier
+    /// of the `ProtocolConfigBuilder`.
+    pub fn max_parent_request_depth(mut self, max_parent_request_depth: u32) -> Self {
+        self.workers.max_parent_request_depth.replace(max_parent_request_depth);
+        self
+    }
+
+    /// Sets the circuit breaker threshold of the `ProtocolConfigBuilder`.
+    pub fn circuit_breaker_threshold(mut self, circuit_breaker_threshold: u32) -> Self {
+        self.workers
+            .circuit_breaker_threshold
+            .replace(circuit_breaker_threshold);
+        self
+    }
+
+    /// Sets the circuit breaker cooldown of the `ProtocolConfigBuilder`.
+    pub fn circuit_breaker_cooldown(mut self, circuit_breaker_cooldown: u64) -> Self {
+        self.workers.circuit_breaker_cooldown.replace(circuit_breaker_cooldown);
+        self
+    }
+
+    /// Sets whether the raw bytes of messages are stored alongside them by the `ProtocolConfigBuilder`.
+    ///
+    /// This keeps the exact bytes that were received but roughly doubles the storage used by messages.
+    pub fn store_raw_messages(mut self, store_raw_messages: bool) -> Self {
+        self.workers.store_raw_messages.replace(store_raw_messages);
+        self
+    }
+
+    /// Sets whether messages of known peers are processed ahead of others by the `ProtocolConfigBuilder`.
+    pub fn prioritize_known_peers(mut self, prioritize_known_peers: bool) -> Self {
+        self.workers.prioritize_known_peers.replace(prioritize_known_peers);
+        self
+    }
+
+    /// Sets whether new messages are broadcast to peers by the `ProtocolConfigBuilder`.
+    ///
+    /// Disabling it turns the node into a leaf, e.g. an indexer, that stores and propagates messages locally only.
+    pub fn broadcast_enabled(mut self, broadcast_enabled: bool) -> Self {
+        self.workers.broadcast_enabled.replace(broadcast_enabled);
+        self
+    }
+
+    /// Sets how many milestones below the solid milestone the milestone referenced by a new message may be for it to
+    /// still be broadcast of the `ProtocolConfigBuilder`.
+    pub fn stale_broadcast_threshold(mut self, stale_broadcast_threshold: u32) -> Self {
+        self.workers
+            .stale_broadcast_threshold
+            .replace(stale_broadcast_threshold);
+        self
+    }
+
+    /// Sets whether the bytes of known messages are verified against the stored ones by the `ProtocolConfigBuilder`.
+    ///
+    /// Only messages whose raw bytes are stored, see `store_raw_messages`, can be verified.
+    pub fn verify_known_messages(mut self, verify_known_messages: bool) -> Self {
+        self.workers.verify_known_messages.replace(verify_known_messages);
+        self
+    }
+
+    /// Sets whether peers sending known messages with corrupt bytes are banned by the `ProtocolConfigBuilder`.
+    pub fn ban_corrupt_peers(mut self, ban_corrupt_peers: bool) -> Self {
+        self.workers.ban_corrupt_peers.replace(ban_corrupt_peers);
+        self
+    }
+
+    /// Sets whether messages carrying the payload of a previously seen message are flagged as reattachments by the
+    /// `ProtocolConfigBuilder`.
+    pub fn detect_reattachments(mut self, detect_reattachments: bool) -> Self {
+        self.workers.detect_reattachments.replace(detect_reattachments);
+        self
+    }
+
+    /// Sets whether the metric counters are reset once the node is first synced by the `ProtocolConfigBuilder`.
+    ///
+    /// This keeps the initial synchronization from skewing the steady-state metrics.
+    pub fn reset_metrics_on_sync(mut self, reset_metrics_on_sync: bool) -> Self {
+        self.workers.reset_metrics_on_sync.replace(reset_metrics_on_sync);
+        self
+    }
+
+    /// Sets the milestone queue capacity of the `ProtocolConfigBuilder`.
+    pub fn milestone_queue_capacity(mut self, milestone_queue_capacity: usize) -> Self {
+        self.workers.milestone_queue_capacity.replace(milestone_queue_capacity);
+        self
+    }
+
+    /// Sets the milestone queue policy of the `ProtocolConfigBuilder`.
+    pub fn milestone_queue_policy(mut self, milestone_queue_policy: MilestoneQueuePolicy) -> Self {
+        self.workers.milestone_queue_policy.replace(milestone_queue_policy);
+        self
+    }
+
+    /// Sets the number of milestone payloads held back to be retried while the milestone queue is full, before
+    /// waiting for room, of the `ProtocolConfigBuilder`.
+    pub fn milestone_retry_queue_capacity(mut self, milestone_retry_queue_capacity: usize) -> Self {
+        self.workers
+            .milestone_retry_queue_capacity
+            .replace(milestone_retry_queue_capacity);
+        self
+    }
+
+    /// Sets the capacity of the processor queue, and of the hasher queue in front of it, of the
+    /// `ProtocolConfigBuilder`.
+    pub fn processor_queue_capacity(mut self, processor_queue_capacity: usize) -> Self {
+        self.workers.processor_queue_capacity.replace(processor_queue_capacity);
+        self
+    }
+
+    /// Sets the processor queue policy of the `ProtocolConfigBuilder`.
+    pub fn processor_queue_policy(mut self, processor_queue_policy: ProcessorQueuePolicy) -> Self {
+        self.workers.processor_queue_policy.replace(processor_queue_policy);
+        self
+    }
+
+    /// Sets the number of tasks processing messages concurrently of the `ProtocolConfigBuilder`.
+    pub fn processor_tasks(mut self, processor_tasks: usize) -> Self {
+        self.workers.processor_tasks.replace(processor_tasks);
+        self
+    }
+
+    /// Sets the number of decisions of the processor on the latest messages kept for analysis of the
+    /// `ProtocolConfigBuilder`.
+    pub fn decision_log_capacity(mut self, decision_log_capacity: usize) -> Self {
+        self.workers.decision_log_capacity.replace(decision_log_capacity);
+        self
+    }
+
+    /// Sets whether the processor finishes processing the queued messages on shutdown of the `ProtocolConfigBuilder`.
+    pub fn processor_drain(mut self, processor_drain: bool) -> Self {
+        self.workers.processor_drain.replace(processor_drain);
+        self
+    }
+
+    /// Sets the maximum time, in seconds, the processor spends processing the queued messages on shutdown of the
+    /// `ProtocolConfigBuilder`.
+    pub fn processor_drain_timeout(mut self, processor_drain_timeout: u64) -> Self {
+        self.workers.processor_drain_timeout.replace(processor_drain_timeout);
+        self
+    }
+
+    /// Sets the minimum interval, in seconds, between two reverifications of the discovered peers triggered by new
+    /// milestones of the `ProtocolConfigBuilder`.
+    pub fn milestone_reverify_interval(mut self, milestone_reverify_interval: u64) -> Self {
+        self.workers
+            .milestone_reverify_interval
+            .replace(milestone_reverify_interval);
+        self
+    }
+
+    /// Sets the PoW score policy of the `ProtocolConfigBuilder`.
+    pub fn pow_score_policy(mut self, pow_score_policy: PowScorePolicyKind) -> Self {
+        self.workers.pow_score_policy.replace(pow_score_policy);
+        self
+    }
+
+    /// Sets the incoming message rate above which the adaptive PoW score policy raises the minimum PoW score of the
+    /// `ProtocolConfigBuilder`.
+    pub fn pow_score_mps_threshold(mut self, pow_score_mps_threshold: u64) -> Self {
+        self.workers.pow_score_mps_threshold.replace(pow_score_mps_threshold);
+        self
+    }
+
+    /// Sets the number of messages per second each peer may send of the `ProtocolConfigBuilder`.
+    pub fn peer_rate_limit(mut self, peer_rate_limit: u32) -> Self {
+        self.workers.peer_rate_limit.replace(peer_rate_limit);
+        self
+    }
+
+    /// Sets the number of messages per second a specific peer may send, overriding `peer_rate_limit`, of the
+    /// `ProtocolConfigBuilder`.
+    pub fn peer_rate_limit_override(mut self, peer_id: PeerId, rate_limit: u32) -> Self {
+        self.workers
+            .peer_rate_limits
+            .get_or_insert_with(HashMap::new)
+            .insert(peer_id.to_string(), rate_limit);
+        self
+    }
+
+    /// Sets by how much each new message of a peer changes its reputation of the `ProtocolConfigBuilder`.
+    pub fn reputation_new_message(mut self, weight: i64) -> Self {
+        self.workers.reputation_new_message.replace(weight);
+        self
+    }
+
+    /// Sets by how much each already known message of a peer changes its reputation of the `ProtocolConfigBuilder`.
+    pub fn reputation_known_message(mut self, weight: i64) -> Self {
+        self.workers.reputation_known_message.replace(weight);
+        self
+    }
+
+    /// Sets by how much each invalid message of a peer changes its reputation of the `ProtocolConfigBuilder`.
+    pub fn reputation_invalid_message(mut self, weight: i64) -> Self {
+        self.workers.reputation_invalid_message.replace(weight);
+        self
+    }
+
+    /// Sets by how much each message of a peer with an insufficient PoW score changes its reputation of the
+    /// `ProtocolConfigBuilder`.
+    pub fn reputation_low_pow_message(mut self, weight: i64) -> Self {
+        self.workers.reputation_low_pow_message.replace(weight);
+        self
+    }
+
+    /// Sets the reputation below which peers are banned of the `ProtocolConfigBuilder`.
+    pub fn reputation_threshold(mut self, reputation_threshold: i64) -> Self {
+        self.workers.reputation_threshold.replace(reputation_threshold);
+        self
+    }
+
+    /// Sets whether peers sending too many invalid messages are disconnected of the `ProtocolConfigBuilder`.
+    pub fn strict_peering(mut self, strict_peering: bool) -> Self {
+        self.workers.strict_peering.replace(strict_peering);
+        self
+    }
+
+    /// Sets the maximum ratio of invalid messages among the messages of a peer in strict peering mode of the
+    /// `ProtocolConfigBuilder`.
+    pub fn strict_peering_max_invalid_ratio(mut self, max_invalid_ratio: f64) -> Self {
+        self.workers.strict_peering_max_invalid_ratio.replace(max_invalid_ratio);
+        self
+    }
+
+    /// Sets the window, in seconds, over which the messages of a peer are considered in strict peering mode of the
+    /// `ProtocolConfigBuilder`.
+    pub fn strict_peering_window(mut self, window: u64) -> Self {
+        self.workers.strict_peering_window.replace(window);
+        self
+    }
+
+    /// Sets the minimum number of messages of a peer within the window before its ratio of invalid messages is
+    /// checked in strict peering mode of the `ProtocolConfigBuilder`.
+    pub fn strict_peering_min_samples(mut self, min_samples: usize) -> Self {
+        self.workers.strict_peering_min_samples.replace(min_samples);
+        self
+    }
+
     /// Finishes the `ProtocolConfigBuilder` into a `ProtocolConfig`.
     #[must_use]
     pub fn finish(self) -> ProtocolConfig {
@@ -102,11 +571,137 @@ impl ProtocolConfigBuilder {
                     .workers
                     .message_worker_cache
                     .unwrap_or(DEFAULT_MESSAGE_WORKER_CACHE),
+                message_worker_cache_policy: self.workers.message_worker_cache_policy.unwrap_or_default(),
+                message_worker_cache_ttl: self.workers.message_worker_cache_ttl,
                 status_interval: self.workers.status_interval.unwrap_or(DEFAULT_STATUS_INTERVAL),
                 milestone_sync_count: self
                     .workers
                     .milestone_sync_count
                     .unwrap_or(DEFAULT_MILESTONE_SYNC_COUNT),
+                message_trace_sample_rate: self
+                    .workers
+                    .message_trace_sample_rate
+                    .unwrap_or(DEFAULT_MESSAGE_TRACE_SAMPLE_RATE),
+                milestone_lookahead: self.workers.milestone_lookahead,
+                max_clock_drift: self.workers.max_clock_drift,
+                blocked_index_prefixes: self
+                    .workers
+                    .blocked_index_prefixes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(String::into_bytes)
+                    .collect(),
+                max_message_length: self.workers.max_message_length.unwrap_or(MESSAGE_LENGTH_MAX),
+                skip_canceled_notifiers: self
+                    .workers
+                    .skip_canceled_notifiers
+                    .unwrap_or(DEFAULT_SKIP_CANCELED_NOTIFIERS),
+                solidification_latency: self
+                    .workers
+                    .solidification_latency
+                    .unwrap_or(DEFAULT_SOLIDIFICATION_LATENCY),
+                min_tips: self.workers.min_tips.unwrap_or(DEFAULT_MIN_TIPS),
+                unpack_time: self.workers.unpack_time.unwrap_or(DEFAULT_UNPACK_TIME),
+                processing_time: self.workers.processing_time.unwrap_or(DEFAULT_PROCESSING_TIME),
+                message_request_timeout: self
+                    .workers
+                    .message_request_timeout
+                    .unwrap_or(DEFAULT_MESSAGE_REQUEST_TIMEOUT),
+                message_request_retries: self.workers.message_request_retries,
+                unavailable_message_capacity: self
+                    .workers
+                    .unavailable_message_capacity
+                    .unwrap_or(DEFAULT_UNAVAILABLE_MESSAGE_CAPACITY),
+                unavailable_message_ttl: self.workers.unavailable_message_ttl,
+                max_parent_request_depth: self.workers.max_parent_request_depth,
+                circuit_breaker_threshold: self.workers.circuit_breaker_threshold,
+                circuit_breaker_cooldown: self
+                    .workers
+                    .circuit_breaker_cooldown
+                    .unwrap_or(DEFAULT_CIRCUIT_BREAKER_COOLDOWN),
+                store_raw_messages: self.workers.store_raw_messages.unwrap_or(DEFAULT_STORE_RAW_MESSAGES),
+                prioritize_known_peers: self
+                    .workers
+                    .prioritize_known_peers
+                    .unwrap_or(DEFAULT_PRIORITIZE_KNOWN_PEERS),
+                broadcast_enabled: self.workers.broadcast_enabled.unwrap_or(DEFAULT_BROADCAST_ENABLED),
+                stale_broadcast_threshold: self.workers.stale_broadcast_threshold,
+                verify_known_messages: self
+                    .workers
+                    .verify_known_messages
+                    .unwrap_or(DEFAULT_VERIFY_KNOWN_MESSAGES),
+                ban_corrupt_peers: self.workers.ban_corrupt_peers.unwrap_or(DEFAULT_BAN_CORRUPT_PEERS),
+                detect_reattachments: self
+                    .workers
+                    .detect_reattachments
+                    .unwrap_or(DEFAULT_DETECT_REATTACHMENTS),
+                reset_metrics_on_sync: self
+                    .workers
+                    .reset_metrics_on_sync
+                    .unwrap_or(DEFAULT_RESET_METRICS_ON_SYNC),
+                milestone_queue_capacity: self.workers.milestone_queue_capacity,
+                milestone_queue_policy: self.workers.milestone_queue_policy.unwrap_or_default(),
+                milestone_retry_queue_capacity: self
+                    .workers
+                    .milestone_retry_queue_capacity
+                    .unwrap_or(DEFAULT_MILESTONE_RETRY_QUEUE_CAPACITY),
+                processor_queue_capacity: self
+                    .workers
+                    .processor_queue_capacity
+                    .unwrap_or(DEFAULT_PROCESSOR_QUEUE_CAPACITY),
+                processor_queue_policy: self.workers.processor_queue_policy.unwrap_or_default(),
+                processor_tasks: self.workers.processor_tasks.unwrap_or(DEFAULT_PROCESSOR_TASKS),
+                decision_log_capacity: self.workers.decision_log_capacity,
+                processor_drain: self.workers.processor_drain.unwrap_or(DEFAULT_PROCESSOR_DRAIN),
+                processor_drain_timeout: self
+                    .workers
+                    .processor_drain_timeout
+                    .unwrap_or(DEFAULT_PROCESSOR_DRAIN_TIMEOUT),
+                milestone_reverify_interval: self
+                    .workers
+                    .milestone_reverify_interval
+                    .unwrap_or(DEFAULT_MILESTONE_REVERIFY_INTERVAL),
+                pow_score_policy: self.workers.pow_score_policy.unwrap_or_default(),
+                pow_score_mps_threshold: self
+                    .workers
+                    .pow_score_mps_threshold
+                    .unwrap_or(DEFAULT_POW_SCORE_MPS_THRESHOLD),
+                peer_rate_limit: self.workers.peer_rate_limit,
+                peer_rate_limits: self.workers.peer_rate_limits.unwrap_or_default(),
+                reputation_policy: {
+                    let default = ReputationPolicy::default();
+
+                    ReputationPolicy {
+                        new_message: self.workers.reputation_new_message.unwrap_or(default.new_message),
+                        known_message: self.workers.reputation_known_message.unwrap_or(default.known_message),
+                        invalid_message: self
+                            .workers
+                            .reputation_invalid_message
+                            .unwrap_or(default.invalid_message),
+                        low_pow_message: self
+                            .workers
+                            .reputation_low_pow_message
+                            .unwrap_or(default.low_pow_message),
+                        threshold: self.workers.reputation_threshold,
+                    }
+                },
+                strict_peering: if self.workers.strict_peering.unwrap_or(DEFAULT_STRICT_PEERING) {
+                    let default = StrictPeeringPolicy::default();
+
+                    Some(StrictPeeringPolicy {
+                        max_invalid_ratio: self
+                            .workers
+                            .strict_peering_max_invalid_ratio
+                            .unwrap_or(default.max_invalid_ratio),
+                        window: self
+                            .workers
+                            .strict_peering_window
+                            .map_or(default.window, Duration::from_secs),
+                        min_samples: self.workers.strict_peering_min_samples.unwrap_or(default.min_samples),
+                    })
+                } else {
+                    None
+                },
             },
         }
     }
@@ -123,8 +718,51 @@ pub struct ProtocolCoordinatorConfig {
 #[derive(Clone)]
 pub struct ProtocolWorkersConfig {
     pub(crate) message_worker_cache: usize,
+    pub(crate) message_worker_cache_policy: MessageCachePolicy,
+    pub(crate) message_worker_cache_ttl: Option<u64>,
     pub(crate) status_interval: u64,
     pub(crate) milestone_sync_count: u32,
+    pub(crate) message_trace_sample_rate: u64,
+    pub(crate) milestone_lookahead: Option<u32>,
+    pub(crate) max_clock_drift: Option<u64>,
+    pub(crate) blocked_index_prefixes: Vec<Vec<u8>>,
+    pub(crate) max_message_length: usize,
+    pub(crate) skip_canceled_notifiers: bool,
+    pub(crate) solidification_latency: bool,
+    pub(crate) min_tips: usize,
+    pub(crate) unpack_time: bool,
+    pub(crate) processing_time: bool,
+    pub(crate) message_request_timeout: u64,
+    pub(crate) message_request_retries: Option<u32>,
+    pub(crate) unavailable_message_capacity: usize,
+    pub(crate) unavailable_message_ttl: Option<u64>,
+    pub(crate) max_parent_request_depth: Option<u32>,
+    pub(crate) circuit_breaker_threshold: Option<u32>,
+    pub(crate) circuit_breaker_cooldown: u64,
+    pub(crate) store_raw_messages: bool,
+    pub(crate) prioritize_known_peers: bool,
+    pub(crate) broadcast_enabled: bool,
+    pub(crate) stale_broadcast_threshold: Option<u32>,
+    pub(crate) verify_known_messages: bool,
+    pub(crate) ban_corrupt_peers: bool,
+    pub(crate) detect_reattachments: bool,
+    pub(crate) reset_metrics_on_sync: bool,
+    pub(crate) milestone_queue_capacity: Option<usize>,
+    pub(crate) milestone_queue_policy: MilestoneQueuePolicy,
+    pub(crate) milestone_retry_queue_capacity: usize,
+    pub(crate) processor_queue_capacity: usize,
+    pub(crate) processor_queue_policy: ProcessorQueuePolicy,
+    pub(crate) processor_tasks: usize,
+    pub(crate) decision_log_capacity: Option<usize>,
+    pub(crate) processor_drain: bool,
+    pub(crate) processor_drain_timeout: u64,
+    pub(crate) milestone_reverify_interval: u64,
+    pub(crate) pow_score_policy: PowScorePolicyKind,
+    pub(crate) pow_score_mps_threshold: u64,
+    pub(crate) peer_rate_limit: Option<u32>,
+    pub(crate) peer_rate_limits: HashMap<String, u32>,
+    pub(crate) reputation_policy: ReputationPolicy,
+    pub(crate) strict_peering: Option<StrictPeeringPolicy>,
 }
 
 /// Configuration for the protocol.
@@ -1,7 +1,8 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use bee_message::MessageId;
+use bee_gossip::PeerId;
+use bee_message::{milestone::MilestoneIndex, MessageId};
 
 /// An event that indicates that a message was processed.
 #[derive(Clone)]
@@ -15,6 +16,54 @@ pub struct MessageProcessed {
 pub struct MessageSolidified {
     /// Message identifier of the solidified message.
     pub message_id: MessageId,
+    /// Index of the milestone, if the solidified message is a milestone.
+    pub milestone_index: Option<MilestoneIndex>,
+}
+
+/// An event that indicates that a requested message was given up on after exhausting its request retries.
+#[derive(Clone)]
+pub struct MessageUnavailable {
+    /// Message identifier of the unavailable message.
+    pub message_id: MessageId,
+    /// Message identifiers of the messages referencing the unavailable message, that can't become solid.
+    pub orphans: Vec<MessageId>,
+}
+
+/// An event that indicates that the node became synced.
+#[derive(Clone)]
+pub struct NodeSynced {
+    /// Index of the solid milestone the node became synced at.
+    pub index: MilestoneIndex,
+}
+
+/// An event that indicates that the reputation of a peer dropped below the threshold.
+#[derive(Clone)]
+pub struct PeerReputationDropped {
+    /// Identifier of the peer.
+    pub peer_id: PeerId,
+    /// Reputation of the peer after the drop.
+    pub reputation: i64,
+}
+
+/// An event that indicates that the share of invalid messages of a peer exceeded the ratio allowed in strict peering
+/// mode.
+#[derive(Clone)]
+pub struct PeerInvalidRatioExceeded {
+    /// Identifier of the peer.
+    pub peer_id: PeerId,
+    /// Number of invalid messages of the peer within the window.
+    pub invalid_messages: usize,
+    /// Number of messages of the peer within the window.
+    pub messages: usize,
+}
+
+/// An event that indicates that a peer sent a milestone that failed validation.
+#[derive(Clone)]
+pub struct InvalidMilestoneReceived {
+    /// Identifier of the peer the milestone was received from.
+    pub peer_id: PeerId,
+    /// Message identifier of the invalid milestone.
+    pub message_id: MessageId,
 }
 
 /// An event that indicates that the MPS metrics were updated.
@@ -66,3 +115,17 @@ pub struct TipRemoved {
     /// Message identifier of the removed tip.
     pub message_id: MessageId,
 }
+
+/// An event that indicates that a peer was discovered by the autopeering.
+#[derive(Clone)]
+pub struct PeerDiscovered {
+    /// Autopeering identity of the discovered peer.
+    pub peer_id: bee_autopeering::PeerId,
+}
+
+/// An event that indicates that a peer was deleted by the autopeering, e.g. after failing its reverification.
+#[derive(Clone)]
+pub struct PeerDeleted {
+    /// Autopeering identity of the deleted peer.
+    pub peer_id: bee_autopeering::PeerId,
+}
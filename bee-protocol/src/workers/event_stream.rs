@@ -0,0 +1,440 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact binary stream of the protocol events, written to a socket for consumers that can't link the crate.
+//!
+//! Every event is written as a frame made of its length as a little-endian `u32`, followed by its kind as a `u8` and
+//! its packed fields. Peers are identified by their 32 bytes ED25519 public key.
+
+use crate::workers::event::{
+    MessageProcessed, MessageSolidified, MessageUnavailable, NodeSynced, PeerDeleted, PeerDiscovered, TipAdded,
+    TipRemoved,
+};
+
+use bee_common::packable::{Packable, Read, Write};
+use bee_message::{milestone::MilestoneIndex, MessageId};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{info, warn};
+use tokio::{
+    io::AsyncWriteExt,
+    net::TcpStream,
+    sync::mpsc,
+    time::{self, Instant},
+};
+use tokio_stream::wrappers::ReceiverStream;
+
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
+
+// Maximum length of a frame, leaving room for tens of thousands of orphans in a `MessageUnavailable` event.
+const MAX_FRAME_LENGTH: u32 = 1 << 20;
+// Events that arrive while this many are queued are dropped rather than slowing down the bus.
+const EVENT_QUEUE_CAPACITY: usize = 10_000;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+const MESSAGE_PROCESSED_KIND: u8 = 0;
+const MESSAGE_SOLIDIFIED_KIND: u8 = 1;
+const MESSAGE_UNAVAILABLE_KIND: u8 = 2;
+const NODE_SYNCED_KIND: u8 = 3;
+const TIP_ADDED_KIND: u8 = 4;
+const TIP_REMOVED_KIND: u8 = 5;
+const PEER_DISCOVERED_KIND: u8 = 6;
+const PEER_DELETED_KIND: u8 = 7;
+
+/// Errors occurring when packing or unpacking a `StreamEvent`.
+#[derive(Debug, thiserror::Error)]
+pub enum EventStreamError {
+    /// I/O error.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Message error.
+    #[error("message error: {0}")]
+    Message(#[from] bee_message::Error),
+    /// Invalid event kind.
+    #[error("invalid event kind: {0}")]
+    InvalidKind(u8),
+    /// Invalid frame length.
+    #[error("invalid frame length: {0}")]
+    InvalidLength(u32),
+}
+
+/// A protocol event as written to the event stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamEvent {
+    /// A message was processed.
+    MessageProcessed(MessageId),
+    /// A message was solidified.
+    MessageSolidified(MessageId),
+    /// A requested message was given up on, together with the messages referencing it.
+    MessageUnavailable(MessageId, Vec<MessageId>),
+    /// The node became synced at the given index.
+    NodeSynced(MilestoneIndex),
+    /// A tip was added.
+    TipAdded(MessageId),
+    /// A tip was removed.
+    TipRemoved(MessageId),
+    /// A peer was discovered by the autopeering.
+    PeerDiscovered([u8; 32]),
+    /// A peer was deleted by the autopeering.
+    PeerDeleted([u8; 32]),
+}
+
+impl StreamEvent {
+    fn kind(&self) -> u8 {
+        match self {
+            Self::MessageProcessed(_) => MESSAGE_PROCESSED_KIND,
+            Self::MessageSolidified(_) => MESSAGE_SOLIDIFIED_KIND,
+            Self::MessageUnavailable(_, _) => MESSAGE_UNAVAILABLE_KIND,
+            Self::NodeSynced(_) => NODE_SYNCED_KIND,
+            Self::TipAdded(_) => TIP_ADDED_KIND,
+            Self::TipRemoved(_) => TIP_REMOVED_KIND,
+            Self::PeerDiscovered(_) => PEER_DISCOVERED_KIND,
+            Self::PeerDeleted(_) => PEER_DELETED_KIND,
+        }
+    }
+
+    /// Returns the frame of the event, i.e. its length followed by the packed event.
+    pub fn to_frame(&self) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(0u32.packed_len() + self.packed_len());
+
+        // Panic: writing to a `Vec` can't fail.
+        (self.packed_len() as u32).pack(&mut frame).unwrap();
+        self.pack(&mut frame).unwrap();
+
+        frame
+    }
+
+    /// Reads the next frame from a reader and unpacks its event. Frames longer than 1 MiB are rejected.
+    pub fn from_frame<R: Read + ?Sized>(reader: &mut R) -> Result<Self, EventStreamError> {
+        let len = u32::unpack(reader)?;
+
+        if len > MAX_FRAME_LENGTH {
+            return Err(EventStreamError::InvalidLength(len));
+        }
+
+        let mut bytes = vec![0u8; len as usize];
+        reader.read_exact(&mut bytes)?;
+
+        let mut bytes = bytes.as_slice();
+        let event = Self::unpack(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(EventStreamError::InvalidLength(len));
+        }
+
+        Ok(event)
+    }
+}
+
+impl From<&MessageProcessed> for StreamEvent {
+    fn from(event: &MessageProcessed) -> Self {
+        Self::MessageProcessed(event.message_id)
+    }
+}
+
+impl From<&MessageSolidified> for StreamEvent {
+    fn from(event: &MessageSolidified) -> Self {
+        Self::MessageSolidified(event.message_id)
+    }
+}
+
+impl From<&MessageUnavailable> for StreamEvent {
+    fn from(event: &MessageUnavailable) -> Self {
+        Self::MessageUnavailable(event.message_id, event.orphans.clone())
+    }
+}
+
+impl From<&NodeSynced> for StreamEvent {
+    fn from(event: &NodeSynced) -> Self {
+        Self::NodeSynced(event.index)
+    }
+}
+
+impl From<&TipAdded> for StreamEvent {
+    fn from(event: &TipAdded) -> Self {
+        Self::TipAdded(event.message_id)
+    }
+}
+
+impl From<&TipRemoved> for StreamEvent {
+    fn from(event: &TipRemoved) -> Self {
+        Self::TipRemoved(event.message_id)
+    }
+}
+
+impl From<&PeerDiscovered> for StreamEvent {
+    fn from(event: &PeerDiscovered) -> Self {
+        Self::PeerDiscovered(event.peer_id.public_key().to_bytes())
+    }
+}
+
+impl From<&PeerDeleted> for StreamEvent {
+    fn from(event: &PeerDeleted) -> Self {
+        Self::PeerDeleted(event.peer_id.public_key().to_bytes())
+    }
+}
+
+impl Packable for StreamEvent {
+    type Error = EventStreamError;
+
+    fn packed_len(&self) -> usize {
+        self.kind().packed_len()
+            + match self {
+                Self::MessageProcessed(message_id)
+                | Self::MessageSolidified(message_id)
+                | Self::TipAdded(message_id)
+                | Self::TipRemoved(message_id) => message_id.packed_len(),
+                Self::MessageUnavailable(message_id, orphans) => message_id.packed_len() + orphans.packed_len(),
+                Self::NodeSynced(index) => index.packed_len(),
+                Self::PeerDiscovered(public_key) | Self::PeerDeleted(public_key) => public_key.packed_len(),
+            }
+    }
+
+    fn pack<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        self.kind().pack(writer)?;
+
+        match self {
+            Self::MessageProcessed(message_id)
+            | Self::MessageSolidified(message_id)
+            | Self::TipAdded(message_id)
+            | Self::TipRemoved(message_id) => message_id.pack(writer)?,
+            Self::MessageUnavailable(message_id, orphans) => {
+                message_id.pack(writer)?;
+                orphans.pack(writer)?;
+            }
+            Self::NodeSynced(index) => index.pack(writer)?,
+            Self::PeerDiscovered(public_key) | Self::PeerDeleted(public_key) => public_key.pack(writer)?,
+        }
+
+        Ok(())
+    }
+
+    fn unpack_inner<R: Read + ?Sized, const CHECK: bool>(reader: &mut R) -> Result<Self, Self::Error> {
+        Ok(match u8::unpack_inner::<R, CHECK>(reader)? {
+            MESSAGE_PROCESSED_KIND => Self::MessageProcessed(MessageId::unpack_inner::<R, CHECK>(reader)?),
+            MESSAGE_SOLIDIFIED_KIND => Self::MessageSolidified(MessageId::unpack_inner::<R, CHECK>(reader)?),
+            MESSAGE_UNAVAILABLE_KIND => Self::MessageUnavailable(
+                MessageId::unpack_inner::<R, CHECK>(reader)?,
+                Vec::<MessageId>::unpack_inner::<R, CHECK>(reader)?,
+            ),
+            NODE_SYNCED_KIND => Self::NodeSynced(MilestoneIndex::unpack_inner::<R, CHECK>(reader)?),
+            TIP_ADDED_KIND => Self::TipAdded(MessageId::unpack_inner::<R, CHECK>(reader)?),
+            TIP_REMOVED_KIND => Self::TipRemoved(MessageId::unpack_inner::<R, CHECK>(reader)?),
+            PEER_DISCOVERED_KIND => Self::PeerDiscovered(<[u8; 32]>::unpack_inner::<R, CHECK>(reader)?),
+            PEER_DELETED_KIND => Self::PeerDeleted(<[u8; 32]>::unpack_inner::<R, CHECK>(reader)?),
+            k => return Err(EventStreamError::InvalidKind(k)),
+        })
+    }
+}
+
+// The connection to the consumer, reestablished with an exponential backoff after failing to connect or write.
+struct Connection {
+    addr: SocketAddr,
+    stream: Option<TcpStream>,
+    backoff: Duration,
+    retry_at: Instant,
+}
+
+impl Connection {
+    fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            stream: None,
+            backoff: RECONNECT_BACKOFF_MIN,
+            retry_at: Instant::now(),
+        }
+    }
+
+    // Writes the event to the consumer, or drops it if no consumer is connected and the next attempt isn't due.
+    async fn write(&mut self, event: &StreamEvent) {
+        if self.stream.is_none() {
+            if Instant::now() < self.retry_at {
+                return;
+            }
+
+            match time::timeout(CONNECT_TIMEOUT, TcpStream::connect(self.addr)).await {
+                Ok(Ok(stream)) => {
+                    info!("Connected to event stream consumer {}.", self.addr);
+                    self.stream = Some(stream);
+                    self.backoff = RECONNECT_BACKOFF_MIN;
+                }
+                Ok(Err(e)) => {
+                    let backoff = self.disconnect();
+                    warn!(
+                        "Connecting to event stream consumer {} failed: {}. Dropping events for {:?}.",
+                        self.addr, e, backoff
+                    );
+                    return;
+                }
+                Err(_) => {
+                    let backoff = self.disconnect();
+                    warn!(
+                        "Connecting to event stream consumer {} timed out. Dropping events for {:?}.",
+                        self.addr, backoff
+                    );
+                    return;
+                }
+            }
+        }
+
+        if let Some(stream) = self.stream.as_mut() {
+            if let Err(e) = stream.write_all(&event.to_frame()).await {
+                let backoff = self.disconnect();
+                warn!(
+                    "Writing to event stream consumer {} failed: {}. Dropping events for {:?}.",
+                    self.addr, e, backoff
+                );
+            }
+        }
+    }
+
+    // Drops the connection and returns how long until the next attempt.
+    fn disconnect(&mut self) -> Duration {
+        let backoff = self.backoff;
+
+        self.stream = None;
+        self.retry_at = Instant::now() + backoff;
+        self.backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
+
+        backoff
+    }
+}
+
+/// A worker writing the protocol and discovery events as binary frames to a TCP consumer.
+///
+/// The worker connects to the consumer lazily and reconnects with an exponential backoff after failing to connect or
+/// write. Events are dropped while no consumer is connected, and when the consumer can't keep up with them.
+pub struct EventStreamWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for EventStreamWorker {
+    type Config = SocketAddr;
+    type Error = Infallible;
+
+    async fn start(node: &mut N, addr: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::channel(EVENT_QUEUE_CAPACITY);
+
+        macro_rules! forward {
+            ($($event:ty),*) => {
+                $({
+                    let tx = tx.clone();
+                    node.bus().add_listener::<Self, $event, _>(move |event| {
+                        // The event is dropped if the queue is full, or if the receiver disappeared during shutdown.
+                        let _ = tx.try_send(StreamEvent::from(event));
+                    });
+                })*
+            };
+        }
+
+        forward!(
+            MessageProcessed,
+            MessageSolidified,
+            MessageUnavailable,
+            NodeSynced,
+            TipAdded,
+            TipRemoved,
+            PeerDiscovered,
+            PeerDeleted
+        );
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, ReceiverStream::new(rx));
+            let mut connection = Connection::new(addr);
+
+            while let Some(event) = receiver.next().await {
+                connection.write(&event).await;
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+
+    #[test]
+    fn frames_round_trip() {
+        let events = vec![
+            StreamEvent::MessageProcessed(MessageId::new([1; 32])),
+            StreamEvent::MessageSolidified(MessageId::new([2; 32])),
+            StreamEvent::MessageUnavailable(MessageId::new([3; 32]), vec![MessageId::new([4; 32])]),
+            StreamEvent::NodeSynced(MilestoneIndex(42)),
+            StreamEvent::TipAdded(MessageId::new([5; 32])),
+            StreamEvent::TipRemoved(MessageId::new([6; 32])),
+            StreamEvent::PeerDiscovered([7; 32]),
+            StreamEvent::PeerDeleted([8; 32]),
+        ];
+
+        let bytes = events.iter().flat_map(StreamEvent::to_frame).collect::<Vec<_>>();
+        let mut reader = bytes.as_slice();
+
+        for event in events {
+            assert_eq!(StreamEvent::from_frame(&mut reader).unwrap(), event);
+        }
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn invalid_frames_are_rejected() {
+        assert!(matches!(
+            StreamEvent::from_frame(&mut [1, 0, 0, 0, 42].as_slice()),
+            Err(EventStreamError::InvalidKind(42))
+        ));
+
+        let mut frame = StreamEvent::NodeSynced(MilestoneIndex(42)).to_frame();
+        frame[0] += 1;
+        frame.push(0);
+        assert!(matches!(
+            StreamEvent::from_frame(&mut frame.as_slice()),
+            Err(EventStreamError::InvalidLength(_))
+        ));
+
+        assert!(matches!(
+            StreamEvent::from_frame(&mut [255, 255, 255, 255].as_slice()),
+            Err(EventStreamError::InvalidLength(u32::MAX))
+        ));
+    }
+
+    #[tokio::test]
+    async fn events_are_dropped_until_reconnecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let mut connection = Connection::new(addr);
+
+        connection.write(&StreamEvent::NodeSynced(MilestoneIndex(1))).await;
+        assert!(connection.stream.is_none());
+        assert_eq!(connection.backoff, RECONNECT_BACKOFF_MIN * 2);
+
+        // No reconnection is attempted before the backoff elapsed.
+        connection.write(&StreamEvent::NodeSynced(MilestoneIndex(2))).await;
+        assert_eq!(connection.backoff, RECONNECT_BACKOFF_MIN * 2);
+
+        let listener = TcpListener::bind(addr).await.unwrap();
+        connection.retry_at = Instant::now();
+
+        let event = StreamEvent::NodeSynced(MilestoneIndex(3));
+        connection.write(&event).await;
+        assert!(connection.stream.is_some());
+        assert_eq!(connection.backoff, RECONNECT_BACKOFF_MIN);
+
+        let (mut consumer, _) = listener.accept().await.unwrap();
+        let mut frame = vec![0; event.to_frame().len()];
+        consumer.read_exact(&mut frame).await.unwrap();
+        assert_eq!(StreamEvent::from_frame(&mut frame.as_slice()).unwrap(), event);
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::workers::storage::StorageBackend;
+use crate::{
+    types::metrics::NodeMetrics,
+    workers::{propagator::update_tips, storage::StorageBackend, MetricsWorker},
+};
 
 use bee_message::{
     milestone::{Milestone, MilestoneIndex},
@@ -30,17 +33,18 @@ impl<N: Node> Worker<N> for IndexUpdaterWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = usize;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
-        vec![TypeId::of::<TangleWorker>()].leak()
+        vec![TypeId::of::<TangleWorker>(), TypeId::of::<MetricsWorker>()].leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, min_tips: Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let tangle = node.resource::<Tangle<N::Backend>>();
+        let metrics = node.resource::<NodeMetrics>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -48,7 +52,7 @@ where
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
             while let Some(IndexUpdaterWorkerEvent(index, milestone)) = receiver.next().await {
-                process(&tangle, milestone, index).await;
+                process(&tangle, &metrics, min_tips, milestone, index).await;
             }
 
             // Before the worker completely stops, the receiver needs to be drained for milestone cones to be updated.
@@ -58,7 +62,7 @@ where
             let mut count: usize = 0;
 
             while let Some(Some(IndexUpdaterWorkerEvent(index, milestone))) = receiver.next().now_or_never() {
-                process(&tangle, milestone, index).await;
+                process(&tangle, &metrics, min_tips, milestone, index).await;
                 count += 1;
             }
 
@@ -71,7 +75,13 @@ where
     }
 }
 
-async fn process<B: StorageBackend>(tangle: &Tangle<B>, milestone: Milestone, index: MilestoneIndex) {
+async fn process<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    metrics: &NodeMetrics,
+    min_tips: usize,
+    milestone: Milestone,
+    index: MilestoneIndex,
+) {
     if let Some(parents) = tangle
         .get(milestone.message_id())
         .await
@@ -87,6 +97,7 @@ async fn process<B: StorageBackend>(tangle: &Tangle<B>, milestone: Milestone, in
 
             // Update tip pool after all values got updated.
             tangle.update_tip_scores().await;
+            update_tips(tangle, metrics, min_tips).await;
         }
     }
 }
@@ -0,0 +1,307 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::metrics::NodeMetrics;
+
+use bee_gossip::PeerId;
+use bee_message::MessageId;
+use bee_runtime::{node::Node, resource::ResourceHandle, shutdown_stream::ShutdownStream, worker::Worker};
+
+use async_trait::async_trait;
+use futures::{
+    future::FutureExt,
+    stream::{Stream, StreamExt},
+};
+use log::{debug, info, warn};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
+
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const AUDIT_BATCH_SIZE: usize = 1000;
+const AUDIT_QUEUE_CAPACITY: usize = 10 * AUDIT_BATCH_SIZE;
+
+/// The terminal outcome of the processing of a message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuditOutcome {
+    /// The message was new and has been processed.
+    Processed,
+    /// The message was already known.
+    Known,
+    /// The message was rejected for the contained reason.
+    Invalid(String),
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Processed => write!(f, "processed"),
+            Self::Known => write!(f, "known"),
+            Self::Invalid(reason) => write!(f, "invalid: {}", reason),
+        }
+    }
+}
+
+/// An entry of the audit trail of processed messages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecord {
+    /// The identifier of the message, if it could be unpacked.
+    pub message_id: Option<MessageId>,
+    /// The peer the message was received from, or `None` if it was submitted locally.
+    pub source: Option<PeerId>,
+    /// The time of the outcome, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The outcome of the processing.
+    pub outcome: AuditOutcome,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(message_id: Option<MessageId>, source: Option<PeerId>, outcome: AuditOutcome) -> Self {
+        Self {
+            message_id,
+            source,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Clock may have gone backwards")
+                .as_millis() as u64,
+            outcome,
+        }
+    }
+}
+
+/// A destination for the audit trail of processed messages.
+///
+/// Records are buffered and handed over in batches, away from the message processing.
+#[async_trait]
+pub trait AuditSink: Send + Sync + 'static {
+    /// Writes a batch of records, in the order of their outcomes.
+    async fn write(&self, records: &[AuditRecord]) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// An `AuditSink` discarding all records.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopAuditSink;
+
+#[async_trait]
+impl AuditSink for NoopAuditSink {
+    async fn write(&self, _records: &[AuditRecord]) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+/// An `AuditSink` appending records as comma separated lines to a file.
+#[derive(Clone, Debug)]
+pub struct FileAuditSink {
+    path: PathBuf,
+}
+
+impl FileAuditSink {
+    /// Creates a new `FileAuditSink` appending to the given file, which is created if missing.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl AuditSink for FileAuditSink {
+    async fn write(&self, records: &[AuditRecord]) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut lines = String::new();
+
+        for record in records {
+            lines.push_str(&format!(
+                "{},{},{},{}\n",
+                record.timestamp,
+                record.message_id.map(|id| id.to_string()).unwrap_or_default(),
+                record.source.map(|id| id.to_string()).unwrap_or_default(),
+                record.outcome
+            ));
+        }
+
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+                .write_all(lines.as_bytes())
+        })
+        .await??;
+
+        Ok(())
+    }
+}
+
+/// The sending half of the audit trail, disabled unless an `AuditSink` is registered.
+///
+/// Records that don't fit into the queue in front of a slow sink are dropped, and counted, rather than holding up the
+/// message processing.
+#[derive(Clone, Default)]
+pub(crate) struct AuditTrail(Option<(mpsc::Sender<AuditRecord>, ResourceHandle<NodeMetrics>)>);
+
+impl AuditTrail {
+    pub(crate) fn new(tx: mpsc::Sender<AuditRecord>, metrics: ResourceHandle<NodeMetrics>) -> Self {
+        Self(Some((tx, metrics)))
+    }
+
+    // Only builds the record if the audit trail is enabled.
+    pub(crate) fn record<F: FnOnce() -> AuditRecord>(&self, f: F) {
+        if let Some((tx, metrics)) = &self.0 {
+            match tx.try_send(f()) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    metrics.audit_records_dropped_inc();
+                }
+                Err(TrySendError::Closed(_)) => warn!("Sending record to the audit sink failed."),
+            }
+        }
+    }
+}
+
+async fn write_batches<S: Stream<Item = AuditRecord> + Unpin>(sink: &dyn AuditSink, records: S) -> usize {
+    let mut batches = records.ready_chunks(AUDIT_BATCH_SIZE);
+    let mut counter = 0;
+
+    while let Some(batch) = batches.next().await {
+        counter += batch.len();
+        if let Err(e) = sink.write(&batch).await {
+            warn!("Writing {} records to the audit sink failed: {}.", batch.len(), e);
+        }
+    }
+
+    counter
+}
+
+/// A worker writing the audit trail of the outcomes of all processed messages to an `AuditSink`.
+pub struct AuditWorker {
+    pub(crate) trail: AuditTrail,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for AuditWorker {
+    type Config = Box<dyn AuditSink>;
+    type Error = Infallible;
+
+    async fn start(node: &mut N, sink: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::channel(AUDIT_QUEUE_CAPACITY);
+        let metrics = node.resource::<NodeMetrics>();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, ReceiverStream::new(rx));
+
+            write_batches(&*sink, &mut receiver).await;
+
+            // Before the worker completely stops, the receiver needs to be drained for the audit trail to be complete.
+
+            let (_, mut receiver) = receiver.split();
+            let mut records = Vec::new();
+
+            while let Some(Some(record)) = receiver.next().now_or_never() {
+                records.push(record);
+            }
+
+            debug!(
+                "Drained {} records.",
+                write_batches(&*sink, futures::stream::iter(records)).await
+            );
+
+            info!("Stopped.");
+        });
+
+        Ok(Self {
+            trail: AuditTrail::new(tx, metrics),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MemoryAuditSink(Arc<Mutex<Vec<AuditRecord>>>);
+
+    #[async_trait]
+    impl AuditSink for MemoryAuditSink {
+        async fn write(&self, records: &[AuditRecord]) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.0.lock().unwrap().extend_from_slice(records);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_sink_captures_outcomes_in_order() {
+        let (tx, rx) = mpsc::channel(AUDIT_QUEUE_CAPACITY);
+        let trail = AuditTrail::new(tx, ResourceHandle::new(NodeMetrics::new()));
+        let sink = MemoryAuditSink::default();
+
+        let message_id = MessageId::new([42; 32]);
+        let source = PeerId::random();
+
+        trail.record(|| {
+            AuditRecord::new(
+                None,
+                Some(source),
+                AuditOutcome::Invalid("Invalid message.".to_string()),
+            )
+        });
+        trail.record(|| AuditRecord::new(Some(message_id), Some(source), AuditOutcome::Processed));
+        trail.record(|| AuditRecord::new(Some(message_id), None, AuditOutcome::Known));
+        drop(trail);
+
+        assert_eq!(write_batches(&sink, ReceiverStream::new(rx)).await, 3);
+
+        let records = sink.0.lock().unwrap();
+        let outcomes = records
+            .iter()
+            .map(|record| (record.message_id, record.source, record.outcome.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                (
+                    None,
+                    Some(source),
+                    AuditOutcome::Invalid("Invalid message.".to_string())
+                ),
+                (Some(message_id), Some(source), AuditOutcome::Processed),
+                (Some(message_id), None, AuditOutcome::Known),
+            ]
+        );
+        assert!(records.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    }
+
+    #[test]
+    fn records_beyond_the_capacity_are_dropped() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let metrics = ResourceHandle::new(NodeMetrics::new());
+        let trail = AuditTrail::new(tx, metrics.clone());
+
+        for _ in 0..3 {
+            trail.record(|| AuditRecord::new(None, None, AuditOutcome::Known));
+        }
+
+        assert_eq!(rx.try_recv().map(|record| record.outcome), Ok(AuditOutcome::Known));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(metrics.audit_records_dropped(), 2);
+    }
+
+    #[test]
+    fn disabled_trail_builds_no_record() {
+        AuditTrail::default().record(|| unreachable!());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_gossip::PeerId;
+use bee_message::{Message, MessageId};
+use bee_runtime::{node::Node, worker::Worker};
+
+use async_trait::async_trait;
+use log::trace;
+use tokio::sync::mpsc;
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+/// The bucket messages are classified into by the `DefaultMessageClassifier`.
+pub const DEFAULT_MESSAGE_BUCKET: &str = "default";
+
+/// Classifies processed messages into buckets, e.g. by payload, size or source.
+pub trait MessageClassifier: Send + Sync + 'static {
+    /// Returns the label of the bucket of a message, received from `source` or submitted locally if `None`.
+    fn classify(&self, message: &Message, source: Option<&PeerId>) -> &'static str;
+}
+
+/// A `MessageClassifier` classifying all messages into the `DEFAULT_MESSAGE_BUCKET`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultMessageClassifier;
+
+impl MessageClassifier for DefaultMessageClassifier {
+    fn classify(&self, _message: &Message, _source: Option<&PeerId>) -> &'static str {
+        DEFAULT_MESSAGE_BUCKET
+    }
+}
+
+/// Routes the identifiers of processed messages to the handler registered for their bucket, if any.
+pub struct MessageRouter {
+    classifier: Box<dyn MessageClassifier>,
+    handlers: HashMap<&'static str, mpsc::UnboundedSender<MessageId>>,
+}
+
+impl Default for MessageRouter {
+    fn default() -> Self {
+        Self::new(DefaultMessageClassifier)
+    }
+}
+
+impl MessageRouter {
+    /// Creates a new `MessageRouter` using the given classifier, without any handlers.
+    pub fn new<C: MessageClassifier>(classifier: C) -> Self {
+        Self {
+            classifier: Box::new(classifier),
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Registers the handler receiving the identifiers of the messages of a bucket, replacing any previous one.
+    #[must_use]
+    pub fn with_handler(mut self, bucket: &'static str, handler: mpsc::UnboundedSender<MessageId>) -> Self {
+        self.handlers.insert(bucket, handler);
+        self
+    }
+
+    // Classifies a message, forwards its identifier to the handler of its bucket and returns the bucket.
+    pub(crate) fn route(&self, message_id: MessageId, message: &Message, source: Option<&PeerId>) -> &'static str {
+        let bucket = self.classifier.classify(message, source);
+
+        if let Some(handler) = self.handlers.get(bucket) {
+            if handler.send(message_id).is_err() {
+                trace!("Handler of bucket {} is gone.", bucket);
+            }
+        }
+
+        bucket
+    }
+}
+
+pub struct MessageRouterWorker {
+    pub(crate) router: Arc<MessageRouter>,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for MessageRouterWorker {
+    type Config = MessageRouter;
+    type Error = Infallible;
+
+    async fn start(_node: &mut N, router: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            router: Arc::new(router),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{
+        milestone::MilestoneIndex,
+        parents::Parents,
+        payload::{
+            milestone::{MilestonePayload, MilestonePayloadEssence, MILESTONE_MERKLE_PROOF_LENGTH},
+            Payload,
+        },
+        MessageBuilder,
+    };
+
+    struct MilestoneClassifier;
+
+    impl MessageClassifier for MilestoneClassifier {
+        fn classify(&self, message: &Message, _source: Option<&PeerId>) -> &'static str {
+            match message.payload() {
+                Some(Payload::Milestone(_)) => "milestones",
+                _ => "others",
+            }
+        }
+    }
+
+    fn message(payload: Option<Payload>) -> Message {
+        let mut builder = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32])]).unwrap());
+        if let Some(payload) = payload {
+            builder = builder.with_payload(payload);
+        }
+        builder.finish().unwrap()
+    }
+
+    fn milestone() -> Payload {
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(1),
+            0,
+            Parents::new(vec![MessageId::new([1; 32])]).unwrap(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            vec![[2; 32]],
+            None,
+        )
+        .unwrap();
+
+        MilestonePayload::new(essence, vec![[0; 64]]).unwrap().into()
+    }
+
+    #[test]
+    fn milestones_are_routed_apart() {
+        let (milestones_tx, mut milestones_rx) = mpsc::unbounded_channel();
+        let (others_tx, mut others_rx) = mpsc::unbounded_channel();
+        let router = MessageRouter::new(MilestoneClassifier)
+            .with_handler("milestones", milestones_tx)
+            .with_handler("others", others_tx);
+
+        let milestone = message(Some(milestone()));
+        let milestone_id = milestone.id().0;
+        let other = message(None);
+        let other_id = other.id().0;
+
+        assert_eq!(router.route(milestone_id, &milestone, None), "milestones");
+        assert_eq!(router.route(other_id, &other, Some(&PeerId::random())), "others");
+
+        assert_eq!(milestones_rx.try_recv().unwrap(), milestone_id);
+        assert!(milestones_rx.try_recv().is_err());
+        assert_eq!(others_rx.try_recv().unwrap(), other_id);
+        assert!(others_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn default_router_uses_a_single_bucket() {
+        let message = message(None);
+
+        assert_eq!(
+            MessageRouter::default().route(message.id().0, &message, None),
+            DEFAULT_MESSAGE_BUCKET
+        );
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_message::{milestone::MilestoneIndex, MessageId};
+
+use futures::channel::oneshot::{self, Receiver, Sender};
+use parking_lot::Mutex;
+
+use std::collections::HashMap;
+
+/// The submitters awaiting the confirmation of their messages by a milestone.
+#[derive(Default)]
+pub(crate) struct ConfirmationWaiters {
+    waiters: Mutex<HashMap<MessageId, Vec<Sender<MilestoneIndex>>>>,
+}
+
+impl ConfirmationWaiters {
+    /// Starts awaiting the confirmation of a message, which is received with the index of the confirming milestone.
+    pub(crate) fn wait(&self, message_id: MessageId) -> Receiver<MilestoneIndex> {
+        let (notifier, waiter) = oneshot::channel();
+
+        self.waiters.lock().entry(message_id).or_default().push(notifier);
+
+        waiter
+    }
+
+    /// Returns whether the confirmation of a message is awaited.
+    #[cfg(test)]
+    pub(crate) fn is_awaited(&self, message_id: &MessageId) -> bool {
+        self.waiters.lock().contains_key(message_id)
+    }
+
+    /// Notifies all the submitters awaiting the confirmation of a message.
+    pub(crate) fn confirm(&self, message_id: &MessageId, milestone_index: MilestoneIndex) {
+        if let Some(notifiers) = self.waiters.lock().remove(message_id) {
+            for notifier in notifiers {
+                // The submitter may have stopped waiting in the meantime.
+                let _ = notifier.send(milestone_index);
+            }
+        }
+    }
+
+    /// Forgets the submitters of a message that stopped awaiting its confirmation, e.g. because they timed out.
+    pub(crate) fn discard_canceled(&self, message_id: &MessageId) {
+        let mut waiters = self.waiters.lock();
+
+        if let Some(notifiers) = waiters.get_mut(message_id) {
+            notifiers.retain(|notifier| !notifier.is_canceled());
+
+            if notifiers.is_empty() {
+                waiters.remove(message_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_gossip::PeerId;
+use bee_message::MessageId;
+
+use parking_lot::Mutex;
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The decision of the processor on a message, i.e. its outcome and the branch taken to reach it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Decision {
+    /// The message was rejected for the contained reason.
+    Rejected(String),
+    /// The message was already known.
+    Known,
+    /// The message was new and had been requested, hence not broadcast.
+    Requested,
+    /// The message was new and broadcast.
+    Broadcast,
+    /// The message was new but not broadcast because broadcasting is disabled.
+    BroadcastDisabled,
+    /// The message was new but not broadcast for referencing an old milestone.
+    Stale,
+    /// The message was new but not broadcast because its PoW was not checked, as it was submitted by a trusted client.
+    PowSkipped,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rejected(reason) => write!(f, "rejected: {}", reason),
+            Self::Known => write!(f, "known"),
+            Self::Requested => write!(f, "requested"),
+            Self::Broadcast => write!(f, "broadcast"),
+            Self::BroadcastDisabled => write!(f, "broadcast disabled"),
+            Self::Stale => write!(f, "stale"),
+            Self::PowSkipped => write!(f, "pow skipped"),
+        }
+    }
+}
+
+/// An entry of the `DecisionLog`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecisionRecord {
+    /// The identifier of the message, if it could be unpacked.
+    pub message_id: Option<MessageId>,
+    /// The peer the message was received from, or `None` if it was submitted locally.
+    pub source: Option<PeerId>,
+    /// The time of the decision, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The decision of the processor.
+    pub decision: Decision,
+}
+
+impl DecisionRecord {
+    pub(crate) fn new(message_id: Option<MessageId>, source: Option<PeerId>, decision: Decision) -> Self {
+        Self {
+            message_id,
+            source,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Clock may have gone backwards")
+                .as_millis() as u64,
+            decision,
+        }
+    }
+}
+
+/// Keeps the decisions of the processor on the latest messages, for post-mortem analysis.
+///
+/// Recording is disabled unless the `DecisionLog` has a capacity.
+pub struct DecisionLog {
+    capacity: Option<usize>,
+    records: Mutex<VecDeque<DecisionRecord>>,
+}
+
+impl DecisionLog {
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity: capacity.filter(|capacity| *capacity > 0),
+            records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns whether decisions are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity.is_some()
+    }
+
+    /// Returns the recorded decisions, the oldest first.
+    pub fn records(&self) -> Vec<DecisionRecord> {
+        self.records.lock().iter().cloned().collect()
+    }
+
+    // Only builds the record if recording is enabled, evicting the oldest one if the log is full.
+    pub(crate) fn record<F: FnOnce() -> DecisionRecord>(&self, f: F) {
+        if let Some(capacity) = self.capacity {
+            let mut records = self.records.lock();
+
+            if records.len() == capacity {
+                records.pop_front();
+            }
+            records.push_back(f());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn record(log: &DecisionLog, byte: u8, decision: Decision) {
+        log.record(|| DecisionRecord::new(Some(MessageId::new([byte; 32])), None, decision));
+    }
+
+    #[test]
+    fn decisions_are_kept_in_order() {
+        let log = DecisionLog::new(Some(4));
+
+        record(
+            &log,
+            0,
+            Decision::Rejected("Incompatible network ID 2 != 1.".to_string()),
+        );
+        record(&log, 1, Decision::Broadcast);
+        record(&log, 2, Decision::Known);
+        record(&log, 3, Decision::Requested);
+
+        let decisions = log
+            .records()
+            .into_iter()
+            .map(|record| (record.message_id.unwrap(), record.decision.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            decisions,
+            vec![
+                (
+                    MessageId::new([0; 32]),
+                    "rejected: Incompatible network ID 2 != 1.".to_string()
+                ),
+                (MessageId::new([1; 32]), "broadcast".to_string()),
+                (MessageId::new([2; 32]), "known".to_string()),
+                (MessageId::new([3; 32]), "requested".to_string()),
+            ]
+        );
+
+        // Once full, the oldest decisions make room for the new ones.
+        record(&log, 4, Decision::Stale);
+        record(&log, 5, Decision::BroadcastDisabled);
+
+        let message_ids = log
+            .records()
+            .into_iter()
+            .map(|record| record.message_id.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            message_ids,
+            (2..6).map(|byte| MessageId::new([byte; 32])).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nothing_is_recorded_when_disabled() {
+        for log in [DecisionLog::new(None), DecisionLog::new(Some(0))] {
+            log.record(|| unreachable!());
+
+            assert!(!log.is_enabled());
+            assert!(log.records().is_empty());
+        }
+    }
+}
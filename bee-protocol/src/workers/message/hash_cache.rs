@@ -1,9 +1,11 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::workers::{config::MessageCachePolicy, timed_cache::TimedCache};
+
 use std::{
-    collections::{HashSet, VecDeque},
     hash::{BuildHasherDefault, Hasher},
+    time::{Duration, Instant},
 };
 
 use twox_hash::XxHash64;
@@ -41,41 +43,34 @@ impl Hasher for CustomHasher {
 }
 
 pub(crate) struct HashCache {
-    max_capacity: usize,
-    cache: HashSet<u64, BuildHasherDefault<CustomHasher>>,
-    elem_order: VecDeque<u64>,
+    policy: MessageCachePolicy,
+    cache: TimedCache<u64, (), BuildHasherDefault<CustomHasher>>,
 }
 
 impl HashCache {
-    pub fn new(max_capacity: usize) -> Self {
+    pub fn new(max_capacity: usize, ttl: Option<Duration>, policy: MessageCachePolicy) -> Self {
         Self {
-            max_capacity,
-            cache: Default::default(),
-            elem_order: Default::default(),
+            policy,
+            cache: TimedCache::new(max_capacity, ttl),
         }
     }
 
-    pub fn insert(&mut self, bytes: &[u8]) -> bool {
+    pub fn insert(&mut self, bytes: &[u8], now: Instant) -> bool {
         let hash = xx_hash(bytes);
 
-        if self.contains(hash) {
+        if self.cache.contains(&hash, now) {
             return false;
         }
 
-        if self.cache.len() >= self.max_capacity {
-            let first = self.elem_order.pop_front().unwrap();
-            self.cache.remove(&first);
+        if self.policy == MessageCachePolicy::Bypass && self.cache.is_full_at(now) {
+            return true;
         }
 
-        self.cache.insert(hash);
-        self.elem_order.push_back(hash);
-
-        true
+        self.cache.insert(hash, (), now)
     }
 
-    #[inline(always)]
-    fn contains(&self, hash: u64) -> bool {
-        self.cache.contains(&hash)
+    pub fn len(&self) -> usize {
+        self.cache.len()
     }
 }
 
@@ -93,38 +88,82 @@ mod tests {
 
     #[test]
     fn test_cache_insert_same_elements() {
-        let mut cache = HashCache::new(10);
+        let mut cache = HashCache::new(10, None, MessageCachePolicy::EvictOldest);
+        let now = Instant::now();
 
         let first_buf = &[1, 2, 3];
         let second_buf = &[1, 2, 3];
 
-        assert!(cache.insert(first_buf));
-        assert!(!cache.insert(second_buf));
-        assert_eq!(cache.cache.len(), 1);
+        assert!(cache.insert(first_buf, now));
+        assert!(!cache.insert(second_buf, now));
+        assert_eq!(cache.len(), 1);
     }
 
     #[test]
     fn test_cache_insert_different_elements() {
-        let mut cache = HashCache::new(10);
+        let mut cache = HashCache::new(10, None, MessageCachePolicy::EvictOldest);
+        let now = Instant::now();
 
         let first_buf = &[1, 2, 3];
         let second_buf = &[3, 4, 5];
 
-        assert!(cache.insert(first_buf));
-        assert!(cache.insert(second_buf));
-        assert_eq!(cache.cache.len(), 2);
+        assert!(cache.insert(first_buf, now));
+        assert!(cache.insert(second_buf, now));
+        assert_eq!(cache.len(), 2);
     }
 
     #[test]
     fn test_cache_max_capacity() {
-        let mut cache = HashCache::new(1);
+        let mut cache = HashCache::new(1, None, MessageCachePolicy::EvictOldest);
+        let now = Instant::now();
 
         let first_buf = &[1, 2, 3];
         let second_buf = &[3, 4, 5];
 
-        assert!(cache.insert(first_buf));
-        assert!(cache.insert(second_buf));
-        assert_eq!(cache.cache.len(), 1);
-        assert!(!cache.insert(second_buf));
+        assert!(cache.insert(first_buf, now));
+        assert!(cache.insert(second_buf, now));
+        assert_eq!(cache.len(), 1);
+        assert!(!cache.insert(second_buf, now));
+    }
+
+    #[test]
+    fn test_cache_evict_oldest_at_capacity() {
+        let mut cache = HashCache::new(3, None, MessageCachePolicy::EvictOldest);
+        let now = Instant::now();
+
+        for i in 0..10u8 {
+            assert!(cache.insert(&[i, i, i], now));
+            assert!(cache.len() <= 3);
+        }
+        assert_eq!(cache.len(), 3);
+        // The newest entries are kept, the oldest ones evicted.
+        assert!(!cache.insert(&[9, 9, 9], now));
+        assert!(cache.insert(&[0, 0, 0], now));
+    }
+
+    #[test]
+    fn test_cache_bypass_at_capacity() {
+        let mut cache = HashCache::new(3, None, MessageCachePolicy::Bypass);
+        let now = Instant::now();
+
+        for i in 0..10u8 {
+            assert!(cache.insert(&[i, i, i], now));
+        }
+        assert_eq!(cache.len(), 3);
+        // The first entries are kept, the new ones pass without being deduplicated.
+        assert!(!cache.insert(&[0, 0, 0], now));
+        assert!(cache.insert(&[9, 9, 9], now));
+        assert!(cache.insert(&[9, 9, 9], now));
+    }
+
+    #[test]
+    fn test_cache_entries_expire() {
+        let mut cache = HashCache::new(10, Some(Duration::from_secs(60)), MessageCachePolicy::EvictOldest);
+        let now = Instant::now();
+
+        assert!(cache.insert(&[1, 2, 3], now));
+        assert!(!cache.insert(&[1, 2, 3], now + Duration::from_secs(59)));
+        assert!(cache.insert(&[1, 2, 3], now + Duration::from_secs(60)));
+        assert_eq!(cache.len(), 1);
     }
 }
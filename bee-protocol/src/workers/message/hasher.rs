@@ -5,10 +5,17 @@ use crate::{
     types::metrics::NodeMetrics,
     workers::{
         config::ProtocolConfig,
+        event::MpsMetricsUpdated,
         message::{
-            submitter::notify_invalid_message, HashCache, MessageSubmitterError, ProcessorWorker, ProcessorWorkerEvent,
+            pow_score_policy,
+            processor::{admit, measure},
+            rate_limiter::PeerRateLimiter,
+            submitter::{notify_invalid_message, notify_known_message},
+            HashCache, MessagePriority, MessageSubmitterError, PowContext, PowScorePolicy, ProcessorWorker,
+            ProcessorWorkerEvent, SubmitOutcome,
         },
         packets::MessagePacket,
+        peer::MessageOutcome,
         storage::StorageBackend,
         MetricsWorker, PeerManager, PeerManagerResWorker,
     },
@@ -20,21 +27,112 @@ use bee_pow::score;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 
 use async_trait::async_trait;
+use crypto::hashes::{blake2b::Blake2b256, Digest};
 use futures::{channel::oneshot::Sender, StreamExt};
-use log::{error, info, trace, warn};
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use log::{info, trace, warn};
 
-use std::{any::TypeId, convert::Infallible};
+use std::{
+    any::TypeId,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+// The message id is the hash of the message bytes. Unpacking the message afterwards makes sure that the bytes are the
+// exact packed message, without trailing bytes.
+pub(crate) fn message_id(bytes: &[u8]) -> MessageId {
+    MessageId::new(Blake2b256::digest(bytes).into())
+}
+
+// Rejects messages longer than `max_length` bytes, which is cheap enough to be done before any other check.
+pub(crate) fn check_length(bytes: &[u8], max_length: usize) -> Result<(), MessageSubmitterError> {
+    if bytes.len() > max_length {
+        Err(MessageSubmitterError::TooLong {
+            length: bytes.len(),
+            maximum: max_length,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+// Rejects messages whose PoW score the policy doesn't accept, recording the time scoring took if `processing_time`.
+pub(crate) fn check_pow(
+    pow: &mut score::PoWScorer,
+    policy: &dyn PowScorePolicy,
+    pow_ctx: &PowContext,
+    bytes: &[u8],
+    metrics: &NodeMetrics,
+    processing_time: bool,
+) -> Result<(), MessageSubmitterError> {
+    let pow_score = measure(
+        processing_time,
+        |time| metrics.pow_check_time_observe(time),
+        || pow.score(bytes),
+    );
+
+    if policy.accept(pow_score, pow_ctx) {
+        Ok(())
+    } else {
+        Err(MessageSubmitterError::InsufficientPowScore {
+            score: pow_score,
+            minimum: policy.minimum_score(pow_ctx),
+        })
+    }
+}
+
+// What becomes of a received message before its PoW is checked.
+#[derive(Debug, Eq, PartialEq)]
+enum Admission {
+    // The peer the message was received from exceeded its rate limit.
+    RateLimited,
+    // The message was already received.
+    Known,
+    New,
+}
+
+// Drops the messages of peers exceeding their rate limit before they are cached, so that a flooding peer can't get a
+// message taken for a known one when other peers send it. Other messages are recorded in the cache.
+fn admission(
+    cache: &mut HashCache,
+    rate_limiter: &mut PeerRateLimiter,
+    from: Option<&PeerId>,
+    bytes: &[u8],
+    now: Instant,
+) -> Admission {
+    if let Some(peer_id) = from {
+        if !rate_limiter.allow(peer_id, now) {
+            return Admission::RateLimited;
+        }
+    }
+
+    if cache.insert(bytes, now) {
+        Admission::New
+    } else {
+        Admission::Known
+    }
+}
 
 pub(crate) struct HasherWorkerEvent {
     pub(crate) from: Option<PeerId>,
     pub(crate) message_packet: MessagePacket,
-    pub(crate) notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
+    pub(crate) notifier: Option<Sender<SubmitOutcome>>,
+    pub(crate) priority: MessagePriority,
+    // Whether the message was submitted by a trusted local client.
+    pub(crate) trusted: bool,
+}
+
+// Returns whether the PoW of a message has to be checked. It always has for messages received from peers, and for
+// locally submitted ones unless they are trusted.
+pub(crate) fn requires_pow(from: Option<&PeerId>, trusted: bool) -> bool {
+    from.is_some() || !trusted
 }
 
 pub(crate) struct HasherWorker {
-    pub(crate) tx: mpsc::UnboundedSender<HasherWorkerEvent>,
+    pub(crate) tx: async_channel::Sender<HasherWorkerEvent>,
 }
 
 #[async_trait]
@@ -55,17 +153,49 @@ where
     }
 
     async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        // Bounded like the processor queue, so that messages can't pile up in front of it.
+        let (tx, rx) = async_channel::bounded(config.workers.processor_queue_capacity.max(1));
         let processor_worker = node.worker::<ProcessorWorker>().unwrap().tx.clone();
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
 
-        let minimum_pow_score = config.minimum_pow_score;
+        let pow_policy = pow_score_policy(&config);
+        let processor_queue_policy = config.workers.processor_queue_policy;
+        let processing_time = config.workers.processing_time;
+        let max_message_length = config.workers.max_message_length;
 
-        let mut cache = HashCache::new(config.workers.message_worker_cache);
+        // The incoming message rate as last measured.
+        let incoming_mps = Arc::new(AtomicU64::new(0));
+        {
+            let incoming_mps = incoming_mps.clone();
+            node.bus().add_listener::<Self, MpsMetricsUpdated, _>(move |event| {
+                incoming_mps.store(event.incoming, Ordering::Relaxed);
+            });
+        }
+
+        let mut cache = HashCache::new(
+            config.workers.message_worker_cache,
+            config.workers.message_worker_cache_ttl.map(Duration::from_secs),
+            config.workers.message_worker_cache_policy,
+        );
+        let mut rate_limiter = PeerRateLimiter::new(
+            config.workers.peer_rate_limit,
+            config
+                .workers
+                .peer_rate_limits
+                .iter()
+                .filter_map(|(peer_id, rate_limit)| match peer_id.parse::<PeerId>() {
+                    Ok(peer_id) => Some((peer_id, *rate_limit)),
+                    Err(e) => {
+                        warn!("Ignoring rate limit of invalid peer id {}: {}.", peer_id, e);
+                        None
+                    }
+                })
+                .collect(),
+        );
 
         node.spawn::<Self, _, _>(|shutdown| async move {
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let mut receiver = ShutdownStream::new(shutdown, rx);
             let mut pow = score::PoWScorer::new();
 
             info!("Running.");
@@ -74,45 +204,91 @@ where
                 from,
                 message_packet,
                 notifier,
+                priority,
+                trusted,
             }) = receiver.next().await
             {
-                if !cache.insert(&message_packet.bytes) {
-                    // If the message was already received, we skip it and poll again.
-                    trace!("Message already received.");
+                if let Err(error) = check_length(&message_packet.bytes, max_message_length) {
+                    notify_invalid_message(error, &metrics, &peer_manager, from.as_ref(), notifier);
+                    continue;
+                }
+
+                // Messages are dropped before being cached, so that they are not taken for known ones if received
+                // again.
+                if !admit(&processor_worker, processor_queue_policy, &metrics) {
+                    trace!("Dropping message, processor queue full.");
+                    continue;
+                }
+
+                let admission = admission(
+                    &mut cache,
+                    &mut rate_limiter,
+                    from.as_ref(),
+                    &message_packet.bytes,
+                    Instant::now(),
+                );
+                metrics.message_cache_size_set(cache.len() as u64);
 
-                    if let Some(notifier) = notifier {
-                        if let Err(e) =
-                            notifier.send(Err(MessageSubmitterError("message already received".to_string())))
-                        {
-                            error!("failed to send error: {:?}.", e);
+                if admission == Admission::RateLimited {
+                    // Messages of peers exceeding their rate limit are dropped before any work is done on them.
+                    if let Some(ref peer_id) = from {
+                        trace!("Dropping message of rate limited peer {}.", peer_id);
+                        if let Some(ref peer) = peer_manager.get(peer_id) {
+                            peer.0.metrics().rate_limited_messages_inc();
                         }
                     }
+                    metrics.rate_limited_messages_inc();
+                    continue;
+                }
+
+                if admission == Admission::Known {
+                    // If the message was already received, we skip it and poll again.
+                    trace!("Message already received.");
+                    metrics.message_cache_hits_inc();
+
+                    notify_known_message(notifier);
 
                     metrics.known_messages_inc();
                     if let Some(peer_id) = from {
                         if let Some(ref peer) = peer_manager.get(&peer_id) {
                             peer.0.metrics().known_messages_inc();
                         }
+                        peer_manager.record_outcome(&peer_id, MessageOutcome::Known);
                     }
                     continue;
                 }
 
-                let pow_score = pow.score(&message_packet.bytes);
+                let pow_skipped = !requires_pow(from.as_ref(), trusted);
 
-                if pow_score < minimum_pow_score {
-                    notify_invalid_message(
-                        format!("Insufficient pow score: {} < {}.", pow_score, minimum_pow_score),
+                if !pow_skipped {
+                    let pow_ctx = PowContext {
+                        incoming_mps: incoming_mps.load(Ordering::Relaxed),
+                    };
+
+                    if let Err(error) = check_pow(
+                        &mut pow,
+                        &*pow_policy,
+                        &pow_ctx,
+                        &message_packet.bytes,
                         &metrics,
-                        notifier,
-                    );
-                    continue;
+                        processing_time,
+                    ) {
+                        notify_invalid_message(error, &metrics, &peer_manager, from.as_ref(), notifier);
+                        continue;
+                    }
                 }
 
-                if let Err(e) = processor_worker.send(ProcessorWorkerEvent {
-                    from,
-                    message_packet,
-                    notifier,
-                }) {
+                if let Err(e) = processor_worker
+                    .send(ProcessorWorkerEvent {
+                        message_id: message_id(&message_packet.bytes),
+                        from,
+                        message_packet,
+                        notifier,
+                        priority,
+                        pow_skipped,
+                    })
+                    .await
+                {
                     warn!("Sending event to the processor worker failed: {}.", e);
                 }
             }
@@ -123,3 +299,107 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_common::packable::Packable;
+    use bee_message::{parents::Parents, MessageBuilder};
+
+    #[test]
+    fn message_id_matches_packed_message() {
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([0; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+
+        assert_eq!(message_id(&message.pack_new()), message.id().0);
+    }
+
+    #[test]
+    fn oversized_messages_are_rejected() {
+        use crate::types::peer::Peer;
+
+        use bee_gossip::{PeerInfo, PeerRelation};
+
+        let metrics = NodeMetrics::default();
+        let peer_manager = PeerManager::new();
+        let peer_id = PeerId::random();
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+
+        assert!(check_length(&[0; 100], 100).is_ok());
+
+        let error = check_length(&[0; 101], 100).unwrap_err();
+        assert!(matches!(
+            error,
+            MessageSubmitterError::TooLong {
+                length: 101,
+                maximum: 100
+            }
+        ));
+
+        notify_invalid_message(error, &metrics, &peer_manager, Some(&peer_id), None);
+
+        let peer = peer_manager.get(&peer_id).unwrap();
+        assert_eq!(metrics.oversized_messages(), 1);
+        assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(peer.0.metrics().oversized_messages(), 1);
+        // The peer is penalized like for any other invalid message.
+        assert!(peer.0.reputation() < 0);
+    }
+
+    #[test]
+    fn messages_dropped_for_a_flooding_peer_are_not_cached() {
+        use crate::workers::config::MessageCachePolicy;
+
+        let mut cache = HashCache::new(100, None, MessageCachePolicy::default());
+        let flooding = PeerId::random();
+        let other = PeerId::random();
+        let mut rate_limiter = PeerRateLimiter::new(None, [(flooding, 1)].into_iter().collect());
+        let now = Instant::now();
+
+        assert_eq!(
+            admission(&mut cache, &mut rate_limiter, Some(&flooding), &[1], now),
+            Admission::New
+        );
+        assert_eq!(
+            admission(&mut cache, &mut rate_limiter, Some(&flooding), &[2], now),
+            Admission::RateLimited
+        );
+        // The message dropped for the flooding peer is still processed when another peer sends it.
+        assert_eq!(
+            admission(&mut cache, &mut rate_limiter, Some(&other), &[2], now),
+            Admission::New
+        );
+        assert_eq!(
+            admission(&mut cache, &mut rate_limiter, Some(&other), &[2], now),
+            Admission::Known
+        );
+        assert_eq!(
+            admission(&mut cache, &mut rate_limiter, None, &[3], now),
+            Admission::New
+        );
+    }
+
+    #[test]
+    fn pow_is_only_skipped_for_trusted_local_submissions() {
+        let peer_id = PeerId::random();
+
+        assert!(!requires_pow(None, true));
+        assert!(requires_pow(None, false));
+        // Messages of peers are never trusted.
+        assert!(requires_pow(Some(&peer_id), true));
+        assert!(requires_pow(Some(&peer_id), false));
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workers::{
+    message::validator::indexation, storage::StorageBackend, IndexationPayloadWorker, IndexationPayloadWorkerEvent,
+    PropagatorWorker, PropagatorWorkerEvent,
+};
+
+use bee_message::{Message, MessageId};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_tangle::{metadata::MessageMetadata, Tangle, TangleWorker};
+
+use async_trait::async_trait;
+use futures::{future::FutureExt, stream::StreamExt};
+use log::{debug, error, info};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use std::{any::TypeId, convert::Infallible};
+
+/// A message of a snapshot to import.
+pub struct MessageImporterWorkerEvent(pub Message);
+
+/// Imports the messages of a snapshot into the tangle.
+pub struct MessageImporterWorker {
+    pub tx: mpsc::UnboundedSender<MessageImporterWorkerEvent>,
+}
+
+// Inserts a message of a snapshot into the tangle, marked as such, and hands it to the propagator for its
+// solidification bookkeeping and to the indexation payload worker if it carries one. Unlike received messages, its PoW
+// is not checked, it is not broadcast and its missing parents are not requested. Returns the id of the message if it
+// was new.
+pub(crate) async fn import<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    propagator: &mpsc::UnboundedSender<PropagatorWorkerEvent>,
+    indexation_payload_worker: &mpsc::UnboundedSender<IndexationPayloadWorkerEvent>,
+    message: Message,
+) -> Option<MessageId> {
+    let (message_id, _) = message.id();
+    let metadata = MessageMetadata::imported();
+    let arrival_timestamp = metadata.arrival_timestamp();
+
+    let message = tangle.insert(message, message_id, metadata).await?;
+
+    if let Err(e) = propagator.send(PropagatorWorkerEvent(message_id)) {
+        error!("Failed to send message id {} to propagator: {:?}.", message_id, e);
+    }
+
+    if indexation(&message).is_some()
+        && indexation_payload_worker
+            .send(IndexationPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            })
+            .is_err()
+    {
+        error!("Sending message {} to indexation payload worker failed.", message_id);
+    }
+
+    Some(message_id)
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for MessageImporterWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ();
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<PropagatorWorker>(),
+            TypeId::of::<IndexationPayloadWorker>(),
+        ]
+        .leak()
+    }
+
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let propagator = node.worker::<PropagatorWorker>().unwrap().tx.clone();
+        let indexation_payload_worker = node.worker::<IndexationPayloadWorker>().unwrap().tx.clone();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let mut count: usize = 0;
+
+            while let Some(MessageImporterWorkerEvent(message)) = receiver.next().await {
+                if import(&tangle, &propagator, &indexation_payload_worker, message)
+                    .await
+                    .is_some()
+                {
+                    count += 1;
+                }
+            }
+
+            // Before the worker completely stops, the receiver needs to be drained for the snapshot to be fully
+            // imported.
+
+            let (_, mut receiver) = receiver.split();
+
+            while let Some(Some(MessageImporterWorkerEvent(message))) = receiver.next().now_or_never() {
+                if import(&tangle, &propagator, &indexation_payload_worker, message)
+                    .await
+                    .is_some()
+                {
+                    count += 1;
+                }
+            }
+
+            debug!("Imported {} messages.", count);
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{
+        parents::Parents,
+        payload::{indexation::IndexationPayload, Payload},
+        MessageBuilder,
+    };
+    use bee_runtime::resource::ResourceHandle;
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::config::TangleConfigBuilder;
+
+    fn message(parent: MessageId) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![parent]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    fn indexation_message(parent: MessageId) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![parent]).unwrap())
+            .with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(b"snapshot", &[]).unwrap(),
+            )))
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn imported_messages_bypass_gossip() {
+        let tangle = Tangle::new(
+            TangleConfigBuilder::new().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        let (propagator, mut propagated) = mpsc::unbounded_channel();
+        let (indexation_payload_worker, mut indexed) = mpsc::unbounded_channel();
+
+        // A chain of messages whose first parent is unknown.
+        let mut parent = MessageId::new([1; 32]);
+        let mut imported = Vec::new();
+
+        for _ in 0..3 {
+            let message_id = import(&tangle, &propagator, &indexation_payload_worker, message(parent))
+                .await
+                .unwrap();
+            imported.push(message_id);
+            parent = message_id;
+        }
+
+        // Importing a message twice has no effect.
+        assert!(import(
+            &tangle,
+            &propagator,
+            &indexation_payload_worker,
+            message(MessageId::new([1; 32]))
+        )
+        .await
+        .is_none());
+
+        for message_id in &imported {
+            let metadata = tangle.get_metadata(message_id).await.unwrap();
+
+            assert!(metadata.flags().is_from_snapshot());
+            assert!(!metadata.flags().was_requested());
+            assert!(!metadata.flags().is_solid());
+            assert_eq!(propagated.try_recv().unwrap().0, *message_id);
+        }
+        // Nothing but the propagation of the imported messages was triggered, in particular no broadcast nor request.
+        assert!(propagated.try_recv().is_err());
+        assert!(indexed.try_recv().is_err());
+        assert!(!tangle.contains(&MessageId::new([1; 32])).await);
+    }
+
+    #[tokio::test]
+    async fn imported_indexation_messages_are_indexed() {
+        let tangle = Tangle::new(
+            TangleConfigBuilder::new().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        let (propagator, _propagated) = mpsc::unbounded_channel();
+        let (indexation_payload_worker, mut indexed) = mpsc::unbounded_channel();
+
+        let message_id = import(
+            &tangle,
+            &propagator,
+            &indexation_payload_worker,
+            indexation_message(MessageId::new([1; 32])),
+        )
+        .await
+        .unwrap();
+
+        let event = indexed.try_recv().unwrap();
+        assert_eq!(event.message_id, message_id);
+        assert_eq!(
+            event.arrival_timestamp,
+            tangle.get_metadata(&message_id).await.unwrap().arrival_timestamp()
+        );
+    }
+}
@@ -1,19 +1,43 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod audit;
+mod classifier;
+mod confirmation;
+mod decision;
 mod hash_cache;
 mod hasher;
+mod importer;
 mod payload;
+mod pow_policy;
 mod processor;
+mod rate_limiter;
+mod reattachment;
+mod sampler;
 mod submitter;
 mod unreferenced_inserter;
+mod validator;
 
+pub use audit::{AuditOutcome, AuditRecord, AuditSink, AuditWorker, FileAuditSink, NoopAuditSink};
+pub use classifier::{
+    DefaultMessageClassifier, MessageClassifier, MessageRouter, MessageRouterWorker, DEFAULT_MESSAGE_BUCKET,
+};
+pub(crate) use confirmation::ConfirmationWaiters;
+pub use decision::{Decision, DecisionLog, DecisionRecord};
 pub(crate) use hash_cache::HashCache;
 pub(crate) use hasher::{HasherWorker, HasherWorkerEvent};
+pub use importer::{MessageImporterWorker, MessageImporterWorkerEvent};
 pub(crate) use payload::{
     IndexationPayloadWorker, IndexationPayloadWorkerEvent, MilestonePayloadWorker, PayloadWorker, PayloadWorkerEvent,
     TransactionPayloadWorker,
 };
-pub(crate) use processor::{ProcessorWorker, ProcessorWorkerEvent};
-pub use submitter::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent};
+pub use payload::{PayloadHandler, PayloadHandlerWorker, PayloadHandlers};
+pub(crate) use pow_policy::pow_score_policy;
+pub use pow_policy::{AdaptivePowScorePolicy, PowContext, PowScorePolicy, StaticPowScorePolicy};
+pub(crate) use processor::{admit, MessagePriority, ProcessorWorker, ProcessorWorkerEvent};
+pub(crate) use sampler::LogSampler;
+pub use submitter::{
+    ConfirmationOutcome, MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent, SubmitOutcome,
+};
 pub(crate) use unreferenced_inserter::{UnreferencedMessageInserterWorker, UnreferencedMessageInserterWorkerEvent};
+pub use validator::{MessageValidator, MessageValidatorWorker, MessageValidators};
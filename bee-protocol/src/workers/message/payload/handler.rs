@@ -0,0 +1,103 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_message::{Message, MessageId};
+use bee_runtime::{node::Node, worker::Worker};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+/// An asynchronous handler of the messages carrying a given payload kind.
+pub type PayloadHandler = Box<dyn for<'a> Fn(&'a Message, MessageId) -> BoxFuture<'a, ()> + Send + Sync>;
+
+/// Maps payload kinds not processed by the node itself to the handlers of the messages carrying them.
+#[derive(Default)]
+pub struct PayloadHandlers {
+    handlers: HashMap<u32, PayloadHandler>,
+}
+
+impl PayloadHandlers {
+    /// Creates a new `PayloadHandlers` without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler of the messages carrying a payload kind, replacing any previous one.
+    ///
+    /// Transaction, milestone and indexation payloads are always processed by the node and never reach a handler.
+    #[must_use]
+    pub fn with_handler<F>(mut self, kind: u32, handler: F) -> Self
+    where
+        F: for<'a> Fn(&'a Message, MessageId) -> BoxFuture<'a, ()> + Send + Sync + 'static,
+    {
+        self.handlers.insert(kind, Box::new(handler));
+        self
+    }
+
+    // Invokes the handler of the payload kind, if any, and returns whether there was one.
+    pub(crate) async fn handle(&self, kind: u32, message: &Message, message_id: MessageId) -> bool {
+        match self.handlers.get(&kind) {
+            Some(handler) => {
+                handler(message, message_id).await;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct PayloadHandlerWorker {
+    pub(crate) handlers: Arc<PayloadHandlers>,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for PayloadHandlerWorker {
+    type Config = PayloadHandlers;
+    type Error = Infallible;
+
+    async fn start(_node: &mut N, handlers: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            handlers: Arc::new(handlers),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{parents::Parents, payload::receipt::ReceiptPayload, MessageBuilder};
+    use futures::future::FutureExt;
+    use parking_lot::Mutex;
+
+    #[tokio::test]
+    async fn registered_handler_is_called() {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let handlers = PayloadHandlers::new().with_handler(ReceiptPayload::KIND, {
+            let handled = handled.clone();
+            move |message: &Message, message_id| {
+                let handled = handled.clone();
+                async move {
+                    handled.lock().push((message_id, message.network_id()));
+                }
+                .boxed()
+            }
+        });
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(42)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+        let message_id = message.id().0;
+
+        assert!(handlers.handle(ReceiptPayload::KIND, &message, message_id).await);
+        // Kinds without a handler are ignored.
+        assert!(!handlers.handle(ReceiptPayload::KIND + 1, &message, message_id).await);
+
+        assert_eq!(*handled.lock(), vec![(message_id, 42)]);
+    }
+}
@@ -12,7 +12,7 @@ use bee_message::{
 };
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_storage::access::Insert;
-use bee_tangle::MessageRef;
+use bee_tangle::{indexed_message::IndexedMessage, MessageRef};
 
 use async_trait::async_trait;
 use futures::{future::FutureExt, stream::StreamExt};
@@ -25,13 +25,20 @@ use std::{any::TypeId, convert::Infallible};
 pub(crate) struct IndexationPayloadWorkerEvent {
     pub(crate) message_id: MessageId,
     pub(crate) message: MessageRef,
+    pub(crate) arrival_timestamp: u64,
 }
 
 pub(crate) struct IndexationPayloadWorker {
     pub(crate) tx: mpsc::UnboundedSender<IndexationPayloadWorkerEvent>,
 }
 
-fn process<B: StorageBackend>(storage: &B, metrics: &NodeMetrics, message_id: MessageId, message: MessageRef) {
+fn process<B: StorageBackend>(
+    storage: &B,
+    metrics: &NodeMetrics,
+    message_id: MessageId,
+    message: MessageRef,
+    arrival_timestamp: u64,
+) {
     let indexation = match message.payload() {
         Some(Payload::Indexation(indexation)) => indexation,
         Some(Payload::Transaction(transaction)) => {
@@ -58,14 +65,23 @@ fn process<B: StorageBackend>(storage: &B, metrics: &NodeMetrics, message_id: Me
 
     metrics.indexation_payload_inc(1);
 
-    if let Err(e) =
-        Insert::<(PaddedIndex, MessageId), ()>::insert(&*storage, &(indexation.padded_index(), message_id), &())
-    {
+    let padded_index = indexation.padded_index();
+
+    if let Err(e) = Insert::<(PaddedIndex, MessageId), ()>::insert(storage, &(padded_index, message_id), &()) {
         error!(
             "Inserting indexation payload for message {} failed: {:?}.",
             message_id, e
         );
     }
+
+    // Also indexed by arrival, for the messages of an index to be paged in the order they arrived.
+    if let Err(e) = Insert::<(PaddedIndex, IndexedMessage), ()>::insert(
+        storage,
+        &(padded_index, IndexedMessage::new(arrival_timestamp, message_id)),
+        &(),
+    ) {
+        error!("Inserting indexed message {} failed: {:?}.", message_id, e);
+    }
 }
 
 #[async_trait]
@@ -91,8 +107,13 @@ where
 
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
-            while let Some(IndexationPayloadWorkerEvent { message_id, message }) = receiver.next().await {
-                process(&*storage, &metrics, message_id, message);
+            while let Some(IndexationPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            }) = receiver.next().await
+            {
+                process(&*storage, &metrics, message_id, message, arrival_timestamp);
             }
 
             // Before the worker completely stops, the receiver needs to be drained for indexation payloads to be
@@ -101,9 +122,13 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(IndexationPayloadWorkerEvent { message_id, message })) = receiver.next().now_or_never()
+            while let Some(Some(IndexationPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            })) = receiver.next().now_or_never()
             {
-                process(&*storage, &metrics, message_id, message);
+                process(&*storage, &metrics, message_id, message, arrival_timestamp);
                 count += 1;
             }
 
@@ -4,12 +4,13 @@
 use crate::{
     types::{metrics::NodeMetrics, milestone_key_manager::MilestoneKeyManager},
     workers::{
-        config::ProtocolConfig, heartbeater::broadcast_heartbeat, peer::PeerManager, storage::StorageBackend,
-        MetricsWorker, MilestoneRequesterWorker, MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent,
-        PeerManagerResWorker, RequestedMilestones,
+        config::ProtocolConfig, event::InvalidMilestoneReceived, heartbeater::broadcast_heartbeat, peer::PeerManager,
+        storage::StorageBackend, MetricsWorker, MilestoneRequesterWorker, MilestoneSolidifierWorker,
+        MilestoneSolidifierWorkerEvent, PeerManagerResWorker, RequestedMilestones,
     },
 };
 
+use bee_gossip::PeerId;
 use bee_message::{
     milestone::Milestone,
     payload::{
@@ -21,13 +22,13 @@ use bee_message::{
 use bee_runtime::{event::Bus, node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{event::LatestMilestoneChanged, MessageRef, Tangle, TangleWorker};
 
+use async_channel::TrySendError;
 use async_trait::async_trait;
 use futures::{future::FutureExt, stream::StreamExt};
 use log::{debug, error, info};
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use std::{any::TypeId, convert::Infallible};
+use std::{any::TypeId, collections::VecDeque, convert::Infallible};
 
 #[derive(Debug)]
 pub(crate) enum Error {
@@ -38,10 +39,117 @@ pub(crate) enum Error {
 pub(crate) struct MilestonePayloadWorkerEvent {
     pub(crate) message_id: MessageId,
     pub(crate) message: MessageRef,
+    pub(crate) from: Option<PeerId>,
 }
 
 pub(crate) struct MilestonePayloadWorker {
-    pub(crate) tx: mpsc::UnboundedSender<MilestonePayloadWorkerEvent>,
+    pub(crate) tx: async_channel::Sender<MilestonePayloadWorkerEvent>,
+}
+
+// Returns whether a milestone message can be told invalid without validating the signatures of its payload.
+pub(crate) fn looks_invalid(message: &Message) -> bool {
+    match message.payload() {
+        Some(Payload::Milestone(milestone)) => !message.parents().eq(milestone.essence().parents()),
+        _ => true,
+    }
+}
+
+// Forwards events to the milestone payload worker. Events not fitting in its queue are held back in a bounded retry
+// queue, so that a slow validation does not hold up the other payloads, and only once the retry queue is full too
+// does forwarding wait for room.
+pub(crate) struct MilestoneForwarder<T> {
+    tx: async_channel::Sender<T>,
+    retries: VecDeque<T>,
+    retry_capacity: usize,
+}
+
+impl<T> MilestoneForwarder<T> {
+    pub(crate) fn new(tx: async_channel::Sender<T>, retry_capacity: usize) -> Self {
+        Self {
+            tx,
+            retries: VecDeque::new(),
+            retry_capacity,
+        }
+    }
+
+    // Returns whether events are held back to be retried.
+    pub(crate) fn has_retries(&self) -> bool {
+        !self.retries.is_empty()
+    }
+
+    // Forwards the held back events, in order, as long as there is room in the queue.
+    pub(crate) fn retry(&mut self, metrics: &NodeMetrics) {
+        while let Some(event) = self.retries.pop_front() {
+            match self.tx.try_send(event) {
+                Ok(()) => {}
+                Err(TrySendError::Full(event)) => {
+                    self.retries.push_front(event);
+                    break;
+                }
+                Err(TrySendError::Closed(_)) => {
+                    error!("Forwarding {} milestone payloads failed.", self.retries.len() + 1);
+                    self.retries.clear();
+                }
+            }
+        }
+
+        metrics.milestone_queue_depth_set(self.tx.len() as u64);
+    }
+
+    // Forwards an event after the held back ones. If the queue is full, holds it back unless `drop_if_full` is set, in
+    // which case the event is dropped. Returns whether the event was forwarded or held back.
+    pub(crate) async fn forward(&mut self, event: T, drop_if_full: bool, metrics: &NodeMetrics) -> bool {
+        self.retry(metrics);
+
+        let event = if self.retries.is_empty() {
+            match self.tx.try_send(event) {
+                Ok(()) => {
+                    metrics.milestone_queue_depth_set(self.tx.len() as u64);
+                    return true;
+                }
+                Err(TrySendError::Full(event)) => event,
+                Err(TrySendError::Closed(_)) => return false,
+            }
+        } else {
+            event
+        };
+
+        if drop_if_full {
+            metrics.milestone_payloads_dropped_inc();
+            return false;
+        }
+
+        // Back-pressure: waits for room for the oldest held back event, if any, or this one.
+        if self.retries.len() >= self.retry_capacity {
+            let oldest = match self.retries.pop_front() {
+                Some(oldest) => {
+                    self.retries.push_back(event);
+                    oldest
+                }
+                None => event,
+            };
+            let forwarded = self.tx.send(oldest).await.is_ok();
+
+            metrics.milestone_queue_depth_set(self.tx.len() as u64);
+
+            return forwarded;
+        }
+
+        metrics.milestone_forward_retries_inc();
+        self.retries.push_back(event);
+
+        true
+    }
+
+    // Forwards all the held back events, waiting for room if needed.
+    pub(crate) async fn flush(&mut self) {
+        while let Some(event) = self.retries.pop_front() {
+            if self.tx.send(event).await.is_err() {
+                error!("Forwarding {} milestone payloads failed.", self.retries.len() + 1);
+                self.retries.clear();
+            }
+        }
+    }
 }
 
 fn validate(
@@ -67,11 +175,19 @@ fn validate(
     Ok(Milestone::new(message_id, milestone.essence().timestamp()))
 }
 
+// Notifies that the peer an invalid milestone was received from, if any, should be penalized.
+fn report_invalid_milestone(message_id: MessageId, from: Option<PeerId>, bus: &Bus<'static>) {
+    if let Some(peer_id) = from {
+        bus.dispatch(InvalidMilestoneReceived { peer_id, message_id });
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn process<B: StorageBackend>(
     tangle: &Tangle<B>,
     message_id: MessageId,
     message: MessageRef,
+    from: Option<PeerId>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     requested_milestones: &RequestedMilestones,
@@ -108,7 +224,10 @@ async fn process<B: StorageBackend>(
                     error!("Sending solidification event failed: {}.", e);
                 }
             }
-            Err(e) => debug!("Invalid milestone message {}: {:?}.", message_id, e),
+            Err(e) => {
+                debug!("Invalid milestone message {}: {:?}.", message_id, e);
+                report_invalid_milestone(message_id, from, bus);
+            }
         }
     } else {
         error!(
@@ -148,18 +267,30 @@ where
             config.coordinator.public_key_ranges.into_boxed_slice(),
         );
         let bus = node.bus();
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = match config.workers.milestone_queue_capacity {
+            Some(capacity) => async_channel::bounded(capacity.max(1)),
+            None => async_channel::unbounded(),
+        };
+        let queue = rx.clone();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let mut receiver = ShutdownStream::new(shutdown, rx);
+
+            while let Some(MilestonePayloadWorkerEvent {
+                message_id,
+                message,
+                from,
+            }) = receiver.next().await
+            {
+                metrics.milestone_queue_depth_set(queue.len() as u64);
 
-            while let Some(MilestonePayloadWorkerEvent { message_id, message }) = receiver.next().await {
                 process(
                     &tangle,
                     message_id,
                     message,
+                    from,
                     &peer_manager,
                     &metrics,
                     &requested_milestones,
@@ -176,11 +307,17 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(MilestonePayloadWorkerEvent { message_id, message })) = receiver.next().now_or_never() {
+            while let Some(Some(MilestonePayloadWorkerEvent {
+                message_id,
+                message,
+                from,
+            })) = receiver.next().now_or_never()
+            {
                 process(
                     &tangle,
                     message_id,
                     message,
+                    from,
                     &peer_manager,
                     &metrics,
                     &requested_milestones,
@@ -200,3 +337,150 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn full_queue_drops_or_blocks() {
+        let metrics = NodeMetrics::new();
+        let (tx, rx) = async_channel::bounded(2);
+        // Without a retry queue, forwarding waits for room right away.
+        let mut forwarder = MilestoneForwarder::new(tx, 0);
+
+        assert!(forwarder.forward(0, true, &metrics).await);
+        assert!(forwarder.forward(1, false, &metrics).await);
+        assert_eq!(metrics.milestone_queue_depth(), 2);
+
+        // Beyond the cap, droppable events are dropped right away.
+        assert!(!forwarder.forward(2, true, &metrics).await);
+        assert_eq!(metrics.milestone_payloads_dropped(), 1);
+        assert_eq!(metrics.milestone_queue_depth(), 2);
+
+        // Other events wait for room.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), forwarder.forward(3, false, &metrics))
+                .await
+                .is_err()
+        );
+        assert_eq!(rx.recv().await.unwrap(), 0);
+        assert!(forwarder.forward(4, false, &metrics).await);
+
+        assert_eq!(rx.recv().await.unwrap(), 1);
+        assert_eq!(rx.recv().await.unwrap(), 4);
+        assert!(rx.is_empty());
+        assert_eq!(metrics.milestone_payloads_dropped(), 1);
+        assert_eq!(metrics.milestone_forward_retries(), 0);
+    }
+
+    // Receives the given number of events, retrying the held back ones whenever there is room.
+    async fn drain(
+        forwarder: &mut MilestoneForwarder<u32>,
+        rx: &async_channel::Receiver<u32>,
+        count: usize,
+        metrics: &NodeMetrics,
+    ) -> Vec<u32> {
+        let mut received = Vec::new();
+
+        while received.len() < count {
+            received.push(rx.recv().await.unwrap());
+            forwarder.retry(metrics);
+        }
+
+        received
+    }
+
+    #[tokio::test]
+    async fn blocked_validation_holds_milestones_back() {
+        let metrics = NodeMetrics::new();
+        let (tx, rx) = async_channel::bounded(1);
+        let mut forwarder = MilestoneForwarder::new(tx, 4);
+
+        // The validation is blocked, yet forwarding does not wait.
+        for i in 0..5 {
+            assert!(
+                tokio::time::timeout(Duration::from_millis(50), forwarder.forward(i, false, &metrics))
+                    .await
+                    .unwrap()
+            );
+        }
+        assert!(forwarder.has_retries());
+        assert_eq!(metrics.milestone_forward_retries(), 4);
+        assert_eq!(metrics.milestone_payloads_dropped(), 0);
+
+        // Once the validation drains, all milestones are delivered in order.
+        assert_eq!(drain(&mut forwarder, &rx, 5, &metrics).await, vec![0, 1, 2, 3, 4]);
+        assert!(!forwarder.has_retries());
+        assert!(rx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_retry_queue_applies_back_pressure() {
+        let metrics = NodeMetrics::new();
+        let (tx, rx) = async_channel::bounded(1);
+        let mut forwarder = MilestoneForwarder::new(tx, 2);
+
+        for i in 0..3 {
+            assert!(forwarder.forward(i, false, &metrics).await);
+        }
+
+        // The retry queue is full, forwarding waits until the validation makes room.
+        let (forwarded, first) = tokio::join!(forwarder.forward(3, false, &metrics), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            rx.recv().await.unwrap()
+        });
+
+        assert!(forwarded);
+        assert_eq!(first, 0);
+        assert_eq!(metrics.milestone_forward_retries(), 2);
+        assert_eq!(drain(&mut forwarder, &rx, 3, &metrics).await, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn held_back_milestones_are_flushed() {
+        let metrics = NodeMetrics::new();
+        let (tx, rx) = async_channel::bounded(1);
+        let mut forwarder = MilestoneForwarder::new(tx, 4);
+
+        for i in 0..3 {
+            assert!(forwarder.forward(i, false, &metrics).await);
+        }
+
+        let (_, received) = tokio::join!(forwarder.flush(), async {
+            let mut received = Vec::new();
+            for _ in 0..3 {
+                received.push(rx.recv().await.unwrap());
+            }
+            received
+        });
+
+        assert_eq!(received, vec![0, 1, 2]);
+        assert!(!forwarder.has_retries());
+    }
+
+    #[test]
+    fn invalid_milestones_penalize_their_sender() {
+        use std::sync::{Arc, Mutex};
+
+        let bus = Bus::default();
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        {
+            let reported = reported.clone();
+            bus.add_static_listener(move |event: &InvalidMilestoneReceived| {
+                reported.lock().unwrap().push((event.peer_id, event.message_id));
+            });
+        }
+        let peer_id = PeerId::random();
+        let message_id = MessageId::new([1; 32]);
+
+        report_invalid_milestone(message_id, Some(peer_id), &bus);
+        // Milestones not received from a peer, e.g. submitted through the API, penalize no one.
+        report_invalid_milestone(MessageId::new([2; 32]), None, &bus);
+
+        assert_eq!(*reported.lock().unwrap(), vec![(peer_id, message_id)]);
+    }
+}
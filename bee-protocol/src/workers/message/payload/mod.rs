@@ -1,70 +1,126 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod handler;
 mod indexation;
 mod milestone;
 mod transaction;
 
+pub use handler::{PayloadHandler, PayloadHandlerWorker, PayloadHandlers};
 pub(crate) use indexation::{IndexationPayloadWorker, IndexationPayloadWorkerEvent};
 pub(crate) use milestone::{MilestonePayloadWorker, MilestonePayloadWorkerEvent};
+
+use milestone::MilestoneForwarder;
 pub(crate) use transaction::{TransactionPayloadWorker, TransactionPayloadWorkerEvent};
 
-use crate::workers::storage::StorageBackend;
+use crate::{
+    types::metrics::NodeMetrics,
+    workers::{config::MilestoneQueuePolicy, storage::StorageBackend, MetricsWorker},
+};
 
+use bee_gossip::PeerId;
 use bee_message::{payload::Payload, MessageId};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::MessageRef;
 
 use async_trait::async_trait;
-use futures::{future::FutureExt, stream::StreamExt};
-use log::{debug, error, info};
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use futures::{
+    future::FutureExt,
+    stream::{self, StreamExt},
+};
+use log::{debug, error, info, trace};
+use tokio::{sync::mpsc, time::interval};
+use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
+
+use std::{any::TypeId, convert::Infallible, sync::Arc, time::Duration};
 
-use std::{any::TypeId, convert::Infallible};
+// How often the milestone payloads held back because the milestone queue was full are retried.
+const MILESTONE_RETRY_INTERVAL: Duration = Duration::from_millis(50);
 
 pub(crate) struct PayloadWorkerEvent {
     pub(crate) message_id: MessageId,
     pub(crate) message: MessageRef,
+    pub(crate) arrival_timestamp: u64,
+    pub(crate) from: Option<PeerId>,
 }
 
 pub(crate) struct PayloadWorker {
     pub(crate) tx: mpsc::UnboundedSender<PayloadWorkerEvent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn process(
     message_id: MessageId,
     message: MessageRef,
+    arrival_timestamp: u64,
+    from: Option<PeerId>,
     transaction_payload_worker: &mpsc::UnboundedSender<TransactionPayloadWorkerEvent>,
-    milestone_payload_worker: &mpsc::UnboundedSender<MilestonePayloadWorkerEvent>,
+    milestone_forwarder: &mut MilestoneForwarder<MilestonePayloadWorkerEvent>,
     indexation_payload_worker: &mpsc::UnboundedSender<IndexationPayloadWorkerEvent>,
+    milestone_queue_policy: MilestoneQueuePolicy,
+    metrics: &NodeMetrics,
+    payload_handlers: Option<&PayloadHandlers>,
 ) {
     match message.payload() {
         Some(Payload::Transaction(_)) => {
             if transaction_payload_worker
-                .send(TransactionPayloadWorkerEvent { message_id, message })
+                .send(TransactionPayloadWorkerEvent {
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                })
                 .is_err()
             {
                 error!("Sending message {} to transaction payload worker failed.", message_id);
             }
         }
         Some(Payload::Milestone(_)) => {
-            if milestone_payload_worker
-                .send(MilestonePayloadWorkerEvent { message_id, message })
-                .is_err()
+            let drop_if_full =
+                milestone_queue_policy == MilestoneQueuePolicy::DropInvalid && milestone::looks_invalid(&message);
+
+            if !milestone_forwarder
+                .forward(
+                    MilestonePayloadWorkerEvent {
+                        message_id,
+                        message,
+                        from,
+                    },
+                    drop_if_full,
+                    metrics,
+                )
+                .await
             {
-                error!("Sending message {} to milestone payload worker failed.", message_id);
+                if drop_if_full {
+                    debug!("Dropping invalid looking milestone message {}.", message_id);
+                } else {
+                    error!("Sending message {} to milestone payload worker failed.", message_id);
+                }
             }
         }
         Some(Payload::Indexation(_)) => {
             if indexation_payload_worker
-                .send(IndexationPayloadWorkerEvent { message_id, message })
+                .send(IndexationPayloadWorkerEvent {
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                })
                 .is_err()
             {
                 error!("Sending message {} to indexation payload worker failed.", message_id);
             }
         }
-        _ => {}
+        Some(payload) => {
+            if let Some(payload_handlers) = payload_handlers {
+                if !payload_handlers.handle(payload.kind(), &message, message_id).await {
+                    trace!(
+                        "No handler for payload kind {} of message {}.",
+                        payload.kind(),
+                        message_id
+                    );
+                }
+            }
+        }
+        None => {}
     }
 }
 
@@ -74,7 +130,7 @@ where
     N: Node,
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = (MilestoneQueuePolicy, usize);
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -82,28 +138,66 @@ where
             TypeId::of::<TransactionPayloadWorker>(),
             TypeId::of::<MilestonePayloadWorker>(),
             TypeId::of::<IndexationPayloadWorker>(),
+            TypeId::of::<MetricsWorker>(),
+            // Optional, only registered if payload handlers are provided.
+            TypeId::of::<PayloadHandlerWorker>(),
         ]
         .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (milestone_queue_policy, milestone_retry_capacity) = config;
         let transaction_payload_worker = node.worker::<TransactionPayloadWorker>().unwrap().tx.clone();
-        let milestone_payload_worker = node.worker::<MilestonePayloadWorker>().unwrap().tx.clone();
+        let mut milestone_forwarder = MilestoneForwarder::new(
+            node.worker::<MilestonePayloadWorker>().unwrap().tx.clone(),
+            milestone_retry_capacity,
+        );
         let indexation_payload_worker = node.worker::<IndexationPayloadWorker>().unwrap().tx.clone();
+        let metrics = node.resource::<NodeMetrics>();
+        let payload_handlers: Option<Arc<PayloadHandlers>> = node
+            .worker::<PayloadHandlerWorker>()
+            .map(|worker| worker.handlers.clone());
         let (tx, rx) = mpsc::unbounded_channel();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            // Ticks are interleaved with the events to retry the held back milestone payloads.
+            let mut receiver = ShutdownStream::new(
+                shutdown,
+                stream::select(
+                    UnboundedReceiverStream::new(rx).map(Some),
+                    IntervalStream::new(interval(MILESTONE_RETRY_INTERVAL)).map(|_| None),
+                ),
+            );
+
+            while let Some(event) = receiver.next().await {
+                let PayloadWorkerEvent {
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                    from,
+                } = match event {
+                    Some(event) => event,
+                    None => {
+                        if milestone_forwarder.has_retries() {
+                            milestone_forwarder.retry(&metrics);
+                        }
+                        continue;
+                    }
+                };
 
-            while let Some(PayloadWorkerEvent { message_id, message }) = receiver.next().await {
                 process(
                     message_id,
                     message,
+                    arrival_timestamp,
+                    from,
                     &transaction_payload_worker,
-                    &milestone_payload_worker,
+                    &mut milestone_forwarder,
                     &indexation_payload_worker,
+                    milestone_queue_policy,
+                    &metrics,
+                    payload_handlers.as_deref(),
                 )
                 .await;
             }
@@ -114,18 +208,35 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(PayloadWorkerEvent { message_id, message })) = receiver.next().now_or_never() {
+            while let Some(Some(event)) = receiver.next().now_or_never() {
+                let PayloadWorkerEvent {
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                    from,
+                } = match event {
+                    Some(event) => event,
+                    None => continue,
+                };
+
                 process(
                     message_id,
                     message,
+                    arrival_timestamp,
+                    from,
                     &transaction_payload_worker,
-                    &milestone_payload_worker,
+                    &mut milestone_forwarder,
                     &indexation_payload_worker,
+                    milestone_queue_policy,
+                    &metrics,
+                    payload_handlers.as_deref(),
                 )
                 .await;
                 count += 1;
             }
 
+            milestone_forwarder.flush().await;
+
             debug!("Drained {} messages.", count);
 
             info!("Stopped.");
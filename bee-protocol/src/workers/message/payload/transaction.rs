@@ -24,6 +24,7 @@ use std::{any::TypeId, convert::Infallible};
 pub(crate) struct TransactionPayloadWorkerEvent {
     pub(crate) message_id: MessageId,
     pub(crate) message: MessageRef,
+    pub(crate) arrival_timestamp: u64,
 }
 
 pub(crate) struct TransactionPayloadWorker {
@@ -33,6 +34,7 @@ pub(crate) struct TransactionPayloadWorker {
 async fn process(
     message_id: MessageId,
     message: MessageRef,
+    arrival_timestamp: u64,
     indexation_payload_worker: &mpsc::UnboundedSender<IndexationPayloadWorkerEvent>,
     metrics: &NodeMetrics,
 ) {
@@ -52,7 +54,11 @@ async fn process(
 
     if let Some(Payload::Indexation(_)) = essence.payload() {
         if indexation_payload_worker
-            .send(IndexationPayloadWorkerEvent { message_id, message })
+            .send(IndexationPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            })
             .is_err()
         {
             error!("Sending message {} to indexation payload worker failed.", message_id);
@@ -84,8 +90,20 @@ where
 
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
-            while let Some(TransactionPayloadWorkerEvent { message_id, message }) = receiver.next().await {
-                process(message_id, message, &indexation_payload_worker, &metrics).await;
+            while let Some(TransactionPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            }) = receiver.next().await
+            {
+                process(
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                    &indexation_payload_worker,
+                    &metrics,
+                )
+                .await;
             }
 
             // Before the worker completely stops, the receiver needs to be drained for transaction payloads to be
@@ -94,9 +112,20 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(TransactionPayloadWorkerEvent { message_id, message })) = receiver.next().now_or_never()
+            while let Some(Some(TransactionPayloadWorkerEvent {
+                message_id,
+                message,
+                arrival_timestamp,
+            })) = receiver.next().now_or_never()
             {
-                process(message_id, message, &indexation_payload_worker, &metrics).await;
+                process(
+                    message_id,
+                    message,
+                    arrival_timestamp,
+                    &indexation_payload_worker,
+                    &metrics,
+                )
+                .await;
                 count += 1;
             }
 
@@ -0,0 +1,132 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workers::config::{PowScorePolicyKind, ProtocolConfig};
+
+/// The conditions under which the PoW score of a message is judged.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PowContext {
+    /// The number of messages received during the last second.
+    pub incoming_mps: u64,
+}
+
+/// Decides whether the PoW score of a message is sufficient.
+pub trait PowScorePolicy: Send + Sync + 'static {
+    /// Returns the minimum PoW score a message needs in the given context.
+    fn minimum_score(&self, ctx: &PowContext) -> f64;
+
+    /// Returns whether a message with the given PoW score is accepted in the given context.
+    fn accept(&self, score: f64, ctx: &PowContext) -> bool {
+        score >= self.minimum_score(ctx)
+    }
+}
+
+/// A `PowScorePolicy` requiring the same minimum PoW score regardless of the context.
+#[derive(Clone, Copy, Debug)]
+pub struct StaticPowScorePolicy {
+    minimum: f64,
+}
+
+impl StaticPowScorePolicy {
+    /// Creates a new `StaticPowScorePolicy` requiring the given minimum PoW score.
+    pub fn new(minimum: f64) -> Self {
+        Self { minimum }
+    }
+}
+
+impl PowScorePolicy for StaticPowScorePolicy {
+    fn minimum_score(&self, _ctx: &PowContext) -> f64 {
+        self.minimum
+    }
+}
+
+/// A `PowScorePolicy` raising the minimum PoW score in proportion to the incoming message rate, once it exceeds a
+/// threshold.
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptivePowScorePolicy {
+    minimum: f64,
+    mps_threshold: u64,
+}
+
+impl AdaptivePowScorePolicy {
+    /// Creates a new `AdaptivePowScorePolicy` requiring the given minimum PoW score up to `mps_threshold` incoming
+    /// messages per second.
+    pub fn new(minimum: f64, mps_threshold: u64) -> Self {
+        Self {
+            minimum,
+            mps_threshold: mps_threshold.max(1),
+        }
+    }
+}
+
+impl PowScorePolicy for AdaptivePowScorePolicy {
+    fn minimum_score(&self, ctx: &PowContext) -> f64 {
+        let load = ctx.incoming_mps as f64 / self.mps_threshold as f64;
+
+        self.minimum * load.max(1.0)
+    }
+}
+
+pub(crate) fn pow_score_policy(config: &ProtocolConfig) -> Box<dyn PowScorePolicy> {
+    match config.workers.pow_score_policy {
+        PowScorePolicyKind::Static => Box::new(StaticPowScorePolicy::new(config.minimum_pow_score)),
+        PowScorePolicyKind::Adaptive => Box::new(AdaptivePowScorePolicy::new(
+            config.minimum_pow_score,
+            config.workers.pow_score_mps_threshold,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn ctx(incoming_mps: u64) -> PowContext {
+        PowContext { incoming_mps }
+    }
+
+    #[test]
+    fn static_policy_ignores_rate() {
+        let policy = StaticPowScorePolicy::new(4000.0);
+
+        for mps in [0, 100, 10_000] {
+            assert!(policy.accept(4000.0, &ctx(mps)));
+            assert!(!policy.accept(3999.9, &ctx(mps)));
+        }
+    }
+
+    #[test]
+    fn adaptive_policy_follows_rate() {
+        let policy = AdaptivePowScorePolicy::new(4000.0, 100);
+
+        // Up to the threshold, the minimum is kept.
+        for mps in [0, 50, 100] {
+            assert_eq!(policy.minimum_score(&ctx(mps)), 4000.0);
+            assert!(policy.accept(4000.0, &ctx(mps)));
+        }
+
+        // Above the threshold, the minimum grows with the rate.
+        assert_eq!(policy.minimum_score(&ctx(150)), 6000.0);
+        assert_eq!(policy.minimum_score(&ctx(400)), 16000.0);
+        assert!(!policy.accept(5999.0, &ctx(150)));
+        assert!(policy.accept(6000.0, &ctx(150)));
+
+        // Once the rate drops again, so does the minimum.
+        assert!(policy.accept(4000.0, &ctx(80)));
+    }
+
+    #[test]
+    fn config_selects_policy() {
+        let config = ProtocolConfig::build()
+            .pow_score_policy(PowScorePolicyKind::Adaptive)
+            .pow_score_mps_threshold(10)
+            .finish();
+
+        assert_eq!(pow_score_policy(&config).minimum_score(&ctx(20)), 8000.0);
+
+        let config = ProtocolConfig::build().finish();
+
+        assert_eq!(pow_score_policy(&config).minimum_score(&ctx(20)), 4000.0);
+    }
+}
@@ -4,40 +4,599 @@
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
+        config::{ProcessorQueuePolicy, ProtocolConfig},
         event::{MessageProcessed, VertexCreated},
-        message::submitter::{notify_invalid_message, notify_message},
+        message::{
+            audit::{AuditOutcome, AuditRecord, AuditTrail, AuditWorker},
+            classifier::{MessageRouter, MessageRouterWorker},
+            decision::{Decision, DecisionLog, DecisionRecord},
+            reattachment::ReattachmentDetector,
+            submitter::{discard_canceled_notifier, notify_invalid_message, notify_known_message, notify_message},
+            validator::{default_validators, MessageValidatorWorker, MessageValidators},
+            LogSampler, MessageSubmitterError, SubmitOutcome,
+        },
+        network_id::AcceptedNetworkIds,
         packets::MessagePacket,
-        peer::PeerManager,
-        requester::request_message,
+        peer::{MessageOutcome, PeerManager},
+        requester::{parent_request_depth, request_message_at_depth},
+        reverifier::ReverifySignal,
         storage::StorageBackend,
-        BroadcasterWorker, BroadcasterWorkerEvent, MessageRequesterWorker, MessageSubmitterError, MetricsWorker,
+        BroadcasterWorker, BroadcasterWorkerEvent, MessageRequesterWorker, MetricsWorker, MilestoneRequesterWorker,
         PayloadWorker, PayloadWorkerEvent, PeerManagerResWorker, PropagatorWorker, PropagatorWorkerEvent,
-        RequestedMessages, UnreferencedMessageInserterWorker, UnreferencedMessageInserterWorkerEvent,
+        RequestedMessages, RequestedMilestones, ReverifierWorker, UnreferencedMessageInserterWorker,
+        UnreferencedMessageInserterWorkerEvent,
     },
 };
 
+use bee_autopeering::TimeSource;
 use bee_common::packable::Packable;
-use bee_gossip::PeerId;
-use bee_message::{Message, MessageId};
-use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
-use bee_tangle::{metadata::MessageMetadata, Tangle, TangleWorker};
+use bee_gossip::{Command, NetworkCommandSender, PeerId, PeerRelation};
+use bee_message::{milestone::MilestoneIndex, payload::Payload, Message, MessageId};
+use bee_runtime::{event::Bus, node::Node, resource::ResourceHandle, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_tangle::{metadata::MessageMetadata, raw_message::RawMessage, Tangle, TangleWorker};
 
+use async_priority_queue::PriorityQueue;
 use async_trait::async_trait;
-use futures::{channel::oneshot::Sender, stream::StreamExt};
-use log::{error, info, trace};
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use futures::{channel::oneshot::Sender, future, stream::StreamExt};
+use parking_lot::Mutex;
+use tokio::{
+    sync::{mpsc, Notify},
+    task::JoinHandle,
+};
+use tracing::{error, field, info, trace, trace_span, warn, Instrument, Span};
+
+use std::{
+    any::TypeId,
+    cmp::{Ord, Ordering, PartialOrd},
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+// Creates the span the processing of a message is instrumented with, for its logs to be told apart from the ones of
+// other messages.
+pub(crate) fn message_span(message_id: MessageId, from: Option<&PeerId>) -> Span {
+    let span = trace_span!("message", message_id = %message_id, peer_id = field::Empty);
+
+    if let Some(peer_id) = from {
+        span.record("peer_id", &field::display(peer_id));
+    }
+
+    span
+}
+
+// Runs `f` and records the time it took, in microseconds, with `observe` if `enabled`.
+pub(crate) fn measure<T>(enabled: bool, observe: impl FnOnce(u64), f: impl FnOnce() -> T) -> T {
+    let start = enabled.then(Instant::now);
+    let output = f();
+
+    if let Some(start) = start {
+        observe(start.elapsed().as_micros() as u64);
+    }
 
-use std::{any::TypeId, convert::Infallible, time::Instant};
+    output
+}
+
+// Returns whether the bytes received for a known message differ from the stored ones, if they were stored.
+fn is_corrupt(stored: Option<&RawMessage>, received: &[u8]) -> bool {
+    match stored {
+        Some(stored) => stored.bytes() != received,
+        None => false,
+    }
+}
+
+// Broadcasts a new message to the peers, or only counts it if broadcasting is disabled. Returns the decision taken.
+fn broadcast(
+    broadcaster: &BroadcasterWorker,
+    metrics: &NodeMetrics,
+    enabled: bool,
+    stale: bool,
+    event: BroadcasterWorkerEvent,
+) -> Decision {
+    if !enabled {
+        metrics.broadcasts_suppressed_inc();
+        Decision::BroadcastDisabled
+    } else if stale {
+        metrics.stale_broadcasts_suppressed_inc();
+        Decision::Stale
+    } else {
+        broadcaster.broadcast(event);
+        Decision::Broadcast
+    }
+}
+
+// Returns the index of the youngest milestone a message references, either as its payload or through its parents, if
+// known.
+async fn referenced_milestone_index<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    message: &Message,
+) -> Option<MilestoneIndex> {
+    let mut index = match message.payload() {
+        Some(Payload::Milestone(milestone)) => Some(milestone.essence().index()),
+        _ => None,
+    };
+
+    for parent in message.parents().iter() {
+        if let Some(metadata) = tangle.get_metadata(parent).await {
+            index = index
+                .max(metadata.milestone_index())
+                .max(metadata.ymrsi().map(|ymrsi| ymrsi.index()));
+        }
+    }
+
+    index
+}
+
+// Returns whether a message referencing the given milestone index is more than `threshold` milestones behind the solid
+// milestone, if a threshold is configured.
+fn is_stale(referenced_index: Option<MilestoneIndex>, solid_index: MilestoneIndex, threshold: Option<u32>) -> bool {
+    match (referenced_index, threshold) {
+        (Some(referenced_index), Some(threshold)) => *referenced_index < solid_index.saturating_sub(threshold),
+        _ => false,
+    }
+}
+
+// Credits the peer a requested message was received from, if any, for helping this node to sync.
+fn credit_requested_message(peer_manager: &PeerManager, from: Option<&PeerId>) {
+    if let Some(peer) = from.and_then(|peer_id| peer_manager.get(peer_id)) {
+        peer.0.metrics().requested_messages_inc();
+    }
+}
+
+// Returns whether an event may be queued for the processor. If the queue is full and the policy is to drop, the event
+// is counted as dropped and may not be queued. Otherwise, sending the event waits for room in the queue.
+pub(crate) fn admit<T>(tx: &async_channel::Sender<T>, policy: ProcessorQueuePolicy, metrics: &NodeMetrics) -> bool {
+    if policy == ProcessorQueuePolicy::Drop && tx.is_full() {
+        metrics.processor_queue_full_inc();
+        false
+    } else {
+        true
+    }
+}
+
+/// The priority with which a message is processed, derived by the network layer from its source.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum MessagePriority {
+    #[default]
+    Normal,
+    High,
+}
+
+impl MessagePriority {
+    // Known peers are configured by the operator and therefore trusted, their messages are optionally prioritized.
+    pub(crate) fn from_relation(relation: PeerRelation, prioritize_known_peers: bool) -> Self {
+        if prioritize_known_peers && relation.is_known() {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+}
 
 pub(crate) struct ProcessorWorkerEvent {
+    pub(crate) message_id: MessageId,
     pub(crate) from: Option<PeerId>,
     pub(crate) message_packet: MessagePacket,
-    pub(crate) notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
+    pub(crate) notifier: Option<Sender<SubmitOutcome>>,
+    pub(crate) priority: MessagePriority,
+    // Whether the PoW check was skipped for a trusted submission.
+    pub(crate) pow_skipped: bool,
+}
+
+// Events are processed by priority first, events of the same priority in the order they were received. Stop markers,
+// without an event, come after all events so that the queue is drained before the processing tasks stop.
+struct QueuedEvent {
+    priority: MessagePriority,
+    sequence: u64,
+    event: Option<ProcessorWorkerEvent>,
+}
+
+impl Ord for QueuedEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.event
+            .is_some()
+            .cmp(&other.event.is_some())
+            .then_with(|| self.priority.cmp(&other.priority))
+            .then_with(|| self.sequence.cmp(&other.sequence).reverse())
+    }
+}
+
+impl PartialOrd for QueuedEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedEvent {}
+
+#[derive(Default)]
+struct ProcessorIntake {
+    queue: PriorityQueue<QueuedEvent>,
+    sequence: AtomicU64,
+    // The number of queued events, stop markers excluded.
+    len: AtomicUsize,
+    popped: Notify,
+}
+
+impl ProcessorIntake {
+    fn len(&self) -> usize {
+        self.len.load(AtomicOrdering::Relaxed)
+    }
+
+    // Waits until fewer than `capacity` events are queued.
+    async fn wait_for_room(&self, capacity: usize) {
+        while self.len() >= capacity {
+            self.popped.notified().await;
+        }
+    }
+
+    fn push(&self, event: ProcessorWorkerEvent) {
+        self.len.fetch_add(1, AtomicOrdering::Relaxed);
+        self.queue.push(QueuedEvent {
+            priority: event.priority,
+            sequence: self.sequence.fetch_add(1, AtomicOrdering::Relaxed),
+            event: Some(event),
+        });
+    }
+
+    // Makes `tasks` calls to `pop` return `None` once all pending events are drained.
+    fn close(&self, tasks: usize) {
+        for _ in 0..tasks {
+            self.queue.push(QueuedEvent {
+                priority: MessagePriority::Normal,
+                sequence: self.sequence.fetch_add(1, AtomicOrdering::Relaxed),
+                event: None,
+            });
+        }
+    }
+
+    async fn pop(&self) -> Option<ProcessorWorkerEvent> {
+        let event = self.queue.pop().await.event;
+
+        if event.is_some() {
+            self.len.fetch_sub(1, AtomicOrdering::Relaxed);
+            self.popped.notify_one();
+        }
+
+        event
+    }
+}
+
+// Stops accepting events and hands the queued ones to the processing tasks, then waits up to `timeout` for the tasks
+// to process them. Tasks still running after the timeout are aborted. Returns whether all events were processed in
+// time.
+async fn drain(
+    queue: &async_channel::Receiver<ProcessorWorkerEvent>,
+    intake: &ProcessorIntake,
+    mut tasks: Vec<JoinHandle<()>>,
+    timeout: Duration,
+) -> bool {
+    queue.close();
+
+    // The events were accepted before the shutdown.
+    while let Ok(event) = queue.try_recv() {
+        intake.push(event);
+    }
+
+    intake.close(tasks.len());
+
+    if tokio::time::timeout(timeout, future::join_all(tasks.iter_mut()))
+        .await
+        .is_ok()
+    {
+        true
+    } else {
+        tasks.iter().for_each(JoinHandle::abort);
+        false
+    }
+}
+
+// The state shared by the processing tasks.
+struct ProcessingContext<B: StorageBackend> {
+    propagator: mpsc::UnboundedSender<PropagatorWorkerEvent>,
+    broadcaster: BroadcasterWorker,
+    message_requester: MessageRequesterWorker,
+    payload_worker: mpsc::UnboundedSender<PayloadWorkerEvent>,
+    unreferenced_inserted_worker: mpsc::UnboundedSender<UnreferencedMessageInserterWorkerEvent>,
+    tangle: ResourceHandle<Tangle<B>>,
+    requested_messages: ResourceHandle<RequestedMessages>,
+    metrics: ResourceHandle<NodeMetrics>,
+    peer_manager: ResourceHandle<PeerManager>,
+    network_command_tx: ResourceHandle<NetworkCommandSender>,
+    bus: ResourceHandle<Bus<'static>>,
+    trace_sampler: LogSampler,
+    audit: AuditTrail,
+    router: Arc<MessageRouter>,
+    reverify_signal: Option<Arc<ReverifySignal>>,
+    reattachments: Option<Mutex<ReattachmentDetector>>,
+    validators: MessageValidators,
+    // The number of requested messages received and the sum of their latencies, in milliseconds.
+    latency: Mutex<(u64, u64)>,
+    skip_canceled_notifiers: bool,
+    store_raw_messages: bool,
+    broadcast_enabled: bool,
+    stale_broadcast_threshold: Option<u32>,
+    unpack_time: bool,
+    processing_time: bool,
+    verify_known_messages: bool,
+    ban_corrupt_peers: bool,
+    max_parent_request_depth: Option<u32>,
+    decisions: ResourceHandle<DecisionLog>,
+}
+
+impl<B: StorageBackend> ProcessingContext<B> {
+    // Records the rejection of a message and notifies its submitter, if any.
+    fn reject(
+        &self,
+        message_id: Option<MessageId>,
+        from: Option<PeerId>,
+        error: MessageSubmitterError,
+        notifier: Option<Sender<SubmitOutcome>>,
+    ) {
+        self.audit
+            .record(|| AuditRecord::new(message_id, from, AuditOutcome::Invalid(error.to_string())));
+        self.decisions
+            .record(|| DecisionRecord::new(message_id, from, Decision::Rejected(error.to_string())));
+        notify_invalid_message(error, &self.metrics, &self.peer_manager, from.as_ref(), notifier);
+    }
+}
+
+// Processes a received message, from unpacking it to handing it to the workers interested in new messages.
+async fn process<B: StorageBackend>(context: &ProcessingContext<B>, event: ProcessorWorkerEvent) {
+    let ProcessorWorkerEvent {
+        message_id,
+        from,
+        message_packet,
+        notifier,
+        pow_skipped,
+        ..
+    } = event;
+
+    if context.trace_sampler.sample() {
+        trace!("Processing received message...");
+    }
+
+    let unpacked = measure(
+        context.unpack_time,
+        |time| context.metrics.unpack_time_observe(time),
+        || Message::unpack(&mut &message_packet.bytes[..]),
+    );
+
+    let message = match unpacked {
+        Ok(message) => message,
+        Err(e) => {
+            let error = MessageSubmitterError::InvalidMessage(e);
+            context.reject(None, from, error, notifier);
+            return;
+        }
+    };
+
+    if let Err(error) = context.validators.validate(&message_id, &message) {
+        context.reject(Some(message_id), from, error, notifier);
+        return;
+    }
+
+    let is_milestone = matches!(message.payload(), Some(Payload::Milestone(_)));
+
+    let mut metadata = MessageMetadata::arrived();
+    let arrival_timestamp = metadata.arrival_timestamp();
+
+    if let Some(ref reattachments) = context.reattachments {
+        if let Some(original_id) = reattachments
+            .lock()
+            .detect(message_id, &message, &mut metadata, Instant::now())
+        {
+            trace!("Message {} is a reattachment of message {}.", message_id, original_id);
+        }
+    }
+
+    let insert_start = context.processing_time.then(Instant::now);
+    let inserted = context.tangle.insert(message, message_id, metadata).await;
+
+    if let Some(start) = insert_start {
+        context.metrics.insert_time_observe(start.elapsed().as_micros() as u64);
+    }
+
+    let message = if let Some(message) = inserted {
+        message
+    } else if context.verify_known_messages
+        && is_corrupt(context.tangle.get_raw(&message_id).as_ref(), &message_packet.bytes)
+    {
+        context.metrics.corrupt_messages_inc();
+        if let Some(ref peer_id) = from {
+            if let Some(ref peer) = context.peer_manager.get(peer_id) {
+                peer.0.metrics().corrupt_messages_inc();
+            }
+            if context.ban_corrupt_peers {
+                warn!("Banning peer {} for sending corrupt message {}.", peer_id, message_id);
+                if let Err(e) = context.network_command_tx.send(Command::BanPeer { peer_id: *peer_id }) {
+                    error!("Banning peer {} failed: {:?}.", peer_id, e);
+                }
+            }
+        }
+        let error = MessageSubmitterError::CorruptBytes(message_id);
+        context.reject(Some(message_id), from, error, notifier);
+        return;
+    } else {
+        context.metrics.known_messages_inc();
+        if let Some(ref peer_id) = from {
+            context
+                .peer_manager
+                .get(peer_id)
+                .map(|peer| (*peer).0.metrics().known_messages_inc());
+            context.peer_manager.record_outcome(peer_id, MessageOutcome::Known);
+        }
+        context
+            .audit
+            .record(|| AuditRecord::new(Some(message_id), from, AuditOutcome::Known));
+        context
+            .decisions
+            .record(|| DecisionRecord::new(Some(message_id), from, Decision::Known));
+        notify_known_message(notifier);
+        return;
+    };
+
+    if context.store_raw_messages {
+        context
+            .tangle
+            .insert_raw(&message_id, &RawMessage::from(message_packet.bytes.clone()));
+    }
+
+    // The message is stored, the remaining work is done regardless of whether the submitter is still waiting for the
+    // outcome.
+    let notifier = if context.skip_canceled_notifiers {
+        discard_canceled_notifier(notifier, &context.metrics)
+    } else {
+        notifier
+    };
+
+    // Send the propagation event ASAP to allow the propagator to do its thing
+    if let Err(e) = context.propagator.send(PropagatorWorkerEvent(message_id)) {
+        error!("Failed to send message id {} to propagator: {:?}.", message_id, e);
+    }
+
+    context.router.route(message_id, &message, from.as_ref());
+
+    if is_milestone {
+        if let Some(ref reverify_signal) = context.reverify_signal {
+            reverify_signal.notify(Instant::now());
+        }
+    }
+
+    let decision = match context.requested_messages.remove(&message_id) {
+        // Message was requested.
+        Some((index, instant, depth)) => {
+            credit_requested_message(&context.peer_manager, from.as_ref());
+
+            let average_latency = {
+                let mut latency = context.latency.lock();
+                latency.0 += 1;
+                latency.1 += (Instant::now() - instant).as_millis() as u64;
+                latency.1 / latency.0
+            };
+            context.metrics.messages_average_latency_set(average_latency);
+
+            match parent_request_depth(depth, context.max_parent_request_depth) {
+                Some(parent_depth) => {
+                    for parent in message.parents().iter() {
+                        // The parent might already be requested as the parent of another message.
+                        if context.requested_messages.contains(parent) {
+                            context.metrics.message_requests_suppressed_inc();
+                            continue;
+                        }
+                        request_message_at_depth(
+                            &context.tangle,
+                            &context.message_requester,
+                            &*context.requested_messages,
+                            *parent,
+                            index,
+                            parent_depth,
+                        )
+                        .await;
+                    }
+                }
+                // The parents are left to the solidifier.
+                None => {
+                    context.metrics.message_requests_deferred_inc();
+                }
+            }
+
+            Decision::Requested
+        }
+        // Message was not requested.
+        None => {
+            // Messages inserted while catching up are old and not worth gossiping.
+            let stale = context.stale_broadcast_threshold.is_some()
+                && is_stale(
+                    referenced_milestone_index(&context.tangle, &message).await,
+                    context.tangle.get_solid_milestone_index(),
+                    context.stale_broadcast_threshold,
+                );
+
+            // Peers would reject a message of which the PoW was not checked, it is only kept by this node. Messages
+            // submitted by this node are otherwise broadcast ahead of relayed ones.
+            let decision = if pow_skipped {
+                context.metrics.broadcasts_suppressed_inc();
+                Decision::PowSkipped
+            } else {
+                broadcast(
+                    &context.broadcaster,
+                    &context.metrics,
+                    context.broadcast_enabled,
+                    stale,
+                    BroadcasterWorkerEvent {
+                        source: from,
+                        message: message_packet,
+                        priority: from.is_none(),
+                    },
+                )
+            };
+            if let Err(e) = context
+                .unreferenced_inserted_worker
+                .send(UnreferencedMessageInserterWorkerEvent(
+                    message_id,
+                    context.tangle.get_latest_milestone_index(),
+                ))
+            {
+                error!("Sending message to unreferenced inserter failed: {}.", e);
+            }
+
+            decision
+        }
+    };
+
+    if context
+        .payload_worker
+        .send(PayloadWorkerEvent {
+            message_id,
+            message: message.clone(),
+            arrival_timestamp,
+            from,
+        })
+        .is_err()
+    {
+        error!("Sending message {} to payload worker failed.", message_id);
+    }
+
+    context
+        .audit
+        .record(|| AuditRecord::new(Some(message_id), from, AuditOutcome::Processed));
+    context
+        .decisions
+        .record(|| DecisionRecord::new(Some(message_id), from, decision));
+    notify_message(message_id, notifier);
+
+    context.bus.dispatch(MessageProcessed { message_id });
+
+    // TODO: boolean values are false at this point in time? trigger event from another location?
+    context.bus.dispatch(VertexCreated {
+        message_id,
+        parent_message_ids: message.parents().to_vec(),
+        is_solid: false,
+        is_referenced: false,
+        is_conflicting: false,
+        is_milestone: false,
+        is_tip: false,
+        is_selected: false,
+    });
+
+    context.metrics.new_messages_inc();
+    if let Some(ref peer_id) = from {
+        context.peer_manager.record_outcome(peer_id, MessageOutcome::New);
+    }
 }
 
 pub(crate) struct ProcessorWorker {
-    pub(crate) tx: mpsc::UnboundedSender<ProcessorWorkerEvent>,
+    pub(crate) tx: async_channel::Sender<ProcessorWorkerEvent>,
 }
 
 #[async_trait]
@@ -45,7 +604,7 @@ impl<N: Node> Worker<N> for ProcessorWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = u64;
+    type Config = (ProtocolConfig, Arc<dyn TimeSource>);
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -54,167 +613,1207 @@ where
             TypeId::of::<PropagatorWorker>(),
             TypeId::of::<BroadcasterWorker>(),
             TypeId::of::<MessageRequesterWorker>(),
+            TypeId::of::<MilestoneRequesterWorker>(),
             TypeId::of::<MetricsWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
             TypeId::of::<PayloadWorker>(),
             TypeId::of::<UnreferencedMessageInserterWorker>(),
+            // Optional, only registered if an audit sink is provided.
+            TypeId::of::<AuditWorker>(),
+            // Optional, only registered if a message router is provided.
+            TypeId::of::<MessageRouterWorker>(),
+            // Optional, only registered if a discovery handle is provided.
+            TypeId::of::<ReverifierWorker>(),
+            // Optional, only registered if validators are provided.
+            TypeId::of::<MessageValidatorWorker>(),
         ]
         .leak()
     }
 
-    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
-        let (tx, rx) = mpsc::unbounded_channel();
-
-        let propagator = node.worker::<PropagatorWorker>().unwrap().tx.clone();
-        let broadcaster = node.worker::<BroadcasterWorker>().unwrap().tx.clone();
-        let message_requester = node.worker::<MessageRequesterWorker>().unwrap().clone();
-        let payload_worker = node.worker::<PayloadWorker>().unwrap().tx.clone();
-        let unreferenced_inserted_worker = node.worker::<UnreferencedMessageInserterWorker>().unwrap().tx.clone();
+    async fn start(node: &mut N, (config, time_source): Self::Config) -> Result<Self, Self::Error> {
+        // The intake of the processing tasks holds up to the same number of events as the channel, so that at most
+        // twice the capacity of events wait to be processed.
+        let queue_capacity = config.workers.processor_queue_capacity.max(1);
+        let (tx, rx) = async_channel::bounded::<ProcessorWorkerEvent>(queue_capacity);
+        let queue = rx.clone();
 
         let tangle = node.resource::<Tangle<N::Backend>>();
-        let requested_messages = node.resource::<RequestedMessages>();
+        let network_ids = node.resource::<AcceptedNetworkIds>();
         let metrics = node.resource::<NodeMetrics>();
-        let peer_manager = node.resource::<PeerManager>();
-        let bus = node.bus();
+        let mut validators = default_validators(
+            network_ids,
+            tangle.clone(),
+            node.resource::<RequestedMessages>(),
+            node.resource::<RequestedMilestones>(),
+            config.workers.milestone_lookahead,
+            config.workers.max_clock_drift,
+            time_source,
+            config.workers.blocked_index_prefixes.clone(),
+        );
+        if let Some(worker) = node.worker::<MessageValidatorWorker>() {
+            validators.extend(&worker.validators);
+        }
+        let context = Arc::new(ProcessingContext {
+            propagator: node.worker::<PropagatorWorker>().unwrap().tx.clone(),
+            broadcaster: node.worker::<BroadcasterWorker>().unwrap().clone(),
+            message_requester: node.worker::<MessageRequesterWorker>().unwrap().clone(),
+            payload_worker: node.worker::<PayloadWorker>().unwrap().tx.clone(),
+            unreferenced_inserted_worker: node.worker::<UnreferencedMessageInserterWorker>().unwrap().tx.clone(),
+            tangle,
+            requested_messages: node.resource::<RequestedMessages>(),
+            metrics: metrics.clone(),
+            peer_manager: node.resource::<PeerManager>(),
+            network_command_tx: node.resource::<NetworkCommandSender>(),
+            bus: node.bus(),
+            trace_sampler: LogSampler::new(config.workers.message_trace_sample_rate),
+            audit: node
+                .worker::<AuditWorker>()
+                .map(|worker| worker.trail.clone())
+                .unwrap_or_default(),
+            router: node
+                .worker::<MessageRouterWorker>()
+                .map(|worker| worker.router.clone())
+                .unwrap_or_default(),
+            reverify_signal: node.worker::<ReverifierWorker>().map(|worker| worker.signal.clone()),
+            reattachments: config
+                .workers
+                .detect_reattachments
+                .then(|| Mutex::new(ReattachmentDetector::default())),
+            validators,
+            latency: Mutex::new((0, 0)),
+            skip_canceled_notifiers: config.workers.skip_canceled_notifiers,
+            store_raw_messages: config.workers.store_raw_messages,
+            broadcast_enabled: config.workers.broadcast_enabled,
+            stale_broadcast_threshold: config.workers.stale_broadcast_threshold,
+            unpack_time: config.workers.unpack_time || config.workers.processing_time,
+            processing_time: config.workers.processing_time,
+            verify_known_messages: config.workers.verify_known_messages,
+            ban_corrupt_peers: config.workers.ban_corrupt_peers,
+            max_parent_request_depth: config.workers.max_parent_request_depth,
+            decisions: node.resource::<DecisionLog>(),
+        });
+        let processor_tasks = config.workers.processor_tasks.max(1);
+        let processor_drain = config.workers.processor_drain;
+        let processor_drain_timeout = Duration::from_secs(config.workers.processor_drain_timeout);
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
-            let mut latency_num: u64 = 0;
-            let mut latency_sum: u64 = 0;
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
-
-            let (tx, rx) = async_channel::unbounded();
-
-            for _ in 0..16 {
-                let rx = rx.clone();
-                let propagator = propagator.clone();
-                let broadcaster = broadcaster.clone();
-                let message_requester = message_requester.clone();
-                let payload_worker = payload_worker.clone();
-                let unreferenced_inserted_worker = unreferenced_inserted_worker.clone();
-                let tangle = tangle.clone();
-                let requested_messages = requested_messages.clone();
-                let metrics = metrics.clone();
-                let peer_manager = peer_manager.clone();
-                let bus = bus.clone();
-                let network_id = config;
+            let mut receiver = ShutdownStream::new(shutdown, rx);
+
+            let intake = Arc::new(ProcessorIntake::default());
+            let mut tasks = Vec::with_capacity(processor_tasks);
+
+            for _ in 0..processor_tasks {
+                let context = context.clone();
+                let intake = intake.clone();
+                let queue = queue.clone();
+
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = intake.pop().await {
+                        context
+                            .metrics
+                            .processor_queue_depth_set((queue.len() + intake.len()) as u64);
+
+                        let span = message_span(event.message_id, event.from.as_ref());
+
+                        process(&context, event).instrument(span).await;
+                    }
+                }));
+            }
+
+            while let Some(event) = receiver.next().await {
+                intake.wait_for_room(queue_capacity).await;
+
+                intake.push(event);
+                metrics.processor_queue_depth_set((queue.len() + intake.len()) as u64);
+            }
+
+            if processor_drain {
+                info!("Draining {} queued messages...", queue.len() + intake.len());
+
+                if !drain(&queue, &intake, tasks, processor_drain_timeout).await {
+                    warn!("Draining timed out, remaining messages are discarded.");
+                }
+            } else {
+                intake.close(processor_tasks);
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::workers::message::MessageSubmitterWorker;
+
+    use bee_message::{parents::Parents, MessageBuilder};
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::config::TangleConfigBuilder;
+
+    // Creates a context backed by an in-memory tangle, leaving the events for the downstream workers unattended.
+    fn processing_context() -> (ProcessingContext<Storage>, mpsc::UnboundedReceiver<Command>) {
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+
+        let context = ProcessingContext {
+            propagator: mpsc::unbounded_channel().0,
+            broadcaster: BroadcasterWorker::new(),
+            message_requester: MessageRequesterWorker::new(),
+            payload_worker: mpsc::unbounded_channel().0,
+            unreferenced_inserted_worker: mpsc::unbounded_channel().0,
+            tangle: ResourceHandle::new(Tangle::new(
+                TangleConfigBuilder::new().finish(),
+                ResourceHandle::new(Storage::start(()).unwrap()),
+            )),
+            requested_messages: ResourceHandle::new(RequestedMessages::default()),
+            metrics: ResourceHandle::new(NodeMetrics::new()),
+            peer_manager: ResourceHandle::new(PeerManager::new()),
+            network_command_tx: ResourceHandle::new(NetworkCommandSender::new(command_tx)),
+            bus: ResourceHandle::new(Bus::default()),
+            trace_sampler: LogSampler::new(0),
+            audit: AuditTrail::default(),
+            router: Arc::default(),
+            reverify_signal: None,
+            reattachments: None,
+            validators: MessageValidators::new(),
+            latency: Mutex::new((0, 0)),
+            skip_canceled_notifiers: false,
+            store_raw_messages: true,
+            broadcast_enabled: true,
+            stale_broadcast_threshold: None,
+            unpack_time: true,
+            processing_time: true,
+            verify_known_messages: true,
+            ban_corrupt_peers: false,
+            max_parent_request_depth: None,
+            decisions: ResourceHandle::new(DecisionLog::new(None)),
+        };
+
+        (context, command_rx)
+    }
+
+    fn message(byte: u8) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([byte; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    // An event for a message received from `from`, or submitted by this node.
+    fn message_event(message: &Message, from: Option<PeerId>) -> ProcessorWorkerEvent {
+        ProcessorWorkerEvent {
+            message_id: message.id().0,
+            from,
+            message_packet: MessagePacket::new(message.pack_new()),
+            notifier: None,
+            priority: MessagePriority::Normal,
+            pow_skipped: false,
+        }
+    }
+
+    fn event(byte: u8, priority: MessagePriority) -> ProcessorWorkerEvent {
+        ProcessorWorkerEvent {
+            message_id: MessageId::new([byte; 32]),
+            from: None,
+            message_packet: MessagePacket::new(vec![byte]),
+            notifier: None,
+            priority,
+            pow_skipped: false,
+        }
+    }
+
+    #[test]
+    fn high_priority_events_are_drained_first() {
+        let intake = ProcessorIntake::default();
+
+        intake.push(event(1, MessagePriority::Normal));
+        intake.push(event(2, MessagePriority::High));
+        intake.push(event(3, MessagePriority::Normal));
+        intake.push(event(4, MessagePriority::High));
+        intake.close(1);
+        intake.push(event(5, MessagePriority::Normal));
+
+        let order = std::iter::from_fn(|| intake.queue.try_pop())
+            .map(|queued| queued.event.map(|event| event.message_packet.bytes[0]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(order, vec![Some(2), Some(4), Some(1), Some(3), Some(5), None]);
+    }
+
+    #[tokio::test]
+    async fn full_queue_drops_or_blocks() {
+        let metrics = NodeMetrics::new();
+        let (tx, rx) = async_channel::bounded(2);
+
+        for i in 0..2 {
+            assert!(admit(&tx, ProcessorQueuePolicy::Drop, &metrics));
+            tx.send(i).await.unwrap();
+        }
+
+        // Once the queue is saturated, events are dropped if so configured.
+        assert!(!admit(&tx, ProcessorQueuePolicy::Drop, &metrics));
+        assert!(!admit(&tx, ProcessorQueuePolicy::Drop, &metrics));
+        assert_eq!(metrics.processor_queue_full(), 2);
+
+        // Otherwise, they wait for room.
+        assert!(admit(&tx, ProcessorQueuePolicy::Block, &metrics));
+        assert!(tokio::time::timeout(Duration::from_millis(50), tx.send(2))
+            .await
+            .is_err());
+        assert_eq!(rx.recv().await.unwrap(), 0);
+        tx.send(3).await.unwrap();
+
+        assert_eq!(rx.recv().await.unwrap(), 1);
+        assert_eq!(rx.recv().await.unwrap(), 3);
+        assert!(rx.is_empty());
+        assert_eq!(metrics.processor_queue_full(), 2);
+    }
+
+    #[tokio::test]
+    async fn intake_waits_for_room() {
+        let intake = ProcessorIntake::default();
+
+        intake.push(event(1, MessagePriority::Normal));
+        intake.push(event(2, MessagePriority::Normal));
+        assert_eq!(intake.len(), 2);
+
+        intake.wait_for_room(3).await;
+        assert!(tokio::time::timeout(Duration::from_millis(50), intake.wait_for_room(2))
+            .await
+            .is_err());
+
+        assert!(intake.pop().await.is_some());
+        assert_eq!(intake.len(), 1);
+        intake.wait_for_room(2).await;
+    }
+
+    // Spawns `tasks` processing tasks recording the processed events, taking `delay` per event.
+    fn processing_tasks(
+        tasks: usize,
+        intake: &Arc<ProcessorIntake>,
+        processed: &Arc<Mutex<Vec<u8>>>,
+        delay: Duration,
+    ) -> Vec<JoinHandle<()>> {
+        (0..tasks)
+            .map(|_| {
+                let intake = intake.clone();
+                let processed = processed.clone();
 
                 tokio::spawn(async move {
-                    while let Ok(ProcessorWorkerEvent {
-                        from,
-                        message_packet,
-                        notifier,
-                    }) = rx.recv().await
-                    {
-                        trace!("Processing received message...");
-
-                        let message = match Message::unpack(&mut &message_packet.bytes[..]) {
-                            Ok(message) => message,
-                            Err(e) => {
-                                notify_invalid_message(format!("Invalid message: {:?}.", e), &metrics, notifier);
-                                continue;
-                            }
-                        };
+                    while let Some(event) = intake.pop().await {
+                        tokio::time::sleep(delay).await;
+                        processed.lock().push(event.message_packet.bytes[0]);
+                    }
+                })
+            })
+            .collect()
+    }
 
-                        if message.network_id() != network_id {
-                            notify_invalid_message(
-                                format!("Incompatible network ID {} != {}.", message.network_id(), network_id),
-                                &metrics,
-                                notifier,
-                            );
-                            continue;
-                        }
+    #[tokio::test]
+    async fn queued_events_are_drained() {
+        let (tx, rx) = async_channel::unbounded();
+        let intake = Arc::new(ProcessorIntake::default());
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let tasks = processing_tasks(4, &intake, &processed, Duration::from_millis(10));
 
-                        let (message_id, _) = message.id();
-                        let metadata = MessageMetadata::arrived();
-
-                        let message = if let Some(message) = tangle.insert(message, message_id, metadata).await {
-                            message
-                        } else {
-                            metrics.known_messages_inc();
-                            if let Some(ref peer_id) = from {
-                                peer_manager
-                                    .get(peer_id)
-                                    .map(|peer| (*peer).0.metrics().known_messages_inc());
-                            }
-                            continue;
-                        };
+        for byte in 0..20 {
+            tx.send(event(byte, MessagePriority::Normal)).await.unwrap();
+        }
 
-                        // Send the propagation event ASAP to allow the propagator to do its thing
-                        if let Err(e) = propagator.send(PropagatorWorkerEvent(message_id)) {
-                            error!("Failed to send message id {} to propagator: {:?}.", message_id, e);
-                        }
+        assert!(drain(&rx, &intake, tasks, Duration::from_secs(10)).await);
 
-                        match requested_messages.remove(&message_id) {
-                            // Message was requested.
-                            Some((index, instant)) => {
-                                latency_num += 1;
-                                latency_sum += (Instant::now() - instant).as_millis() as u64;
-                                metrics.messages_average_latency_set(latency_sum / latency_num);
-
-                                for parent in message.parents().iter() {
-                                    request_message(&tangle, &message_requester, &*requested_messages, *parent, index)
-                                        .await;
-                                }
-                            }
-                            // Message was not requested.
-                            None => {
-                                if let Err(e) = broadcaster.send(BroadcasterWorkerEvent {
-                                    source: from,
-                                    message: message_packet,
-                                }) {
-                                    error!("Broadcasting message failed: {}.", e);
-                                }
-                                if let Err(e) =
-                                    unreferenced_inserted_worker.send(UnreferencedMessageInserterWorkerEvent(
-                                        message_id,
-                                        tangle.get_latest_milestone_index(),
-                                    ))
-                                {
-                                    error!("Sending message to unreferenced inserter failed: {}.", e);
-                                }
-                            }
-                        };
+        let mut processed = processed.lock().clone();
+        processed.sort_unstable();
 
-                        if payload_worker
-                            .send(PayloadWorkerEvent {
-                                message_id,
-                                message: message.clone(),
-                            })
-                            .is_err()
-                        {
-                            error!("Sending message {} to payload worker failed.", message_id);
-                        }
+        assert_eq!(processed, (0..20).collect::<Vec<_>>());
+        // No events are accepted anymore.
+        assert!(tx.send(event(20, MessagePriority::Normal)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn draining_times_out() {
+        let (tx, rx) = async_channel::unbounded();
+        let intake = Arc::new(ProcessorIntake::default());
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let tasks = processing_tasks(4, &intake, &processed, Duration::from_millis(100));
+
+        for byte in 0..8 {
+            tx.send(event(byte, MessagePriority::Normal)).await.unwrap();
+        }
+
+        assert!(!drain(&rx, &intake, tasks, Duration::from_millis(10)).await);
+        assert!(processed.lock().is_empty());
+
+        // The tasks are aborted, nothing is processed after draining returned.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(processed.lock().is_empty());
+    }
 
-                        notify_message(message_id, notifier);
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn events_are_processed_concurrently() {
+        use tokio::sync::Barrier;
 
-                        bus.dispatch(MessageProcessed { message_id });
+        const TASKS: usize = 8;
 
-                        // TODO: boolean values are false at this point in time? trigger event from another location?
-                        bus.dispatch(VertexCreated {
-                            message_id,
-                            parent_message_ids: message.parents().to_vec(),
-                            is_solid: false,
-                            is_referenced: false,
-                            is_conflicting: false,
-                            is_milestone: false,
-                            is_tip: false,
-                            is_selected: false,
-                        });
+        let intake = Arc::new(ProcessorIntake::default());
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        // Each task only completes its event once all tasks are processing one.
+        let in_flight = Arc::new(Barrier::new(TASKS));
+        let handles = (0..TASKS)
+            .map(|_| {
+                let intake = intake.clone();
+                let processed = processed.clone();
+                let in_flight = in_flight.clone();
 
-                        metrics.new_messages_inc();
+                tokio::spawn(async move {
+                    while let Some(event) = intake.pop().await {
+                        in_flight.wait().await;
+                        processed.lock().push(event.message_packet.bytes[0]);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for byte in 0..TASKS as u8 * 4 {
+            intake.push(event(byte, MessagePriority::Normal));
+        }
+        intake.close(TASKS);
+
+        assert!(tokio::time::timeout(Duration::from_secs(10), future::join_all(handles))
+            .await
+            .is_ok());
+
+        let mut processed = processed.lock().clone();
+        processed.sort_unstable();
+
+        // Every event is processed exactly once.
+        assert_eq!(processed, (0..TASKS as u8 * 4).collect::<Vec<_>>());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrently_processed_duplicates_are_new_once() {
+        let (context, _command_rx) = processing_context();
+        let context = Arc::new(context);
+        let message = message(1);
+        let message_id = message.id().0;
+
+        // The same message received from several peers at once is processed by as many tasks.
+        let tasks = (0..8)
+            .map(|_| {
+                let context = context.clone();
+                let event = message_event(&message, None);
+
+                tokio::spawn(async move { process(&context, event).await })
+            })
+            .collect::<Vec<_>>();
+        for task in future::join_all(tasks).await {
+            task.unwrap();
+        }
+
+        // Only one of the tasks processes it as a new message, the others as a known one.
+        assert_eq!(context.metrics.new_messages(), 1);
+        assert_eq!(context.metrics.known_messages(), 7);
+        assert_eq!(context.metrics.corrupt_messages(), 0);
+        assert_eq!(context.broadcaster.queued(), 1);
+        assert_eq!(
+            context
+                .tangle
+                .get(&message_id)
+                .await
+                .map(|message| message.parents().len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn nothing_is_broadcast_when_disabled() {
+        let broadcaster = BroadcasterWorker::new();
+        let metrics = NodeMetrics::default();
+        let event = |byte| BroadcasterWorkerEvent {
+            source: None,
+            message: MessagePacket::new(vec![byte]),
+            priority: true,
+        };
+
+        broadcast(&broadcaster, &metrics, false, false, event(1));
+        broadcast(&broadcaster, &metrics, false, false, event(2));
+
+        assert_eq!(broadcaster.queued(), 0);
+        assert_eq!(metrics.broadcasts_suppressed(), 2);
+
+        broadcast(&broadcaster, &metrics, true, false, event(3));
+
+        assert_eq!(broadcaster.queued(), 1);
+        assert_eq!(metrics.broadcasts_suppressed(), 2);
+    }
+
+    #[tokio::test]
+    async fn messages_without_checked_pow_are_not_broadcast() {
+        let (context, _command_rx) = processing_context();
+        let trusted = message(0);
+        let mut event = message_event(&trusted, None);
+        event.pow_skipped = true;
+
+        process(&context, event).await;
+
+        assert!(context.tangle.contains(&trusted.id().0).await);
+        assert_eq!(context.broadcaster.queued(), 0);
+        assert_eq!(context.metrics.broadcasts_suppressed(), 1);
+
+        process(&context, message_event(&message(1), None)).await;
+
+        assert_eq!(context.broadcaster.queued(), 1);
+    }
+
+    #[test]
+    fn stale_messages_are_not_broadcast() {
+        let broadcaster = BroadcasterWorker::new();
+        let metrics = NodeMetrics::default();
+        let solid_index = MilestoneIndex(1000);
+        let threshold = Some(50);
+        let event = |byte| BroadcasterWorkerEvent {
+            source: None,
+            message: MessagePacket::new(vec![byte]),
+            priority: false,
+        };
+
+        let old = is_stale(Some(MilestoneIndex(900)), solid_index, threshold);
+        broadcast(&broadcaster, &metrics, true, old, event(1));
+
+        assert_eq!(broadcaster.queued(), 0);
+        assert_eq!(metrics.stale_broadcasts_suppressed(), 1);
+
+        let recent = is_stale(Some(MilestoneIndex(990)), solid_index, threshold);
+        broadcast(&broadcaster, &metrics, true, recent, event(2));
+
+        assert_eq!(broadcaster.queued(), 1);
+        assert_eq!(metrics.stale_broadcasts_suppressed(), 1);
+    }
+
+    #[test]
+    fn staleness_boundary() {
+        let solid_index = MilestoneIndex(1000);
+
+        assert!(is_stale(Some(MilestoneIndex(949)), solid_index, Some(50)));
+        assert!(!is_stale(Some(MilestoneIndex(950)), solid_index, Some(50)));
+        // Messages are never stale without threshold or known referenced milestone.
+        assert!(!is_stale(Some(MilestoneIndex(0)), solid_index, None));
+        assert!(!is_stale(None, solid_index, Some(50)));
+        // The threshold saturates while the node has hardly any solid milestones.
+        assert!(!is_stale(Some(MilestoneIndex(0)), MilestoneIndex(10), Some(50)));
+    }
+
+    #[test]
+    fn corrupt_bytes_are_detected_for_colliding_ids() {
+        // A weak hasher giving the same id to all messages of the same length.
+        let weak_id = |bytes: &[u8]| bytes.len();
+        let original = vec![1, 2, 3];
+        let forged = vec![3, 2, 1];
+        let stored = std::collections::HashMap::from([(weak_id(&original), RawMessage::from(original.clone()))]);
+
+        assert_eq!(weak_id(&forged), weak_id(&original));
+        assert!(!is_corrupt(stored.get(&weak_id(&original)), &original));
+        assert!(is_corrupt(stored.get(&weak_id(&forged)), &forged));
+        // Messages without stored bytes can't be verified.
+        assert!(!is_corrupt(None, &forged));
+    }
+
+    #[tokio::test]
+    async fn corrupt_known_messages_are_rejected() {
+        use crate::types::peer::Peer;
+
+        use bee_gossip::PeerInfo;
+        use futures::channel::oneshot;
+
+        // Sends the message with the given id but different bytes than the stored ones, returning the outcome.
+        async fn send_corrupt(context: &ProcessingContext<Storage>, peer_id: PeerId) -> SubmitOutcome {
+            let (notifier, outcome) = oneshot::channel();
+            let mut event = message_event(&message(0), Some(peer_id));
+            event.notifier = Some(notifier);
+
+            process(context, event).await;
+            outcome.await.unwrap()
+        }
+
+        let (mut context, mut command_rx) = processing_context();
+        let peer_id = PeerId::random();
+        context.peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+
+        let message = message(0);
+        let message_id = message.id().0;
+        process(&context, message_event(&message, None)).await;
+        context.tangle.insert_raw(&message_id, &RawMessage::from(vec![0; 8]));
+
+        assert!(matches!(
+            send_corrupt(&context, peer_id).await,
+            SubmitOutcome::Invalid(MessageSubmitterError::CorruptBytes(id)) if id == message_id
+        ));
+        assert_eq!(context.metrics.corrupt_messages(), 1);
+        assert_eq!(
+            context
+                .peer_manager
+                .get(&peer_id)
+                .unwrap()
+                .0
+                .metrics()
+                .corrupt_messages(),
+            1
+        );
+        assert!(command_rx.try_recv().is_err());
+
+        // The peer is banned if so configured.
+        context.ban_corrupt_peers = true;
+
+        assert!(matches!(
+            send_corrupt(&context, peer_id).await,
+            SubmitOutcome::Invalid(MessageSubmitterError::CorruptBytes(_))
+        ));
+        assert_eq!(context.metrics.corrupt_messages(), 2);
+        assert_eq!(command_rx.try_recv().unwrap(), Command::BanPeer { peer_id });
+    }
+
+    #[tokio::test]
+    async fn network_id_updates_switch_the_accepted_messages() {
+        use futures::channel::oneshot;
+
+        async fn send(context: &ProcessingContext<Storage>, network_id: u64, byte: u8) -> SubmitOutcome {
+            let message = MessageBuilder::<u64>::new()
+                .with_network_id(network_id)
+                .with_parents(Parents::new(vec![MessageId::new([byte; 32])]).unwrap())
+                .with_nonce_provider(0, 0.0)
+                .finish()
+                .unwrap();
+            let (notifier, outcome) = oneshot::channel();
+            let mut event = message_event(&message, None);
+            event.notifier = Some(notifier);
+
+            process(context, event).await;
+            outcome.await.unwrap()
+        }
+
+        let (mut context, _command_rx) = processing_context();
+        let network_ids = ResourceHandle::new(AcceptedNetworkIds::new(0));
+        context.validators = default_validators(
+            network_ids.clone(),
+            context.tangle.clone(),
+            context.requested_messages.clone(),
+            ResourceHandle::new(RequestedMilestones::default()),
+            None,
+            None,
+            Arc::new(bee_autopeering::SystemTimeSource),
+            Vec::new(),
+        );
+
+        assert!(matches!(send(&context, 0, 0).await, SubmitOutcome::Processed(_)));
+        assert!(matches!(
+            send(&context, 1, 1).await,
+            SubmitOutcome::Invalid(MessageSubmitterError::IncompatibleNetworkId { expected: 0, got: 1 })
+        ));
+
+        // Both network ids are accepted during the transition window.
+        assert!(network_ids.update(1, Duration::from_secs(60)));
+
+        assert!(matches!(send(&context, 0, 2).await, SubmitOutcome::Processed(_)));
+        assert!(matches!(send(&context, 1, 3).await, SubmitOutcome::Processed(_)));
+
+        // Only the new one without a transition window.
+        assert!(network_ids.update(2, Duration::ZERO));
+
+        assert!(matches!(
+            send(&context, 1, 4).await,
+            SubmitOutcome::Invalid(MessageSubmitterError::IncompatibleNetworkId { expected: 2, got: 1 })
+        ));
+        assert!(matches!(send(&context, 2, 5).await, SubmitOutcome::Processed(_)));
+    }
+
+    #[tokio::test]
+    async fn raw_messages_are_stored_as_received() {
+        let (mut context, _command_rx) = processing_context();
+        let stored = message(0);
+        let not_stored = message(1);
+
+        process(&context, message_event(&stored, None)).await;
+        context.store_raw_messages = false;
+        process(&context, message_event(&not_stored, None)).await;
+
+        assert_eq!(
+            context.tangle.get_raw(&stored.id().0).map(RawMessage::into_bytes),
+            Some(stored.pack_new())
+        );
+        assert!(context.tangle.contains(&not_stored.id().0).await);
+        assert_eq!(context.tangle.get_raw(&not_stored.id().0), None);
+    }
+
+    #[tokio::test]
+    async fn outcomes_reach_the_audit_trail() {
+        let (mut context, _command_rx) = processing_context();
+        let (tx, mut rx) = mpsc::channel(8);
+        context.audit = AuditTrail::new(tx, context.metrics.clone());
+        let message = message(0);
+
+        process(&context, message_event(&message, None)).await;
+        process(&context, message_event(&message, None)).await;
+        process(&context, event(1, MessagePriority::Normal)).await;
+
+        let mut outcomes = Vec::new();
+        while let Ok(record) = rx.try_recv() {
+            outcomes.push((record.message_id, record.outcome));
+        }
+
+        assert!(matches!(
+            outcomes.as_slice(),
+            [
+                (Some(processed), AuditOutcome::Processed),
+                (Some(known), AuditOutcome::Known),
+                (None, AuditOutcome::Invalid(_)),
+            ] if *processed == message.id().0 && *known == message.id().0
+        ));
+    }
+
+    // A submitter of which the messages go through the checks of the hasher, as configured, and then get processed
+    // with the given context.
+    fn submitter(context: ProcessingContext<Storage>, config: &ProtocolConfig) -> MessageSubmitterWorker {
+        use crate::workers::message::{
+            hasher::{self, check_length, check_pow, requires_pow},
+            pow_score_policy, MessageSubmitterWorkerEvent, PowContext,
+        };
+
+        use bee_pow::score::PoWScorer;
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+        let pow_policy = pow_score_policy(config);
+        let max_message_length = config.workers.max_message_length;
+
+        tokio::spawn(async move {
+            let mut pow = PoWScorer::new();
+
+            while let Some(MessageSubmitterWorkerEvent {
+                message,
+                notifier,
+                trusted,
+            }) = rx.recv().await
+            {
+                let pow_skipped = !requires_pow(None, trusted);
+                let checked = check_length(&message, max_message_length).and_then(|_| {
+                    if pow_skipped {
+                        Ok(())
+                    } else {
+                        check_pow(
+                            &mut pow,
+                            &*pow_policy,
+                            &PowContext::default(),
+                            &message,
+                            &context.metrics,
+                            false,
+                        )
                     }
                 });
+
+                match checked {
+                    Ok(()) => {
+                        let event = ProcessorWorkerEvent {
+                            message_id: hasher::message_id(&message),
+                            from: None,
+                            message_packet: MessagePacket::new(message),
+                            notifier: Some(notifier),
+                            priority: MessagePriority::Normal,
+                            pow_skipped,
+                        };
+
+                        process(&context, event).await;
+                    }
+                    Err(error) => {
+                        notify_invalid_message(error, &context.metrics, &context.peer_manager, None, Some(notifier))
+                    }
+                }
             }
+        });
 
-            while let Some(event) = receiver.next().await {
-                let _ = tx.send(event).await;
+        MessageSubmitterWorker::new(tx)
+    }
+
+    // A configuration accepting messages of any PoW score.
+    fn without_pow() -> ProtocolConfig {
+        ProtocolConfig::build().minimum_pow_score(0.0).finish()
+    }
+
+    // The validators of the node accepting the network id 0, with the given milestone lookahead, maximum clock drift
+    // and blocked index prefixes.
+    fn validators(
+        context: &ProcessingContext<Storage>,
+        milestone_lookahead: Option<u32>,
+        max_clock_drift: Option<u64>,
+        blocked_index_prefixes: Vec<Vec<u8>>,
+    ) -> MessageValidators {
+        default_validators(
+            ResourceHandle::new(AcceptedNetworkIds::new(0)),
+            context.tangle.clone(),
+            context.requested_messages.clone(),
+            ResourceHandle::new(RequestedMilestones::default()),
+            milestone_lookahead,
+            max_clock_drift,
+            Arc::new(bee_autopeering::SystemTimeSource),
+            blocked_index_prefixes,
+        )
+    }
+
+    fn message_with_payload(payload: Payload) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([0; 32])]).unwrap())
+            .with_payload(payload)
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    fn milestone(index: u32, timestamp: u64) -> Message {
+        use bee_message::payload::milestone::{
+            MilestonePayload, MilestonePayloadEssence, MILESTONE_MERKLE_PROOF_LENGTH,
+        };
+
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(index),
+            timestamp,
+            Parents::new(vec![MessageId::new([1; 32])]).unwrap(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            vec![[2; 32]],
+            None,
+        )
+        .unwrap();
+
+        message_with_payload(MilestonePayload::new(essence, vec![[0; 64]]).unwrap().into())
+    }
+
+    #[tokio::test]
+    async fn submit_processed() {
+        let (context, _command_rx) = processing_context();
+        let tangle = context.tangle.clone();
+        let submitter = submitter(context, &without_pow());
+        let (message_id, bytes) = message(0).id();
+
+        assert_eq!(submitter.submit_message(bytes).await.unwrap(), message_id);
+        assert_eq!(submitter.submit(message(1)).await.unwrap(), message(1).id().0);
+        assert!(tangle.contains(&message_id).await);
+    }
+
+    #[tokio::test]
+    async fn submit_known() {
+        let (context, _command_rx) = processing_context();
+        let submitter = submitter(context, &without_pow());
+        let (message_id, bytes) = message(0).id();
+
+        assert_eq!(submitter.submit(message(0)).await.unwrap(), message_id);
+        // Submitting a known message is a success, unless its bytes are submitted.
+        assert_eq!(submitter.submit(message(0)).await.unwrap(), message_id);
+        assert!(matches!(
+            submitter.submit_message(bytes).await,
+            Err(MessageSubmitterError::Known(id)) if id == message_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_invalid_message() {
+        let (context, _command_rx) = processing_context();
+        let submitter = submitter(context, &without_pow());
+
+        assert!(matches!(
+            submitter.submit_message(vec![0; 16]).await,
+            Err(MessageSubmitterError::InvalidMessage(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_incompatible_network_id() {
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, None, None, Vec::new());
+        let submitter = submitter(context, &without_pow());
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(1)
+            .with_parents(Parents::new(vec![MessageId::new([0; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+
+        assert!(matches!(
+            submitter.submit(message).await,
+            Err(MessageSubmitterError::IncompatibleNetworkId { expected: 0, got: 1 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_insufficient_pow_score() {
+        let (context, _command_rx) = processing_context();
+        let submitter = submitter(context, &ProtocolConfig::build().finish());
+        let (message_id, bytes) = message(0).id();
+
+        assert!(matches!(
+            submitter.submit_message(bytes.clone()).await,
+            Err(MessageSubmitterError::InsufficientPowScore { minimum, .. }) if minimum == 4000.0
+        ));
+        assert!(matches!(
+            submitter.submit(message(0)).await,
+            Err(MessageSubmitterError::InsufficientPowScore { .. })
+        ));
+        // Only trusted submissions skip the PoW check.
+        assert_eq!(submitter.submit_trusted_message(bytes).await.unwrap(), message_id);
+    }
+
+    #[tokio::test]
+    async fn submit_milestone_too_far_ahead() {
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, Some(10), None, Vec::new());
+        let submitter = submitter(context, &without_pow());
+
+        assert!(matches!(
+            submitter.submit(milestone(11, 0)).await,
+            Err(MessageSubmitterError::MilestoneTooFarAhead {
+                index: MilestoneIndex(11),
+                confirmed_index: MilestoneIndex(0)
+            })
+        ));
+        assert!(submitter.submit(milestone(10, 0)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn submit_timestamp_too_far_ahead() {
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, None, Some(60), Vec::new());
+        let submitter = submitter(context, &without_pow());
+
+        assert!(matches!(
+            submitter.submit(milestone(1, u64::MAX)).await,
+            Err(MessageSubmitterError::TimestampTooFarAhead {
+                timestamp: u64::MAX,
+                ..
+            })
+        ));
+        assert!(submitter.submit(milestone(1, 0)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn submit_too_long() {
+        let (context, _command_rx) = processing_context();
+        let submitter = submitter(
+            context,
+            &ProtocolConfig::build()
+                .minimum_pow_score(0.0)
+                .max_message_length(16)
+                .finish(),
+        );
+        let bytes = message(0).pack_new();
+        let length = bytes.len();
+
+        assert!(matches!(
+            submitter.submit_message(bytes).await,
+            Err(MessageSubmitterError::TooLong { length: l, maximum: 16 }) if l == length
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_blocked_index() {
+        use bee_message::payload::indexation::IndexationPayload;
+
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, None, None, vec![b"reserved.".to_vec()]);
+        let submitter = submitter(context, &without_pow());
+        let indexation = |index: &[u8]| {
+            message_with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(index, &[]).unwrap(),
+            )))
+        };
+
+        assert!(matches!(
+            submitter.submit(indexation(b"reserved.namespace")).await,
+            Err(MessageSubmitterError::BlockedIndex(index)) if index == hex::encode(b"reserved.namespace")
+        ));
+        assert!(submitter.submit(indexation(b"wallet")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn blocked_indexes_are_rejected_before_insertion() {
+        use bee_message::payload::indexation::IndexationPayload;
+        use futures::channel::oneshot;
+
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, None, None, vec![b"reserved.".to_vec()]);
+        let indexation = |index: &[u8]| {
+            message_with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(index, &[]).unwrap(),
+            )))
+        };
+        let send = |message: &Message| {
+            let (notifier, outcome) = oneshot::channel();
+            let mut event = message_event(message, Some(PeerId::random()));
+            event.notifier = Some(notifier);
+
+            async {
+                process(&context, event).await;
+                outcome.await.unwrap()
             }
+        };
 
-            info!("Stopped.");
-        });
+        let blocked = indexation(b"reserved.namespace");
 
-        Ok(Self { tx })
+        assert!(matches!(
+            send(&blocked).await,
+            SubmitOutcome::Invalid(MessageSubmitterError::BlockedIndex(index))
+                if index == hex::encode(b"reserved.namespace")
+        ));
+        assert!(!context.tangle.contains(&blocked.id().0).await);
+        assert_eq!(context.metrics.blocked_index_messages(), 1);
+        assert_eq!(context.metrics.invalid_messages(), 1);
+
+        // Others are accepted and stored.
+        let allowed = indexation(b"wallet");
+
+        assert!(matches!(send(&allowed).await, SubmitOutcome::Processed(id) if id == allowed.id().0));
+        assert!(context.tangle.contains(&allowed.id().0).await);
+        assert_eq!(context.metrics.invalid_messages(), 1);
+    }
+
+    #[tokio::test]
+    async fn submit_rejected() {
+        use crate::workers::message::MessageValidator;
+
+        // Only accepts messages without payload.
+        struct PayloadValidator;
+
+        impl MessageValidator for PayloadValidator {
+            fn validate(&self, _message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+                match message.payload() {
+                    None => Ok(()),
+                    Some(_) => Err(MessageSubmitterError::Rejected("payload".to_string())),
+                }
+            }
+        }
+
+        let (mut context, _command_rx) = processing_context();
+        context.validators = MessageValidators::new().with_validator(PayloadValidator);
+        let submitter = submitter(context, &without_pow());
+
+        assert!(matches!(
+            submitter.submit(milestone(1, 0)).await,
+            Err(MessageSubmitterError::Rejected(reason)) if reason == "payload"
+        ));
+        assert!(submitter.submit(message(0)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn submit_corrupt_bytes() {
+        let (context, _command_rx) = processing_context();
+        let tangle = context.tangle.clone();
+        let submitter = submitter(context, &without_pow());
+        let (message_id, bytes) = message(0).id();
+
+        assert_eq!(submitter.submit_message(bytes.clone()).await.unwrap(), message_id);
+        tangle.insert_raw(&message_id, &RawMessage::from(vec![0; 8]));
+
+        assert!(matches!(
+            submitter.submit_message(bytes).await,
+            Err(MessageSubmitterError::CorruptBytes(id)) if id == message_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_messages_results_are_aligned() {
+        let (mut context, _command_rx) = processing_context();
+        context.validators = validators(&context, None, None, Vec::new());
+        let submitter = submitter(context, &without_pow());
+        // Every third message belongs to another network.
+        let messages = (0..2000u32)
+            .map(|i| {
+                let mut parent = [0; 32];
+                parent[..4].copy_from_slice(&i.to_le_bytes());
+
+                MessageBuilder::<u64>::new()
+                    .with_network_id((i % 3 == 0) as u64)
+                    .with_parents(Parents::new(vec![MessageId::new(parent)]).unwrap())
+                    .with_nonce_provider(0, 0.0)
+                    .finish()
+                    .unwrap()
+                    .id()
+            })
+            .collect::<Vec<_>>();
+
+        let results = submitter
+            .submit_messages(messages.iter().map(|(_, bytes)| bytes.clone()).collect())
+            .await;
+
+        assert_eq!(results.len(), messages.len());
+        for (i, (result, (message_id, _))) in results.iter().zip(messages.iter()).enumerate() {
+            if i % 3 == 0 {
+                assert!(matches!(
+                    result,
+                    Err(MessageSubmitterError::IncompatibleNetworkId { .. })
+                ));
+            } else {
+                assert_eq!(result.as_ref().unwrap(), message_id);
+            }
+        }
+    }
+
+    #[test]
+    fn requested_deliveries_are_credited() {
+        use crate::types::peer::Peer;
+
+        use bee_gossip::PeerInfo;
+
+        let peer_manager = PeerManager::new();
+        let peer_id = PeerId::random();
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+
+        credit_requested_message(&peer_manager, Some(&peer_id));
+        credit_requested_message(&peer_manager, Some(&peer_id));
+        // Neither locally submitted messages nor messages of unknown peers are credited.
+        credit_requested_message(&peer_manager, None);
+        credit_requested_message(&peer_manager, Some(&PeerId::random()));
+
+        assert_eq!(peer_manager.get(&peer_id).unwrap().0.metrics().requested_messages(), 2);
+    }
+
+    #[test]
+    fn only_known_peers_are_prioritized() {
+        assert_eq!(
+            MessagePriority::from_relation(PeerRelation::Known, true),
+            MessagePriority::High
+        );
+        assert_eq!(
+            MessagePriority::from_relation(PeerRelation::Discovered, true),
+            MessagePriority::Normal
+        );
+        assert_eq!(
+            MessagePriority::from_relation(PeerRelation::Known, false),
+            MessagePriority::Normal
+        );
+    }
+
+    #[tokio::test]
+    async fn processing_times_are_recorded() {
+        use crate::workers::message::{hasher::check_pow, PowContext, StaticPowScorePolicy};
+
+        use bee_pow::score::PoWScorer;
+
+        // Checks the PoW of the messages and processes them, like the hasher and the processor do.
+        async fn receive(context: &ProcessingContext<Storage>, pow: &mut PoWScorer, bytes: std::ops::Range<u8>) {
+            for byte in bytes {
+                let event = message_event(&message(byte), Some(PeerId::random()));
+
+                check_pow(
+                    pow,
+                    &StaticPowScorePolicy::new(0.0),
+                    &PowContext::default(),
+                    &event.message_packet.bytes,
+                    &context.metrics,
+                    context.processing_time,
+                )
+                .unwrap();
+                process(context, event).await;
+            }
+        }
+
+        let (mut context, _command_rx) = processing_context();
+        let mut pow = PoWScorer::new();
+
+        receive(&context, &mut pow, 0..5).await;
+
+        assert_eq!(context.metrics.new_messages(), 5);
+        assert_eq!(context.metrics.pow_check_time().count(), 5);
+        assert_eq!(context.metrics.unpack_time().count(), 5);
+        assert_eq!(context.metrics.insert_time().count(), 5);
+
+        // Nothing is recorded when disabled.
+        context.unpack_time = false;
+        context.processing_time = false;
+        receive(&context, &mut pow, 5..10).await;
+
+        assert_eq!(context.metrics.new_messages(), 10);
+        assert_eq!(context.metrics.pow_check_time().count(), 5);
+        assert_eq!(context.metrics.unpack_time().count(), 5);
+        assert_eq!(context.metrics.insert_time().count(), 5);
+    }
+
+    #[test]
+    fn message_span_carries_message_and_peer_ids() {
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        use std::fmt::Debug;
+
+        // A subscriber capturing the fields recorded on spans.
+        #[derive(Clone, Default)]
+        struct SpanFields(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+        impl Visit for SpanFields {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                self.0.lock().push((field.name(), format!("{:?}", value)));
+            }
+        }
+
+        impl Subscriber for SpanFields {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                span.record(&mut self.clone());
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, values: &Record<'_>) {
+                values.record(&mut self.clone());
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let message_id = MessageId::new([1; 32]);
+        let peer_id = PeerId::random();
+
+        let fields = SpanFields::default();
+        tracing::subscriber::with_default(fields.clone(), || message_span(message_id, Some(&peer_id)));
+        assert_eq!(
+            *fields.0.lock(),
+            vec![("message_id", message_id.to_string()), ("peer_id", peer_id.to_string())]
+        );
+
+        // Messages submitted by this node have no peer id.
+        let fields = SpanFields::default();
+        tracing::subscriber::with_default(fields.clone(), || message_span(message_id, None));
+        assert_eq!(*fields.0.lock(), vec![("message_id", message_id.to_string())]);
     }
 }
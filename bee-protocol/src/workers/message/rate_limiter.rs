@@ -0,0 +1,122 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_gossip::PeerId;
+
+use std::{collections::HashMap, time::Instant};
+
+// Holds up to `rate` tokens and is refilled at `rate` tokens per second.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, now: Instant) -> Self {
+        Self {
+            rate: rate as f64,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// Limits the number of messages per second each peer may send, allowing bursts of up to a second worth of messages.
+pub(crate) struct PeerRateLimiter {
+    default_rate: Option<u32>,
+    rates: HashMap<PeerId, u32>,
+    buckets: HashMap<PeerId, TokenBucket>,
+}
+
+impl PeerRateLimiter {
+    // Creates a limiter applying `default_rate` to all peers, if any, unless `rates` holds a rate for the peer.
+    pub(crate) fn new(default_rate: Option<u32>, rates: HashMap<PeerId, u32>) -> Self {
+        Self {
+            default_rate,
+            rates,
+            buckets: HashMap::new(),
+        }
+    }
+
+    // Returns whether a message of the peer may be processed, consuming a token of its bucket if so.
+    pub(crate) fn allow(&mut self, peer_id: &PeerId, now: Instant) -> bool {
+        let rate = match self.rates.get(peer_id).copied().or(self.default_rate) {
+            Some(rate) => rate,
+            None => return true,
+        };
+
+        self.buckets
+            .entry(*peer_id)
+            .or_insert_with(|| TokenBucket::new(rate, now))
+            .try_take(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::time::Duration;
+
+    fn burst(limiter: &mut PeerRateLimiter, peer_id: &PeerId, count: usize, now: Instant) -> usize {
+        (0..count).filter(|_| limiter.allow(peer_id, now)).count()
+    }
+
+    #[test]
+    fn excess_messages_of_a_peer_are_dropped() {
+        let mut limiter = PeerRateLimiter::new(Some(10), HashMap::new());
+        let flooding = PeerId::random();
+        let other = PeerId::random();
+        let now = Instant::now();
+
+        assert_eq!(burst(&mut limiter, &flooding, 25, now), 10);
+        assert_eq!(burst(&mut limiter, &other, 5, now), 5);
+
+        // Half a second later, half of the budget is available again.
+        let later = now + Duration::from_millis(500);
+        assert_eq!(burst(&mut limiter, &flooding, 25, later), 5);
+        assert_eq!(burst(&mut limiter, &other, 5, later), 5);
+
+        // The budget does not grow beyond a second worth of messages.
+        let much_later = now + Duration::from_secs(60);
+        assert_eq!(burst(&mut limiter, &flooding, 25, much_later), 10);
+    }
+
+    #[test]
+    fn peer_rates_override_the_default() {
+        let trusted = PeerId::random();
+        let muted = PeerId::random();
+        let mut limiter = PeerRateLimiter::new(Some(10), [(trusted, 100), (muted, 0)].into_iter().collect());
+        let now = Instant::now();
+
+        assert_eq!(burst(&mut limiter, &trusted, 150, now), 100);
+        assert_eq!(burst(&mut limiter, &muted, 5, now), 0);
+        assert_eq!(burst(&mut limiter, &PeerId::random(), 15, now), 10);
+    }
+
+    #[test]
+    fn no_limit_without_rate() {
+        let limited = PeerId::random();
+        let mut limiter = PeerRateLimiter::new(None, [(limited, 1)].into_iter().collect());
+        let now = Instant::now();
+
+        assert_eq!(burst(&mut limiter, &PeerId::random(), 1000, now), 1000);
+        assert_eq!(burst(&mut limiter, &limited, 5, now), 1);
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workers::timed_cache::TimedCache;
+
+use bee_common::packable::Packable;
+use bee_message::{payload::Payload, Message, MessageId};
+use bee_tangle::metadata::MessageMetadata;
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+
+use std::time::Instant;
+
+// The maximum number of payloads remembered to detect reattachments.
+const REATTACHMENT_CACHE_CAPACITY: usize = 50_000;
+
+// Detects messages carrying the same indexation or transaction payload as a previously seen message with another id.
+pub(crate) struct ReattachmentDetector {
+    payloads: TimedCache<[u8; 32], MessageId>,
+}
+
+impl Default for ReattachmentDetector {
+    fn default() -> Self {
+        Self {
+            payloads: TimedCache::new(REATTACHMENT_CACHE_CAPACITY, None),
+        }
+    }
+}
+
+impl ReattachmentDetector {
+    // Flags the metadata of a reattached message and returns the id of the message it reattaches, if any.
+    pub(crate) fn detect(
+        &mut self,
+        message_id: MessageId,
+        message: &Message,
+        metadata: &mut MessageMetadata,
+        now: Instant,
+    ) -> Option<MessageId> {
+        let payload = match message.payload() {
+            Some(payload @ (Payload::Indexation(_) | Payload::Transaction(_))) => payload,
+            _ => return None,
+        };
+        let digest = Blake2b256::digest(&payload.pack_new()).into();
+
+        match self.payloads.get(&digest, now) {
+            Some(original) if *original != message_id => {
+                let original = *original;
+                metadata.flags_mut().set_reattachment(true);
+                Some(original)
+            }
+            Some(_) => None,
+            None => {
+                self.payloads.insert(digest, message_id, now);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{parents::Parents, payload::indexation::IndexationPayload, MessageBuilder};
+
+    fn message(parent: u8, payload: Option<Payload>) -> (MessageId, Message) {
+        let builder = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([parent; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0);
+        let message = match payload {
+            Some(payload) => builder.with_payload(payload),
+            None => builder,
+        }
+        .finish()
+        .unwrap();
+
+        (message.id().0, message)
+    }
+
+    fn indexation(data: &[u8]) -> Option<Payload> {
+        Some(Payload::Indexation(Box::new(
+            IndexationPayload::new(b"index", data).unwrap(),
+        )))
+    }
+
+    #[test]
+    fn reattachments_are_flagged() {
+        let mut detector = ReattachmentDetector::default();
+        let now = Instant::now();
+        let (original_id, original) = message(1, indexation(b"data"));
+        let (reattachment_id, reattachment) = message(2, indexation(b"data"));
+        let (other_id, other) = message(3, indexation(b"other data"));
+
+        let mut metadata = MessageMetadata::arrived();
+        assert_eq!(detector.detect(original_id, &original, &mut metadata, now), None);
+        assert!(!metadata.flags().is_reattachment());
+
+        let mut metadata = MessageMetadata::arrived();
+        assert_eq!(
+            detector.detect(reattachment_id, &reattachment, &mut metadata, now),
+            Some(original_id)
+        );
+        assert!(metadata.flags().is_reattachment());
+
+        let mut metadata = MessageMetadata::arrived();
+        assert_eq!(detector.detect(other_id, &other, &mut metadata, now), None);
+        assert!(!metadata.flags().is_reattachment());
+    }
+
+    #[test]
+    fn messages_are_not_reattachments_of_themselves() {
+        let mut detector = ReattachmentDetector::default();
+        let now = Instant::now();
+        let (message_id, message) = message(1, indexation(b"data"));
+
+        for _ in 0..2 {
+            let mut metadata = MessageMetadata::arrived();
+            assert_eq!(detector.detect(message_id, &message, &mut metadata, now), None);
+            assert!(!metadata.flags().is_reattachment());
+        }
+    }
+
+    #[test]
+    fn messages_without_payload_are_ignored() {
+        let mut detector = ReattachmentDetector::default();
+        let now = Instant::now();
+        let (first_id, first) = message(1, None);
+        let (second_id, second) = message(2, None);
+        let mut metadata = MessageMetadata::arrived();
+
+        assert_eq!(detector.detect(first_id, &first, &mut metadata, now), None);
+        assert_eq!(detector.detect(second_id, &second, &mut metadata, now), None);
+        assert!(!metadata.flags().is_reattachment());
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Samples high-volume log lines by only letting one in every `rate` calls through.
+pub(crate) struct LogSampler {
+    rate: u64,
+    counter: AtomicU64,
+}
+
+impl LogSampler {
+    /// Creates a new `LogSampler`. A rate of `0` or `1` lets every call through.
+    pub(crate) fn new(rate: u64) -> Self {
+        Self {
+            rate: rate.max(1),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns whether the current call should be logged.
+    pub(crate) fn sample(&self) -> bool {
+        if self.rate == 1 {
+            return true;
+        }
+
+        // Panic: the closure always returns `Some`.
+        let previous = self
+            .counter
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                Some(if count + 1 >= self.rate { 0 } else { count + 1 })
+            })
+            .unwrap();
+
+        previous == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sampler_log_all() {
+        let sampler = LogSampler::new(1);
+
+        assert!((0..10).all(|_| sampler.sample()));
+    }
+
+    #[test]
+    fn sampler_zero_rate_logs_all() {
+        let sampler = LogSampler::new(0);
+
+        assert!((0..10).all(|_| sampler.sample()));
+    }
+
+    #[test]
+    fn sampler_one_in_n() {
+        let sampler = LogSampler::new(4);
+
+        assert_eq!((0..20).filter(|_| sampler.sample()).count(), 5);
+    }
+}
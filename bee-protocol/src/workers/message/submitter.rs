@@ -2,67 +2,366 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    types::metrics::NodeMetrics,
-    workers::{packets::MessagePacket, storage::StorageBackend, HasherWorker, HasherWorkerEvent},
+    types::metrics::{NodeMetrics, PeerMetrics},
+    workers::{
+        message::{hasher, ConfirmationWaiters, MessagePriority},
+        packets::MessagePacket,
+        peer::MessageOutcome,
+        storage::StorageBackend,
+        HasherWorker, HasherWorkerEvent, PeerManager,
+    },
 };
 
-use bee_message::MessageId;
+use bee_gossip::PeerId;
+use bee_ledger::workers::event::MessageReferenced;
+use bee_message::{milestone::MilestoneIndex, Message, MessageId};
 use bee_runtime::{
     node::Node,
+    resource::ResourceHandle,
     shutdown_stream::ShutdownStream,
     worker::{Error as WorkerError, Worker},
 };
+use bee_tangle::{Tangle, TangleWorker};
 
 use async_trait::async_trait;
-use futures::{channel::oneshot::Sender, stream::StreamExt};
+use futures::{
+    channel::oneshot::{self, Sender},
+    future::{BoxFuture, FutureExt},
+    stream::{self, StreamExt},
+};
 use log::{error, info, trace};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use std::{any::TypeId, fmt};
+use std::{any::TypeId, time::Duration};
+
+// The maximum number of messages of a batch awaiting their outcome at the same time.
+const MAX_IN_FLIGHT_SUBMISSIONS: usize = 512;
+
+// Looks up the index of the milestone that referenced a message, if the message is known and referenced.
+type ReferencingMilestone = Box<dyn Fn(MessageId) -> BoxFuture<'static, Option<MilestoneIndex>> + Send + Sync>;
+
+// Counts an invalid message in the aggregate and, if any, the counter of its kind.
+fn count_invalid_message(error: &MessageSubmitterError, metrics: &NodeMetrics, peer_metrics: Option<&PeerMetrics>) {
+    metrics.invalid_messages_inc();
+    peer_metrics.map(PeerMetrics::invalid_messages_inc);
+
+    match error {
+        MessageSubmitterError::InvalidMessage(e) => {
+            metrics.invalid_parse_inc();
+            peer_metrics.map(PeerMetrics::invalid_parse_inc);
+
+            // Parents are checked to be unique and sorted while unpacking.
+            if matches!(e, bee_message::Error::ParentsNotUniqueSorted) {
+                metrics.invalid_parents_inc();
+                peer_metrics.map(PeerMetrics::invalid_parents_inc);
+            }
+        }
+        MessageSubmitterError::IncompatibleNetworkId { .. } => {
+            metrics.invalid_network_id_inc();
+            peer_metrics.map(PeerMetrics::invalid_network_id_inc);
+        }
+        MessageSubmitterError::InsufficientPowScore { .. } => {
+            metrics.invalid_pow_inc();
+            peer_metrics.map(PeerMetrics::invalid_pow_inc);
+        }
+        MessageSubmitterError::MilestoneTooFarAhead { .. } => {
+            metrics.future_milestone_messages_inc();
+        }
+        MessageSubmitterError::TimestampTooFarAhead { .. } => {
+            metrics.invalid_timestamp_inc();
+            peer_metrics.map(PeerMetrics::invalid_timestamp_inc);
+        }
+        MessageSubmitterError::TooLong { .. } => {
+            metrics.oversized_messages_inc();
+            peer_metrics.map(PeerMetrics::oversized_messages_inc);
+        }
+        MessageSubmitterError::Rejected(_) => {
+            metrics.rejected_messages_inc();
+        }
+        MessageSubmitterError::BlockedIndex(_) => {
+            metrics.blocked_index_messages_inc();
+        }
+        _ => {}
+    }
+}
 
 pub(crate) fn notify_invalid_message(
-    error: String,
+    error: MessageSubmitterError,
     metrics: &NodeMetrics,
-    notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
+    peer_manager: &PeerManager,
+    from: Option<&PeerId>,
+    notifier: Option<Sender<SubmitOutcome>>,
 ) {
     trace!("{}", error);
-    metrics.invalid_messages_inc();
+    count_invalid_message(
+        &error,
+        metrics,
+        from.and_then(|peer_id| peer_manager.get(peer_id))
+            .as_ref()
+            .map(|peer| peer.0.metrics()),
+    );
+
+    if let Some(peer_id) = from {
+        let outcome = match error {
+            MessageSubmitterError::InsufficientPowScore { .. } => MessageOutcome::LowPow,
+            _ => MessageOutcome::Invalid,
+        };
+        peer_manager.record_outcome(peer_id, outcome);
+    }
 
     if let Some(notifier) = notifier {
-        if let Err(e) = notifier.send(Err(MessageSubmitterError(error))) {
+        if let Err(e) = notifier.send(SubmitOutcome::Invalid(error)) {
             error!("Failed to send error: {:?}.", e);
         }
     }
 }
 
-pub(crate) fn notify_message(
-    message_id: MessageId,
-    notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
-) {
+pub(crate) fn notify_known_message(notifier: Option<Sender<SubmitOutcome>>) {
     if let Some(notifier) = notifier {
-        if let Err(e) = notifier.send(Ok(message_id)) {
+        if let Err(e) = notifier.send(SubmitOutcome::Known) {
+            error!("Failed to send known message: {:?}.", e);
+        }
+    }
+}
+
+/// Discards the notifier if its receiver is gone, e.g. because the submitter timed out.
+pub(crate) fn discard_canceled_notifier(
+    notifier: Option<Sender<SubmitOutcome>>,
+    metrics: &NodeMetrics,
+) -> Option<Sender<SubmitOutcome>> {
+    match notifier {
+        Some(notifier) if notifier.is_canceled() => {
+            trace!("Submitter gone before being notified.");
+            metrics.notifiers_gone_inc();
+            None
+        }
+        notifier => notifier,
+    }
+}
+
+pub(crate) fn notify_message(message_id: MessageId, notifier: Option<Sender<SubmitOutcome>>) {
+    if let Some(notifier) = notifier {
+        if let Err(e) = notifier.send(SubmitOutcome::Processed(message_id)) {
             error!("Failed to send message id: {:?}.", e);
         }
     }
 }
 
+/// Errors occurring when submitting a message.
+#[derive(Debug, thiserror::Error)]
+pub enum MessageSubmitterError {
+    /// The message could not be unpacked.
+    #[error("Invalid message: {0:?}.")]
+    InvalidMessage(bee_message::Error),
+    /// The message belongs to another network.
+    #[error("Incompatible network ID {got} != {expected}.")]
+    IncompatibleNetworkId {
+        /// The network id of the node.
+        expected: u64,
+        /// The network id of the message.
+        got: u64,
+    },
+    /// The PoW score of the message is too low.
+    #[error("Insufficient pow score: {score} < {minimum}.")]
+    InsufficientPowScore {
+        /// The PoW score of the message.
+        score: f64,
+        /// The minimum PoW score required by the node.
+        minimum: f64,
+    },
+    /// The message contains a milestone too far ahead of the confirmed milestone.
+    #[error("Milestone index {index} too far ahead of confirmed milestone index {confirmed_index}.")]
+    MilestoneTooFarAhead {
+        /// The index of the milestone.
+        index: MilestoneIndex,
+        /// The index of the confirmed milestone.
+        confirmed_index: MilestoneIndex,
+    },
+    /// The message contains a milestone with a timestamp too far ahead of the local time.
+    #[error("Timestamp {timestamp} too far ahead of local time {now}.")]
+    TimestampTooFarAhead {
+        /// The timestamp of the milestone, in seconds since the Unix epoch.
+        timestamp: u64,
+        /// The local time, in seconds since the Unix epoch.
+        now: u64,
+    },
+    /// The message exceeds the maximum length.
+    #[error("Message length {length} exceeds the maximum length {maximum}.")]
+    TooLong {
+        /// The length of the message, in bytes.
+        length: usize,
+        /// The maximum length of a message, in bytes.
+        maximum: usize,
+    },
+    /// The message contains an indexation payload whose index matches a blocked prefix.
+    #[error("Index {0} matches a blocked prefix.")]
+    BlockedIndex(String),
+    /// The message was rejected by a validator provided by the operator.
+    #[error("Message rejected: {0}.")]
+    Rejected(String),
+    /// The bytes of the message differ from the stored ones of the known message with the same id.
+    #[error("Corrupt bytes for known message {0}.")]
+    CorruptBytes(MessageId),
+    /// The message is already known.
+    #[error("Message {0} is already known.")]
+    Known(MessageId),
+    /// The message could not be handed to, or its outcome not be received from, the submitter.
+    #[error("can not submit message: {0}")]
+    Unavailable(String),
+}
+
+/// The outcome of processing a submitted message.
 #[derive(Debug)]
-pub struct MessageSubmitterError(pub String);
+pub enum SubmitOutcome {
+    /// The message was new and got processed.
+    Processed(MessageId),
+    /// The message was already known.
+    Known,
+    /// The message was rejected for being invalid.
+    Invalid(MessageSubmitterError),
+}
 
-impl fmt::Display for MessageSubmitterError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+impl SubmitOutcome {
+    /// Interprets the outcome of submitting the message with the given id, treating an already known message as a
+    /// successful submission.
+    pub fn into_result(self, message_id: MessageId) -> Result<MessageId, MessageSubmitterError> {
+        match self {
+            Self::Processed(message_id) => Ok(message_id),
+            Self::Known => Ok(message_id),
+            Self::Invalid(e) => Err(e),
+        }
     }
 }
 
+/// The outcome of awaiting the confirmation of an accepted message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfirmationOutcome {
+    /// The message was referenced by the milestone with the given index.
+    Confirmed {
+        /// The index of the confirming milestone.
+        milestone_index: MilestoneIndex,
+    },
+    /// The message was not confirmed before the timeout.
+    TimedOut,
+}
+
 pub struct MessageSubmitterWorkerEvent {
     pub message: Vec<u8>,
-    pub notifier: Sender<Result<MessageId, MessageSubmitterError>>,
+    pub notifier: Sender<SubmitOutcome>,
+    /// Whether the message comes from a trusted local client, in which case its PoW is not checked and it is not
+    /// broadcast.
+    pub trusted: bool,
 }
 
 pub struct MessageSubmitterWorker {
     pub tx: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    confirmations: ResourceHandle<ConfirmationWaiters>,
+    referencing_milestone: ReferencingMilestone,
+}
+
+impl MessageSubmitterWorker {
+    #[cfg(test)]
+    pub(crate) fn new(tx: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>) -> Self {
+        Self {
+            tx,
+            confirmations: ResourceHandle::new(ConfirmationWaiters::default()),
+            referencing_milestone: Box::new(|_| futures::future::ready(None).boxed()),
+        }
+    }
+
+    /// Submits a message and waits for its outcome, treating an already known message as a successful submission.
+    pub async fn submit(&self, message: Message) -> Result<MessageId, MessageSubmitterError> {
+        let (message_id, message_bytes) = message.id();
+
+        self.outcome(message_bytes, false).await?.into_result(message_id)
+    }
+
+    /// Submits the bytes of a message and waits for its outcome, rejecting an already known message with
+    /// `MessageSubmitterError::Known`.
+    pub async fn submit_message(&self, bytes: Vec<u8>) -> Result<MessageId, MessageSubmitterError> {
+        self.submit_message_with(bytes, false).await
+    }
+
+    /// Submits the bytes of a message from a trusted local client and waits for its outcome, like `submit_message`
+    /// but without checking the PoW of the message. Since peers would reject it, the message is not broadcast.
+    pub async fn submit_trusted_message(&self, bytes: Vec<u8>) -> Result<MessageId, MessageSubmitterError> {
+        self.submit_message_with(bytes, true).await
+    }
+
+    async fn submit_message_with(&self, bytes: Vec<u8>, trusted: bool) -> Result<MessageId, MessageSubmitterError> {
+        let message_id = hasher::message_id(&bytes);
+
+        match self.outcome(bytes, trusted).await? {
+            SubmitOutcome::Processed(message_id) => Ok(message_id),
+            SubmitOutcome::Known => Err(MessageSubmitterError::Known(message_id)),
+            SubmitOutcome::Invalid(e) => Err(e),
+        }
+    }
+
+    /// Submits the bytes of a message and waits for its outcome then, if it is accepted, for its confirmation by a
+    /// milestone for at most the given timeout. An already known message is treated as a successful submission.
+    pub async fn submit_message_confirmed(
+        &self,
+        bytes: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<(MessageId, ConfirmationOutcome), MessageSubmitterError> {
+        let message_id = hasher::message_id(&bytes);
+        // Awaiting the confirmation ahead of the submission, it can not be missed.
+        let waiter = self.confirmations.wait(message_id);
+
+        // The waiter is dropped by the end of the block, whatever the outcome.
+        let confirmation = async move {
+            let outcome = self.outcome(bytes, false).await?;
+
+            // A known message may have been confirmed before the submission, in which case it won't be again.
+            if let SubmitOutcome::Known = outcome {
+                if let Some(milestone_index) = (self.referencing_milestone)(message_id).await {
+                    return Ok((message_id, ConfirmationOutcome::Confirmed { milestone_index }));
+                }
+            }
+
+            outcome.into_result(message_id)?;
+
+            match tokio::time::timeout(timeout, waiter).await {
+                Ok(Ok(milestone_index)) => Ok((message_id, ConfirmationOutcome::Confirmed { milestone_index })),
+                _ => Ok((message_id, ConfirmationOutcome::TimedOut)),
+            }
+        }
+        .await;
+
+        self.confirmations.discard_canceled(&message_id);
+
+        confirmation
+    }
+
+    /// Submits the bytes of a batch of messages and waits for their outcomes, which are returned in the order of the
+    /// batch. Already known messages are rejected with `MessageSubmitterError::Known`.
+    ///
+    /// Messages are submitted concurrently, but only up to a bounded number of them await their outcome at any time.
+    pub async fn submit_messages(&self, messages: Vec<Vec<u8>>) -> Vec<Result<MessageId, MessageSubmitterError>> {
+        stream::iter(messages)
+            .map(|bytes| self.submit_message(bytes))
+            .buffered(MAX_IN_FLIGHT_SUBMISSIONS)
+            .collect()
+            .await
+    }
+
+    async fn outcome(&self, message: Vec<u8>, trusted: bool) -> Result<SubmitOutcome, MessageSubmitterError> {
+        let (notifier, waiter) = oneshot::channel::<SubmitOutcome>();
+
+        self.tx
+            .send(MessageSubmitterWorkerEvent {
+                message,
+                notifier,
+                trusted,
+            })
+            .map_err(|e| MessageSubmitterError::Unavailable(e.to_string()))?;
+
+        waiter
+            .await
+            .map_err(|e| MessageSubmitterError::Unavailable(e.to_string()))
+    }
 }
 
 #[async_trait]
@@ -74,26 +373,54 @@ where
     type Error = WorkerError;
 
     fn dependencies() -> &'static [TypeId] {
-        vec![TypeId::of::<HasherWorker>()].leak()
+        vec![TypeId::of::<HasherWorker>(), TypeId::of::<TangleWorker>()].leak()
     }
 
     async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let hasher = node.worker::<HasherWorker>().unwrap().tx.clone();
+        let confirmations = node.resource::<ConfirmationWaiters>();
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let referencing_milestone: ReferencingMilestone = Box::new(move |message_id| {
+            let tangle = tangle.clone();
+
+            async move {
+                tangle
+                    .get_metadata(&message_id)
+                    .await
+                    .filter(|metadata| metadata.flags().is_referenced())
+                    .and_then(|metadata| metadata.milestone_index())
+            }
+            .boxed()
+        });
+
+        {
+            let confirmations = confirmations.clone();
+            node.bus().add_listener::<Self, MessageReferenced, _>(move |event| {
+                confirmations.confirm(&event.message_id, event.milestone_index);
+            });
+        }
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
-            while let Some(MessageSubmitterWorkerEvent { message, notifier }) = receiver.next().await {
+            while let Some(MessageSubmitterWorkerEvent {
+                message,
+                notifier,
+                trusted,
+            }) = receiver.next().await
+            {
                 let event = HasherWorkerEvent {
                     from: None,
                     message_packet: MessagePacket::new(message),
                     notifier: Some(notifier),
+                    priority: MessagePriority::Normal,
+                    trusted,
                 };
-                if let Err(e) = hasher.send(event) {
+                if let Err(e) = hasher.send(event).await {
                     error!("Sending HasherWorkerEvent failed: {}.", e);
                 }
             }
@@ -101,6 +428,305 @@ where
             info!("Stopped.");
         });
 
-        Ok(Self { tx })
+        Ok(Self {
+            tx,
+            confirmations,
+            referencing_milestone,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_common::packable::Packable;
+    use bee_message::{parents::Parents, MessageBuilder};
+
+    fn message() -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([0; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    // A submitter answering every message with the given outcome, for the tests of the confirmations.
+    fn submitter(outcome: fn() -> SubmitOutcome) -> MessageSubmitterWorker {
+        let (tx, mut rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+
+        tokio::spawn(async move {
+            while let Some(MessageSubmitterWorkerEvent { notifier, .. }) = rx.recv().await {
+                let _ = notifier.send(outcome());
+            }
+        });
+
+        MessageSubmitterWorker::new(tx)
+    }
+
+    #[test]
+    fn invalid_messages_are_counted_by_kind() {
+        use crate::types::peer::Peer;
+
+        use bee_gossip::{PeerInfo, PeerRelation};
+
+        let metrics = NodeMetrics::default();
+        let peer_manager = PeerManager::new();
+        let peer_id = PeerId::random();
+        peer_manager.add(std::sync::Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+        let invalid = |error| notify_invalid_message(error, &metrics, &peer_manager, Some(&peer_id), None);
+
+        invalid(MessageSubmitterError::InvalidMessage(
+            bee_message::Error::InvalidPayloadKind(42),
+        ));
+        invalid(MessageSubmitterError::IncompatibleNetworkId { expected: 1, got: 2 });
+        invalid(MessageSubmitterError::IncompatibleNetworkId { expected: 1, got: 3 });
+        invalid(MessageSubmitterError::InsufficientPowScore {
+            score: 1.0,
+            minimum: 4000.0,
+        });
+        invalid(MessageSubmitterError::InsufficientPowScore {
+            score: 2.0,
+            minimum: 4000.0,
+        });
+        invalid(MessageSubmitterError::InsufficientPowScore {
+            score: 3.0,
+            minimum: 4000.0,
+        });
+        invalid(MessageSubmitterError::TimestampTooFarAhead {
+            timestamp: 100,
+            now: 10,
+        });
+        // Other rejections only count towards the aggregate.
+        invalid(MessageSubmitterError::CorruptBytes(MessageId::new([0; 32])));
+        // Locally submitted messages have no peer to blame.
+        notify_invalid_message(
+            MessageSubmitterError::InsufficientPowScore {
+                score: 1.0,
+                minimum: 4000.0,
+            },
+            &metrics,
+            &peer_manager,
+            None,
+            None,
+        );
+
+        assert_eq!(metrics.invalid_messages(), 9);
+        assert_eq!(metrics.invalid_parse(), 1);
+        assert_eq!(metrics.invalid_network_id(), 2);
+        assert_eq!(metrics.invalid_pow(), 4);
+        assert_eq!(metrics.invalid_timestamp(), 1);
+
+        let peer = peer_manager.get(&peer_id).unwrap();
+        let peer_metrics = peer.0.metrics();
+
+        assert_eq!(peer_metrics.invalid_messages(), 8);
+        assert_eq!(peer_metrics.invalid_parse(), 1);
+        assert_eq!(peer_metrics.invalid_network_id(), 2);
+        assert_eq!(peer_metrics.invalid_pow(), 3);
+        assert_eq!(peer_metrics.invalid_timestamp(), 1);
+    }
+
+    // Packs a message with the given parents, bypassing their validation.
+    fn message_bytes(parents: [u8; 2]) -> Vec<u8> {
+        let mut bytes = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([0; 32]), MessageId::new([1; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+            .pack_new();
+        // Network id (8 bytes) and number of parents (1 byte) are followed by the parents.
+        bytes[9..41].copy_from_slice(&[parents[0]; 32]);
+        bytes[41..73].copy_from_slice(&[parents[1]; 32]);
+        bytes
+    }
+
+    fn count_unpacked(bytes: Vec<u8>, metrics: &NodeMetrics) {
+        if let Err(e) = Message::unpack(&mut &bytes[..]) {
+            notify_invalid_message(
+                MessageSubmitterError::InvalidMessage(e),
+                metrics,
+                &PeerManager::new(),
+                None,
+                None,
+            );
+        }
+    }
+
+    #[test]
+    fn equal_parents_are_invalid() {
+        let metrics = NodeMetrics::default();
+
+        count_unpacked(message_bytes([1, 1]), &metrics);
+
+        assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(metrics.invalid_parents(), 1);
+    }
+
+    #[test]
+    fn unsorted_parents_are_invalid() {
+        let metrics = NodeMetrics::default();
+
+        count_unpacked(message_bytes([2, 1]), &metrics);
+
+        assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(metrics.invalid_parents(), 1);
+    }
+
+    #[test]
+    fn sorted_unique_parents_are_valid() {
+        let metrics = NodeMetrics::default();
+
+        count_unpacked(message_bytes([1, 2]), &metrics);
+
+        assert_eq!(metrics.invalid_messages(), 0);
+        assert_eq!(metrics.invalid_parents(), 0);
+    }
+
+    #[tokio::test]
+    async fn submit_message_unavailable() {
+        let (tx, rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+        drop(rx);
+
+        assert!(matches!(
+            MessageSubmitterWorker::new(tx)
+                .submit_message(message().pack_new())
+                .await,
+            Err(MessageSubmitterError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_bytes_are_described() {
+        let error = MessageSubmitterError::InvalidMessage(Message::unpack(&mut &[0u8; 4][..]).unwrap_err());
+
+        assert!(error.to_string().starts_with("Invalid message: "));
+    }
+
+    #[test]
+    fn discard_canceled_notifier_dropped_receiver() {
+        let metrics = NodeMetrics::new();
+
+        let (notifier, waiter) = oneshot::channel::<SubmitOutcome>();
+        assert!(discard_canceled_notifier(Some(notifier), &metrics).is_some());
+        drop(waiter);
+        assert_eq!(metrics.notifiers_gone(), 0);
+
+        let (notifier, waiter) = oneshot::channel::<SubmitOutcome>();
+        drop(waiter);
+        assert!(discard_canceled_notifier(Some(notifier), &metrics).is_none());
+        assert_eq!(metrics.notifiers_gone(), 1);
+
+        assert!(discard_canceled_notifier(None, &metrics).is_none());
+        assert_eq!(metrics.notifiers_gone(), 1);
+    }
+
+    #[tokio::test]
+    async fn submit_dropped_notifier() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+        tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        assert!(MessageSubmitterWorker::new(tx).submit(message()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn submit_message_confirmed() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+        let submitter = MessageSubmitterWorker::new(tx);
+        let confirmations = submitter.confirmations.clone();
+
+        // Accepts the message, which then gets referenced by a milestone.
+        tokio::spawn(async move {
+            while let Some(MessageSubmitterWorkerEvent { message, notifier, .. }) = rx.recv().await {
+                let message_id = hasher::message_id(&message);
+                let _ = notifier.send(SubmitOutcome::Processed(message_id));
+                confirmations.confirm(&message_id, MilestoneIndex(42));
+            }
+        });
+
+        let (message_id, bytes) = message().id();
+
+        assert_eq!(
+            submitter
+                .submit_message_confirmed(bytes, Duration::from_secs(5))
+                .await
+                .unwrap(),
+            (
+                message_id,
+                ConfirmationOutcome::Confirmed {
+                    milestone_index: MilestoneIndex(42)
+                }
+            )
+        );
+        assert!(!submitter.confirmations.is_awaited(&message_id));
+    }
+
+    #[tokio::test]
+    async fn submit_message_unconfirmed() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<MessageSubmitterWorkerEvent>();
+        let submitter = MessageSubmitterWorker::new(tx);
+
+        tokio::spawn(async move {
+            while let Some(MessageSubmitterWorkerEvent { notifier, .. }) = rx.recv().await {
+                let _ = notifier.send(SubmitOutcome::Known);
+            }
+        });
+
+        let (message_id, bytes) = message().id();
+
+        assert_eq!(
+            submitter
+                .submit_message_confirmed(bytes, Duration::from_millis(10))
+                .await
+                .unwrap(),
+            (message_id, ConfirmationOutcome::TimedOut)
+        );
+        // Confirmations of messages nobody awaits anymore are not kept.
+        assert!(!submitter.confirmations.is_awaited(&message_id));
+        submitter.confirmations.confirm(&message_id, MilestoneIndex(42));
+    }
+
+    #[tokio::test]
+    async fn known_message_confirmed_before_the_submission() {
+        let mut submitter = submitter(|| SubmitOutcome::Known);
+        submitter.referencing_milestone = Box::new(|_| futures::future::ready(Some(MilestoneIndex(42))).boxed());
+        let (message_id, bytes) = message().id();
+
+        // The confirmation is not awaited, it already happened.
+        assert_eq!(
+            submitter
+                .submit_message_confirmed(bytes, Duration::from_secs(60))
+                .await
+                .unwrap(),
+            (
+                message_id,
+                ConfirmationOutcome::Confirmed {
+                    milestone_index: MilestoneIndex(42)
+                }
+            )
+        );
+        assert!(!submitter.confirmations.is_awaited(&message_id));
+    }
+
+    #[tokio::test]
+    async fn submit_message_confirmed_rejected() {
+        let submitter = submitter(|| SubmitOutcome::Invalid(MessageSubmitterError::Rejected("spam".to_string())));
+        let (message_id, bytes) = message().id();
+
+        assert!(matches!(
+            submitter.submit_message_confirmed(bytes, Duration::from_secs(5)).await,
+            Err(MessageSubmitterError::Rejected(_))
+        ));
+        assert!(!submitter.confirmations.is_awaited(&message_id));
     }
 }
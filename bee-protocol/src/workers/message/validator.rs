@@ -0,0 +1,451 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::workers::{
+    message::MessageSubmitterError, network_id::AcceptedNetworkIds, storage::StorageBackend, RequestedMessages,
+    RequestedMilestones,
+};
+
+use bee_autopeering::TimeSource;
+use bee_message::{
+    milestone::MilestoneIndex,
+    payload::{indexation::IndexationPayload, transaction::Essence, Payload},
+    Message, MessageId,
+};
+use bee_runtime::{node::Node, resource::ResourceHandle, worker::Worker};
+use bee_tangle::Tangle;
+
+use async_trait::async_trait;
+
+use std::{convert::Infallible, sync::Arc};
+
+/// Checks received messages before they are inserted into the tangle.
+pub trait MessageValidator: Send + Sync + 'static {
+    /// Returns `Ok` if the message passes the check, or why it is rejected otherwise.
+    fn validate(&self, message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError>;
+}
+
+/// An ordered list of validators, a message being rejected by the first one it does not pass.
+#[derive(Clone, Default)]
+pub struct MessageValidators {
+    validators: Vec<Arc<dyn MessageValidator>>,
+}
+
+impl MessageValidators {
+    /// Creates a new `MessageValidators` without any validators.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a validator, run after the previously registered ones.
+    #[must_use]
+    pub fn with_validator<V: MessageValidator>(mut self, validator: V) -> Self {
+        self.validators.push(Arc::new(validator));
+        self
+    }
+
+    // Appends the validators of `other`, run after the current ones.
+    pub(crate) fn extend(&mut self, other: &MessageValidators) {
+        self.validators.extend(other.validators.iter().cloned());
+    }
+
+    // Runs the validators in order, stopping at the first rejection.
+    pub(crate) fn validate(&self, message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+        self.validators
+            .iter()
+            .try_for_each(|validator| validator.validate(message_id, message))
+    }
+}
+
+// Returns whether a milestone index lies beyond the allowed lookahead from the confirmed milestone index.
+fn exceeds_lookahead(index: MilestoneIndex, confirmed_index: MilestoneIndex, lookahead: Option<u32>) -> bool {
+    match lookahead {
+        Some(lookahead) => *index > confirmed_index.saturating_add(lookahead),
+        None => false,
+    }
+}
+
+// Returns whether a timestamp is more than `max_drift` seconds ahead of `now`, if a maximum drift is configured.
+fn exceeds_clock_drift(timestamp: u64, now: u64, max_drift: Option<u64>) -> bool {
+    match max_drift {
+        Some(max_drift) => timestamp > now.saturating_add(max_drift),
+        None => false,
+    }
+}
+
+// Returns whether an index starts with any of the blocked prefixes.
+fn is_blocked(index: &[u8], blocked_prefixes: &[Vec<u8>]) -> bool {
+    blocked_prefixes.iter().any(|prefix| index.starts_with(prefix))
+}
+
+// Returns the indexation payload of a message, either standalone or within a transaction.
+pub(crate) fn indexation(message: &Message) -> Option<&IndexationPayload> {
+    match message.payload() {
+        Some(Payload::Indexation(indexation)) => Some(indexation),
+        Some(Payload::Transaction(transaction)) => {
+            let Essence::Regular(essence) = transaction.essence();
+
+            match essence.payload() {
+                Some(Payload::Indexation(indexation)) => Some(indexation),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Rejects the messages of network ids not accepted by the node.
+struct NetworkIdValidator {
+    network_ids: ResourceHandle<AcceptedNetworkIds>,
+}
+
+impl MessageValidator for NetworkIdValidator {
+    fn validate(&self, _message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+        if self.network_ids.accepts(message.network_id()) {
+            Ok(())
+        } else {
+            Err(MessageSubmitterError::IncompatibleNetworkId {
+                expected: self.network_ids.current(),
+                got: message.network_id(),
+            })
+        }
+    }
+}
+
+// Rejects the milestones too far ahead of the confirmed milestone, unless the node requested them itself, e.g. while
+// syncing.
+struct MilestoneLookaheadValidator<B: StorageBackend> {
+    tangle: ResourceHandle<Tangle<B>>,
+    requested_messages: ResourceHandle<RequestedMessages>,
+    requested_milestones: ResourceHandle<RequestedMilestones>,
+    lookahead: Option<u32>,
+}
+
+impl<B: StorageBackend> MessageValidator for MilestoneLookaheadValidator<B> {
+    fn validate(&self, message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+        if let Some(Payload::Milestone(milestone)) = message.payload() {
+            let index = milestone.essence().index();
+            let confirmed_index = self.tangle.get_confirmed_milestone_index();
+
+            if exceeds_lookahead(index, confirmed_index, self.lookahead)
+                && !self.requested_milestones.contains(&index)
+                && !self.requested_messages.contains(message_id)
+            {
+                return Err(MessageSubmitterError::MilestoneTooFarAhead { index, confirmed_index });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Rejects the milestones with a timestamp too far ahead of the time of the given source.
+struct ClockDriftValidator {
+    max_drift: Option<u64>,
+    time_source: Arc<dyn TimeSource>,
+}
+
+impl MessageValidator for ClockDriftValidator {
+    fn validate(&self, _message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+        if let Some(Payload::Milestone(milestone)) = message.payload() {
+            let timestamp = milestone.essence().timestamp();
+            let now = self.time_source.now_secs();
+
+            if exceeds_clock_drift(timestamp, now, self.max_drift) {
+                return Err(MessageSubmitterError::TimestampTooFarAhead { timestamp, now });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Rejects the indexation payloads whose index matches a blocked prefix, e.g. to reserve namespaces.
+struct IndexPrefixValidator {
+    blocked_prefixes: Vec<Vec<u8>>,
+}
+
+impl MessageValidator for IndexPrefixValidator {
+    fn validate(&self, _message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+        match indexation(message) {
+            Some(indexation) if is_blocked(indexation.index(), &self.blocked_prefixes) => {
+                Err(MessageSubmitterError::BlockedIndex(hex::encode(indexation.index())))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// Returns the validators every received message goes through, before the ones provided by the operator.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn default_validators<B: StorageBackend>(
+    network_ids: ResourceHandle<AcceptedNetworkIds>,
+    tangle: ResourceHandle<Tangle<B>>,
+    requested_messages: ResourceHandle<RequestedMessages>,
+    requested_milestones: ResourceHandle<RequestedMilestones>,
+    milestone_lookahead: Option<u32>,
+    max_clock_drift: Option<u64>,
+    time_source: Arc<dyn TimeSource>,
+    blocked_index_prefixes: Vec<Vec<u8>>,
+) -> MessageValidators {
+    MessageValidators::new()
+        .with_validator(NetworkIdValidator { network_ids })
+        .with_validator(MilestoneLookaheadValidator {
+            tangle,
+            requested_messages,
+            requested_milestones,
+            lookahead: milestone_lookahead,
+        })
+        .with_validator(ClockDriftValidator {
+            max_drift: max_clock_drift,
+            time_source,
+        })
+        .with_validator(IndexPrefixValidator {
+            blocked_prefixes: blocked_index_prefixes,
+        })
+}
+
+/// A worker holding the validators provided by the operator, which the processor runs after the ones of the node.
+pub struct MessageValidatorWorker {
+    pub(crate) validators: Arc<MessageValidators>,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for MessageValidatorWorker {
+    type Config = MessageValidators;
+    type Error = Infallible;
+
+    async fn start(_node: &mut N, validators: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            validators: Arc::new(validators),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{
+        parents::Parents,
+        payload::milestone::{MilestonePayload, MilestonePayloadEssence, MILESTONE_MERKLE_PROOF_LENGTH},
+        MessageBuilder,
+    };
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::config::TangleConfigBuilder;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingValidator(Arc<AtomicUsize>);
+
+    impl MessageValidator for CountingValidator {
+        fn validate(&self, _message_id: &MessageId, _message: &Message) -> Result<(), MessageSubmitterError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    // Only accepts messages without payload.
+    struct PayloadValidator;
+
+    impl MessageValidator for PayloadValidator {
+        fn validate(&self, message_id: &MessageId, message: &Message) -> Result<(), MessageSubmitterError> {
+            match message.payload() {
+                None => Ok(()),
+                Some(_) => Err(MessageSubmitterError::Rejected(format!(
+                    "message {} carries a payload",
+                    message_id
+                ))),
+            }
+        }
+    }
+
+    fn message(network_id: u64, payload: Option<Payload>) -> Message {
+        let mut builder = MessageBuilder::<u64>::new()
+            .with_network_id(network_id)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0);
+
+        if let Some(payload) = payload {
+            builder = builder.with_payload(payload);
+        }
+
+        builder.finish().unwrap()
+    }
+
+    #[test]
+    fn rejection_short_circuits_the_pipeline() {
+        let before = Arc::new(AtomicUsize::new(0));
+        let after = Arc::new(AtomicUsize::new(0));
+        let validators = MessageValidators::new()
+            .with_validator(CountingValidator(before.clone()))
+            .with_validator(PayloadValidator)
+            .with_validator(CountingValidator(after.clone()));
+        let message_id = MessageId::new([2; 32]);
+
+        assert!(validators.validate(&message_id, &message(0, None)).is_ok());
+        assert_eq!(before.load(Ordering::SeqCst), 1);
+        assert_eq!(after.load(Ordering::SeqCst), 1);
+
+        let indexation = Payload::Indexation(Box::new(IndexationPayload::new(b"index", &[]).unwrap()));
+
+        assert!(matches!(
+            validators.validate(&message_id, &message(0, Some(indexation))),
+            Err(MessageSubmitterError::Rejected(_))
+        ));
+        assert_eq!(before.load(Ordering::SeqCst), 2);
+        // The validators following the rejecting one are not run.
+        assert_eq!(after.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn custom_validators_run_after_the_network_id_check() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut validators = MessageValidators::new().with_validator(NetworkIdValidator {
+            network_ids: ResourceHandle::new(AcceptedNetworkIds::new(1)),
+        });
+
+        validators.extend(&MessageValidators::new().with_validator(CountingValidator(calls.clone())));
+
+        assert!(matches!(
+            validators.validate(&MessageId::new([2; 32]), &message(2, None)),
+            Err(MessageSubmitterError::IncompatibleNetworkId { expected: 1, got: 2 })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert!(validators.validate(&MessageId::new([2; 32]), &message(1, None)).is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn blocked_prefixes_are_matched() {
+        let blocked_prefixes = vec![b"reserved.".to_vec(), b"x".to_vec()];
+
+        assert!(is_blocked(b"reserved.namespace", &blocked_prefixes));
+        assert!(is_blocked(b"reserved.", &blocked_prefixes));
+        assert!(is_blocked(b"xyz", &blocked_prefixes));
+        assert!(!is_blocked(b"reserved", &blocked_prefixes));
+        assert!(!is_blocked(b"wallet", &blocked_prefixes));
+        assert!(!is_blocked(b"reserved.namespace", &[]));
+    }
+
+    #[test]
+    fn requested_milestones_are_exempt_from_the_lookahead() {
+        let requested_messages = ResourceHandle::new(RequestedMessages::default());
+        let requested_milestones = ResourceHandle::new(RequestedMilestones::default());
+        let validator = MilestoneLookaheadValidator {
+            tangle: ResourceHandle::new(Tangle::new(
+                TangleConfigBuilder::new().finish(),
+                ResourceHandle::new(Storage::start(()).unwrap()),
+            )),
+            requested_messages: requested_messages.clone(),
+            requested_milestones: requested_milestones.clone(),
+            lookahead: Some(10),
+        };
+
+        let by_index = message(0, milestone_payload(100, 0));
+        let by_id = message(0, milestone_payload(200, 0));
+
+        assert!(matches!(
+            validator.validate(&by_index.id().0, &by_index),
+            Err(MessageSubmitterError::MilestoneTooFarAhead { .. })
+        ));
+        assert!(matches!(
+            validator.validate(&by_id.id().0, &by_id),
+            Err(MessageSubmitterError::MilestoneTooFarAhead { .. })
+        ));
+
+        // Milestones requested while syncing, by index or as a parent, are accepted.
+        requested_milestones.insert(MilestoneIndex(100));
+        requested_messages.insert_many([(by_id.id().0, MilestoneIndex(200), 0)]);
+
+        assert!(validator.validate(&by_index.id().0, &by_index).is_ok());
+        assert!(validator.validate(&by_id.id().0, &by_id).is_ok());
+    }
+
+    #[test]
+    fn lookahead_disabled_accepts_all() {
+        assert!(!exceeds_lookahead(MilestoneIndex(u32::MAX), MilestoneIndex(0), None));
+    }
+
+    #[test]
+    fn lookahead_boundary() {
+        assert!(!exceeds_lookahead(MilestoneIndex(100), MilestoneIndex(90), Some(10)));
+        assert!(exceeds_lookahead(MilestoneIndex(101), MilestoneIndex(90), Some(10)));
+        assert!(!exceeds_lookahead(MilestoneIndex(90), MilestoneIndex(90), Some(0)));
+        assert!(exceeds_lookahead(MilestoneIndex(91), MilestoneIndex(90), Some(0)));
+    }
+
+    #[test]
+    fn lookahead_saturates() {
+        assert!(!exceeds_lookahead(
+            MilestoneIndex(u32::MAX),
+            MilestoneIndex(u32::MAX - 1),
+            Some(10)
+        ));
+    }
+
+    #[test]
+    fn future_timestamps_are_rejected() {
+        // Too far in the future.
+        assert!(exceeds_clock_drift(1_000_061, 1_000_000, Some(60)));
+        // Slightly in the future, within the drift.
+        assert!(!exceeds_clock_drift(1_000_060, 1_000_000, Some(60)));
+        assert!(!exceeds_clock_drift(1_000_001, 1_000_000, Some(60)));
+        // In the past.
+        assert!(!exceeds_clock_drift(0, 1_000_000, Some(60)));
+        assert!(!exceeds_clock_drift(999_999, 1_000_000, Some(0)));
+    }
+
+    // A clock stopped at the given time.
+    struct FixedTimeSource(u64);
+
+    impl TimeSource for FixedTimeSource {
+        fn now_secs(&self) -> u64 {
+            self.0
+        }
+    }
+
+    fn milestone_payload(index: u32, timestamp: u64) -> Option<Payload> {
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(index),
+            timestamp,
+            Parents::new(vec![MessageId::new([1; 32])]).unwrap(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            vec![[2; 32]],
+            None,
+        )
+        .unwrap();
+
+        Some(MilestonePayload::new(essence, vec![[0; 64]]).unwrap().into())
+    }
+
+    #[test]
+    fn clock_drift_is_judged_by_the_time_source() {
+        let validator = ClockDriftValidator {
+            max_drift: Some(60),
+            time_source: Arc::new(FixedTimeSource(1_000_000)),
+        };
+
+        let within = message(0, milestone_payload(1, 1_000_060));
+        assert!(validator.validate(&within.id().0, &within).is_ok());
+
+        let beyond = message(0, milestone_payload(1, 1_000_061));
+        assert!(matches!(
+            validator.validate(&beyond.id().0, &beyond),
+            Err(MessageSubmitterError::TimestampTooFarAhead {
+                timestamp: 1_000_061,
+                now: 1_000_000
+            })
+        ));
+    }
+
+    #[test]
+    fn clock_drift_disabled_accepts_all() {
+        assert!(!exceeds_clock_drift(u64::MAX, 0, None));
+        assert!(!exceeds_clock_drift(u64::MAX, u64::MAX - 1, Some(60)));
+    }
+}
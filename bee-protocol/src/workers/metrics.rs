@@ -1,7 +1,7 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::types::metrics::NodeMetrics;
+use crate::{types::metrics::NodeMetrics, workers::event::NodeSynced};
 
 use bee_ledger::workers::event::{MilestoneConfirmed, PrunedIndex, SnapshottedIndex};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
@@ -12,7 +12,11 @@ use log::info;
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
 
-use std::{convert::Infallible, time::Duration};
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 const METRICS_INTERVAL: Duration = Duration::from_secs(60);
 
@@ -20,12 +24,24 @@ pub struct MetricsWorker {}
 
 #[async_trait]
 impl<N: Node> Worker<N> for MetricsWorker {
-    type Config = ();
+    type Config = bool;
     type Error = Infallible;
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, reset_on_sync: Self::Config) -> Result<Self, Self::Error> {
         node.register_resource(NodeMetrics::new());
 
+        if reset_on_sync {
+            let metrics = node.resource::<NodeMetrics>();
+            let reset = AtomicBool::new(false);
+            // Only the first sync ends the warm-up, later ones follow transient desyncs.
+            node.bus().add_listener::<Self, NodeSynced, _>(move |event| {
+                if !reset.swap(true, Ordering::Relaxed) {
+                    info!("Synced at {}, resetting the warm-up metrics.", *event.index);
+                    metrics.reset_counters();
+                }
+            });
+        }
+
         let metrics = node.resource::<NodeMetrics>();
         node.bus().add_listener::<Self, MilestoneConfirmed, _>(move |event| {
             metrics.referenced_messages_inc(event.referenced_messages as u64);
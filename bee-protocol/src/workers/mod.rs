@@ -3,6 +3,8 @@
 
 pub mod config;
 pub mod event;
+#[cfg(feature = "event-stream")]
+pub mod event_stream;
 pub mod storage;
 
 mod broadcaster;
@@ -11,78 +13,209 @@ mod index_updater;
 mod message;
 mod metrics;
 mod mps;
+mod network_id;
 mod packets;
 mod peer;
 mod propagator;
 mod requester;
 mod responder;
+mod reverifier;
 mod sender;
 mod solidifier;
 mod status;
+mod timed_cache;
 
 pub(crate) use broadcaster::{BroadcasterWorker, BroadcasterWorkerEvent};
 pub(crate) use heartbeater::HeartbeaterWorker;
 pub(crate) use index_updater::{IndexUpdaterWorker, IndexUpdaterWorkerEvent};
+pub use message::{
+    AdaptivePowScorePolicy, AuditOutcome, AuditRecord, AuditSink, AuditWorker, ConfirmationOutcome, Decision,
+    DecisionLog, DecisionRecord, DefaultMessageClassifier, FileAuditSink, MessageClassifier, MessageImporterWorker,
+    MessageImporterWorkerEvent, MessageRouter, MessageRouterWorker, MessageSubmitterError, MessageSubmitterWorker,
+    MessageSubmitterWorkerEvent, MessageValidator, MessageValidatorWorker, MessageValidators, NoopAuditSink,
+    PayloadHandler, PayloadHandlerWorker, PayloadHandlers, PowContext, PowScorePolicy, StaticPowScorePolicy,
+    SubmitOutcome, DEFAULT_MESSAGE_BUCKET,
+};
 pub(crate) use message::{
-    HasherWorker, HasherWorkerEvent, IndexationPayloadWorker, IndexationPayloadWorkerEvent, MilestonePayloadWorker,
-    PayloadWorker, PayloadWorkerEvent, ProcessorWorker, TransactionPayloadWorker, UnreferencedMessageInserterWorker,
-    UnreferencedMessageInserterWorkerEvent,
+    ConfirmationWaiters, HasherWorker, HasherWorkerEvent, IndexationPayloadWorker, IndexationPayloadWorkerEvent,
+    MilestonePayloadWorker, PayloadWorker, PayloadWorkerEvent, ProcessorWorker, TransactionPayloadWorker,
+    UnreferencedMessageInserterWorker, UnreferencedMessageInserterWorkerEvent,
 };
-pub use message::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent};
 pub use metrics::MetricsWorker;
 pub(crate) use mps::MpsWorker;
+pub use network_id::AcceptedNetworkIds;
 use peer::PeerManagerConfig;
-pub use peer::{PeerManager, PeerManagerResWorker};
+pub use peer::{PeerManager, PeerManagerResWorker, PeerStats, ReputationPolicy, StrictPeeringPolicy};
 pub(crate) use peer::{PeerManagerWorker, PeerWorker};
+pub use propagator::SolidificationEvents;
 pub(crate) use propagator::{PropagatorWorker, PropagatorWorkerEvent};
-pub use requester::{request_message, MessageRequesterWorker, RequestedMessages, RequestedMilestones};
+pub use requester::{
+    message_status, request_message, MessageRequesterWorker, MessageStatus, RequestedMessages, RequestedMilestones,
+};
 pub(crate) use requester::{MilestoneRequesterWorker, MilestoneRequesterWorkerEvent};
 pub(crate) use responder::{
     MessageResponderWorker, MessageResponderWorkerEvent, MilestoneResponderWorker, MilestoneResponderWorkerEvent,
 };
+pub use reverifier::ReverifierWorker;
 pub(crate) use solidifier::{MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent};
 pub(crate) use status::StatusWorker;
 
-use bee_autopeering::event::EventRx as AutopeeringEventRx;
+use bee_autopeering::{event::EventRx as AutopeeringEventRx, DiscoveryHandle, SystemTimeSource, TimeSource};
 use bee_gossip::NetworkEventReceiver as NetworkEventRx;
 use bee_runtime::node::{Node, NodeBuilder};
 
+use std::sync::Arc;
+
+/// The optional extensions of the protocol, registered by `init` in addition to the workers of the node.
+#[derive(Default)]
+#[must_use]
+pub struct ProtocolExtensions {
+    audit_sink: Option<Box<dyn AuditSink>>,
+    message_router: Option<MessageRouter>,
+    message_validators: Option<MessageValidators>,
+    payload_handlers: Option<PayloadHandlers>,
+    discovery_handle: Option<DiscoveryHandle>,
+    time_source: Option<Arc<dyn TimeSource>>,
+    #[cfg(feature = "event-stream")]
+    event_stream_addr: Option<std::net::SocketAddr>,
+}
+
+impl ProtocolExtensions {
+    /// Writes an audit trail of the outcomes of all processed messages to the given sink.
+    pub fn with_audit_sink<A: AuditSink>(mut self, audit_sink: A) -> Self {
+        self.audit_sink.replace(Box::new(audit_sink));
+        self
+    }
+
+    /// Classifies all processed messages and routes them to the handlers of the given router.
+    pub fn with_message_router(mut self, message_router: MessageRouter) -> Self {
+        self.message_router.replace(message_router);
+        self
+    }
+
+    /// Rejects the received messages not passing the given validators, which run after the ones of the node.
+    pub fn with_message_validators(mut self, message_validators: MessageValidators) -> Self {
+        self.message_validators.replace(message_validators);
+        self
+    }
+
+    /// Hands the messages carrying a payload kind not processed by the node itself to the given handlers.
+    pub fn with_payload_handlers(mut self, payload_handlers: PayloadHandlers) -> Self {
+        self.payload_handlers.replace(payload_handlers);
+        self
+    }
+
+    /// Reverifies some of the discovered peers right away whenever a new milestone is processed, at most once per
+    /// `milestone_reverify_interval`.
+    pub fn with_discovery_handle(mut self, discovery_handle: DiscoveryHandle) -> Self {
+        self.discovery_handle.replace(discovery_handle);
+        self
+    }
+
+    /// Validates the timestamps of received messages against the given time source instead of the local clock.
+    pub fn with_time_source(mut self, time_source: Arc<dyn TimeSource>) -> Self {
+        self.time_source.replace(time_source);
+        self
+    }
+
+    /// Writes the protocol events as binary frames to the consumer at the given address.
+    #[cfg(feature = "event-stream")]
+    pub fn with_event_stream(mut self, event_stream_addr: std::net::SocketAddr) -> Self {
+        self.event_stream_addr.replace(event_stream_addr);
+        self
+    }
+}
+
 pub fn init<N: Node>(
     config: config::ProtocolConfig,
     network_id: (String, u64),
     network_events: NetworkEventRx,
     autopeering_events: Option<AutopeeringEventRx>,
+    extensions: ProtocolExtensions,
     node_builder: N::Builder,
 ) -> N::Builder
 where
     N::Backend: storage::StorageBackend,
 {
-    node_builder
-        .with_worker::<MetricsWorker>()
-        .with_worker::<PeerManagerResWorker>()
+    let ProtocolExtensions {
+        audit_sink,
+        message_router,
+        message_validators,
+        payload_handlers,
+        discovery_handle,
+        time_source,
+        #[cfg(feature = "event-stream")]
+        event_stream_addr,
+    } = extensions;
+    let time_source = time_source.unwrap_or_else(|| Arc::new(SystemTimeSource));
+
+    let mut node_builder = node_builder
+        .with_resource(AcceptedNetworkIds::new(network_id.1))
+        .with_resource(DecisionLog::new(config.workers.decision_log_capacity))
+        .with_resource(ConfirmationWaiters::default())
+        .with_worker_cfg::<MetricsWorker>(config.workers.reset_metrics_on_sync)
+        .with_worker_cfg::<PeerManagerResWorker>((
+            config.workers.reputation_policy.clone(),
+            config.workers.strict_peering.clone(),
+        ))
         .with_worker_cfg::<PeerManagerWorker>(PeerManagerConfig {
             network_rx: network_events,
             peering_rx: autopeering_events,
             network_name: network_id.0,
+            prioritize_known_peers: config.workers.prioritize_known_peers,
+            processor_queue_policy: config.workers.processor_queue_policy,
         })
         .with_worker_cfg::<HasherWorker>(config.clone())
-        .with_worker_cfg::<ProcessorWorker>(network_id.1)
+        .with_worker_cfg::<ProcessorWorker>((config.clone(), time_source))
         .with_worker::<MessageResponderWorker>()
         .with_worker::<MilestoneResponderWorker>()
-        .with_worker::<MessageRequesterWorker>()
+        .with_worker_cfg::<MessageRequesterWorker>(config.clone())
         .with_worker::<MilestoneRequesterWorker>()
-        .with_worker::<PayloadWorker>()
+        .with_worker_cfg::<PayloadWorker>((
+            config.workers.milestone_queue_policy,
+            config.workers.milestone_retry_queue_capacity,
+        ))
         .with_worker::<TransactionPayloadWorker>()
         .with_worker_cfg::<MilestonePayloadWorker>(config.clone())
         .with_worker::<IndexationPayloadWorker>()
-        .with_worker::<PayloadWorker>()
-        .with_worker::<BroadcasterWorker>()
-        .with_worker::<PropagatorWorker>()
+        .with_worker_cfg::<PayloadWorker>((
+            config.workers.milestone_queue_policy,
+            config.workers.milestone_retry_queue_capacity,
+        ))
+        .with_worker_cfg::<BroadcasterWorker>((
+            config.workers.circuit_breaker_threshold,
+            config.workers.circuit_breaker_cooldown,
+        ))
+        .with_worker_cfg::<PropagatorWorker>((config.workers.solidification_latency, config.workers.min_tips))
         .with_worker::<MpsWorker>()
         .with_worker_cfg::<MilestoneSolidifierWorker>(config.workers.milestone_sync_count)
-        .with_worker::<IndexUpdaterWorker>()
+        .with_worker_cfg::<IndexUpdaterWorker>(config.workers.min_tips)
         .with_worker_cfg::<StatusWorker>(config.workers.status_interval)
         .with_worker::<HeartbeaterWorker>()
         .with_worker::<MessageSubmitterWorker>()
-        .with_worker::<UnreferencedMessageInserterWorker>()
+        .with_worker::<MessageImporterWorker>()
+        .with_worker::<UnreferencedMessageInserterWorker>();
+
+    if let Some(audit_sink) = audit_sink {
+        node_builder = node_builder.with_worker_cfg::<AuditWorker>(audit_sink);
+    }
+    if let Some(message_router) = message_router {
+        node_builder = node_builder.with_worker_cfg::<MessageRouterWorker>(message_router);
+    }
+    if let Some(message_validators) = message_validators {
+        node_builder = node_builder.with_worker_cfg::<MessageValidatorWorker>(message_validators);
+    }
+    if let Some(payload_handlers) = payload_handlers {
+        node_builder = node_builder.with_worker_cfg::<PayloadHandlerWorker>(payload_handlers);
+    }
+    if let Some(discovery_handle) = discovery_handle {
+        node_builder = node_builder
+            .with_worker_cfg::<ReverifierWorker>((discovery_handle, config.workers.milestone_reverify_interval));
+    }
+    #[cfg(feature = "event-stream")]
+    if let Some(event_stream_addr) = event_stream_addr {
+        node_builder = node_builder.with_worker_cfg::<event_stream::EventStreamWorker>(event_stream_addr);
+    }
+
+    node_builder
 }
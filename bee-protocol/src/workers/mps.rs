@@ -53,11 +53,12 @@ impl<N: Node> Worker<N> for MpsWorker {
                 let outgoing = metrics.messages_sent();
 
                 bus.dispatch(MpsMetricsUpdated {
-                    incoming: incoming - total_incoming,
-                    new: new - total_new,
-                    known: known - total_known,
-                    invalid: invalid - total_invalid,
-                    outgoing: outgoing - total_outgoing,
+                    // The counters may have been reset in the meantime.
+                    incoming: incoming.saturating_sub(total_incoming),
+                    new: new.saturating_sub(total_new),
+                    known: known.saturating_sub(total_known),
+                    invalid: invalid.saturating_sub(total_invalid),
+                    outgoing: outgoing.saturating_sub(total_outgoing),
                 });
 
                 total_incoming = incoming;
@@ -0,0 +1,128 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+
+use std::time::{Duration, Instant};
+
+/// The network ids messages are accepted for, which may be updated while the node is running.
+///
+/// After an update, messages of the previous network id are still accepted for a transition window, which allows
+/// migrating between network versions without restarting the node.
+pub struct AcceptedNetworkIds {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    current: u64,
+    // The previous network id and until when it is accepted.
+    previous: Option<(u64, Instant)>,
+}
+
+impl AcceptedNetworkIds {
+    /// Creates a new `AcceptedNetworkIds` only accepting the given network id.
+    pub fn new(network_id: u64) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                current: network_id,
+                previous: None,
+            }),
+        }
+    }
+
+    /// Returns the current network id.
+    pub fn current(&self) -> u64 {
+        self.inner.lock().current
+    }
+
+    /// Switches to a new network id, accepting the current one for the given transition window.
+    ///
+    /// Returns `false`, without switching, if the transition window is too large to be represented.
+    pub fn update(&self, network_id: u64, transition_window: Duration) -> bool {
+        self.update_at(network_id, transition_window, Instant::now())
+    }
+
+    /// Returns whether messages of the given network id are accepted.
+    pub fn accepts(&self, network_id: u64) -> bool {
+        self.accepts_at(network_id, Instant::now())
+    }
+
+    pub(crate) fn update_at(&self, network_id: u64, transition_window: Duration, now: Instant) -> bool {
+        let mut inner = self.inner.lock();
+
+        if inner.current != network_id {
+            let until = match now.checked_add(transition_window) {
+                Some(until) => until,
+                None => return false,
+            };
+            inner.previous = Some((inner.current, until));
+            inner.current = network_id;
+        }
+
+        true
+    }
+
+    pub(crate) fn accepts_at(&self, network_id: u64, now: Instant) -> bool {
+        let inner = self.inner.lock();
+
+        match inner.previous {
+            Some((previous, until)) if previous == network_id => now < until,
+            _ => inner.current == network_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn acceptance_switches_after_transition_window() {
+        let network_ids = AcceptedNetworkIds::new(1);
+        let start = Instant::now();
+
+        assert!(network_ids.accepts_at(1, start));
+        assert!(!network_ids.accepts_at(2, start));
+
+        network_ids.update_at(2, Duration::from_secs(60), start);
+
+        assert_eq!(network_ids.current(), 2);
+        // Both network ids are accepted during the transition window.
+        assert!(network_ids.accepts_at(1, start + Duration::from_secs(59)));
+        assert!(network_ids.accepts_at(2, start + Duration::from_secs(59)));
+        // Only the new one afterwards.
+        assert!(!network_ids.accepts_at(1, start + Duration::from_secs(60)));
+        assert!(network_ids.accepts_at(2, start + Duration::from_secs(60)));
+
+        // Updating without a transition window switches right away.
+        network_ids.update_at(3, Duration::ZERO, start + Duration::from_secs(60));
+
+        assert!(!network_ids.accepts_at(2, start + Duration::from_secs(60)));
+        assert!(network_ids.accepts_at(3, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn too_large_transition_windows_are_refused() {
+        let network_ids = AcceptedNetworkIds::new(1);
+        let start = Instant::now();
+
+        assert!(!network_ids.update_at(2, Duration::MAX, start));
+
+        assert_eq!(network_ids.current(), 1);
+        assert!(network_ids.accepts_at(1, start));
+        assert!(!network_ids.accepts_at(2, start));
+    }
+
+    #[test]
+    fn updating_to_the_current_network_id_keeps_the_transition() {
+        let network_ids = AcceptedNetworkIds::new(1);
+        let start = Instant::now();
+
+        network_ids.update_at(2, Duration::from_secs(60), start);
+        network_ids.update_at(2, Duration::ZERO, start);
+
+        assert!(network_ids.accepts_at(1, start));
+        assert!(network_ids.accepts_at(2, start));
+    }
+}
@@ -4,8 +4,12 @@
 use crate::{
     types::{metrics::NodeMetrics, peer::Peer},
     workers::{
+        config::ProcessorQueuePolicy,
+        event::{
+            InvalidMilestoneReceived, PeerDeleted, PeerDiscovered, PeerInvalidRatioExceeded, PeerReputationDropped,
+        },
         heartbeater::{new_heartbeat, send_heartbeat},
-        peer::PeerManager,
+        peer::{MessageOutcome, PeerManager},
         storage::StorageBackend,
         HasherWorker, MessageResponderWorker, MetricsWorker, MilestoneRequesterWorker, MilestoneResponderWorker,
         PeerManagerResWorker, PeerWorker, RequestedMilestones,
@@ -22,7 +26,7 @@ use bee_tangle::{Tangle, TangleWorker};
 
 use async_trait::async_trait;
 use futures::{channel::oneshot, StreamExt};
-use log::{info, trace, warn};
+use log::{error, info, trace, warn};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use std::{any::TypeId, convert::Infallible, sync::Arc};
@@ -31,6 +35,8 @@ pub(crate) struct PeerManagerConfig {
     pub(crate) network_rx: NetworkEventRx,
     pub(crate) peering_rx: Option<AutopeeringEventRx>,
     pub(crate) network_name: String,
+    pub(crate) prioritize_known_peers: bool,
+    pub(crate) processor_queue_policy: ProcessorQueuePolicy,
 }
 
 pub(crate) struct PeerManagerWorker {}
@@ -74,9 +80,55 @@ where
             network_rx,
             peering_rx,
             network_name,
+            prioritize_known_peers,
+            processor_queue_policy,
         } = config;
 
+        {
+            let network_command_tx = network_command_tx.clone();
+
+            node.bus().add_listener::<Self, PeerReputationDropped, _>(move |event| {
+                warn!(
+                    "Banning peer {} for its reputation {}.",
+                    event.peer_id, event.reputation
+                );
+                if let Err(e) = network_command_tx.send(Command::BanPeer { peer_id: event.peer_id }) {
+                    error!("Banning peer {} failed: {:?}.", event.peer_id, e);
+                }
+            });
+        }
+
+        {
+            let network_command_tx = network_command_tx.clone();
+
+            node.bus()
+                .add_listener::<Self, PeerInvalidRatioExceeded, _>(move |event| {
+                    warn!(
+                        "Disconnecting peer {} for sending {} invalid messages out of {}.",
+                        event.peer_id, event.invalid_messages, event.messages
+                    );
+                    if let Err(e) = network_command_tx.send(Command::DisconnectPeer { peer_id: event.peer_id }) {
+                        error!("Disconnecting peer {} failed: {:?}.", event.peer_id, e);
+                    }
+                });
+        }
+
+        {
+            let peer_manager = peer_manager.clone();
+
+            node.bus()
+                .add_listener::<Self, InvalidMilestoneReceived, _>(move |event| {
+                    warn!(
+                        "Downgrading peer {} for sending invalid milestone {}.",
+                        event.peer_id, event.message_id
+                    );
+                    peer_manager.record_outcome(&event.peer_id, MessageOutcome::Invalid);
+                });
+        }
+
         if let Some(peering_rx) = peering_rx {
+            let bus = node.bus();
+
             node.spawn::<Self, _, _>(|shutdown| async move {
                 info!("Autopeering handler running.");
 
@@ -95,6 +147,8 @@ where
                         AutopeeringEvent::PeeringDropped { peer_id } => {
                             handle_peering_dropped(peer_id, &network_command_tx);
                         }
+                        AutopeeringEvent::PeerDiscovered { peer_id } => bus.dispatch(PeerDiscovered { peer_id }),
+                        AutopeeringEvent::PeerDeleted { peer_id } => bus.dispatch(PeerDeleted { peer_id }),
                         _ => {}
                     }
                 }
@@ -145,6 +199,8 @@ where
                                     message_responder.clone(),
                                     milestone_responder.clone(),
                                     milestone_requester.clone(),
+                                    prioritize_known_peers,
+                                    processor_queue_policy,
                                 )
                                 .run(
                                     tangle.clone(),
@@ -3,10 +3,20 @@
 
 // TODO This exist to avoid a cyclic dependency, there has to be another way.
 
-use crate::types::peer::Peer;
+use crate::{
+    types::peer::Peer,
+    workers::{
+        event::{PeerInvalidRatioExceeded, PeerReputationDropped},
+        peer::{
+            reputation::{MessageOutcome, ReputationPolicy},
+            strict::{InvalidRatioTracker, StrictPeeringPolicy},
+            PeerStats,
+        },
+    },
+};
 
 use bee_gossip::{GossipSender, PeerId};
-use bee_runtime::{node::Node, worker::Worker};
+use bee_runtime::{event::Bus, node::Node, resource::ResourceHandle, worker::Worker};
 
 use async_trait::async_trait;
 use futures::channel::oneshot;
@@ -25,11 +35,11 @@ pub struct PeerManagerResWorker {}
 
 #[async_trait]
 impl<N: Node> Worker<N> for PeerManagerResWorker {
-    type Config = ();
+    type Config = (ReputationPolicy, Option<StrictPeeringPolicy>);
     type Error = Infallible;
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
-        node.register_resource(PeerManager::new());
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        node.register_resource(PeerManager::with_policies(config.0, config.1, node.bus()));
 
         Ok(Self {})
     }
@@ -90,13 +100,84 @@ impl PeerManagerInner {
 pub struct PeerManager {
     inner: RwLock<PeerManagerInner>,
     counter: AtomicUsize,
+    reputation_policy: ReputationPolicy,
+    invalid_ratio_tracker: Option<InvalidRatioTracker>,
+    bus: Option<ResourceHandle<Bus<'static>>>,
 }
 
 impl PeerManager {
+    #[cfg(test)]
     pub(crate) fn new() -> Self {
         Self::default()
     }
 
+    pub(crate) fn with_policies(
+        reputation_policy: ReputationPolicy,
+        strict_peering: Option<StrictPeeringPolicy>,
+        bus: ResourceHandle<Bus<'static>>,
+    ) -> Self {
+        Self {
+            reputation_policy,
+            invalid_ratio_tracker: strict_peering.map(InvalidRatioTracker::new),
+            bus: Some(bus),
+            ..Self::default()
+        }
+    }
+
+    // Adjusts the reputation of the peer, if known, and notifies when it drops below the threshold or, in strict
+    // peering mode, when its share of invalid messages exceeds the allowed ratio.
+    pub(crate) fn record_outcome(&self, id: &PeerId, outcome: MessageOutcome) {
+        if let Some(ref tracker) = self.invalid_ratio_tracker {
+            self.track_invalid_ratio(tracker, id, outcome);
+        }
+
+        let weight = self.reputation_policy.weight(outcome);
+
+        if weight == 0 {
+            return;
+        }
+
+        // The peer is not held on to while notifying, listeners may need to access it.
+        let previous = match self.get(id) {
+            Some(peer) => peer.0.adjust_reputation(weight),
+            None => return,
+        };
+        let reputation = previous.saturating_add(weight);
+
+        if self.reputation_policy.crosses_threshold(previous, reputation) {
+            debug!("Reputation of peer {} dropped to {}.", id, reputation);
+            if let Some(ref bus) = self.bus {
+                bus.dispatch(PeerReputationDropped {
+                    peer_id: *id,
+                    reputation,
+                });
+            }
+        }
+    }
+
+    fn track_invalid_ratio(&self, tracker: &InvalidRatioTracker, id: &PeerId, outcome: MessageOutcome) {
+        // Only the messages of known peers are tracked.
+        if self.get(id).is_none() {
+            return;
+        }
+
+        let invalid = matches!(outcome, MessageOutcome::Invalid | MessageOutcome::LowPow);
+
+        if let Some((invalid_messages, messages)) = tracker.record(id, invalid) {
+            debug!(
+                "Peer {} sent {} invalid messages out of {}.",
+                id, invalid_messages, messages
+            );
+            if let Some(ref bus) = self.bus {
+                bus.dispatch(PeerInvalidRatioExceeded {
+                    peer_id: *id,
+                    invalid_messages,
+                    messages,
+                });
+            }
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.inner.read().peers.is_empty()
     }
@@ -120,6 +201,11 @@ impl PeerManager {
             .collect()
     }
 
+    /// Returns a snapshot of the processing stats of a peer, if known.
+    pub fn peer_stats(&self, id: &PeerId) -> Option<PeerStats> {
+        self.get(id).map(|peer| PeerStats::new(&peer.0))
+    }
+
     pub(crate) fn add(&self, peer: Arc<Peer>) {
         debug!("Added peer {}.", peer.id());
         let mut lock = self.inner.write();
@@ -128,6 +214,9 @@ impl PeerManager {
 
     pub(crate) fn remove(&self, id: &PeerId) -> Option<PeerTuple> {
         debug!("Removed peer {}.", id);
+        if let Some(ref tracker) = self.invalid_ratio_tracker {
+            tracker.remove(id);
+        }
         let mut lock = self.inner.write();
         lock.remove(id)
     }
@@ -4,13 +4,22 @@
 mod manager;
 mod manager_res;
 mod packet_handler;
+mod reputation;
+mod stats;
+mod strict;
 
 pub(crate) use manager::{PeerManagerConfig, PeerManagerWorker};
 pub use manager_res::{PeerManager, PeerManagerResWorker};
+pub(crate) use reputation::MessageOutcome;
+pub use reputation::ReputationPolicy;
+pub use stats::PeerStats;
+pub use strict::StrictPeeringPolicy;
 
 use crate::{
     types::{metrics::NodeMetrics, peer::Peer},
     workers::{
+        config::ProcessorQueuePolicy,
+        message::{admit, MessagePriority},
         packets::{
             tlv_from_bytes, HeaderPacket, HeartbeatPacket, MessagePacket, MessageRequestPacket, MilestoneRequestPacket,
             Packet, TlvError,
@@ -49,21 +58,28 @@ impl From<TlvError> for Error {
 pub struct PeerWorker {
     peer: Arc<Peer>,
     metrics: ResourceHandle<NodeMetrics>,
-    hasher: mpsc::UnboundedSender<HasherWorkerEvent>,
+    hasher: async_channel::Sender<HasherWorkerEvent>,
     message_responder: mpsc::UnboundedSender<MessageResponderWorkerEvent>,
     milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
     milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+    message_priority: MessagePriority,
+    queue_policy: ProcessorQueuePolicy,
 }
 
 impl PeerWorker {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         peer: Arc<Peer>,
         metrics: ResourceHandle<NodeMetrics>,
-        hasher: mpsc::UnboundedSender<HasherWorkerEvent>,
+        hasher: async_channel::Sender<HasherWorkerEvent>,
         message_responder: mpsc::UnboundedSender<MessageResponderWorkerEvent>,
         milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
         milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+        prioritize_known_peers: bool,
+        queue_policy: ProcessorQueuePolicy,
     ) -> Self {
+        let message_priority = MessagePriority::from_relation(peer.relation(), prioritize_known_peers);
+
         Self {
             peer,
             metrics,
@@ -71,6 +87,8 @@ impl PeerWorker {
             message_responder,
             milestone_responder,
             milestone_requester,
+            message_priority,
+            queue_policy,
         }
     }
 
@@ -101,7 +119,7 @@ impl PeerWorker {
         while let Some((header, bytes)) = packet_handler.fetch_packet().await {
             let tangle = tangle.upgrade().expect("Needed Tangle resource but it was removed");
 
-            if let Err(e) = self.process_packet(&tangle, &header, bytes) {
+            if let Err(e) = self.process_packet(&tangle, &header, bytes).await {
                 error!("[{}] Processing packet failed: {:?}.", self.peer.alias(), e);
                 self.peer.metrics().invalid_packets_inc();
                 self.metrics.invalid_packets_inc();
@@ -111,7 +129,7 @@ impl PeerWorker {
         info!("[{}] Stopped.", self.peer.alias());
     }
 
-    fn process_packet<B: StorageBackend>(
+    async fn process_packet<B: StorageBackend>(
         &mut self,
         tangle: &Tangle<B>,
         header: &HeaderPacket,
@@ -136,14 +154,23 @@ impl PeerWorker {
 
                 let packet = tlv_from_bytes::<MessagePacket>(header, bytes)?;
 
-                let _ = self.hasher.send(HasherWorkerEvent {
-                    from: Some(*self.peer.id()),
-                    message_packet: packet,
-                    notifier: None,
-                });
-
                 self.peer.metrics().messages_received_inc();
                 self.metrics.messages_received_inc();
+
+                // Messages are dropped as they enter the node if the queue is full and so configured, otherwise
+                // reading from the peer waits for room in the queue.
+                if admit(&self.hasher, self.queue_policy, &self.metrics) {
+                    let _ = self
+                        .hasher
+                        .send(HasherWorkerEvent {
+                            from: Some(*self.peer.id()),
+                            message_packet: packet,
+                            notifier: None,
+                            priority: self.message_priority,
+                            trusted: false,
+                        })
+                        .await;
+                }
             }
             MessageRequestPacket::ID => {
                 trace!("[{}] Reading MessageRequestPacket...", self.peer.alias());
@@ -194,3 +221,57 @@ impl PeerWorker {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_gossip::{PeerId, PeerInfo, PeerRelation};
+    use bee_message::MESSAGE_LENGTH_MIN;
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::config::TangleConfigBuilder;
+
+    #[tokio::test]
+    async fn gossiped_messages_are_dropped_while_the_hasher_queue_is_full() {
+        let tangle = Tangle::new(
+            TangleConfigBuilder::new().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        let metrics = ResourceHandle::new(NodeMetrics::new());
+        let (hasher, hashed) = async_channel::bounded(1);
+        let mut worker = PeerWorker::new(
+            Arc::new(Peer::new(
+                PeerId::random(),
+                PeerInfo {
+                    address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                    alias: "peer".to_string(),
+                    relation: PeerRelation::Known,
+                },
+            )),
+            metrics.clone(),
+            hasher,
+            mpsc::unbounded_channel().0,
+            mpsc::unbounded_channel().0,
+            mpsc::unbounded_channel().0,
+            false,
+            ProcessorQueuePolicy::Drop,
+        );
+        let header = HeaderPacket {
+            packet_type: MessagePacket::ID,
+            packet_length: MESSAGE_LENGTH_MIN as u16,
+        };
+
+        for _ in 0..3 {
+            worker
+                .process_packet(&tangle, &header, &[0; MESSAGE_LENGTH_MIN])
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(hashed.len(), 1);
+        assert_eq!(metrics.messages_received(), 3);
+        assert_eq!(metrics.processor_queue_full(), 2);
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+const DEFAULT_NEW_MESSAGE_WEIGHT: i64 = 1;
+const DEFAULT_KNOWN_MESSAGE_WEIGHT: i64 = -1;
+const DEFAULT_INVALID_MESSAGE_WEIGHT: i64 = -10;
+const DEFAULT_LOW_POW_MESSAGE_WEIGHT: i64 = -5;
+
+// The outcome of processing a message of a peer, as far as its reputation is concerned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum MessageOutcome {
+    New,
+    Known,
+    Invalid,
+    LowPow,
+}
+
+/// Defines how the reputation of peers evolves with the messages they send.
+#[derive(Clone, Debug)]
+pub struct ReputationPolicy {
+    pub(crate) new_message: i64,
+    pub(crate) known_message: i64,
+    pub(crate) invalid_message: i64,
+    pub(crate) low_pow_message: i64,
+    pub(crate) threshold: Option<i64>,
+}
+
+impl Default for ReputationPolicy {
+    fn default() -> Self {
+        Self {
+            new_message: DEFAULT_NEW_MESSAGE_WEIGHT,
+            known_message: DEFAULT_KNOWN_MESSAGE_WEIGHT,
+            invalid_message: DEFAULT_INVALID_MESSAGE_WEIGHT,
+            low_pow_message: DEFAULT_LOW_POW_MESSAGE_WEIGHT,
+            threshold: None,
+        }
+    }
+}
+
+impl ReputationPolicy {
+    pub(crate) fn weight(&self, outcome: MessageOutcome) -> i64 {
+        match outcome {
+            MessageOutcome::New => self.new_message,
+            MessageOutcome::Known => self.known_message,
+            MessageOutcome::Invalid => self.invalid_message,
+            MessageOutcome::LowPow => self.low_pow_message,
+        }
+    }
+
+    // Returns whether going from the `previous` to the `current` reputation drops below the threshold, if any.
+    pub(crate) fn crosses_threshold(&self, previous: i64, current: i64) -> bool {
+        matches!(self.threshold, Some(threshold) if previous >= threshold && current < threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn threshold_is_crossed_once() {
+        let policy = ReputationPolicy {
+            threshold: Some(-5),
+            ..Default::default()
+        };
+
+        assert!(!policy.crosses_threshold(0, -5));
+        assert!(policy.crosses_threshold(-5, -6));
+        assert!(!policy.crosses_threshold(-6, -16));
+        assert!(!policy.crosses_threshold(-16, -15));
+        assert!(!ReputationPolicy::default().crosses_threshold(0, i64::MIN));
+    }
+
+    #[test]
+    fn mixed_messages_drop_the_reputation_below_the_threshold() {
+        use crate::{
+            types::peer::Peer,
+            workers::{event::PeerReputationDropped, PeerManager},
+        };
+
+        use bee_gossip::{PeerId, PeerInfo, PeerRelation};
+        use bee_runtime::{event::Bus, resource::ResourceHandle};
+
+        use std::sync::{Arc, Mutex};
+
+        let bus = ResourceHandle::new(Bus::default());
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        {
+            let dropped = dropped.clone();
+            bus.add_static_listener(move |event: &PeerReputationDropped| {
+                dropped.lock().unwrap().push((event.peer_id, event.reputation));
+            });
+        }
+        let peer_manager = PeerManager::with_policies(
+            ReputationPolicy {
+                threshold: Some(-20),
+                ..Default::default()
+            },
+            None,
+            bus.clone(),
+        );
+        let peer_id = PeerId::random();
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+        let record = |outcome, times| (0..times).for_each(|_| peer_manager.record_outcome(&peer_id, outcome));
+        let reputation = || peer_manager.get(&peer_id).unwrap().0.reputation();
+
+        // Duplicates lower the reputation by default.
+        record(MessageOutcome::New, 5);
+        record(MessageOutcome::Known, 3);
+        assert_eq!(reputation(), 2);
+
+        record(MessageOutcome::Invalid, 1);
+        record(MessageOutcome::LowPow, 1);
+        assert_eq!(reputation(), -13);
+
+        record(MessageOutcome::New, 10);
+        record(MessageOutcome::Invalid, 1);
+        record(MessageOutcome::LowPow, 1);
+        record(MessageOutcome::Known, 2);
+        assert_eq!(reputation(), -20);
+        assert!(dropped.lock().unwrap().is_empty());
+
+        // Duplicate spam alone is enough to drop the reputation below the threshold.
+        record(MessageOutcome::Known, 1);
+        assert_eq!(reputation(), -21);
+        assert_eq!(*dropped.lock().unwrap(), vec![(peer_id, -21)]);
+
+        // The peer is only reported when crossing the threshold, not for every message while below.
+        record(MessageOutcome::Invalid, 1);
+        record(MessageOutcome::New, 1);
+        assert_eq!(reputation(), -30);
+        assert_eq!(dropped.lock().unwrap().len(), 1);
+
+        // Messages of unknown peers are ignored.
+        peer_manager.record_outcome(&PeerId::random(), MessageOutcome::Invalid);
+        assert_eq!(dropped.lock().unwrap().len(), 1);
+    }
+}
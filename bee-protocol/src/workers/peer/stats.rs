@@ -0,0 +1,122 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::peer::Peer;
+
+use bee_gossip::PeerId;
+
+/// A snapshot of how the messages of a peer were processed, e.g. to rank peers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerStats {
+    /// The id of the peer.
+    pub peer_id: PeerId,
+    /// The number of new messages received from the peer.
+    pub new_messages: u64,
+    /// The number of already known messages received from the peer.
+    pub known_messages: u64,
+    /// The number of invalid messages received from the peer.
+    pub invalid_messages: u64,
+    /// The number of messages received from the peer whose bytes differ from the stored ones.
+    pub corrupt_messages: u64,
+    /// The number of messages received from the peer that had been requested.
+    pub requested_messages: u64,
+    /// The number of messages of the peer dropped for exceeding its rate limit.
+    pub rate_limited_messages: u64,
+    /// The total number of messages of the peer that were processed, i.e. new, known or invalid.
+    pub total_messages: u64,
+    /// The reputation of the peer.
+    pub reputation: i64,
+}
+
+impl PeerStats {
+    pub(crate) fn new(peer: &Peer) -> Self {
+        let metrics = peer.metrics();
+        let new_messages = metrics.new_messages();
+        let known_messages = metrics.known_messages();
+        let invalid_messages = metrics.invalid_messages();
+
+        Self {
+            peer_id: *peer.id(),
+            new_messages,
+            known_messages,
+            invalid_messages,
+            corrupt_messages: metrics.corrupt_messages(),
+            requested_messages: metrics.requested_messages(),
+            rate_limited_messages: metrics.rate_limited_messages(),
+            total_messages: new_messages + known_messages + invalid_messages,
+            reputation: peer.reputation(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::workers::{peer::MessageOutcome, PeerManager};
+
+    use bee_gossip::{PeerInfo, PeerRelation};
+
+    use std::sync::Arc;
+
+    fn add_peer(peer_manager: &PeerManager) -> PeerId {
+        let peer_id = PeerId::random();
+
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+
+        peer_id
+    }
+
+    #[test]
+    fn stats_differ_between_peers() {
+        let peer_manager = PeerManager::new();
+        let honest = add_peer(&peer_manager);
+        let faulty = add_peer(&peer_manager);
+
+        // The honest peer sends new messages and a few duplicates.
+        for _ in 0..10 {
+            peer_manager.get(&honest).unwrap().0.metrics().new_messages_inc();
+            peer_manager.record_outcome(&honest, MessageOutcome::New);
+        }
+        for _ in 0..3 {
+            peer_manager.get(&honest).unwrap().0.metrics().known_messages_inc();
+            peer_manager.record_outcome(&honest, MessageOutcome::Known);
+        }
+        // The faulty peer mostly sends invalid messages.
+        peer_manager.get(&faulty).unwrap().0.metrics().new_messages_inc();
+        peer_manager.record_outcome(&faulty, MessageOutcome::New);
+        for _ in 0..4 {
+            peer_manager.get(&faulty).unwrap().0.metrics().invalid_messages_inc();
+            peer_manager.record_outcome(&faulty, MessageOutcome::Invalid);
+        }
+        peer_manager.get(&faulty).unwrap().0.metrics().corrupt_messages_inc();
+
+        let honest_stats = peer_manager.peer_stats(&honest).unwrap();
+        let faulty_stats = peer_manager.peer_stats(&faulty).unwrap();
+
+        assert_eq!(honest_stats.peer_id, honest);
+        assert_eq!(honest_stats.new_messages, 10);
+        assert_eq!(honest_stats.known_messages, 3);
+        assert_eq!(honest_stats.invalid_messages, 0);
+        assert_eq!(honest_stats.total_messages, 13);
+        assert_eq!(honest_stats.reputation, 7);
+
+        assert_eq!(faulty_stats.peer_id, faulty);
+        assert_eq!(faulty_stats.new_messages, 1);
+        assert_eq!(faulty_stats.invalid_messages, 4);
+        assert_eq!(faulty_stats.corrupt_messages, 1);
+        assert_eq!(faulty_stats.total_messages, 5);
+        assert_eq!(faulty_stats.reputation, -39);
+
+        assert!(honest_stats.reputation > faulty_stats.reputation);
+        assert!(peer_manager.peer_stats(&PeerId::random()).is_none());
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_gossip::PeerId;
+
+use parking_lot::Mutex;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_MAX_INVALID_RATIO: f64 = 0.5;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_MIN_SAMPLES: usize = 20;
+
+/// Defines when peers sending too many invalid messages are disconnected, in strict peering mode.
+#[derive(Clone, Debug)]
+pub struct StrictPeeringPolicy {
+    pub(crate) max_invalid_ratio: f64,
+    pub(crate) window: Duration,
+    pub(crate) min_samples: usize,
+}
+
+impl Default for StrictPeeringPolicy {
+    fn default() -> Self {
+        Self {
+            max_invalid_ratio: DEFAULT_MAX_INVALID_RATIO,
+            window: DEFAULT_WINDOW,
+            min_samples: DEFAULT_MIN_SAMPLES,
+        }
+    }
+}
+
+// The outcomes of the messages of a peer within the window, and how many of them were invalid.
+#[derive(Default)]
+struct Outcomes {
+    outcomes: VecDeque<(Instant, bool)>,
+    invalid: usize,
+}
+
+// Tracks the outcomes of the recent messages of each peer to spot the ones sending too many invalid messages.
+pub(crate) struct InvalidRatioTracker {
+    policy: StrictPeeringPolicy,
+    peers: Mutex<HashMap<PeerId, Outcomes>>,
+}
+
+impl InvalidRatioTracker {
+    pub(crate) fn new(policy: StrictPeeringPolicy) -> Self {
+        Self {
+            policy,
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Records whether a message of a peer was invalid. If the peer now exceeds the ratio of invalid messages, returns
+    // the number of invalid messages and of messages within the window, and starts over for that peer.
+    pub(crate) fn record(&self, peer_id: &PeerId, invalid: bool) -> Option<(usize, usize)> {
+        self.record_at(peer_id, invalid, Instant::now())
+    }
+
+    pub(crate) fn record_at(&self, peer_id: &PeerId, invalid: bool, now: Instant) -> Option<(usize, usize)> {
+        let mut peers = self.peers.lock();
+        let outcomes = peers.entry(*peer_id).or_default();
+
+        while let Some((at, was_invalid)) = outcomes.outcomes.front() {
+            if now.saturating_duration_since(*at) < self.policy.window {
+                break;
+            }
+            if *was_invalid {
+                outcomes.invalid -= 1;
+            }
+            outcomes.outcomes.pop_front();
+        }
+
+        outcomes.outcomes.push_back((now, invalid));
+        if invalid {
+            outcomes.invalid += 1;
+        }
+
+        let messages = outcomes.outcomes.len();
+        let invalid = outcomes.invalid;
+
+        if messages >= self.policy.min_samples && invalid as f64 > self.policy.max_invalid_ratio * messages as f64 {
+            peers.remove(peer_id);
+            Some((invalid, messages))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn remove(&self, peer_id: &PeerId) {
+        self.peers.lock().remove(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::{
+        types::peer::Peer,
+        workers::{event::PeerInvalidRatioExceeded, peer::MessageOutcome, PeerManager, ReputationPolicy},
+    };
+
+    use bee_gossip::{PeerInfo, PeerRelation};
+    use bee_runtime::{event::Bus, resource::ResourceHandle};
+
+    use std::sync::Arc;
+
+    type ExceededPeers = Arc<Mutex<Vec<(PeerId, usize, usize)>>>;
+
+    fn policy() -> StrictPeeringPolicy {
+        StrictPeeringPolicy {
+            max_invalid_ratio: 0.2,
+            window: Duration::from_secs(10),
+            min_samples: 10,
+        }
+    }
+
+    #[test]
+    fn ratio_is_only_checked_with_enough_samples() {
+        let tracker = InvalidRatioTracker::new(policy());
+        let peer_id = PeerId::random();
+        let start = Instant::now();
+
+        for _ in 0..9 {
+            assert!(tracker.record_at(&peer_id, true, start).is_none());
+        }
+        assert_eq!(tracker.record_at(&peer_id, true, start), Some((10, 10)));
+        // The peer starts over once reported.
+        assert!(tracker.record_at(&peer_id, true, start).is_none());
+    }
+
+    #[test]
+    fn outcomes_leave_the_window() {
+        let tracker = InvalidRatioTracker::new(policy());
+        let peer_id = PeerId::random();
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            assert!(tracker.record_at(&peer_id, true, start).is_none());
+        }
+        // The invalid messages are out of the window by the time there are enough samples.
+        for _ in 0..10 {
+            assert!(tracker
+                .record_at(&peer_id, false, start + Duration::from_secs(10))
+                .is_none());
+        }
+        assert!(tracker
+            .record_at(&peer_id, true, start + Duration::from_secs(10))
+            .is_none());
+    }
+
+    fn peer_manager() -> (PeerManager, ExceededPeers) {
+        let bus = ResourceHandle::new(Bus::default());
+        let exceeded = Arc::new(Mutex::new(Vec::new()));
+        {
+            let exceeded = exceeded.clone();
+            bus.add_static_listener(move |event: &PeerInvalidRatioExceeded| {
+                exceeded
+                    .lock()
+                    .push((event.peer_id, event.invalid_messages, event.messages));
+            });
+        }
+
+        (
+            PeerManager::with_policies(ReputationPolicy::default(), Some(policy()), bus),
+            exceeded,
+        )
+    }
+
+    fn add_peer(peer_manager: &PeerManager) -> PeerId {
+        let peer_id = PeerId::random();
+
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+
+        peer_id
+    }
+
+    #[test]
+    fn peer_exceeding_the_ratio_is_reported() {
+        let (peer_manager, exceeded) = peer_manager();
+        let peer_id = add_peer(&peer_manager);
+
+        (0..7).for_each(|_| peer_manager.record_outcome(&peer_id, MessageOutcome::New));
+        peer_manager.record_outcome(&peer_id, MessageOutcome::Invalid);
+        peer_manager.record_outcome(&peer_id, MessageOutcome::LowPow);
+        assert!(exceeded.lock().is_empty());
+
+        // 3 invalid messages out of 10 exceed the ratio.
+        peer_manager.record_outcome(&peer_id, MessageOutcome::Invalid);
+        assert_eq!(*exceeded.lock(), vec![(peer_id, 3, 10)]);
+    }
+
+    #[test]
+    fn noisy_but_mostly_valid_peer_is_spared() {
+        let (peer_manager, exceeded) = peer_manager();
+        let peer_id = add_peer(&peer_manager);
+
+        // One invalid message out of ten, known messages counting as valid.
+        for i in 0..100 {
+            peer_manager.record_outcome(
+                &peer_id,
+                match i % 10 {
+                    0 => MessageOutcome::Invalid,
+                    1..=4 => MessageOutcome::Known,
+                    _ => MessageOutcome::New,
+                },
+            );
+        }
+
+        assert!(exceeded.lock().is_empty());
+        // Messages of unknown peers are not tracked.
+        (0..20).for_each(|_| peer_manager.record_outcome(&PeerId::random(), MessageOutcome::Invalid));
+        assert!(exceeded.lock().is_empty());
+    }
+}
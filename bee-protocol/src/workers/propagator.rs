@@ -1,33 +1,110 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::workers::{
-    event::MessageSolidified, storage::StorageBackend, MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent,
+use crate::{
+    types::metrics::NodeMetrics,
+    workers::{
+        event::MessageSolidified, storage::StorageBackend, MetricsWorker, MilestoneSolidifierWorker,
+        MilestoneSolidifierWorkerEvent,
+    },
 };
 
 use bee_message::{milestone::MilestoneIndex, MessageId};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
-use bee_tangle::{metadata::IndexId, solid_entry_point::SolidEntryPoint, Tangle, TangleWorker};
+use bee_tangle::{
+    metadata::{IndexId, MessageMetadata},
+    solid_entry_point::SolidEntryPoint,
+    Tangle, TangleWorker,
+};
 
 use async_trait::async_trait;
-use futures::{future::FutureExt, stream::StreamExt};
+use futures::{
+    future::FutureExt,
+    stream::{self, Stream, StreamExt},
+};
 use log::*;
 use ref_cast::RefCast;
-use tokio::sync::mpsc;
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use std::{any::TypeId, convert::Infallible};
 
+// The number of solidification events kept for subscribers falling behind.
+const SOLIDIFICATION_EVENTS_CAPACITY: usize = 1024;
+
 #[derive(Debug)]
 pub(crate) struct PropagatorWorkerEvent(pub(crate) MessageId);
 
+/// Broadcasts an event to any number of subscribers whenever a message is solidified.
+pub struct SolidificationEvents {
+    tx: broadcast::Sender<MessageSolidified>,
+}
+
+impl SolidificationEvents {
+    fn new(capacity: usize) -> Self {
+        Self {
+            tx: broadcast::channel(capacity).0,
+        }
+    }
+
+    fn publish(&self, event: MessageSolidified) {
+        // Sending only fails if there are no subscribers.
+        let _ = self.tx.send(event);
+    }
+
+    /// Subscribes to the events of the messages solidified from now on.
+    ///
+    /// Subscribers falling behind by more than the capacity of the `SolidificationEvents` miss the oldest events.
+    pub fn subscribe(&self) -> impl Stream<Item = MessageSolidified> {
+        stream::unfold(self.tx.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(RecvError::Lagged(skipped)) => debug!("Subscriber lagging, skipped {} events.", skipped),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
 pub(crate) struct PropagatorWorker {
     pub(crate) tx: mpsc::UnboundedSender<PropagatorWorkerEvent>,
 }
 
+// Returns the latency from arrival to solidification of a solid message, if it has an arrival timestamp.
+fn solidification_latency(metadata: &MessageMetadata) -> Option<u64> {
+    if metadata.arrival_timestamp() == 0 {
+        None
+    } else {
+        Some(
+            metadata
+                .solidification_timestamp()
+                .saturating_sub(metadata.arrival_timestamp()),
+        )
+    }
+}
+
+// Sets the tips gauge to the current size of the tip pool, warning when it drops below the floor. Only dropping
+// below the floor is reported, not staying there, which notably spares a warning per message while synchronizing.
+pub(crate) async fn update_tips<B: StorageBackend>(tangle: &Tangle<B>, metrics: &NodeMetrics, min_tips: usize) {
+    let tips = tangle.tips_num().await;
+    let previous = metrics.tips();
+
+    metrics.tips_set(tips as u64);
+
+    if tips < min_tips && previous >= min_tips as u64 {
+        warn!("Running low on tips: {} left, below the floor of {}.", tips, min_tips);
+    }
+}
+
 async fn propagate<B: StorageBackend>(
     message_id: MessageId,
     tangle: &Tangle<B>,
+    latency_metrics: Option<&NodeMetrics>,
     solidified_tx: &async_channel::Sender<(MessageId, Vec<MessageId>, Option<MilestoneIndex>)>,
 ) {
     let mut children = vec![message_id];
@@ -85,6 +162,12 @@ async fn propagate<B: StorageBackend>(
                     // The child inherits the solid property from its parents.
                     metadata.mark_solid();
 
+                    if let Some(metrics) = latency_metrics {
+                        if let Some(latency) = solidification_latency(metadata) {
+                            metrics.solidification_latency_observe(latency);
+                        }
+                    }
+
                     if metadata.flags().is_milestone() {
                         metadata.milestone_index()
                     } else {
@@ -119,19 +202,33 @@ impl<N: Node> Worker<N> for PropagatorWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = (bool, usize);
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
-        vec![TypeId::of::<TangleWorker>(), TypeId::of::<MilestoneSolidifierWorker>()].leak()
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<MilestoneSolidifierWorker>(),
+            TypeId::of::<MetricsWorker>(),
+        ]
+        .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, (solidification_latency, min_tips): Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = mpsc::unbounded_channel();
         let milestone_solidifier = node.worker::<MilestoneSolidifierWorker>().unwrap().tx.clone();
 
         let tangle = node.resource::<Tangle<N::Backend>>();
         let bus = node.bus();
+        node.register_resource(SolidificationEvents::new(SOLIDIFICATION_EVENTS_CAPACITY));
+        let solidification_events = node.resource::<SolidificationEvents>();
+        let metrics = node.resource::<NodeMetrics>();
+        // Only measured if enabled, the arrival timestamps being kept in the metadata anyway.
+        let latency_metrics = if solidification_latency {
+            Some(node.resource::<NodeMetrics>())
+        } else {
+            None
+        };
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -143,14 +240,19 @@ where
 
                 async move {
                     while let Ok((message_id, parents, index)) = solidified_rx.recv().await {
-                        bus.dispatch(MessageSolidified { message_id });
+                        let event = MessageSolidified {
+                            message_id,
+                            milestone_index: index,
+                        };
+                        solidification_events.publish(event.clone());
+                        bus.dispatch(event);
 
                         const SAFETY_THRESHOLD: u32 = 5; // Number of ms before eligible section of the Tangle begins
 
                         // NOTE: We need to decide whether we want to put this new solid message into the tip-pool.
                         // Some things to consider:
-                        // 1) During synchronization we receive many non-eligible messages, that are way too old for
-                        //    the TSA, hence we want to exclude them.
+                        // 1) During synchronization we receive many non-eligible messages, that are way too old for the
+                        //    TSA, hence we want to exclude them.
                         // 2) We don't know the confirming milestone index of each eventually confirmed message at this
                         // point in time, hence we need to employ a heuristic with a security threshold to minimise the
                         // risk of a false-negative (something excluded from the tip-pool, that would be eligible as
@@ -164,6 +266,7 @@ where
                             <= (tangle.config().below_max_depth() + SAFETY_THRESHOLD).into()
                         {
                             tangle.insert_tip(message_id, parents).await;
+                            update_tips(&tangle, &metrics, min_tips).await;
                         }
 
                         if let Some(index) = index {
@@ -178,7 +281,7 @@ where
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
             while let Some(PropagatorWorkerEvent(message_id)) = receiver.next().await {
-                propagate(message_id, &tangle, &solidified_tx).await;
+                propagate(message_id, &tangle, latency_metrics.as_deref(), &solidified_tx).await;
             }
 
             // Before the worker completely stops, the receiver needs to be drained for statuses to be propagated.
@@ -188,7 +291,7 @@ where
             let mut count: usize = 0;
 
             while let Some(Some(PropagatorWorkerEvent(message_id))) = receiver.next().now_or_never() {
-                propagate(message_id, &tangle, &solidified_tx).await;
+                propagate(message_id, &tangle, latency_metrics.as_deref(), &solidified_tx).await;
                 count += 1;
             }
 
@@ -200,3 +303,123 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use bee_message::{parents::Parents, Message, MessageBuilder};
+    use bee_runtime::resource::ResourceHandle;
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::{config::TangleConfigBuilder, flags::Flags};
+
+    fn solidified(byte: u8) -> MessageSolidified {
+        MessageSolidified {
+            message_id: MessageId::new([byte; 32]),
+            milestone_index: (byte > 1).then(|| MilestoneIndex(byte.into())),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribers_observe_solidified_messages() {
+        let events = SolidificationEvents::new(16);
+        // Events published before subscribing are not observed.
+        events.publish(solidified(0));
+
+        let subscriber = tokio::spawn(events.subscribe().take(2).collect::<Vec<_>>());
+
+        // Wait until the subscriber is polled, which happens right away on the current thread runtime.
+        tokio::task::yield_now().await;
+        events.publish(solidified(1));
+        events.publish(solidified(2));
+
+        let observed = subscriber.await.unwrap();
+
+        assert_eq!(observed[0].message_id, MessageId::new([1; 32]));
+        assert_eq!(observed[0].milestone_index, None);
+        assert_eq!(observed[1].message_id, MessageId::new([2; 32]));
+        assert_eq!(observed[1].milestone_index, Some(MilestoneIndex(2)));
+    }
+
+    #[tokio::test]
+    async fn lagging_subscribers_miss_the_oldest_events() {
+        let events = SolidificationEvents::new(2);
+        let mut subscriber = Box::pin(events.subscribe());
+
+        for byte in 0..5 {
+            events.publish(solidified(byte));
+        }
+        drop(events);
+
+        let observed = subscriber
+            .by_ref()
+            .map(|event| event.message_id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(observed, vec![MessageId::new([3; 32]), MessageId::new([4; 32])]);
+    }
+
+    fn message(parent: MessageId) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![parent]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn tips_gauge_follows_the_tip_pool() {
+        let tangle = Tangle::new(
+            TangleConfigBuilder::new().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        let metrics = NodeMetrics::new();
+
+        for byte in 0..3 {
+            let message = message(MessageId::new([byte; 32]));
+            let (message_id, _) = message.id();
+            let mut metadata = MessageMetadata::arrived();
+
+            metadata.mark_solid();
+            metadata.set_omrsi(IndexId::new(MilestoneIndex(0), message_id));
+            metadata.set_ymrsi(IndexId::new(MilestoneIndex(0), message_id));
+            tangle.insert(message, message_id, metadata).await;
+            tangle.insert_tip(message_id, Vec::new()).await;
+
+            update_tips(&tangle, &metrics, 2).await;
+
+            assert_eq!(metrics.tips(), u64::from(byte) + 1);
+        }
+
+        // Once referenced by a later milestone, the tips get lazy and leave the pool.
+        tangle.update_solid_milestone_index(MilestoneIndex(100));
+        tangle.update_tip_scores().await;
+        update_tips(&tangle, &metrics, 2).await;
+
+        assert_eq!(metrics.tips(), 0);
+    }
+
+    #[test]
+    fn latency_from_arrival_to_solidification() {
+        let metadata = |arrival_timestamp, solidification_timestamp| {
+            MessageMetadata::new(
+                Flags::default(),
+                None,
+                arrival_timestamp,
+                solidification_timestamp,
+                0,
+                None,
+                None,
+                Default::default(),
+            )
+        };
+
+        assert_eq!(solidification_latency(&metadata(1000, 1250)), Some(250));
+        assert_eq!(solidification_latency(&metadata(1000, 900)), Some(0));
+        assert_eq!(solidification_latency(&metadata(0, 900)), None);
+    }
+}
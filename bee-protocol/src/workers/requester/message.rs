@@ -4,20 +4,27 @@
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
-        packets::MessageRequestPacket, peer::PeerManager, sender::Sender, storage::StorageBackend, MetricsWorker,
-        PeerManagerResWorker,
+        config::{ProtocolConfig, DEFAULT_UNAVAILABLE_MESSAGE_CAPACITY},
+        event::MessageUnavailable,
+        packets::MessageRequestPacket,
+        peer::PeerManager,
+        sender::Sender,
+        storage::StorageBackend,
+        timed_cache::TimedCache,
+        MetricsWorker, PeerManagerResWorker,
     },
 };
 
+use bee_ledger::workers::event::PrunedIndex;
 use bee_message::{milestone::MilestoneIndex, MessageId};
-use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_runtime::{event::Bus, node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{Tangle, TangleWorker};
 
 use async_priority_queue::PriorityQueue;
 use async_trait::async_trait;
 use futures::StreamExt;
 use fxhash::FxBuildHasher;
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 use parking_lot::RwLock;
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
@@ -25,13 +32,13 @@ use tokio_stream::wrappers::IntervalStream;
 use std::{
     any::TypeId,
     cmp::{Ord, Ordering, PartialOrd},
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     convert::Infallible,
     sync::Arc,
     time::{Duration, Instant},
 };
 
-const RETRY_INTERVAL: Duration = Duration::from_millis(2500);
+const REQUEST_BATCH_SIZE: usize = 1024;
 
 pub async fn request_message<B: StorageBackend>(
     tangle: &Tangle<B>,
@@ -39,44 +46,252 @@ pub async fn request_message<B: StorageBackend>(
     requested_messages: &RequestedMessages,
     message_id: MessageId,
     index: MilestoneIndex,
+) {
+    request_message_at_depth(tangle, message_requester, requested_messages, message_id, index, 0).await;
+}
+
+/// The status of a message, as far as the node is concerned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageStatus {
+    /// The message is in the tangle.
+    Known,
+    /// The message is not in the tangle yet, but requested from the peers.
+    Requested,
+    /// The message is neither in the tangle nor requested.
+    Unknown,
+}
+
+/// Returns the status of a message, derived from both the tangle and the requested messages.
+pub async fn message_status<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    requested_messages: &RequestedMessages,
+    message_id: &MessageId,
+) -> MessageStatus {
+    // A requested message is inserted into the tangle before it stops being requested. Looking up the requests first,
+    // a message arriving in between is therefore never reported as unknown.
+    let requested = requested_messages.contains(message_id);
+
+    if tangle.contains(message_id).await {
+        MessageStatus::Known
+    } else if requested {
+        MessageStatus::Requested
+    } else {
+        MessageStatus::Unknown
+    }
+}
+
+// Requests a message `depth` generations behind the message that triggered the request.
+pub(crate) async fn request_message_at_depth<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    message_requester: &MessageRequesterWorker,
+    requested_messages: &RequestedMessages,
+    message_id: MessageId,
+    index: MilestoneIndex,
+    depth: u32,
 ) {
     if !tangle.contains(&message_id).await
         && !tangle.is_solid_entry_point(&message_id).await
-        && !requested_messages.contains(&message_id)
+        && requested_messages.should_request(&message_id, index, tangle.get_pruning_index())
     {
-        message_requester.request(MessageRequesterWorkerEvent(message_id, index));
+        message_requester.request(MessageRequesterWorkerEvent(message_id, index, depth));
     }
 }
 
-#[derive(Default)]
-pub struct RequestedMessages(RwLock<HashMap<MessageId, (MilestoneIndex, Instant), FxBuildHasher>>);
+// Returns the depth the parents of a requested message at `depth` are requested at, or `None` if it exceeds
+// `max_depth` and the parents are left to the solidifier.
+pub(crate) fn parent_request_depth(depth: u32, max_depth: Option<u32>) -> Option<u32> {
+    let parent_depth = depth.saturating_add(1);
+
+    match max_depth {
+        Some(max_depth) if parent_depth > max_depth => None,
+        _ => Some(parent_depth),
+    }
+}
+
+// The milestone index, request time, retries and depth of a requested message.
+type Request = (MilestoneIndex, Instant, u32, u32);
+
+// Requests that are due for a retry or exhausted their retries, each with its milestone index.
+type ExpiredRequests = Vec<(MessageId, MilestoneIndex)>;
+
+pub struct RequestedMessages {
+    requested: RwLock<HashMap<MessageId, Request, FxBuildHasher>>,
+    // Messages that were given up on, with the milestone index they were requested for and the messages that
+    // referenced them at that time.
+    unavailable: RwLock<TimedCache<MessageId, (MilestoneIndex, Vec<MessageId>), FxBuildHasher>>,
+}
+
+impl Default for RequestedMessages {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNAVAILABLE_MESSAGE_CAPACITY, None)
+    }
+}
 
 #[allow(clippy::len_without_is_empty)]
 impl RequestedMessages {
+    /// Creates a new `RequestedMessages`, remembering at most `unavailable_capacity` messages that were given up on,
+    /// each for at most `unavailable_ttl` if any.
+    pub(crate) fn new(unavailable_capacity: usize, unavailable_ttl: Option<Duration>) -> Self {
+        Self {
+            requested: Default::default(),
+            unavailable: RwLock::new(TimedCache::new(unavailable_capacity, unavailable_ttl)),
+        }
+    }
+
     pub fn contains(&self, message_id: &MessageId) -> bool {
-        self.0.read().contains_key(message_id)
+        self.requested.read().contains_key(message_id)
     }
 
-    pub(crate) fn insert(&self, message_id: MessageId, index: MilestoneIndex) {
+    /// Registers several requested messages at once, and returns the newly inserted ones and the ids of the already
+    /// present ones.
+    pub(crate) fn insert_many<I: IntoIterator<Item = (MessageId, MilestoneIndex, u32)>>(
+        &self,
+        entries: I,
+    ) -> (Vec<(MessageId, MilestoneIndex)>, Vec<MessageId>) {
         let now = Instant::now();
-        self.0.write().insert(message_id, (index, now));
+        let mut inserted = Vec::new();
+        let mut present = Vec::new();
+        let mut requested = self.requested.write();
+
+        for (message_id, index, depth) in entries {
+            match requested.entry(message_id) {
+                Entry::Occupied(_) => present.push(message_id),
+                Entry::Vacant(entry) => {
+                    entry.insert((index, now, 0, depth));
+                    inserted.push((message_id, index));
+                }
+            }
+        }
+
+        (inserted, present)
     }
 
     pub fn len(&self) -> usize {
-        self.0.read().len()
+        self.requested.read().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.read().is_empty()
+        self.requested.read().is_empty()
+    }
+
+    pub(crate) fn remove(&self, message_id: &MessageId) -> Option<(MilestoneIndex, Instant, u32)> {
+        // A message that was given up on might still show up.
+        if !self.unavailable.read().is_empty() {
+            self.unavailable.write().remove(message_id);
+        }
+
+        self.requested
+            .write()
+            .remove(message_id)
+            .map(|(index, instant, _, depth)| (index, instant, depth))
+    }
+
+    /// Returns the outstanding requests from the oldest to the most recent, each with its milestone index and age,
+    /// skipping the first `offset` ones and returning at most `limit` ones. Alongside the page, the total number of
+    /// outstanding requests is returned.
+    pub fn requests(&self, offset: usize, limit: usize) -> (usize, Vec<(MessageId, MilestoneIndex, Duration)>) {
+        // The requests are copied so that the lock is not held while sorting.
+        let mut requests = self
+            .requested
+            .read()
+            .iter()
+            .map(|(message_id, (index, instant, _, _))| (*message_id, *index, *instant))
+            .collect::<Vec<_>>();
+        let count = requests.len();
+        let now = Instant::now();
+
+        requests.sort_unstable_by_key(|(_, _, instant)| *instant);
+
+        (
+            count,
+            requests
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|(message_id, index, instant)| (message_id, index, now.saturating_duration_since(instant)))
+                .collect(),
+        )
+    }
+
+    /// Returns whether the message was given up on after exhausting its request retries.
+    pub fn is_unavailable(&self, message_id: &MessageId) -> bool {
+        self.unavailable.read().contains(message_id, Instant::now())
+    }
+
+    /// Returns the messages that were given up on, each with the messages that referenced it at that time.
+    pub fn unavailable(&self) -> Vec<(MessageId, Vec<MessageId>)> {
+        self.unavailable
+            .read()
+            .iter(Instant::now())
+            .map(|(message_id, (_, orphans))| (*message_id, orphans.clone()))
+            .collect()
     }
 
-    pub(crate) fn remove(&self, message_id: &MessageId) -> Option<(MilestoneIndex, Instant)> {
-        self.0.write().remove(message_id)
+    fn mark_unavailable(&self, message_id: MessageId, index: MilestoneIndex, orphans: Vec<MessageId>) {
+        self.unavailable
+            .write()
+            .insert(message_id, (index, orphans), Instant::now());
+    }
+
+    // Forgets about the messages that were given up on for milestones up to the pruning index, since those are not
+    // requested anymore anyway.
+    fn prune_unavailable(&self, pruning_index: MilestoneIndex) {
+        self.unavailable.write().retain(|_, (index, _)| *index > pruning_index);
+    }
+
+    // Returns whether a message missing from the tangle is worth requesting for the given milestone index. Messages
+    // already requested or given up on are not, nor are messages below the pruning index, which peers can't be expected
+    // to still have and which are remembered as unavailable.
+    fn should_request(&self, message_id: &MessageId, index: MilestoneIndex, pruning_index: MilestoneIndex) -> bool {
+        if self.contains(message_id) || self.is_unavailable(message_id) {
+            return false;
+        }
+
+        if index <= pruning_index {
+            trace!(
+                "Not requesting message {} below the pruning index {}.",
+                message_id,
+                *pruning_index
+            );
+            self.mark_unavailable(*message_id, index, Vec::new());
+            return false;
+        }
+
+        true
+    }
+
+    // Returns the requests that are unanswered for longer than `timeout` and due for a retry, and removes the ones that
+    // exhausted their retries, each with its milestone index.
+    fn take_expired(
+        &self,
+        now: Instant,
+        timeout: Duration,
+        max_retries: Option<u32>,
+    ) -> (ExpiredRequests, ExpiredRequests) {
+        let mut to_retry = Vec::with_capacity(1024);
+        let mut exhausted = Vec::new();
+
+        self.requested
+            .write()
+            .retain(|message_id, (index, instant, retries, _)| {
+                if now.checked_duration_since(*instant).is_none_or(|d| d <= timeout) {
+                    true
+                } else if max_retries.is_some_and(|max_retries| *retries >= max_retries) {
+                    exhausted.push((*message_id, *index));
+                    false
+                } else {
+                    *retries += 1;
+                    to_retry.push((*message_id, *index));
+                    true
+                }
+            });
+
+        (to_retry, exhausted)
     }
 }
 
 #[derive(Eq, PartialEq)]
-pub struct MessageRequesterWorkerEvent(pub(crate) MessageId, pub(crate) MilestoneIndex);
+pub struct MessageRequesterWorkerEvent(pub(crate) MessageId, pub(crate) MilestoneIndex, pub(crate) u32);
 
 impl Ord for MessageRequesterWorkerEvent {
     fn cmp(&self, other: &Self) -> Ordering {
@@ -96,29 +311,43 @@ pub struct MessageRequesterWorker {
 }
 
 impl MessageRequesterWorker {
+    pub(crate) fn new() -> Self {
+        Self {
+            req_queue: Arc::new(PriorityQueue::new()),
+        }
+    }
+
     pub fn request(&self, request: MessageRequesterWorkerEvent) {
         self.req_queue.push(request);
     }
 }
 
-fn process_request(
-    message_id: MessageId,
-    index: MilestoneIndex,
+fn process_requests(
+    requests: Vec<MessageRequesterWorkerEvent>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     requested_messages: &RequestedMessages,
 ) {
-    if requested_messages.contains(&message_id) {
+    if peer_manager.is_empty() {
         return;
     }
 
-    if peer_manager.is_empty() {
-        return;
+    let (inserted, present) = requested_messages.insert_many(
+        requests
+            .into_iter()
+            .map(|MessageRequesterWorkerEvent(message_id, index, depth)| (message_id, index, depth)),
+    );
+
+    // A message might have been queued several times, e.g. as the shared parent of several messages.
+    for _ in present {
+        metrics.message_requests_suppressed_inc();
     }
 
-    requested_messages.insert(message_id, index);
+    for (message_id, index) in inserted {
+        trace!("Requesting message {}.", message_id);
 
-    process_request_unchecked(message_id, index, peer_manager, metrics);
+        process_request_unchecked(message_id, index, peer_manager, metrics);
+    }
 }
 
 fn process_request_unchecked(
@@ -142,25 +371,16 @@ async fn retry_requests<B: StorageBackend>(
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     tangle: &Tangle<B>,
+    bus: &Bus<'static>,
+    timeout: Duration,
+    max_retries: Option<u32>,
 ) {
     if peer_manager.is_empty() {
         return;
     }
 
-    let now = Instant::now();
-    let mut retry_counts: usize = 0;
-    let mut to_retry = Vec::with_capacity(1024);
-
-    // TODO this needs abstraction
-    for (message_id, (index, instant)) in requested_messages.0.read().iter() {
-        if now
-            .checked_duration_since(*instant)
-            .map_or(false, |d| d > RETRY_INTERVAL)
-        {
-            to_retry.push((*message_id, *index));
-            retry_counts += 1;
-        }
-    }
+    let (to_retry, exhausted) = requested_messages.take_expired(Instant::now(), timeout, max_retries);
+    let retry_counts = to_retry.len();
 
     for (message_id, index) in to_retry {
         if tangle.contains(&message_id).await {
@@ -170,6 +390,25 @@ async fn retry_requests<B: StorageBackend>(
         }
     }
 
+    for (message_id, index) in exhausted {
+        if tangle.contains(&message_id).await {
+            continue;
+        }
+
+        let orphans = tangle.get_children(&message_id).await.unwrap_or_default();
+
+        warn!(
+            "Giving up on message {} after {} retries, orphaning {} message(s).",
+            message_id,
+            max_retries.unwrap_or_default(),
+            orphans.len()
+        );
+
+        metrics.message_requests_abandoned_inc();
+        requested_messages.mark_unavailable(message_id, index, orphans.clone());
+        bus.dispatch(MessageUnavailable { message_id, orphans });
+    }
+
     if retry_counts > 0 {
         debug!("Retried {} messages.", retry_counts);
     }
@@ -180,7 +419,7 @@ impl<N: Node> Worker<N> for MessageRequesterWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = ProtocolConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -192,27 +431,35 @@ where
         .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
-        let req_queue = Arc::new(PriorityQueue::new());
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let requester = Self::new();
+        let timeout = Duration::from_millis(config.workers.message_request_timeout);
+        let max_retries = config.workers.message_request_retries;
+
+        node.register_resource(RequestedMessages::new(
+            config.workers.unavailable_message_capacity,
+            config.workers.unavailable_message_ttl.map(Duration::from_secs),
+        ));
 
-        let requested_messages: RequestedMessages = Default::default();
-        node.register_resource(requested_messages);
+        let requested_messages = node.resource::<RequestedMessages>();
+        node.bus().add_listener::<Self, PrunedIndex, _>(move |event| {
+            requested_messages.prune_unavailable(event.index);
+        });
 
         let requested_messages = node.resource::<RequestedMessages>();
         let peer_manager = node.resource::<PeerManager>();
         let metrics = node.resource::<NodeMetrics>();
 
         node.spawn::<Self, _, _>({
-            let req_queue = req_queue.clone();
+            let req_queue = requester.req_queue.clone();
             |shutdown| async move {
                 info!("Requester running.");
 
-                let mut receiver = ShutdownStream::new(shutdown, req_queue.incoming());
+                // Requests that are queued at once, e.g. when expanding a milestone cone, are registered together.
+                let mut receiver = ShutdownStream::new(shutdown, req_queue.incoming()).ready_chunks(REQUEST_BATCH_SIZE);
 
-                while let Some(MessageRequesterWorkerEvent(message_id, index)) = receiver.next().await {
-                    trace!("Requesting message {}.", message_id);
-
-                    process_request(message_id, index, &peer_manager, &metrics, &requested_messages);
+                while let Some(requests) = receiver.next().await {
+                    process_requests(requests, &peer_manager, &metrics, &requested_messages);
                 }
 
                 info!("Requester stopped.");
@@ -223,19 +470,373 @@ where
         let requested_messages = node.resource::<RequestedMessages>();
         let peer_manager = node.resource::<PeerManager>();
         let metrics = node.resource::<NodeMetrics>();
+        let bus = node.bus();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Retryer running.");
 
-            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_INTERVAL)));
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(timeout)));
 
             while ticker.next().await.is_some() {
-                retry_requests(&requested_messages, &peer_manager, &metrics, &tangle).await;
+                retry_requests(
+                    &requested_messages,
+                    &peer_manager,
+                    &metrics,
+                    &tangle,
+                    &bus,
+                    timeout,
+                    max_retries,
+                )
+                .await;
             }
 
             info!("Retryer stopped.");
         });
 
-        Ok(Self { req_queue })
+        Ok(requester)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::types::peer::Peer;
+
+    use bee_gossip::{PeerId, PeerInfo, PeerRelation};
+
+    const TIMEOUT: Duration = Duration::from_millis(2500);
+
+    #[tokio::test]
+    async fn status_combines_tangle_and_requests() {
+        use bee_message::{parents::Parents, MessageBuilder};
+        use bee_runtime::resource::ResourceHandle;
+        use bee_storage::backend::StorageBackend as _;
+        use bee_storage_memory::storage::Storage;
+        use bee_tangle::{config::TangleConfigBuilder, metadata::MessageMetadata};
+
+        let tangle = Tangle::new(
+            TangleConfigBuilder::new().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        let requested_messages = RequestedMessages::default();
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::new([1; 32])]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+        let message_id = message.id().0;
+        let status = || message_status(&tangle, &requested_messages, &message_id);
+
+        assert_eq!(status().await, MessageStatus::Unknown);
+
+        requested_messages.insert_many(vec![(message_id, MilestoneIndex(1), 0)]);
+        assert_eq!(status().await, MessageStatus::Requested);
+
+        // The message arrived but is not yet removed from the requests.
+        tangle.insert(message, message_id, MessageMetadata::arrived()).await;
+        assert_eq!(status().await, MessageStatus::Known);
+
+        requested_messages.remove(&message_id);
+        assert_eq!(status().await, MessageStatus::Known);
+    }
+
+    #[test]
+    fn shared_parent_is_requested_once() {
+        let peer_manager = PeerManager::new();
+        peer_manager.add(Arc::new(Peer::new(
+            PeerId::random(),
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )));
+        let metrics = NodeMetrics::new();
+        let requested_messages = RequestedMessages::default();
+
+        let parent = MessageId::new([1; 32]);
+        let other_parents = [MessageId::new([2; 32]), MessageId::new([3; 32])];
+
+        // Two messages sharing a parent request their parents.
+        for other_parent in other_parents {
+            for message_id in [parent, other_parent] {
+                process_requests(
+                    vec![MessageRequesterWorkerEvent(message_id, MilestoneIndex(1), 0)],
+                    &peer_manager,
+                    &metrics,
+                    &requested_messages,
+                );
+            }
+        }
+
+        assert_eq!(requested_messages.len(), 3);
+        assert_eq!(metrics.message_requests_suppressed(), 1);
+    }
+
+    #[test]
+    fn insert_many_partial_overlap() {
+        let requested_messages = RequestedMessages::default();
+        let present = MessageId::new([1; 32]);
+        let new = [MessageId::new([2; 32]), MessageId::new([3; 32])];
+
+        requested_messages.insert_many(vec![(present, MilestoneIndex(1), 0)]);
+        let before = requested_messages.requested.read()[&present];
+
+        let (inserted, already_present) = requested_messages.insert_many(vec![
+            (new[0], MilestoneIndex(2), 0),
+            (present, MilestoneIndex(2), 0),
+            (new[1], MilestoneIndex(3), 0),
+            // Duplicates within the batch are reported as already present too.
+            (new[0], MilestoneIndex(4), 0),
+        ]);
+
+        assert_eq!(inserted, vec![(new[0], MilestoneIndex(2)), (new[1], MilestoneIndex(3))]);
+        assert_eq!(already_present, vec![present, new[0]]);
+        assert_eq!(requested_messages.len(), 3);
+        // Already present entries are left untouched.
+        assert_eq!(
+            requested_messages.remove(&present),
+            Some((before.0, before.1, before.3))
+        );
+        assert_eq!(
+            requested_messages.remove(&new[0]).map(|(index, _, _)| index),
+            Some(MilestoneIndex(2))
+        );
+    }
+
+    // Simulates the messages of a chain, each having the next one as parent, arriving one by one after being requested
+    // and returns the number of requests made.
+    fn walk_back(chain_length: u8, max_depth: Option<u32>) -> usize {
+        let requested_messages = RequestedMessages::default();
+        let chain = (0..chain_length).map(|i| MessageId::new([i; 32])).collect::<Vec<_>>();
+        let mut requests = requested_messages
+            .insert_many(vec![(chain[0], MilestoneIndex(1), 0)])
+            .0
+            .len();
+
+        for (message_id, parent) in chain.iter().zip(chain.iter().skip(1)) {
+            let (index, _, depth) = match requested_messages.remove(message_id) {
+                Some(request) => request,
+                None => break,
+            };
+
+            if let Some(parent_depth) = parent_request_depth(depth, max_depth) {
+                requests += requested_messages
+                    .insert_many(vec![(*parent, index, parent_depth)])
+                    .0
+                    .len();
+            }
+        }
+
+        requests
+    }
+
+    #[test]
+    fn parent_requests_stop_at_max_depth() {
+        // The initial request and 10 generations of parents.
+        assert_eq!(walk_back(200, Some(10)), 11);
+        // Only the initial request.
+        assert_eq!(walk_back(200, Some(0)), 1);
+        // Shallow chains are not affected.
+        assert_eq!(walk_back(5, Some(10)), 5);
+        // The whole chain without maximum depth.
+        assert_eq!(walk_back(200, None), 200);
+    }
+
+    #[test]
+    fn missing_parent_is_abandoned() {
+        let requested_messages = RequestedMessages::default();
+        let parent = MessageId::new([1; 32]);
+        let child = MessageId::new([2; 32]);
+
+        requested_messages.insert_many(vec![(parent, MilestoneIndex(1), 0)]);
+
+        // The parent never arrives, it is retried until the retries are exhausted.
+        let mut now = Instant::now();
+        assert_eq!(requested_messages.take_expired(now, TIMEOUT, Some(2)), (vec![], vec![]));
+
+        for _ in 0..2 {
+            now += TIMEOUT * 2;
+            assert_eq!(
+                requested_messages.take_expired(now, TIMEOUT, Some(2)),
+                (vec![(parent, MilestoneIndex(1))], vec![])
+            );
+        }
+
+        now += TIMEOUT * 2;
+        assert_eq!(
+            requested_messages.take_expired(now, TIMEOUT, Some(2)),
+            (vec![], vec![(parent, MilestoneIndex(1))])
+        );
+        assert!(!requested_messages.contains(&parent));
+
+        requested_messages.mark_unavailable(parent, MilestoneIndex(1), vec![child]);
+        assert!(requested_messages.is_unavailable(&parent));
+        assert_eq!(requested_messages.unavailable(), vec![(parent, vec![child])]);
+
+        // The parent is no longer unavailable once it shows up.
+        requested_messages.remove(&parent);
+        assert!(!requested_messages.is_unavailable(&parent));
+    }
+
+    #[test]
+    fn unanswered_parent_is_no_longer_requested() {
+        let requested_messages = RequestedMessages::default();
+        let parent = MessageId::new([1; 32]);
+        let pruning_index = MilestoneIndex(10);
+
+        assert!(requested_messages.should_request(&parent, MilestoneIndex(20), pruning_index));
+        requested_messages.insert_many(vec![(parent, MilestoneIndex(20), 0)]);
+        // Already requested.
+        assert!(!requested_messages.should_request(&parent, MilestoneIndex(20), pruning_index));
+
+        let mut now = Instant::now();
+        let mut requests = 1;
+
+        loop {
+            now += TIMEOUT * 2;
+            let (to_retry, exhausted) = requested_messages.take_expired(now, TIMEOUT, Some(3));
+            requests += to_retry.len();
+            if exhausted.contains(&(parent, MilestoneIndex(20))) {
+                requested_messages.mark_unavailable(parent, MilestoneIndex(20), Vec::new());
+                break;
+            }
+        }
+
+        // The initial request and 3 retries.
+        assert_eq!(requests, 4);
+
+        // Children keep referencing the parent, but it is not requested again.
+        for _ in 0..10 {
+            assert!(!requested_messages.should_request(&parent, MilestoneIndex(21), pruning_index));
+        }
+        assert!(!requested_messages.contains(&parent));
+    }
+
+    #[test]
+    fn messages_below_pruning_index_are_not_requested() {
+        let requested_messages = RequestedMessages::default();
+        let pruned = MessageId::new([1; 32]);
+        let pruning_index = MilestoneIndex(10);
+
+        assert!(!requested_messages.should_request(&pruned, MilestoneIndex(9), pruning_index));
+        assert!(!requested_messages.should_request(&pruned, MilestoneIndex(10), pruning_index));
+        assert!(requested_messages.is_unavailable(&pruned));
+        // Even once requested for a more recent milestone.
+        assert!(!requested_messages.should_request(&pruned, MilestoneIndex(11), pruning_index));
+
+        // A message showing up is requested again if it goes missing.
+        requested_messages.remove(&pruned);
+        assert!(requested_messages.should_request(&pruned, MilestoneIndex(11), pruning_index));
+    }
+
+    #[test]
+    fn unavailable_messages_are_forgotten_once_pruned() {
+        let requested_messages = RequestedMessages::new(2, None);
+        let message_ids = (1..=3).map(|i| MessageId::new([i; 32])).collect::<Vec<_>>();
+
+        for (i, message_id) in message_ids.iter().enumerate() {
+            requested_messages.mark_unavailable(*message_id, MilestoneIndex(10 + i as u32), Vec::new());
+        }
+
+        // The oldest one was evicted to stay within the capacity.
+        assert!(!requested_messages.is_unavailable(&message_ids[0]));
+        assert!(requested_messages.is_unavailable(&message_ids[1]));
+        assert!(requested_messages.is_unavailable(&message_ids[2]));
+
+        requested_messages.prune_unavailable(MilestoneIndex(11));
+
+        assert!(!requested_messages.is_unavailable(&message_ids[1]));
+        assert!(requested_messages.is_unavailable(&message_ids[2]));
+    }
+
+    #[test]
+    fn unavailable_messages_expire() {
+        let requested_messages = RequestedMessages::new(10, Some(Duration::ZERO));
+        let message_id = MessageId::new([1; 32]);
+
+        requested_messages.mark_unavailable(message_id, MilestoneIndex(1), Vec::new());
+
+        assert!(!requested_messages.is_unavailable(&message_id));
+        assert!(requested_messages.unavailable().is_empty());
+    }
+
+    #[test]
+    fn requests_are_listed_by_age() {
+        let requested_messages = RequestedMessages::default();
+        let now = Instant::now();
+
+        requested_messages.insert_many((1..=4).map(|i| (MessageId::new([i; 32]), MilestoneIndex(i as u32), 0)));
+        for (message_id, (_, instant, _, _)) in requested_messages.requested.write().iter_mut() {
+            *instant = now - Duration::from_secs(message_id.as_ref()[0] as u64 * 10);
+        }
+
+        let (count, page) = requested_messages.requests(0, 10);
+
+        assert_eq!(count, 4);
+        assert_eq!(
+            page.iter()
+                .map(|(message_id, index, _)| (message_id.as_ref()[0], **index))
+                .collect::<Vec<_>>(),
+            vec![(4, 4), (3, 3), (2, 2), (1, 1)]
+        );
+        assert!(page[0].2 >= Duration::from_secs(40));
+        assert!(page.windows(2).all(|pair| pair[0].2 > pair[1].2));
+
+        let (count, page) = requested_messages.requests(1, 2);
+
+        assert_eq!(count, 4);
+        assert_eq!(
+            page.iter()
+                .map(|(message_id, _, _)| message_id.as_ref()[0])
+                .collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+        assert!(requested_messages.requests(4, 10).1.is_empty());
+    }
+
+    #[test]
+    fn stale_request_is_reissued_then_abandoned() {
+        let requested_messages = RequestedMessages::default();
+        let message_id = MessageId::new([1; 32]);
+        let timeout = Duration::from_secs(10);
+        let start = Instant::now();
+
+        requested_messages.insert_many(vec![(message_id, MilestoneIndex(1), 0)]);
+
+        // The request is not re-issued before the timeout.
+        assert_eq!(
+            requested_messages.take_expired(start + Duration::from_secs(9), timeout, Some(1)),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            requested_messages.take_expired(start + Duration::from_secs(11), timeout, Some(1)),
+            (vec![(message_id, MilestoneIndex(1))], vec![])
+        );
+        assert_eq!(
+            requested_messages.take_expired(start + Duration::from_secs(22), timeout, Some(1)),
+            (vec![], vec![(message_id, MilestoneIndex(1))])
+        );
+        assert!(!requested_messages.contains(&message_id));
+    }
+
+    #[test]
+    fn unlimited_retries() {
+        let requested_messages = RequestedMessages::default();
+        let message_id = MessageId::new([1; 32]);
+
+        requested_messages.insert_many(vec![(message_id, MilestoneIndex(1), 0)]);
+
+        let mut now = Instant::now();
+
+        for _ in 0..10 {
+            now += TIMEOUT * 2;
+            assert_eq!(
+                requested_messages.take_expired(now, TIMEOUT, None),
+                (vec![(message_id, MilestoneIndex(1))], vec![])
+            );
+        }
     }
 }
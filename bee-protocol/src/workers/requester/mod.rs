@@ -4,7 +4,11 @@
 mod message;
 mod milestone;
 
-pub use message::{request_message, MessageRequesterWorker, MessageRequesterWorkerEvent, RequestedMessages};
+pub use message::{
+    message_status, request_message, MessageRequesterWorker, MessageRequesterWorkerEvent, MessageStatus,
+    RequestedMessages,
+};
+pub(crate) use message::{parent_request_depth, request_message_at_depth};
 pub use milestone::RequestedMilestones;
 pub(crate) use milestone::{
     request_latest_milestone, request_milestone, MilestoneRequesterWorker, MilestoneRequesterWorkerEvent,
@@ -0,0 +1,110 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_autopeering::DiscoveryHandle;
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{debug, info};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// The maximum number of peers reverified by a single burst.
+const REVERIFY_BURST_PEERS: usize = 4;
+
+// Signals the peer discovery to reverify its peers, at most once per interval.
+pub(crate) struct ReverifySignal {
+    tx: mpsc::UnboundedSender<()>,
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl ReverifySignal {
+    fn new(tx: mpsc::UnboundedSender<()>, interval: Duration) -> Self {
+        Self {
+            tx,
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+
+    // Sends a signal unless one was sent less than an interval ago. Returns whether a signal was sent.
+    pub(crate) fn notify(&self, now: Instant) -> bool {
+        // Panic: we don't allow poisoned locks.
+        let mut last = self.last.lock().expect("error locking reverify signal");
+
+        if let Some(last) = *last {
+            if now.saturating_duration_since(last) < self.interval {
+                return false;
+            }
+        }
+
+        last.replace(now);
+
+        // The receiver only disappears during shutdown.
+        self.tx.send(()).is_ok()
+    }
+}
+
+/// A worker triggering an off-schedule reverification of the discovered peers whenever a new milestone is processed.
+pub struct ReverifierWorker {
+    pub(crate) signal: Arc<ReverifySignal>,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for ReverifierWorker {
+    type Config = (DiscoveryHandle, u64);
+    type Error = Infallible;
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (discovery_handle, interval) = config;
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+
+            while receiver.next().await.is_some() {
+                let num_peers = discovery_handle.reverify_burst(REVERIFY_BURST_PEERS);
+                debug!("Reverifying {} peer/s after a new milestone.", num_peers);
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self {
+            signal: Arc::new(ReverifySignal::new(tx, Duration::from_secs(interval))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn signal_is_sent_at_most_once_per_interval() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let signal = ReverifySignal::new(tx, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(signal.notify(start));
+        assert!(!signal.notify(start));
+        assert!(!signal.notify(start + Duration::from_secs(9)));
+        assert!(signal.notify(start + Duration::from_secs(10)));
+        assert!(!signal.notify(start + Duration::from_secs(15)));
+
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+}
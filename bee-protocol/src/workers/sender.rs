@@ -43,13 +43,15 @@ impl Sender<MilestoneRequestPacket> {
 }
 
 impl Sender<MessagePacket> {
-    pub(crate) fn send(packet: &MessagePacket, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) {
+    // Returns whether the packet was sent.
+    pub(crate) fn send(packet: &MessagePacket, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) -> bool {
         if let Some(ref peer) = peer_manager.get(id) {
             if let Some(ref sender) = peer.1 {
                 match sender.0.send(tlv_to_bytes(packet)) {
                     Ok(_) => {
                         peer.0.metrics().messages_sent_inc();
                         metrics.messages_sent_inc();
+                        return true;
                     }
                     Err(e) => {
                         warn!("Sending MessagePacket to {} failed: {:?}.", id, e);
@@ -57,6 +59,8 @@ impl Sender<MessagePacket> {
                 }
             }
         }
+
+        false
     }
 }
 
@@ -4,6 +4,7 @@
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
+        event::NodeSynced,
         heartbeater::broadcast_heartbeat,
         peer::PeerManager,
         requester::{request_message, request_milestone},
@@ -77,8 +78,14 @@ fn solidify<B: StorageBackend>(
 ) {
     debug!("New solid milestone {}.", *index);
 
+    let was_synced = tangle.is_synced();
+
     tangle.update_solid_milestone_index(index);
 
+    if !was_synced && tangle.is_synced() {
+        bus.dispatch(NodeSynced { index });
+    }
+
     if let Err(e) = consensus_worker.send(ConsensusWorkerCommand::ConfirmMilestone(id)) {
         warn!("Sending message_id to consensus worker failed: {}.", e);
     }
@@ -4,11 +4,12 @@
 use bee_ledger::workers::StorageBackend as LedgerStorageBackend;
 use bee_message::{milestone::MilestoneIndex, payload::indexation::PaddedIndex, MessageId};
 use bee_storage::{access::Insert, backend};
-use bee_tangle::unreferenced_message::UnreferencedMessage;
+use bee_tangle::{indexed_message::IndexedMessage, unreferenced_message::UnreferencedMessage};
 
 pub trait StorageBackend:
     backend::StorageBackend
     + Insert<(PaddedIndex, MessageId), ()>
+    + Insert<(PaddedIndex, IndexedMessage), ()>
     + Insert<(MilestoneIndex, UnreferencedMessage), ()>
     + LedgerStorageBackend
 {
@@ -17,6 +18,7 @@ pub trait StorageBackend:
 impl<T> StorageBackend for T where
     T: backend::StorageBackend
         + Insert<(PaddedIndex, MessageId), ()>
+        + Insert<(PaddedIndex, IndexedMessage), ()>
         + Insert<(MilestoneIndex, UnreferencedMessage), ()>
         + LedgerStorageBackend
 {
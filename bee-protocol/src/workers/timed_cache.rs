@@ -0,0 +1,207 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{hash_map::RandomState, HashMap, VecDeque},
+    hash::{BuildHasher, Hash},
+    time::{Duration, Instant},
+};
+
+// A cache bounded both in size and in the age of its entries.
+//
+// Expired entries are evicted lazily, whenever the cache is accessed mutably. Times are provided by the caller, which
+// allows to test the expiry without waiting.
+pub(crate) struct TimedCache<K, V, S = RandomState> {
+    capacity: usize,
+    ttl: Option<Duration>,
+    entries: HashMap<K, (V, Instant), S>,
+    // Keys in insertion order, i.e. from the oldest to the newest entry.
+    order: VecDeque<K>,
+}
+
+impl<K: Clone + Eq + Hash, V, S: BuildHasher + Default> TimedCache<K, V, S> {
+    // Creates a cache holding at most `capacity` entries, each for at most `ttl` if any.
+    pub(crate) fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::default(),
+            order: VecDeque::new(),
+        }
+    }
+
+    // Returns the value of a key, unless it is missing or expired.
+    pub(crate) fn get(&self, key: &K, now: Instant) -> Option<&V> {
+        match self.entries.get(key) {
+            Some((value, inserted)) if !self.is_expired(*inserted, now) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn contains(&self, key: &K, now: Instant) -> bool {
+        self.get(key, now).is_some()
+    }
+
+    // Returns the entries that are not expired, from the oldest to the newest.
+    pub(crate) fn iter(&self, now: Instant) -> impl Iterator<Item = (&K, &V)> {
+        self.order.iter().filter_map(move |key| match self.entries.get(key) {
+            Some((value, inserted)) if !self.is_expired(*inserted, now) => Some((key, value)),
+            _ => None,
+        })
+    }
+
+    // Inserts a value unless the key is already present, evicting the oldest entry if the cache is full. Returns
+    // whether the value was inserted.
+    pub(crate) fn insert(&mut self, key: K, value: V, now: Instant) -> bool {
+        self.evict_expired(now);
+
+        if self.entries.contains_key(&key) {
+            return false;
+        }
+
+        if self.is_full() {
+            self.evict_oldest();
+        }
+
+        self.entries.insert(key.clone(), (value, now));
+        self.order.push_back(key);
+
+        true
+    }
+
+    // Removes a key, returning its value if it was present, even if expired.
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, _) = self.entries.remove(key)?;
+        self.order.retain(|k| k != key);
+
+        Some(value)
+    }
+
+    // Removes all entries that don't satisfy the predicate.
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) {
+        let entries = &mut self.entries;
+
+        entries.retain(|key, (value, _)| f(key, value));
+        self.order.retain(|key| entries.contains_key(key));
+    }
+
+    // Returns whether inserting a new key would evict the oldest entry, once the expired ones are evicted.
+    pub(crate) fn is_full_at(&mut self, now: Instant) -> bool {
+        self.evict_expired(now);
+        self.is_full()
+    }
+
+    pub(crate) fn evict_expired(&mut self, now: Instant) {
+        while let Some(key) = self.order.front() {
+            match self.entries.get(key) {
+                Some((_, inserted)) if self.is_expired(*inserted, now) => {
+                    self.evict_oldest();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn is_full(&self) -> bool {
+        self.entries.len() >= self.capacity
+    }
+
+    fn is_expired(&self, inserted: Instant, now: Instant) -> bool {
+        match self.ttl {
+            Some(ttl) => now.saturating_duration_since(inserted) >= ttl,
+            None => false,
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(key) = self.order.pop_front() {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let mut cache = TimedCache::<u8, u8>::new(10, Some(Duration::from_secs(10)));
+        let start = Instant::now();
+
+        assert!(cache.insert(1, 10, start));
+        assert!(cache.insert(2, 20, start + Duration::from_secs(5)));
+        assert_eq!(cache.get(&1, start + Duration::from_secs(9)), Some(&10));
+        assert!(!cache.insert(1, 11, start + Duration::from_secs(9)));
+
+        // The first entry expired, the second one did not.
+        assert!(!cache.contains(&1, start + Duration::from_secs(10)));
+        assert!(cache.contains(&2, start + Duration::from_secs(10)));
+        assert_eq!(cache.len(), 2);
+
+        cache.evict_expired(start + Duration::from_secs(10));
+        assert_eq!(cache.len(), 1);
+
+        // An expired key can be inserted again.
+        assert!(cache.insert(2, 21, start + Duration::from_secs(15)));
+        assert_eq!(cache.get(&2, start + Duration::from_secs(15)), Some(&21));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted_at_capacity() {
+        let mut cache = TimedCache::<u8, ()>::new(3, None);
+        let now = Instant::now();
+
+        for i in 0..5 {
+            assert!(cache.insert(i, (), now));
+            assert!(cache.len() <= 3);
+        }
+
+        assert!(cache.is_full_at(now));
+        assert!(!cache.contains(&0, now));
+        assert!(!cache.contains(&1, now));
+        assert!((2..5).all(|i| cache.contains(&i, now)));
+    }
+
+    #[test]
+    fn removed_entries_free_capacity() {
+        let mut cache = TimedCache::<u8, u8>::new(3, Some(Duration::from_secs(10)));
+        let start = Instant::now();
+
+        for i in 0..3 {
+            assert!(cache.insert(i, i * 10, start + Duration::from_secs(i as u64)));
+        }
+
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.remove(&1), None);
+        cache.retain(|key, _| *key != 2);
+
+        assert_eq!(cache.len(), 1);
+        assert!(!cache.is_full_at(start));
+        assert_eq!(cache.iter(start).collect::<Vec<_>>(), vec![(&0, &0)]);
+        // Expired entries are skipped.
+        assert_eq!(cache.iter(start + Duration::from_secs(10)).count(), 0);
+    }
+
+    #[test]
+    fn expired_entries_free_capacity() {
+        let mut cache = TimedCache::<u8, ()>::new(2, Some(Duration::from_secs(1)));
+        let start = Instant::now();
+
+        assert!(cache.insert(0, (), start));
+        assert!(cache.insert(1, (), start));
+        assert!(cache.is_full_at(start));
+        assert!(!cache.is_full_at(start + Duration::from_secs(1)));
+        assert_eq!(cache.len(), 0);
+    }
+}
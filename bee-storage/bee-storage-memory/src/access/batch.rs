@@ -21,7 +21,8 @@ use bee_storage::{
     backend::StorageBackend,
 };
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 /// A writing batch that can be applied atomically.
@@ -29,8 +30,10 @@ use bee_tangle::{
 pub struct StorageBatch {
     message_id_to_message: TableBatch<MessageId, Message>,
     message_id_to_metadata: TableBatch<MessageId, MessageMetadata>,
+    message_id_to_raw_message: TableBatch<MessageId, RawMessage>,
     message_id_to_message_id: TableBatch<(MessageId, MessageId), ()>,
     index_to_message_id: TableBatch<(PaddedIndex, MessageId), ()>,
+    index_to_indexed_message: TableBatch<(PaddedIndex, IndexedMessage), ()>,
     output_id_to_created_output: TableBatch<OutputId, CreatedOutput>,
     output_id_to_consumed_output: TableBatch<OutputId, ConsumedOutput>,
     output_id_unspent: TableBatch<Unspent, ()>,
@@ -64,8 +67,10 @@ impl BatchBuilder for Storage {
 
         apply_batch!(message_id_to_message);
         apply_batch!(message_id_to_metadata);
+        apply_batch!(message_id_to_raw_message);
         apply_batch!(message_id_to_message_id);
         apply_batch!(index_to_message_id);
+        apply_batch!(index_to_indexed_message);
         apply_batch!(output_id_to_created_output);
         apply_batch!(output_id_to_consumed_output);
         apply_batch!(output_id_unspent);
@@ -109,8 +114,10 @@ macro_rules! impl_batch {
 
 impl_batch!(MessageId, Message, message_id_to_message);
 impl_batch!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_batch!(MessageId, RawMessage, message_id_to_raw_message);
 impl_batch!((MessageId, MessageId), (), message_id_to_message_id);
 impl_batch!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_batch!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_batch!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_batch!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_batch!(Unspent, (), output_id_unspent);
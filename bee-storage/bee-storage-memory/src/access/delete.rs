@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Delete, backend::StorageBackend};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_delete {
@@ -35,8 +36,10 @@ macro_rules! impl_delete {
 
 impl_delete!(MessageId, Message, message_id_to_message);
 impl_delete!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_delete!(MessageId, RawMessage, message_id_to_raw_message);
 impl_delete!((MessageId, MessageId), (), message_id_to_message_id);
 impl_delete!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_delete!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_delete!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_delete!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_delete!(Unspent, (), output_id_unspent);
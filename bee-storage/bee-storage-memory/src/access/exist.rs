@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Exist, backend::StorageBackend};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_exist {
@@ -33,8 +34,10 @@ macro_rules! impl_exist {
 
 impl_exist!(MessageId, Message, message_id_to_message);
 impl_exist!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_exist!(MessageId, RawMessage, message_id_to_raw_message);
 impl_exist!((MessageId, MessageId), (), message_id_to_message_id);
 impl_exist!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_exist!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_exist!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_exist!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_exist!(Unspent, (), output_id_unspent);
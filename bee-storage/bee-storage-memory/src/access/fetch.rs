@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Fetch, backend::StorageBackend, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_fetch {
@@ -34,8 +35,10 @@ macro_rules! impl_fetch {
 impl_fetch!(u8, System, system);
 impl_fetch!(MessageId, Message, message_id_to_message);
 impl_fetch!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_fetch!(MessageId, RawMessage, message_id_to_raw_message);
 impl_fetch!(MessageId, Vec<MessageId>, message_id_to_message_id);
 impl_fetch!(PaddedIndex, Vec<MessageId>, index_to_message_id);
+impl_fetch!(PaddedIndex, Vec<IndexedMessage>, index_to_indexed_message);
 impl_fetch!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_fetch!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_fetch!(Ed25519Address, Vec<OutputId>, ed25519_address_to_output_id);
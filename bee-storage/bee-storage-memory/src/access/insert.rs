@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Insert, backend::StorageBackend, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_insert {
@@ -36,8 +37,10 @@ macro_rules! impl_insert {
 impl_insert!(u8, System, system);
 impl_insert!(MessageId, Message, message_id_to_message);
 impl_insert!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_insert!(MessageId, RawMessage, message_id_to_raw_message);
 impl_insert!((MessageId, MessageId), (), message_id_to_message_id);
 impl_insert!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_insert!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_insert!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_insert!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_insert!(Unspent, (), output_id_unspent);
@@ -21,7 +21,8 @@ use bee_message::{
 };
 use bee_storage::{access::AsIterator, backend::StorageBackend, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_iter {
@@ -58,8 +59,10 @@ macro_rules! impl_iter {
 impl_iter!(u8, System, system);
 impl_iter!(MessageId, Message, message_id_to_message);
 impl_iter!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_iter!(MessageId, RawMessage, message_id_to_raw_message);
 impl_iter!((MessageId, MessageId), (), message_id_to_message_id);
 impl_iter!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_iter!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_iter!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_iter!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_iter!(Unspent, (), output_id_unspent);
@@ -13,7 +13,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, backend::StorageBackend, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, raw_message::RawMessage, solid_entry_point::SolidEntryPoint};
 
 use std::{iter::Map, vec::IntoIter};
 
@@ -35,6 +35,7 @@ macro_rules! impl_multi_fetch {
 impl_multi_fetch!(u8, System, system);
 impl_multi_fetch!(MessageId, Message, message_id_to_message);
 impl_multi_fetch!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_multi_fetch!(MessageId, RawMessage, message_id_to_raw_message);
 impl_multi_fetch!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_multi_fetch!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_multi_fetch!(MilestoneIndex, Milestone, milestone_index_to_milestone);
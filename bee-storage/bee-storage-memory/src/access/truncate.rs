@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Truncate, backend::StorageBackend};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_truncate {
@@ -35,8 +36,10 @@ macro_rules! impl_truncate {
 
 impl_truncate!(MessageId, Message, message_id_to_message);
 impl_truncate!(MessageId, MessageMetadata, message_id_to_metadata);
+impl_truncate!(MessageId, RawMessage, message_id_to_raw_message);
 impl_truncate!((MessageId, MessageId), (), message_id_to_message_id);
 impl_truncate!((PaddedIndex, MessageId), (), index_to_message_id);
+impl_truncate!((PaddedIndex, IndexedMessage), (), index_to_indexed_message);
 impl_truncate!(OutputId, CreatedOutput, output_id_to_created_output);
 impl_truncate!(OutputId, ConsumedOutput, output_id_to_consumed_output);
 impl_truncate!(Unspent, (), output_id_unspent);
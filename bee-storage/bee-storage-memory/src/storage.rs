@@ -21,7 +21,8 @@ use bee_storage::{
     system::{StorageHealth, StorageVersion, System, SYSTEM_HEALTH_KEY, SYSTEM_VERSION_KEY},
 };
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 use thiserror::Error;
@@ -61,8 +62,10 @@ pub(crate) struct InnerStorage {
     pub(crate) system: Table<u8, System>,
     pub(crate) message_id_to_message: Table<MessageId, Message>,
     pub(crate) message_id_to_metadata: Table<MessageId, MessageMetadata>,
+    pub(crate) message_id_to_raw_message: Table<MessageId, RawMessage>,
     pub(crate) message_id_to_message_id: VecBinTable<MessageId, MessageId>,
     pub(crate) index_to_message_id: VecBinTable<PaddedIndex, MessageId>,
+    pub(crate) index_to_indexed_message: VecBinTable<PaddedIndex, IndexedMessage>,
     pub(crate) output_id_to_created_output: Table<OutputId, CreatedOutput>,
     pub(crate) output_id_to_consumed_output: Table<OutputId, ConsumedOutput>,
     pub(crate) output_id_unspent: Table<Unspent, ()>,
@@ -20,7 +20,8 @@ use bee_message::{
 };
 use bee_storage::access::{Batch, BatchBuilder};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 use rocksdb::{WriteBatch, WriteOptions};
@@ -107,6 +108,39 @@ impl Batch<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Batch<MessageId, RawMessage> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+        raw_message: &RawMessage,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        raw_message.pack(&mut batch.value_buf).unwrap();
+
+        batch.inner.put_cf(
+            self.cf_handle(CF_MESSAGE_ID_TO_RAW_MESSAGE)?,
+            message_id,
+            &batch.value_buf,
+        );
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch
+            .inner
+            .delete_cf(self.cf_handle(CF_MESSAGE_ID_TO_RAW_MESSAGE)?, message_id);
+
+        Ok(())
+    }
+}
+
 impl Batch<(MessageId, MessageId), ()> for Storage {
     fn batch_insert(
         &self,
@@ -177,6 +211,43 @@ impl Batch<(PaddedIndex, MessageId), ()> for Storage {
     }
 }
 
+impl Batch<(PaddedIndex, IndexedMessage), ()> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+        (): &(),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        batch.key_buf.extend_from_slice(index.as_ref());
+        // Packing to bytes can't fail.
+        indexed_message.pack(&mut batch.key_buf).unwrap();
+
+        batch
+            .inner
+            .put_cf(self.cf_handle(CF_INDEX_TO_INDEXED_MESSAGE)?, &batch.key_buf, []);
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        batch.key_buf.extend_from_slice(index.as_ref());
+        // Packing to bytes can't fail.
+        indexed_message.pack(&mut batch.key_buf).unwrap();
+
+        batch
+            .inner
+            .delete_cf(self.cf_handle(CF_INDEX_TO_INDEXED_MESSAGE)?, &batch.key_buf);
+
+        Ok(())
+    }
+}
+
 impl Batch<OutputId, CreatedOutput> for Storage {
     fn batch_insert(
         &self,
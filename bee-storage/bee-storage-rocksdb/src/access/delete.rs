@@ -20,7 +20,8 @@ use bee_message::{
 };
 use bee_storage::access::Delete;
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 impl Delete<MessageId, Message> for Storage {
@@ -41,6 +42,15 @@ impl Delete<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Delete<MessageId, RawMessage> for Storage {
+    fn delete(&self, message_id: &MessageId) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner
+            .delete_cf(self.cf_handle(CF_MESSAGE_ID_TO_RAW_MESSAGE)?, message_id)?;
+
+        Ok(())
+    }
+}
+
 impl Delete<(MessageId, MessageId), ()> for Storage {
     fn delete(&self, (parent, child): &(MessageId, MessageId)) -> Result<(), <Self as StorageBackend>::Error> {
         let mut key = parent.as_ref().to_vec();
@@ -64,6 +74,21 @@ impl Delete<(PaddedIndex, MessageId), ()> for Storage {
     }
 }
 
+impl Delete<(PaddedIndex, IndexedMessage), ()> for Storage {
+    fn delete(
+        &self,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        let mut key = index.as_ref().to_vec();
+        key.extend_from_slice(&indexed_message.pack_new());
+
+        self.inner
+            .delete_cf(self.cf_handle(CF_INDEX_TO_INDEXED_MESSAGE)?, key)?;
+
+        Ok(())
+    }
+}
+
 impl Delete<OutputId, CreatedOutput> for Storage {
     fn delete(&self, output_id: &OutputId) -> Result<(), <Self as StorageBackend>::Error> {
         self.inner
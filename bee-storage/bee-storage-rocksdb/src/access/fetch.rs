@@ -20,7 +20,8 @@ use bee_message::{
 };
 use bee_storage::{access::Fetch, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 impl Fetch<u8, System> for Storage {
@@ -53,6 +54,16 @@ impl Fetch<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Fetch<MessageId, RawMessage> for Storage {
+    fn fetch(&self, message_id: &MessageId) -> Result<Option<RawMessage>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .get_cf(self.cf_handle(CF_MESSAGE_ID_TO_RAW_MESSAGE)?, message_id)?
+            // Unpacking from storage is fine.
+            .map(|v| RawMessage::unpack_unchecked(&mut v.as_slice()).unwrap()))
+    }
+}
+
 impl Fetch<MessageId, Vec<MessageId>> for Storage {
     fn fetch(&self, parent: &MessageId) -> Result<Option<Vec<MessageId>>, <Self as StorageBackend>::Error> {
         Ok(Some(
@@ -87,6 +98,22 @@ impl Fetch<PaddedIndex, Vec<MessageId>> for Storage {
     }
 }
 
+impl Fetch<PaddedIndex, Vec<IndexedMessage>> for Storage {
+    fn fetch(&self, index: &PaddedIndex) -> Result<Option<Vec<IndexedMessage>>, <Self as StorageBackend>::Error> {
+        Ok(Some(
+            self.inner
+                .prefix_iterator_cf(self.cf_handle(CF_INDEX_TO_INDEXED_MESSAGE)?, index)
+                .map(|(key, _)| {
+                    let (_, mut indexed_message) = key.split_at(INDEXATION_PADDED_INDEX_LENGTH);
+                    // Unpacking from storage is fine.
+                    IndexedMessage::unpack_unchecked(&mut indexed_message).unwrap()
+                })
+                .take(self.config.fetch_index_limit)
+                .collect(),
+        ))
+    }
+}
+
 impl Fetch<OutputId, CreatedOutput> for Storage {
     fn fetch(&self, output_id: &OutputId) -> Result<Option<CreatedOutput>, <Self as StorageBackend>::Error> {
         Ok(self
@@ -20,7 +20,8 @@ use bee_message::{
 };
 use bee_storage::{access::Insert, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 impl Insert<u8, System> for Storage {
@@ -60,6 +61,18 @@ impl Insert<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Insert<MessageId, RawMessage> for Storage {
+    fn insert(&self, message_id: &MessageId, raw_message: &RawMessage) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner.put_cf(
+            self.cf_handle(CF_MESSAGE_ID_TO_RAW_MESSAGE)?,
+            message_id,
+            raw_message.pack_new(),
+        )?;
+
+        Ok(())
+    }
+}
+
 impl Insert<(MessageId, MessageId), ()> for Storage {
     fn insert(&self, (parent, child): &(MessageId, MessageId), (): &()) -> Result<(), <Self as StorageBackend>::Error> {
         let mut key = parent.as_ref().to_vec();
@@ -87,6 +100,22 @@ impl Insert<(PaddedIndex, MessageId), ()> for Storage {
     }
 }
 
+impl Insert<(PaddedIndex, IndexedMessage), ()> for Storage {
+    fn insert(
+        &self,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+        (): &(),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        let mut key = index.as_ref().to_vec();
+        key.extend_from_slice(&indexed_message.pack_new());
+
+        self.inner
+            .put_cf(self.cf_handle(CF_INDEX_TO_INDEXED_MESSAGE)?, key, [])?;
+
+        Ok(())
+    }
+}
+
 impl Insert<OutputId, CreatedOutput> for Storage {
     fn insert(&self, output_id: &OutputId, output: &CreatedOutput) -> Result<(), <Self as StorageBackend>::Error> {
         self.inner.put_cf(
@@ -15,7 +15,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, raw_message::RawMessage, solid_entry_point::SolidEntryPoint};
 
 use std::{marker::PhantomData, vec::IntoIter};
 
@@ -60,6 +60,7 @@ macro_rules! impl_multi_fetch {
 impl_multi_fetch!(u8, System, CF_SYSTEM);
 impl_multi_fetch!(MessageId, Message, CF_MESSAGE_ID_TO_MESSAGE);
 impl_multi_fetch!(MessageId, MessageMetadata, CF_MESSAGE_ID_TO_METADATA);
+impl_multi_fetch!(MessageId, RawMessage, CF_MESSAGE_ID_TO_RAW_MESSAGE);
 impl_multi_fetch!(OutputId, CreatedOutput, CF_OUTPUT_ID_TO_CREATED_OUTPUT);
 impl_multi_fetch!(OutputId, ConsumedOutput, CF_OUTPUT_ID_TO_CONSUMED_OUTPUT);
 impl_multi_fetch!(MilestoneIndex, Milestone, CF_MILESTONE_INDEX_TO_MILESTONE);
@@ -19,7 +19,8 @@ use bee_message::{
 };
 use bee_storage::access::Truncate;
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 fn truncate(storage: &Storage, cf_str: &'static str) -> Result<(), <Storage as StorageBackend>::Error> {
@@ -66,8 +67,10 @@ macro_rules! impl_truncate {
 
 impl_truncate!(MessageId, Message, CF_MESSAGE_ID_TO_MESSAGE);
 impl_truncate!(MessageId, MessageMetadata, CF_MESSAGE_ID_TO_METADATA);
+impl_truncate!(MessageId, RawMessage, CF_MESSAGE_ID_TO_RAW_MESSAGE);
 impl_truncate!((MessageId, MessageId), (), CF_MESSAGE_ID_TO_MESSAGE_ID);
 impl_truncate!((PaddedIndex, MessageId), (), CF_INDEX_TO_MESSAGE_ID);
+impl_truncate!((PaddedIndex, IndexedMessage), (), CF_INDEX_TO_INDEXED_MESSAGE);
 impl_truncate!(OutputId, CreatedOutput, CF_OUTPUT_ID_TO_CREATED_OUTPUT);
 impl_truncate!(OutputId, ConsumedOutput, CF_OUTPUT_ID_TO_CONSUMED_OUTPUT);
 impl_truncate!(Unspent, (), CF_OUTPUT_ID_UNSPENT);
@@ -38,6 +38,9 @@ impl Storage {
 
         let cf_message_id_to_metadata = ColumnFamilyDescriptor::new(CF_MESSAGE_ID_TO_METADATA, Options::default());
 
+        let cf_message_id_to_raw_message =
+            ColumnFamilyDescriptor::new(CF_MESSAGE_ID_TO_RAW_MESSAGE, Options::default());
+
         let mut options = Options::default();
         options.set_prefix_extractor(SliceTransform::create_fixed_prefix(MESSAGE_ID_LENGTH));
         let cf_message_id_to_message_id = ColumnFamilyDescriptor::new(CF_MESSAGE_ID_TO_MESSAGE_ID, options);
@@ -46,6 +49,10 @@ impl Storage {
         options.set_prefix_extractor(SliceTransform::create_fixed_prefix(INDEXATION_PADDED_INDEX_LENGTH));
         let cf_index_to_message_id = ColumnFamilyDescriptor::new(CF_INDEX_TO_MESSAGE_ID, options);
 
+        let mut options = Options::default();
+        options.set_prefix_extractor(SliceTransform::create_fixed_prefix(INDEXATION_PADDED_INDEX_LENGTH));
+        let cf_index_to_indexed_message = ColumnFamilyDescriptor::new(CF_INDEX_TO_INDEXED_MESSAGE, options);
+
         let cf_output_id_to_created_output =
             ColumnFamilyDescriptor::new(CF_OUTPUT_ID_TO_CREATED_OUTPUT, Options::default());
 
@@ -128,8 +135,10 @@ impl Storage {
                 cf_system,
                 cf_message_id_to_message,
                 cf_message_id_to_metadata,
+                cf_message_id_to_raw_message,
                 cf_message_id_to_message_id,
                 cf_index_to_message_id,
+                cf_index_to_indexed_message,
                 cf_output_id_to_created_output,
                 cf_output_id_to_consumed_output,
                 cf_output_id_unspent,
@@ -22,7 +22,8 @@ use bee_storage::{
     backend::StorageBackend,
 };
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 use sled::{transaction::TransactionError, Transactional};
@@ -138,6 +139,41 @@ impl Batch<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Batch<MessageId, RawMessage> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+        raw_message: &RawMessage,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        raw_message.pack(&mut batch.value_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_MESSAGE_ID_TO_RAW_MESSAGE)
+            .or_default()
+            .insert(message_id.as_ref(), batch.value_buf.as_slice());
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch
+            .inner
+            .entry(TREE_MESSAGE_ID_TO_RAW_MESSAGE)
+            .or_default()
+            .remove(message_id.as_ref());
+
+        Ok(())
+    }
+}
+
 impl Batch<(MessageId, MessageId), ()> for Storage {
     fn batch_insert(
         &self,
@@ -216,6 +252,47 @@ impl Batch<(PaddedIndex, MessageId), ()> for Storage {
     }
 }
 
+impl Batch<(PaddedIndex, IndexedMessage), ()> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+        (): &(),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        batch.key_buf.extend_from_slice(index.as_ref());
+        // Packing to bytes can't fail.
+        indexed_message.pack(&mut batch.key_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_INDEX_TO_INDEXED_MESSAGE)
+            .or_default()
+            .insert(batch.key_buf.as_slice(), &[]);
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        batch.key_buf.extend_from_slice(index.as_ref());
+        // Packing to bytes can't fail.
+        indexed_message.pack(&mut batch.key_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_INDEX_TO_INDEXED_MESSAGE)
+            .or_default()
+            .remove(batch.key_buf.as_slice());
+
+        Ok(())
+    }
+}
+
 impl Batch<OutputId, CreatedOutput> for Storage {
     fn batch_insert(
         &self,
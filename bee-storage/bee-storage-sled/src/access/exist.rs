@@ -19,7 +19,8 @@ use bee_message::{
 };
 use bee_storage::{access::Exist, backend::StorageBackend};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 impl Exist<MessageId, Message> for Storage {
@@ -40,6 +41,15 @@ impl Exist<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Exist<MessageId, RawMessage> for Storage {
+    fn exist(&self, message_id: &MessageId) -> Result<bool, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .open_tree(TREE_MESSAGE_ID_TO_RAW_MESSAGE)?
+            .contains_key(message_id)?)
+    }
+}
+
 impl Exist<(MessageId, MessageId), ()> for Storage {
     fn exist(&self, (parent, child): &(MessageId, MessageId)) -> Result<bool, <Self as StorageBackend>::Error> {
         let mut key = parent.as_ref().to_vec();
@@ -58,6 +68,18 @@ impl Exist<(PaddedIndex, MessageId), ()> for Storage {
     }
 }
 
+impl Exist<(PaddedIndex, IndexedMessage), ()> for Storage {
+    fn exist(
+        &self,
+        (index, indexed_message): &(PaddedIndex, IndexedMessage),
+    ) -> Result<bool, <Self as StorageBackend>::Error> {
+        let mut key = index.as_ref().to_vec();
+        key.extend_from_slice(&indexed_message.pack_new());
+
+        Ok(self.inner.open_tree(TREE_INDEX_TO_INDEXED_MESSAGE)?.contains_key(key)?)
+    }
+}
+
 impl Exist<OutputId, CreatedOutput> for Storage {
     fn exist(&self, output_id: &OutputId) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
@@ -19,7 +19,8 @@ use bee_message::{
 };
 use bee_storage::{access::Fetch, backend::StorageBackend, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 impl Fetch<u8, System> for Storage {
@@ -54,6 +55,17 @@ impl Fetch<MessageId, MessageMetadata> for Storage {
     }
 }
 
+impl Fetch<MessageId, RawMessage> for Storage {
+    fn fetch(&self, message_id: &MessageId) -> Result<Option<RawMessage>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .open_tree(TREE_MESSAGE_ID_TO_RAW_MESSAGE)?
+            .get(message_id)?
+            // Unpacking from storage is fine.
+            .map(|v| RawMessage::unpack_unchecked(&mut v.as_ref()).unwrap()))
+    }
+}
+
 impl Fetch<MessageId, Vec<MessageId>> for Storage {
     fn fetch(&self, parent: &MessageId) -> Result<Option<Vec<MessageId>>, <Self as StorageBackend>::Error> {
         Ok(Some(
@@ -92,6 +104,24 @@ impl Fetch<PaddedIndex, Vec<MessageId>> for Storage {
     }
 }
 
+impl Fetch<PaddedIndex, Vec<IndexedMessage>> for Storage {
+    fn fetch(&self, index: &PaddedIndex) -> Result<Option<Vec<IndexedMessage>>, <Self as StorageBackend>::Error> {
+        Ok(Some(
+            self.inner
+                .open_tree(TREE_INDEX_TO_INDEXED_MESSAGE)?
+                .scan_prefix(index)
+                .map(|result| {
+                    let (key, _) = result?;
+                    let (_, mut indexed_message) = key.split_at(INDEXATION_PADDED_INDEX_LENGTH);
+                    // Unpacking from storage is fine.
+                    Ok(IndexedMessage::unpack_unchecked(&mut indexed_message).unwrap())
+                })
+                .take(self.config.storage.fetch_index_limit)
+                .collect::<Result<Vec<IndexedMessage>, Self::Error>>()?,
+        ))
+    }
+}
+
 impl Fetch<OutputId, CreatedOutput> for Storage {
     fn fetch(&self, output_id: &OutputId) -> Result<Option<CreatedOutput>, <Self as StorageBackend>::Error> {
         Ok(self
@@ -19,7 +19,8 @@ use bee_message::{
 };
 use bee_storage::{access::AsIterator, backend::StorageBackend, system::System};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 use std::marker::PhantomData;
@@ -105,6 +106,17 @@ impl<'a> StorageIterator<'a, MessageId, MessageMetadata> {
     }
 }
 
+impl<'a> StorageIterator<'a, MessageId, RawMessage> {
+    fn unpack_key_value(mut key: &[u8], mut value: &[u8]) -> (MessageId, RawMessage) {
+        (
+            // Unpacking from storage is fine.
+            MessageId::unpack_unchecked(&mut key).unwrap(),
+            // Unpacking from storage is fine.
+            RawMessage::unpack_unchecked(&mut value).unwrap(),
+        )
+    }
+}
+
 impl<'a> StorageIterator<'a, (MessageId, MessageId), ()> {
     fn unpack_key_value(key: &[u8], _: &[u8]) -> ((MessageId, MessageId), ()) {
         let (mut parent, mut child) = key.split_at(MESSAGE_ID_LENGTH);
@@ -138,6 +150,23 @@ impl<'a> StorageIterator<'a, (PaddedIndex, MessageId), ()> {
     }
 }
 
+impl<'a> StorageIterator<'a, (PaddedIndex, IndexedMessage), ()> {
+    fn unpack_key_value(key: &[u8], _: &[u8]) -> ((PaddedIndex, IndexedMessage), ()) {
+        let (index, mut indexed_message) = key.split_at(INDEXATION_PADDED_INDEX_LENGTH);
+        // Unpacking from storage is fine.
+        let index: [u8; INDEXATION_PADDED_INDEX_LENGTH] = index.try_into().unwrap();
+
+        (
+            (
+                PaddedIndex::new(index),
+                // Unpacking from storage is fine.
+                IndexedMessage::unpack_unchecked(&mut indexed_message).unwrap(),
+            ),
+            (),
+        )
+    }
+}
+
 impl<'a> StorageIterator<'a, OutputId, CreatedOutput> {
     fn unpack_key_value(mut key: &[u8], mut value: &[u8]) -> (OutputId, CreatedOutput) {
         (
@@ -321,8 +350,10 @@ impl<'a> Iterator for StorageIterator<'a, u8, System> {
 
 impl_iter!(MessageId, Message, TREE_MESSAGE_ID_TO_MESSAGE);
 impl_iter!(MessageId, MessageMetadata, TREE_MESSAGE_ID_TO_METADATA);
+impl_iter!(MessageId, RawMessage, TREE_MESSAGE_ID_TO_RAW_MESSAGE);
 impl_iter!((MessageId, MessageId), (), TREE_MESSAGE_ID_TO_MESSAGE_ID);
 impl_iter!((PaddedIndex, MessageId), (), TREE_INDEX_TO_MESSAGE_ID);
+impl_iter!((PaddedIndex, IndexedMessage), (), TREE_INDEX_TO_INDEXED_MESSAGE);
 impl_iter!(OutputId, CreatedOutput, TREE_OUTPUT_ID_TO_CREATED_OUTPUT);
 impl_iter!(OutputId, ConsumedOutput, TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT);
 impl_iter!(Unspent, (), TREE_OUTPUT_ID_UNSPENT);
@@ -14,7 +14,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, backend::StorageBackend, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, raw_message::RawMessage, solid_entry_point::SolidEntryPoint};
 
 use std::{marker::PhantomData, slice::Iter};
 
@@ -92,6 +92,7 @@ macro_rules! impl_multi_fetch {
 
 impl_multi_fetch!(MessageId, Message, TREE_MESSAGE_ID_TO_MESSAGE);
 impl_multi_fetch!(MessageId, MessageMetadata, TREE_MESSAGE_ID_TO_METADATA);
+impl_multi_fetch!(MessageId, RawMessage, TREE_MESSAGE_ID_TO_RAW_MESSAGE);
 impl_multi_fetch!(OutputId, CreatedOutput, TREE_OUTPUT_ID_TO_CREATED_OUTPUT);
 impl_multi_fetch!(OutputId, ConsumedOutput, TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT);
 impl_multi_fetch!(MilestoneIndex, Milestone, TREE_MILESTONE_INDEX_TO_MILESTONE);
@@ -18,7 +18,8 @@ use bee_message::{
 };
 use bee_storage::{access::Truncate, backend::StorageBackend};
 use bee_tangle::{
-    metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    indexed_message::IndexedMessage, metadata::MessageMetadata, raw_message::RawMessage,
+    solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
 };
 
 macro_rules! impl_truncate {
@@ -35,8 +36,10 @@ macro_rules! impl_truncate {
 
 impl_truncate!(MessageId, Message, TREE_MESSAGE_ID_TO_MESSAGE);
 impl_truncate!(MessageId, MessageMetadata, TREE_MESSAGE_ID_TO_METADATA);
+impl_truncate!(MessageId, RawMessage, TREE_MESSAGE_ID_TO_RAW_MESSAGE);
 impl_truncate!((MessageId, MessageId), (), TREE_MESSAGE_ID_TO_MESSAGE_ID);
 impl_truncate!((PaddedIndex, MessageId), (), TREE_INDEX_TO_MESSAGE_ID);
+impl_truncate!((PaddedIndex, IndexedMessage), (), TREE_INDEX_TO_INDEXED_MESSAGE);
 impl_truncate!(OutputId, CreatedOutput, TREE_OUTPUT_ID_TO_CREATED_OUTPUT);
 impl_truncate!(OutputId, ConsumedOutput, TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT);
 impl_truncate!(Unspent, (), TREE_OUTPUT_ID_UNSPENT);
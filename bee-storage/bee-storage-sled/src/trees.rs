@@ -10,10 +10,14 @@
 pub const TREE_MESSAGE_ID_TO_MESSAGE: &str = "message_id_to_message";
 /// Identifier for the `MessageId` to `MessageMetadata` tree.
 pub const TREE_MESSAGE_ID_TO_METADATA: &str = "message_id_to_metadata";
+/// Identifier for the `MessageId` to `RawMessage` tree.
+pub const TREE_MESSAGE_ID_TO_RAW_MESSAGE: &str = "message_id_to_raw_message";
 /// Identifier for the `MessageId` to `Vec<MessageId>` tree.
 pub const TREE_MESSAGE_ID_TO_MESSAGE_ID: &str = "message_id_to_message_id";
 /// Identifier for the `PaddedIndex` to `Vec<MessageId>` tree.
 pub const TREE_INDEX_TO_MESSAGE_ID: &str = "index_to_message_id";
+/// Identifier for the `PaddedIndex` to `Vec<IndexedMessage>` tree.
+pub const TREE_INDEX_TO_INDEXED_MESSAGE: &str = "index_to_indexed_message";
 /// Identifier for the `OutputId` to `CreatedOutput` tree.
 pub const TREE_OUTPUT_ID_TO_CREATED_OUTPUT: &str = "output_id_to_created_output";
 /// Identifier for the `OutputId` to `ConsumedOutput` tree.
@@ -0,0 +1,135 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_message::payload::indexation::PaddedIndex;
+use bee_storage::{
+    access::{AsIterator, Batch, BatchBuilder, Delete, Exist, Fetch, Insert, Truncate},
+    backend,
+};
+use bee_tangle::indexed_message::IndexedMessage;
+use bee_test::rand::{message::rand_message_id, number::rand_number, payload::rand_indexation_payload};
+
+use std::collections::HashMap;
+
+fn rand_indexed_message() -> IndexedMessage {
+    IndexedMessage::new(rand_number(), rand_message_id())
+}
+
+pub trait StorageBackend:
+    backend::StorageBackend
+    + Exist<(PaddedIndex, IndexedMessage), ()>
+    + Fetch<PaddedIndex, Vec<IndexedMessage>>
+    + Insert<(PaddedIndex, IndexedMessage), ()>
+    + Delete<(PaddedIndex, IndexedMessage), ()>
+    + BatchBuilder
+    + Batch<(PaddedIndex, IndexedMessage), ()>
+    + for<'a> AsIterator<'a, (PaddedIndex, IndexedMessage), ()>
+    + Truncate<(PaddedIndex, IndexedMessage), ()>
+{
+}
+
+impl<T> StorageBackend for T where
+    T: backend::StorageBackend
+        + Exist<(PaddedIndex, IndexedMessage), ()>
+        + Fetch<PaddedIndex, Vec<IndexedMessage>>
+        + Insert<(PaddedIndex, IndexedMessage), ()>
+        + Delete<(PaddedIndex, IndexedMessage), ()>
+        + BatchBuilder
+        + Batch<(PaddedIndex, IndexedMessage), ()>
+        + for<'a> AsIterator<'a, (PaddedIndex, IndexedMessage), ()>
+        + Truncate<(PaddedIndex, IndexedMessage), ()>
+{
+}
+
+pub fn index_to_indexed_message_access<B: StorageBackend>(storage: &B) {
+    let (index, indexed_message) = (rand_indexation_payload().padded_index(), rand_indexed_message());
+
+    assert!(!Exist::<(PaddedIndex, IndexedMessage), ()>::exist(storage, &(index, indexed_message)).unwrap());
+    assert!(Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, &index)
+        .unwrap()
+        .unwrap()
+        .is_empty());
+
+    Insert::<(PaddedIndex, IndexedMessage), ()>::insert(storage, &(index, indexed_message), &()).unwrap();
+
+    assert!(Exist::<(PaddedIndex, IndexedMessage), ()>::exist(storage, &(index, indexed_message)).unwrap());
+    assert_eq!(
+        Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, &index)
+            .unwrap()
+            .unwrap(),
+        vec![indexed_message]
+    );
+
+    Delete::<(PaddedIndex, IndexedMessage), ()>::delete(storage, &(index, indexed_message)).unwrap();
+
+    assert!(!Exist::<(PaddedIndex, IndexedMessage), ()>::exist(storage, &(index, indexed_message)).unwrap());
+    assert!(Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, &index)
+        .unwrap()
+        .unwrap()
+        .is_empty());
+
+    // Indexed messages are fetched in the order they arrived, whatever the order they were inserted in.
+    let mut indexed_messages = (0..5).map(|_| rand_indexed_message()).collect::<Vec<_>>();
+
+    for indexed_message in &indexed_messages {
+        Insert::<(PaddedIndex, IndexedMessage), ()>::insert(storage, &(index, *indexed_message), &()).unwrap();
+    }
+
+    indexed_messages.sort_unstable();
+
+    assert_eq!(
+        Fetch::<PaddedIndex, Vec<IndexedMessage>>::fetch(storage, &index)
+            .unwrap()
+            .unwrap(),
+        indexed_messages
+    );
+
+    for indexed_message in &indexed_messages {
+        Delete::<(PaddedIndex, IndexedMessage), ()>::delete(storage, &(index, *indexed_message)).unwrap();
+    }
+
+    let mut batch = B::batch_begin();
+
+    for _ in 0..10 {
+        let (index, indexed_message) = (rand_indexation_payload().padded_index(), rand_indexed_message());
+        Insert::<(PaddedIndex, IndexedMessage), ()>::insert(storage, &(index, indexed_message), &()).unwrap();
+        Batch::<(PaddedIndex, IndexedMessage), ()>::batch_delete(storage, &mut batch, &(index, indexed_message))
+            .unwrap();
+    }
+
+    let mut indexed_messages = HashMap::<PaddedIndex, Vec<IndexedMessage>>::new();
+
+    for _ in 0..5 {
+        let index = rand_indexation_payload().padded_index();
+        for _ in 0..5 {
+            let indexed_message = rand_indexed_message();
+            Batch::<(PaddedIndex, IndexedMessage), ()>::batch_insert(
+                storage,
+                &mut batch,
+                &(index, indexed_message),
+                &(),
+            )
+            .unwrap();
+            indexed_messages.entry(index).or_default().push(indexed_message);
+        }
+    }
+
+    storage.batch_commit(batch, true).unwrap();
+
+    let iter = AsIterator::<(PaddedIndex, IndexedMessage), ()>::iter(storage).unwrap();
+    let mut count = 0;
+
+    for result in iter {
+        let ((index, indexed_message), _) = result.unwrap();
+        assert!(indexed_messages.get(&index).unwrap().contains(&indexed_message));
+        count += 1;
+    }
+
+    assert_eq!(count, indexed_messages.iter().fold(0, |acc, v| acc + v.1.len()));
+
+    Truncate::<(PaddedIndex, IndexedMessage), ()>::truncate(storage).unwrap();
+
+    let mut iter = AsIterator::<(PaddedIndex, IndexedMessage), ()>::iter(storage).unwrap();
+
+    assert!(iter.next().is_none());
+}
@@ -3,11 +3,13 @@
 
 mod address_to_balance;
 mod ed25519_address_to_output_id;
+mod index_to_indexed_message;
 mod index_to_message_id;
 mod ledger_index;
 mod message_id_to_message;
 mod message_id_to_message_id;
 mod message_id_to_metadata;
+mod message_id_to_raw_message;
 mod milestone_index_to_milestone;
 mod milestone_index_to_output_diff;
 mod milestone_index_to_receipt;
@@ -21,11 +23,13 @@ mod spent_to_treasury_output;
 
 pub use address_to_balance::address_to_balance_access;
 pub use ed25519_address_to_output_id::ed25519_address_to_output_id_access;
+pub use index_to_indexed_message::index_to_indexed_message_access;
 pub use index_to_message_id::index_to_message_id_access;
 pub use ledger_index::ledger_index_access;
 pub use message_id_to_message::message_id_to_message_access;
 pub use message_id_to_message_id::message_id_to_message_id_access;
 pub use message_id_to_metadata::message_id_to_metadata_access;
+pub use message_id_to_raw_message::message_id_to_raw_message_access;
 pub use milestone_index_to_milestone::milestone_index_to_milestone_access;
 pub use milestone_index_to_output_diff::milestone_index_to_output_diff_access;
 pub use milestone_index_to_receipt::milestone_index_to_receipt_access;
@@ -0,0 +1,132 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_common::packable::Packable;
+use bee_message::MessageId;
+use bee_storage::{
+    access::{AsIterator, Batch, BatchBuilder, Delete, Exist, Fetch, Insert, MultiFetch, Truncate},
+    backend,
+};
+use bee_tangle::raw_message::RawMessage;
+use bee_test::rand::message::{rand_message, rand_message_id};
+
+fn rand_raw_message() -> RawMessage {
+    RawMessage::from(rand_message().pack_new())
+}
+
+pub trait StorageBackend:
+    backend::StorageBackend
+    + Exist<MessageId, RawMessage>
+    + Fetch<MessageId, RawMessage>
+    + for<'a> MultiFetch<'a, MessageId, RawMessage>
+    + Insert<MessageId, RawMessage>
+    + Delete<MessageId, RawMessage>
+    + BatchBuilder
+    + Batch<MessageId, RawMessage>
+    + for<'a> AsIterator<'a, MessageId, RawMessage>
+    + Truncate<MessageId, RawMessage>
+{
+}
+
+impl<T> StorageBackend for T where
+    T: backend::StorageBackend
+        + Exist<MessageId, RawMessage>
+        + Fetch<MessageId, RawMessage>
+        + for<'a> MultiFetch<'a, MessageId, RawMessage>
+        + Insert<MessageId, RawMessage>
+        + Delete<MessageId, RawMessage>
+        + BatchBuilder
+        + Batch<MessageId, RawMessage>
+        + for<'a> AsIterator<'a, MessageId, RawMessage>
+        + Truncate<MessageId, RawMessage>
+{
+}
+
+pub fn message_id_to_raw_message_access<B: StorageBackend>(storage: &B) {
+    let (message_id, raw_message) = (rand_message_id(), rand_raw_message());
+
+    assert!(!Exist::<MessageId, RawMessage>::exist(storage, &message_id).unwrap());
+    assert!(Fetch::<MessageId, RawMessage>::fetch(storage, &message_id)
+        .unwrap()
+        .is_none());
+    let results = MultiFetch::<MessageId, RawMessage>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    Insert::<MessageId, RawMessage>::insert(storage, &message_id, &raw_message).unwrap();
+
+    assert!(Exist::<MessageId, RawMessage>::exist(storage, &message_id).unwrap());
+    assert_eq!(
+        Fetch::<MessageId, RawMessage>::fetch(storage, &message_id)
+            .unwrap()
+            .unwrap(),
+        raw_message
+    );
+    let results = MultiFetch::<MessageId, RawMessage>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(Some(v))) if v == &raw_message));
+
+    Delete::<MessageId, RawMessage>::delete(storage, &message_id).unwrap();
+
+    assert!(!Exist::<MessageId, RawMessage>::exist(storage, &message_id).unwrap());
+    assert!(Fetch::<MessageId, RawMessage>::fetch(storage, &message_id)
+        .unwrap()
+        .is_none());
+    let results = MultiFetch::<MessageId, RawMessage>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    let mut batch = B::batch_begin();
+    let mut message_ids = Vec::new();
+    let mut raw_messages = Vec::new();
+
+    for _ in 0..10 {
+        let (message_id, raw_message) = (rand_message_id(), rand_raw_message());
+        Insert::<MessageId, RawMessage>::insert(storage, &message_id, &raw_message).unwrap();
+        Batch::<MessageId, RawMessage>::batch_delete(storage, &mut batch, &message_id).unwrap();
+        message_ids.push(message_id);
+        raw_messages.push((message_id, None));
+    }
+
+    for _ in 0..10 {
+        let (message_id, raw_message) = (rand_message_id(), rand_raw_message());
+        Batch::<MessageId, RawMessage>::batch_insert(storage, &mut batch, &message_id, &raw_message).unwrap();
+        message_ids.push(message_id);
+        raw_messages.push((message_id, Some(raw_message)));
+    }
+
+    storage.batch_commit(batch, true).unwrap();
+
+    let iter = AsIterator::<MessageId, RawMessage>::iter(storage).unwrap();
+    let mut count = 0;
+
+    for result in iter {
+        let (message_id, raw_message) = result.unwrap();
+        assert!(raw_messages.contains(&(message_id, Some(raw_message))));
+        count += 1;
+    }
+
+    assert_eq!(count, 10);
+
+    let results = MultiFetch::<MessageId, RawMessage>::multi_fetch(storage, &message_ids)
+        .unwrap()
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.len(), message_ids.len());
+
+    for ((_, raw_message), result) in raw_messages.into_iter().zip(results.into_iter()) {
+        assert_eq!(raw_message, result.unwrap());
+    }
+
+    Truncate::<MessageId, RawMessage>::truncate(storage).unwrap();
+
+    let mut iter = AsIterator::<MessageId, RawMessage>::iter(storage).unwrap();
+
+    assert!(iter.next().is_none());
+}
@@ -20,6 +20,10 @@ bitflags! {
         const VALID = 0b0000_1000;
         /// The message was requested.
         const REQUESTED = 0b0001_0000;
+        /// The message carries the same payload as a previously seen message.
+        const REATTACHMENT = 0b0010_0000;
+        /// The message was imported from a snapshot.
+        const SNAPSHOT = 0b0100_0000;
     }
 }
 
@@ -73,6 +77,26 @@ impl Flags {
     pub fn set_requested(&mut self, was_requested: bool) {
         self.set(Flags::REQUESTED, was_requested);
     }
+
+    /// Return whether the flags indicate that the message is a reattachment.
+    pub fn is_reattachment(&self) -> bool {
+        self.contains(Flags::REATTACHMENT)
+    }
+
+    /// Set the reattachment flag for this message.
+    pub fn set_reattachment(&mut self, is_reattachment: bool) {
+        self.set(Flags::REATTACHMENT, is_reattachment);
+    }
+
+    /// Return whether the flags indicate that the message was imported from a snapshot.
+    pub fn is_from_snapshot(&self) -> bool {
+        self.contains(Flags::SNAPSHOT)
+    }
+
+    /// Set the snapshot flag for this message.
+    pub fn set_from_snapshot(&mut self, is_from_snapshot: bool) {
+        self.set(Flags::SNAPSHOT, is_from_snapshot);
+    }
 }
 
 impl Packable for Flags {
@@ -0,0 +1,74 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_common::packable::{Packable, Read, Write};
+use bee_message::MessageId;
+
+/// A type representing a message stored under the index of its indexation payload.
+///
+/// Indexed messages are ordered by arrival, which their packed form preserves for storage backends ordering keys by
+/// their bytes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IndexedMessage {
+    arrival_timestamp: u64,
+    message_id: MessageId,
+}
+
+impl IndexedMessage {
+    /// Create a new `IndexedMessage`.
+    pub fn new(arrival_timestamp: u64, message_id: MessageId) -> Self {
+        Self {
+            arrival_timestamp,
+            message_id,
+        }
+    }
+
+    /// Get the arrival timestamp of this indexed message, in milliseconds.
+    pub fn arrival_timestamp(&self) -> u64 {
+        self.arrival_timestamp
+    }
+
+    /// Get the message ID of this indexed message.
+    pub fn message_id(&self) -> &MessageId {
+        &self.message_id
+    }
+}
+
+impl Packable for IndexedMessage {
+    type Error = <MessageId as Packable>::Error;
+
+    fn packed_len(&self) -> usize {
+        self.arrival_timestamp.packed_len() + self.message_id.packed_len()
+    }
+
+    fn pack<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        // Big endian, for the packed indexed messages to be ordered by arrival.
+        writer.write_all(&self.arrival_timestamp.to_be_bytes())?;
+        self.message_id.pack(writer)
+    }
+
+    fn unpack_inner<R: Read + ?Sized, const CHECK: bool>(reader: &mut R) -> Result<Self, Self::Error> {
+        let mut arrival_timestamp = [0u8; std::mem::size_of::<u64>()];
+        reader.read_exact(&mut arrival_timestamp)?;
+
+        Ok(Self {
+            arrival_timestamp: u64::from_be_bytes(arrival_timestamp),
+            message_id: MessageId::unpack_inner::<R, CHECK>(reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_order_is_arrival_order() {
+        let earlier = IndexedMessage::new(255, MessageId::new([9; 32]));
+        let later = IndexedMessage::new(256, MessageId::new([1; 32]));
+
+        assert!(earlier < later);
+        assert!(earlier.pack_new() < later.pack_new());
+        assert_eq!(IndexedMessage::unpack(&mut later.pack_new().as_slice()).unwrap(), later);
+    }
+}
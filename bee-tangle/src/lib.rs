@@ -11,8 +11,12 @@ pub mod config;
 pub mod event;
 /// Message flags.
 pub mod flags;
+/// Types used to represent messages stored under the index of their indexation payload.
+pub mod indexed_message;
 /// Message data, including message flags.
 pub mod metadata;
+/// Types used to represent messages as received.
+pub mod raw_message;
 /// Types used to represent SEPs (Solid Entry Points).
 pub mod solid_entry_point;
 /// Types used for interoperation with a node's storage layer.
@@ -65,6 +65,14 @@ impl MessageMetadata {
         }
     }
 
+    /// Create metadata that corresponds to a message imported from a snapshot using the current system time.
+    pub fn imported() -> Self {
+        let mut metadata = Self::arrived();
+
+        metadata.flags.set_from_snapshot(true);
+        metadata
+    }
+
     /// Get the flags associated with this metadata.
     pub fn flags(&self) -> &Flags {
         &self.flags
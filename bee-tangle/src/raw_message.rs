@@ -0,0 +1,76 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_common::packable::{Packable, Read, Write};
+
+use std::ops::Deref;
+
+/// A type representing the bytes of a message exactly as they were received.
+///
+/// Since the bytes are not length-prefixed, unpacking a `RawMessage` consumes the whole reader.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RawMessage(Vec<u8>);
+
+impl From<Vec<u8>> for RawMessage {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Deref for RawMessage {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl RawMessage {
+    /// Create a new `RawMessage`.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        bytes.into()
+    }
+
+    /// Get the bytes of this raw message.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consume this raw message and return its bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl Packable for RawMessage {
+    type Error = std::io::Error;
+
+    fn packed_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn pack<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        writer.write_all(&self.0)
+    }
+
+    fn unpack_inner<R: Read + ?Sized, const CHECK: bool>(reader: &mut R) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing_round_trip() {
+        let raw = RawMessage::new(vec![0, 1, 2, 3, 255, 0]);
+        let bytes = raw.pack_new();
+
+        assert_eq!(bytes, raw.bytes());
+        assert_eq!(RawMessage::unpack(&mut bytes.as_slice()).unwrap(), raw);
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use crate::{metadata::MessageMetadata, raw_message::RawMessage, solid_entry_point::SolidEntryPoint};
 
 use bee_message::{
     milestone::{Milestone, MilestoneIndex},
@@ -18,11 +18,13 @@ pub trait StorageBackend:
     backend::StorageBackend
     + Insert<MessageId, Message>
     + Insert<MessageId, MessageMetadata>
+    + Insert<MessageId, RawMessage>
     + Insert<(MessageId, MessageId), ()>
     + Insert<MilestoneIndex, Milestone>
     + Insert<SolidEntryPoint, MilestoneIndex>
     + Fetch<MessageId, Message>
     + Fetch<MessageId, MessageMetadata>
+    + Fetch<MessageId, RawMessage>
     + Fetch<MessageId, Vec<MessageId>>
     + Fetch<MilestoneIndex, Milestone>
 {
@@ -32,11 +34,13 @@ impl<T> StorageBackend for T where
     T: backend::StorageBackend
         + Insert<MessageId, Message>
         + Insert<MessageId, MessageMetadata>
+        + Insert<MessageId, RawMessage>
         + Insert<(MessageId, MessageId), ()>
         + Insert<MilestoneIndex, Milestone>
         + Insert<SolidEntryPoint, MilestoneIndex>
         + Fetch<MessageId, Message>
         + Fetch<MessageId, MessageMetadata>
+        + Fetch<MessageId, RawMessage>
         + Fetch<MessageId, Vec<MessageId>>
         + Fetch<MilestoneIndex, Milestone>
 {
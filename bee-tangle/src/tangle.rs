@@ -4,6 +4,7 @@
 use crate::{
     config::TangleConfig,
     metadata::{IndexId, MessageMetadata},
+    raw_message::RawMessage,
     solid_entry_point::SolidEntryPoint,
     storage::StorageBackend,
     urts::UrtsTipPool,
@@ -114,6 +115,21 @@ impl<B: StorageBackend> Tangle<B> {
         msg
     }
 
+    /// Store the bytes of a message exactly as they were received, in addition to the decoded message.
+    pub fn insert_raw(&self, message_id: &MessageId, raw_message: &RawMessage) {
+        self.storage
+            .insert(message_id, raw_message)
+            .unwrap_or_else(|e| info!("Failed to insert raw message {:?}", e));
+    }
+
+    /// Get the bytes of a message exactly as they were received, if they were stored.
+    pub fn get_raw(&self, message_id: &MessageId) -> Option<RawMessage> {
+        self.storage.fetch(message_id).unwrap_or_else(|e| {
+            info!("Failed to fetch raw message {:?}", e);
+            None
+        })
+    }
+
     /// Add a milestone to the tangle.
     pub async fn add_milestone(&self, idx: MilestoneIndex, milestone: Milestone) {
         // TODO: only insert if vacant
@@ -383,6 +399,11 @@ impl<B: StorageBackend> Tangle<B> {
         self.tip_pool.lock().await.reduce_tips();
     }
 
+    /// Return the number of tips.
+    pub async fn tips_num(&self) -> usize {
+        self.tip_pool.lock().await.num_tips()
+    }
+
     /// Return the number of non-lazy tips.
     pub async fn non_lazy_tips_num(&self) -> usize {
         self.tip_pool.lock().await.non_lazy_tips().len()
@@ -61,6 +61,10 @@ impl UrtsTipPool {
         }
     }
 
+    pub(crate) fn num_tips(&self) -> usize {
+        self.tips.len()
+    }
+
     pub(crate) fn non_lazy_tips(&self) -> &HashSet<MessageId> {
         &self.non_lazy_tips
     }